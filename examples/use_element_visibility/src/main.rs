@@ -1,13 +1,13 @@
 use leptos::html::Div;
 use leptos::*;
 use leptos_use::docs::{demo_or_body, BooleanDisplay, Note};
-use leptos_use::use_element_visibility;
+use leptos_use::{use_element_visibility, UseElementVisibilityReturn};
 
 #[component]
 fn Demo() -> impl IntoView {
     let el = create_node_ref::<Div>();
 
-    let is_visible = use_element_visibility(el);
+    let UseElementVisibilityReturn { is_visible, .. } = use_element_visibility(el);
 
     view! {
         <div>
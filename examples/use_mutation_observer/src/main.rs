@@ -16,7 +16,7 @@ fn Demo() -> impl IntoView {
         move |mutations, _| {
             if let Some(mutation) = mutations.first() {
                 set_messages.update(move |messages| {
-                    messages.push(format!("{:?}", mutation.attribute_name()));
+                    messages.push(format!("{:?}", mutation.attribute_name));
                 });
             }
         },
@@ -1,8 +1,8 @@
 use leptos::*;
 use leptos_use::docs::demo_or_body;
 use leptos_use::{
-    core::ConnectionReadyState, use_websocket, use_websocket_with_options, UseWebSocketOptions,
-    UseWebsocketReturn,
+    core::ConnectionReadyState, use_websocket, use_websocket_with_options,
+    utils::FromToStringCodec, UseWebSocketOptions, UseWebsocketReturn,
 };
 
 use web_sys::{CloseEvent, Event};
@@ -21,26 +21,18 @@ fn Demo() -> impl IntoView {
     let UseWebsocketReturn {
         ready_state,
         message,
-        message_bytes,
         send,
-        send_bytes,
         open,
         close,
         ..
-    } = use_websocket("wss://echo.websocket.events/");
+    } = use_websocket::<String, FromToStringCodec>("wss://echo.websocket.events/");
 
     let send_message = move |_| {
-        let m = "Hello, world!";
-        send(m);
+        let m = "Hello, world!".to_string();
+        send(&m);
         set_history.update(|history: &mut Vec<_>| history.push(format! {"[send]: {:?}", m}));
     };
 
-    let send_byte_message = move |_| {
-        let m = b"Hello, world!\r\n".to_vec();
-        send_bytes(m.clone());
-        set_history.update(|history: &mut Vec<_>| history.push(format! {"[send_bytes]: {:?}", m}));
-    };
-
     let status = move || ready_state().to_string();
 
     let connected = move || ready_state.get() == ConnectionReadyState::Open;
@@ -58,12 +50,6 @@ fn Demo() -> impl IntoView {
         };
     });
 
-    create_effect(move |_| {
-        if let Some(m) = message_bytes.get() {
-            update_history(&set_history, format! {"[message_bytes]: {:?}", m});
-        };
-    });
-
     // ----------------------------
     // use_websocket_with_options
     // ----------------------------
@@ -88,33 +74,25 @@ fn Demo() -> impl IntoView {
         });
     };
 
-    let on_message_callback = move |m: String| {
+    let on_message_callback = move |m: &String| {
         set_history2.update(|history: &mut Vec<_>| history.push(format! {"[onmessage]: {:?}", m}));
     };
 
-    let on_message_bytes_callback = move |m: Vec<u8>| {
-        set_history2
-            .update(|history: &mut Vec<_>| history.push(format! {"[onmessage_bytes]: {:?}", m}));
-    };
-
     let UseWebsocketReturn {
         ready_state: ready_state2,
         send: send2,
-        send_bytes: send_bytes2,
         open: open2,
         close: close2,
         message: message2,
-        message_bytes: message_bytes2,
         ..
-    } = use_websocket_with_options(
+    } = use_websocket_with_options::<String, FromToStringCodec>(
         "wss://echo.websocket.events/",
         UseWebSocketOptions::default()
             .immediate(false)
             .on_open(on_open_callback.clone())
             .on_close(on_close_callback.clone())
             .on_error(on_error_callback.clone())
-            .on_message(on_message_callback.clone())
-            .on_message_bytes(on_message_bytes_callback.clone()),
+            .on_message(on_message_callback.clone()),
     );
 
     let open_connection2 = move |_| {
@@ -125,17 +103,11 @@ fn Demo() -> impl IntoView {
     };
 
     let send_message2 = move |_| {
-        let message = "Hello, use_leptos!";
-        send2(message);
+        let message = "Hello, use_leptos!".to_string();
+        send2(&message);
         update_history(&set_history2, format! {"[send]: {:?}", message});
     };
 
-    let send_byte_message2 = move |_| {
-        let m = b"Hello, world!\r\n".to_vec();
-        send_bytes2(m.clone());
-        update_history(&set_history2, format! {"[send_bytes]: {:?}", m});
-    };
-
     let status2 = move || ready_state2.get().to_string();
 
     create_effect(move |_| {
@@ -144,11 +116,6 @@ fn Demo() -> impl IntoView {
         };
     });
 
-    create_effect(move |_| {
-        if let Some(m) = message_bytes2.get() {
-            update_history(&set_history2, format! {"[message_bytes]: {:?}", m});
-        };
-    });
 
     let connected2 = move || ready_state2.get() == ConnectionReadyState::Open;
 
@@ -161,9 +128,6 @@ fn Demo() -> impl IntoView {
                     <button on:click=send_message disabled=move || !connected()>
                         "Send"
                     </button>
-                    <button on:click=send_byte_message disabled=move || !connected()>
-                        "Send bytes"
-                    </button>
                     <button on:click=open_connection disabled=connected>
                         "Open"
                     </button>
@@ -200,9 +164,6 @@ fn Demo() -> impl IntoView {
                     <button on:click=send_message2 disabled=move || !connected2()>
                         "Send"
                     </button>
-                    <button on:click=send_byte_message2 disabled=move || !connected2()>
-                        "Send Bytes"
-                    </button>
                     <div class="flex items-center">
                         <h3 class="text-2xl mr-2">"History"</h3>
                         <button
@@ -36,7 +36,7 @@ fn Demo() -> impl IntoView {
         move |_| {
             set_datagrams_log.update(|log| log.push(format!("Sent datagram: '{}'", text())));
 
-            transport.send_datagrams(text().as_bytes());
+            transport.send_datagram(text().as_bytes());
             set_text("".to_string());
         }
     };
@@ -65,7 +65,7 @@ fn Demo() -> impl IntoView {
             let transport = transport.clone();
 
             spawn_local(async move {
-                match transport.open_bidir_stream().await {
+                match transport.open_bidirectional_stream().await {
                     Ok(bidir_stream) => {
                         let i = id.get_value();
                         id.set_value(i + 1);
@@ -0,0 +1,175 @@
+use cfg_if::cfg_if;
+use std::fmt;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// How long connection hooks like [`fn@crate::use_websocket`] wait before trying to reconnect.
+///
+/// ```
+/// # use leptos_use::core::ReconnectStrategy;
+/// #
+/// // wait 1s, 2s, 4s, ... but never longer than 30s (plus up to 10% jitter)
+/// let strategy = ReconnectStrategy::exponential(1000, 30_000);
+///
+/// // wait 500ms more for every attempt
+/// let strategy = ReconnectStrategy::custom(|attempt| 500 * attempt);
+/// ```
+#[derive(Clone)]
+pub enum ReconnectStrategy {
+    /// Always wait the given number of milliseconds.
+    Fixed(u64),
+
+    /// Wait `initial` milliseconds before the first attempt and multiply the delay by `factor`
+    /// for every following attempt until `max` milliseconds are reached.
+    /// A random amount of up to `jitter * delay` is added so that many clients don't reconnect
+    /// to a recovering server at the same time.
+    Exponential {
+        initial: u64,
+        factor: f64,
+        max: u64,
+        jitter: f64,
+    },
+
+    /// Computes the delay in milliseconds from the attempt number, which starts at 1.
+    Custom(Rc<dyn Fn(u64) -> u64>),
+}
+
+impl ReconnectStrategy {
+    /// Exponential backoff that doubles the delay on every attempt and adds up to 10% jitter.
+    pub fn exponential(initial: u64, max: u64) -> Self {
+        Self::Exponential {
+            initial,
+            factor: 2.0,
+            max,
+            jitter: 0.1,
+        }
+    }
+
+    /// Strategy that calls `f` with the attempt number to compute the delay in milliseconds.
+    pub fn custom(f: impl Fn(u64) -> u64 + 'static) -> Self {
+        Self::Custom(Rc::new(f))
+    }
+
+    /// The time to wait before the given reconnection attempt. Attempts start at 1.
+    pub fn delay(&self, attempt: u64) -> Duration {
+        let millis = match self {
+            Self::Fixed(interval) => *interval,
+            Self::Exponential {
+                initial,
+                factor,
+                max,
+                jitter,
+            } => {
+                let exponent = attempt.saturating_sub(1).min(i32::MAX as u64) as i32;
+                let delay = (*initial as f64 * factor.powi(exponent)).min(*max as f64);
+
+                if *jitter > 0.0 {
+                    (delay + delay * jitter * random()) as u64
+                } else {
+                    delay as u64
+                }
+            }
+            Self::Custom(f) => f(attempt),
+        };
+
+        Duration::from_millis(millis)
+    }
+}
+
+impl Default for ReconnectStrategy {
+    fn default() -> Self {
+        Self::Fixed(3000)
+    }
+}
+
+impl fmt::Debug for ReconnectStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fixed(interval) => f.debug_tuple("Fixed").field(interval).finish(),
+            Self::Exponential {
+                initial,
+                factor,
+                max,
+                jitter,
+            } => f
+                .debug_struct("Exponential")
+                .field("initial", initial)
+                .field("factor", factor)
+                .field("max", max)
+                .field("jitter", jitter)
+                .finish(),
+            Self::Custom(_) => f.debug_tuple("Custom").finish_non_exhaustive(),
+        }
+    }
+}
+
+fn random() -> f64 {
+    cfg_if! { if #[cfg(feature = "ssr")] {
+        0.0
+    } else {
+        js_sys::Math::random()
+    }}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exponential(initial: u64, factor: f64, max: u64) -> ReconnectStrategy {
+        ReconnectStrategy::Exponential {
+            initial,
+            factor,
+            max,
+            jitter: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_fixed() {
+        let strategy = ReconnectStrategy::Fixed(3000);
+
+        assert_eq!(strategy.delay(0), Duration::from_millis(3000));
+        assert_eq!(strategy.delay(1), Duration::from_millis(3000));
+        assert_eq!(strategy.delay(10), Duration::from_millis(3000));
+    }
+
+    #[test]
+    fn test_exponential_growth() {
+        let strategy = exponential(1000, 2.0, 60_000);
+
+        let delays: Vec<_> = (1..=5).map(|attempt| strategy.delay(attempt)).collect();
+
+        assert_eq!(
+            delays,
+            [1000, 2000, 4000, 8000, 16_000].map(Duration::from_millis)
+        );
+    }
+
+    #[test]
+    fn test_exponential_first_attempts() {
+        let strategy = exponential(500, 3.0, 60_000);
+
+        assert_eq!(strategy.delay(0), Duration::from_millis(500));
+        assert_eq!(strategy.delay(1), Duration::from_millis(500));
+        assert_eq!(strategy.delay(2), Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn test_exponential_max() {
+        let strategy = exponential(1000, 2.0, 5000);
+
+        assert_eq!(strategy.delay(3), Duration::from_millis(4000));
+        assert_eq!(strategy.delay(4), Duration::from_millis(5000));
+        assert_eq!(strategy.delay(100), Duration::from_millis(5000));
+        assert_eq!(strategy.delay(u64::MAX), Duration::from_millis(5000));
+    }
+
+    #[test]
+    fn test_custom() {
+        let strategy = ReconnectStrategy::custom(|attempt| 500 * attempt);
+
+        assert_eq!(strategy.delay(0), Duration::from_millis(0));
+        assert_eq!(strategy.delay(1), Duration::from_millis(500));
+        assert_eq!(strategy.delay(4), Duration::from_millis(2000));
+    }
+}
@@ -9,16 +9,61 @@ pub mod math;
 pub mod storage;
 pub mod utils;
 
-// #[cfg(web_sys_unstable_apis)]
-// mod use_webtransport;
-// #[cfg(web_sys_unstable_apis)]
-// pub use use_webtransport::*;
+#[cfg(web_sys_unstable_apis)]
+mod use_webtransport;
+#[cfg(web_sys_unstable_apis)]
+pub use use_webtransport::*;
 
 #[cfg(web_sys_unstable_apis)]
 mod use_clipboard;
 #[cfg(web_sys_unstable_apis)]
 pub use use_clipboard::*;
 
+#[cfg(web_sys_unstable_apis)]
+mod use_hid;
+#[cfg(web_sys_unstable_apis)]
+pub use use_hid::*;
+
+#[cfg(web_sys_unstable_apis)]
+mod use_serial;
+#[cfg(web_sys_unstable_apis)]
+pub use use_serial::*;
+
+#[cfg(web_sys_unstable_apis)]
+mod use_web_bluetooth;
+#[cfg(web_sys_unstable_apis)]
+pub use use_web_bluetooth::*;
+
+#[cfg(web_sys_unstable_apis)]
+mod use_webusb;
+#[cfg(web_sys_unstable_apis)]
+pub use use_webusb::*;
+
+#[cfg(web_sys_unstable_apis)]
+mod use_media_session;
+#[cfg(web_sys_unstable_apis)]
+pub use use_media_session::*;
+
+#[cfg(web_sys_unstable_apis)]
+mod use_picture_in_picture;
+#[cfg(web_sys_unstable_apis)]
+pub use use_picture_in_picture::*;
+
+#[cfg(web_sys_unstable_apis)]
+mod use_compression;
+#[cfg(web_sys_unstable_apis)]
+pub use use_compression::*;
+
+#[cfg(web_sys_unstable_apis)]
+mod use_video_decoder;
+#[cfg(web_sys_unstable_apis)]
+pub use use_video_decoder::*;
+
+#[cfg(web_sys_unstable_apis)]
+mod use_video_encoder;
+#[cfg(web_sys_unstable_apis)]
+pub use use_video_encoder::*;
+
 mod is_err;
 mod is_none;
 mod is_ok;
@@ -27,14 +72,23 @@ mod on_click_outside;
 mod signal_debounced;
 mod signal_throttled;
 mod sync_signal;
+mod use_accelerometer;
 mod use_active_element;
+mod use_ambient_light_sensor;
+mod use_app_badge;
+mod use_audio_analyser;
+mod use_background_sync;
+mod use_battery;
 mod use_breakpoints;
 mod use_broadcast_channel;
 mod use_color_mode;
 mod use_cookie;
+mod use_credential_management;
+mod use_csp_violation;
 mod use_css_var;
 mod use_cycle_list;
 mod use_debounce_fn;
+mod use_device_motion;
 mod use_device_orientation;
 mod use_device_pixel_ratio;
 mod use_display_media;
@@ -43,37 +97,78 @@ mod use_document_visibility;
 mod use_draggable;
 mod use_drop_zone;
 mod use_element_bounding;
+mod use_element_by_point;
 mod use_element_hover;
 mod use_element_size;
 mod use_element_visibility;
 mod use_event_listener;
 mod use_event_source;
 mod use_favicon;
+mod use_fedcm;
+mod use_gamepad;
 mod use_geolocation;
+mod use_gyroscope;
+mod use_hash;
 mod use_idle;
+mod use_idle_detection;
 mod use_infinite_scroll;
+mod use_install_prompt;
 mod use_intersection_observer;
 mod use_interval;
 mod use_interval_fn;
 mod use_intl_number_format;
+mod use_launch_queue;
+mod use_long_press;
+mod use_media_controls;
+mod use_media_devices;
 mod use_media_query;
+mod use_media_recorder;
 mod use_mouse;
 mod use_mouse_in_element;
+mod use_mouse_pressed;
 mod use_mutation_observer;
+mod use_offscreen_canvas;
+mod use_parallax;
+mod use_periodic_background_sync;
 mod use_permission;
+mod use_pinch;
+mod use_pointer;
+mod use_pointer_lock;
+mod use_pointer_swipe;
 mod use_preferred_contrast;
 mod use_preferred_dark;
+mod use_protocol_handler;
+mod use_push_subscription;
 mod use_raf_fn;
+mod use_readable_stream;
+mod use_reporting_observer;
 mod use_resize_observer;
+mod use_rtc_data_channel;
+mod use_rtc_peer_connection;
 mod use_scroll;
+mod use_sensor;
 mod use_service_worker;
+mod use_shared_worker;
+mod use_sortable;
 mod use_sorted;
+mod use_speech_recognition;
+mod use_speech_synthesis;
 mod use_supported;
+mod use_swipe;
+mod use_text_selection;
 mod use_throttle_fn;
 mod use_timeout_fn;
 mod use_timestamp;
 mod use_to_string;
+mod use_transport;
+mod use_trusted_types;
+mod use_user_media;
+mod use_web_authn;
+mod use_web_midi;
+mod use_web_nfc;
 mod use_web_notification;
+mod use_web_worker;
+mod use_web_worker_fn;
 mod use_websocket;
 mod use_window;
 mod use_window_focus;
@@ -92,14 +187,23 @@ pub use on_click_outside::*;
 pub use signal_debounced::*;
 pub use signal_throttled::*;
 pub use sync_signal::*;
+pub use use_accelerometer::*;
 pub use use_active_element::*;
+pub use use_ambient_light_sensor::*;
+pub use use_app_badge::*;
+pub use use_audio_analyser::*;
+pub use use_background_sync::*;
+pub use use_battery::*;
 pub use use_breakpoints::*;
 pub use use_broadcast_channel::*;
 pub use use_color_mode::*;
 pub use use_cookie::*;
+pub use use_credential_management::*;
+pub use use_csp_violation::*;
 pub use use_css_var::*;
 pub use use_cycle_list::*;
 pub use use_debounce_fn::*;
+pub use use_device_motion::*;
 pub use use_device_orientation::*;
 pub use use_device_pixel_ratio::*;
 pub use use_display_media::*;
@@ -108,37 +212,78 @@ pub use use_document_visibility::*;
 pub use use_draggable::*;
 pub use use_drop_zone::*;
 pub use use_element_bounding::*;
+pub use use_element_by_point::*;
 pub use use_element_hover::*;
 pub use use_element_size::*;
 pub use use_element_visibility::*;
 pub use use_event_listener::*;
 pub use use_event_source::*;
 pub use use_favicon::*;
+pub use use_fedcm::*;
+pub use use_gamepad::*;
 pub use use_geolocation::*;
+pub use use_gyroscope::*;
+pub use use_hash::*;
 pub use use_idle::*;
+pub use use_idle_detection::*;
 pub use use_infinite_scroll::*;
+pub use use_install_prompt::*;
 pub use use_intersection_observer::*;
 pub use use_interval::*;
 pub use use_interval_fn::*;
 pub use use_intl_number_format::*;
+pub use use_launch_queue::*;
+pub use use_long_press::*;
+pub use use_media_controls::*;
+pub use use_media_devices::*;
 pub use use_media_query::*;
+pub use use_media_recorder::*;
 pub use use_mouse::*;
 pub use use_mouse_in_element::*;
+pub use use_mouse_pressed::*;
 pub use use_mutation_observer::*;
+pub use use_offscreen_canvas::*;
+pub use use_parallax::*;
+pub use use_periodic_background_sync::*;
 pub use use_permission::*;
+pub use use_pinch::*;
+pub use use_pointer::*;
+pub use use_pointer_lock::*;
+pub use use_pointer_swipe::*;
 pub use use_preferred_contrast::*;
 pub use use_preferred_dark::*;
+pub use use_protocol_handler::*;
+pub use use_push_subscription::*;
 pub use use_raf_fn::*;
+pub use use_readable_stream::*;
+pub use use_reporting_observer::*;
 pub use use_resize_observer::*;
+pub use use_rtc_data_channel::*;
+pub use use_rtc_peer_connection::*;
 pub use use_scroll::*;
+pub use use_sensor::*;
 pub use use_service_worker::*;
+pub use use_shared_worker::*;
+pub use use_sortable::*;
 pub use use_sorted::*;
+pub use use_speech_recognition::*;
+pub use use_speech_synthesis::*;
 pub use use_supported::*;
+pub use use_swipe::*;
+pub use use_text_selection::*;
 pub use use_throttle_fn::*;
 pub use use_timeout_fn::*;
 pub use use_timestamp::*;
 pub use use_to_string::*;
+pub use use_transport::*;
+pub use use_trusted_types::*;
+pub use use_user_media::*;
+pub use use_web_authn::*;
+pub use use_web_midi::*;
+pub use use_web_nfc::*;
 pub use use_web_notification::*;
+pub use use_web_worker::*;
+pub use use_web_worker_fn::*;
 pub use use_websocket::*;
 pub use use_window::*;
 pub use use_window_focus::*;
@@ -0,0 +1,167 @@
+use crate::js_fut;
+use crate::storage::StorageBackend;
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use leptos::window;
+use wasm_bindgen::{JsCast, JsValue};
+
+const ALGORITHM: &str = "AES-GCM";
+const IV_LENGTH: usize = 12;
+
+/// Wraps another [`StorageBackend`] and encrypts all values with
+/// [AES-GCM](https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/encrypt#aes-gcm)
+/// before they are stored so that sensitive data like tokens isn't kept in clear text.
+///
+/// The [Web Crypto API](https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto) is
+/// asynchronous which is why this wraps a backend of [`fn@crate::storage::use_async_storage`]
+/// instead of being a [`crate::utils::StringCodec`]. The codec of the hook is applied as usual
+/// before the encryption.
+///
+/// Every value is stored base64 encoded with a new random IV prepended. The key names are not
+/// encrypted but authenticated: the storage key is passed to AES-GCM as additional data, so a
+/// value that was copied to another key fails to decrypt.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::storage::{import_encryption_key, use_async_storage, EncryptedBackend};
+/// # use leptos_use::utils::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let token = create_local_resource(|| (), |_| async {
+///     // a 128 or 256 bit key, for example received from your server after login
+///     let raw_key = [0_u8; 32];
+///     let key = import_encryption_key(&raw_key).await.ok()?;
+///
+///     let storage = EncryptedBackend::new(window().local_storage().ok()??, key);
+///
+///     Some(storage)
+/// });
+///
+/// view! {
+///     {move || token.get().flatten().map(|storage| {
+///         let token = use_async_storage::<String, FromToStringCodec>(storage, "token");
+///         view! { <p>{move || token.data.get()}</p> }
+///     })}
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct EncryptedBackend<B> {
+    backend: B,
+    key: web_sys::CryptoKey,
+}
+
+impl<B> EncryptedBackend<B> {
+    /// Encrypts the values of `backend` with `key` which has to be an AES-GCM key that can be
+    /// used to encrypt and decrypt. See [`import_encryption_key`] and [`generate_encryption_key`].
+    pub fn new(backend: B, key: web_sys::CryptoKey) -> Self {
+        Self { backend, key }
+    }
+
+    async fn encrypt(&self, key: &str, value: &str) -> Result<String, JsValue> {
+        let crypto = window().crypto()?;
+
+        let mut iv = [0_u8; IV_LENGTH];
+        crypto.get_random_values_with_u8_array(&mut iv)?;
+
+        let encrypted = js_fut!(crypto.subtle().encrypt_with_object_and_u8_array(
+            &params(&iv, key),
+            &self.key,
+            value.as_bytes(),
+        )?)
+        .await?;
+
+        let mut data = iv.to_vec();
+        data.extend(js_sys::Uint8Array::new(&encrypted).to_vec());
+
+        Ok(STANDARD.encode(data))
+    }
+
+    async fn decrypt(&self, key: &str, value: &str) -> Result<String, JsValue> {
+        let data = STANDARD
+            .decode(value)
+            .map_err(|err| js_sys::Error::new(&err.to_string()))?;
+
+        if data.len() < IV_LENGTH {
+            return Err(js_sys::Error::new("encrypted value is too short").into());
+        }
+
+        let (iv, encrypted) = data.split_at(IV_LENGTH);
+
+        let decrypted = js_fut!(window()
+            .crypto()?
+            .subtle()
+            .decrypt_with_object_and_u8_array(&params(iv, key), &self.key, encrypted,)?)
+        .await?;
+
+        String::from_utf8(js_sys::Uint8Array::new(&decrypted).to_vec())
+            .map_err(|err| js_sys::Error::new(&err.to_string()).into())
+    }
+}
+
+/// Parameters of AES-GCM that bind the encrypted value to its storage `key`.
+fn params(iv: &[u8], key: &str) -> web_sys::AesGcmParams {
+    let params = web_sys::AesGcmParams::new_with_u8_slice(ALGORITHM, &mut iv.to_vec());
+    params.set_additional_data_u8_slice(&mut key.as_bytes().to_vec());
+    params
+}
+
+/// Imports a raw 128, 192 or 256 bit AES-GCM key for [`EncryptedBackend`].
+pub async fn import_encryption_key(raw_key: &[u8]) -> Result<web_sys::CryptoKey, JsValue> {
+    let subtle = window().crypto()?.subtle();
+
+    let key = js_fut!(subtle.import_key_with_str(
+        "raw",
+        &js_sys::Uint8Array::from(raw_key),
+        ALGORITHM,
+        false,
+        &key_usages(),
+    )?)
+    .await?;
+
+    Ok(key.unchecked_into())
+}
+
+/// Generates a new random 256 bit AES-GCM key for [`EncryptedBackend`]. The key isn't extractable
+/// but can be kept in IndexedDB to decrypt the values again after a reload.
+pub async fn generate_encryption_key() -> Result<web_sys::CryptoKey, JsValue> {
+    let subtle = window().crypto()?.subtle();
+
+    let key = js_fut!(subtle.generate_key_with_object(
+        &web_sys::AesKeyGenParams::new(ALGORITHM, 256),
+        false,
+        &key_usages(),
+    )?)
+    .await?;
+
+    Ok(key.unchecked_into())
+}
+
+fn key_usages() -> JsValue {
+    js_sys::Array::of2(&"encrypt".into(), &"decrypt".into()).into()
+}
+
+#[async_trait(?Send)]
+impl<B> StorageBackend for EncryptedBackend<B>
+where
+    B: StorageBackend,
+{
+    async fn get(&self, key: &str) -> Result<Option<String>, JsValue> {
+        match self.backend.get(key).await? {
+            Some(value) => Ok(Some(self.decrypt(key, &value).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn set(&self, key: &str, value: &str) -> Result<(), JsValue> {
+        let encrypted = self.encrypt(key, value).await?;
+
+        self.backend.set(key, &encrypted).await
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), JsValue> {
+        self.backend.remove(key).await
+    }
+}
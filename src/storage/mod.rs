@@ -1,8 +1,26 @@
+mod encrypted_backend;
+mod storage_backend;
+mod storage_merge;
+mod use_async_storage;
+mod use_cache_storage;
+mod use_indexed_db;
 mod use_local_storage;
+mod use_persistent_storage;
 mod use_session_storage;
 mod use_storage;
+mod use_storage_quota;
+mod use_storage_slice;
 
 pub use crate::core::StorageType;
+pub use encrypted_backend::*;
+pub use storage_backend::*;
+pub use storage_merge::*;
+pub use use_async_storage::*;
+pub use use_cache_storage::*;
+pub use use_indexed_db::*;
 pub use use_local_storage::*;
+pub use use_persistent_storage::*;
 pub use use_session_storage::*;
 pub use use_storage::*;
+pub use use_storage_quota::*;
+pub use use_storage_slice::*;
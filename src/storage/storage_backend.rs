@@ -0,0 +1,220 @@
+use crate::js_fut;
+use crate::storage::{IndexedDbError, IndexedDbStore, IndexedDbStoreError};
+use crate::utils::FromToStringCodec;
+use async_trait::async_trait;
+use leptos::window;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Key-value store that persists the values of [`fn@crate::storage::use_async_storage`].
+///
+/// Values are already encoded strings. Implementations are provided for `web_sys::Storage`
+/// (local and session storage), [`IndexedDbStore`], [`CacheStorageBackend`] and [`OpfsBackend`].
+#[async_trait(?Send)]
+pub trait StorageBackend {
+    /// Reads the value of `key`. Returns `None` if it isn't set.
+    async fn get(&self, key: &str) -> Result<Option<String>, JsValue>;
+
+    /// Sets `key` to `value`.
+    async fn set(&self, key: &str, value: &str) -> Result<(), JsValue>;
+
+    /// Removes `key`. Does nothing if it isn't set.
+    async fn remove(&self, key: &str) -> Result<(), JsValue>;
+}
+
+#[async_trait(?Send)]
+impl StorageBackend for web_sys::Storage {
+    async fn get(&self, key: &str) -> Result<Option<String>, JsValue> {
+        self.get_item(key)
+    }
+
+    async fn set(&self, key: &str, value: &str) -> Result<(), JsValue> {
+        self.set_item(key, value)
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), JsValue> {
+        self.remove_item(key)
+    }
+}
+
+/// Stores the values in an object store of [`fn@crate::storage::use_indexed_db`].
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::storage::{use_async_storage, use_indexed_db_with_options, UseIndexedDbOptions};
+/// # use leptos_use::utils::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let db = use_indexed_db_with_options(
+///     "my-app",
+///     UseIndexedDbOptions::default().stores(vec!["settings".to_string()]),
+/// );
+///
+/// let storage = use_async_storage::<i32, FromToStringCodec>(
+///     db.store::<String, FromToStringCodec>("settings"),
+///     "count",
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+#[async_trait(?Send)]
+impl StorageBackend for IndexedDbStore<String, FromToStringCodec> {
+    async fn get(&self, key: &str) -> Result<Option<String>, JsValue> {
+        IndexedDbStore::get(self, key)
+            .await
+            .map_err(indexed_db_error_to_js)
+    }
+
+    async fn set(&self, key: &str, value: &str) -> Result<(), JsValue> {
+        self.put(key, &value.to_string())
+            .await
+            .map_err(indexed_db_error_to_js)
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), JsValue> {
+        self.delete(key).await.map_err(indexed_db_error_to_js)
+    }
+}
+
+fn indexed_db_error_to_js<Err>(err: IndexedDbStoreError<Err>) -> JsValue {
+    match err {
+        IndexedDbStoreError::Db(
+            IndexedDbError::OpenFailed(err) | IndexedDbError::RequestFailed(err),
+        ) => err,
+        err => js_sys::Error::new(&err.to_string()).into(),
+    }
+}
+
+/// Stores the values in a cache of the [Cache API](https://developer.mozilla.org/en-US/docs/Web/API/Cache)
+/// with the given name.
+///
+/// Every key is stored as the response of the request `/<prefix><key>`. Keys should therefore
+/// be valid URL path segments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CacheStorageBackend {
+    cache_name: String,
+    prefix: String,
+}
+
+impl CacheStorageBackend {
+    pub fn new(cache_name: impl Into<String>) -> Self {
+        Self {
+            cache_name: cache_name.into(),
+            prefix: "__leptos_use_storage/".to_string(),
+        }
+    }
+
+    /// The prefix of the request path under which the keys are stored.
+    /// Defaults to `"__leptos_use_storage/"`.
+    pub fn prefix(self, prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            ..self
+        }
+    }
+
+    async fn cache(&self) -> Result<web_sys::Cache, JsValue> {
+        let caches = window().caches()?;
+
+        Ok(js_fut!(caches.open(&self.cache_name))
+            .await?
+            .unchecked_into())
+    }
+
+    fn request(&self, key: &str) -> String {
+        format!("/{}{}", self.prefix, key)
+    }
+}
+
+#[async_trait(?Send)]
+impl StorageBackend for CacheStorageBackend {
+    async fn get(&self, key: &str) -> Result<Option<String>, JsValue> {
+        let response = js_fut!(self.cache().await?.match_with_str(&self.request(key))).await?;
+
+        if response.is_undefined() {
+            return Ok(None);
+        }
+
+        let response: web_sys::Response = response.unchecked_into();
+        Ok(js_fut!(response.text()?).await?.as_string())
+    }
+
+    async fn set(&self, key: &str, value: &str) -> Result<(), JsValue> {
+        let response = web_sys::Response::new_with_opt_str(Some(value))?;
+        js_fut!(self
+            .cache()
+            .await?
+            .put_with_str(&self.request(key), &response))
+        .await?;
+
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), JsValue> {
+        js_fut!(self.cache().await?.delete_with_str(&self.request(key))).await?;
+
+        Ok(())
+    }
+}
+
+/// Stores every value in a file of the
+/// [Origin Private File System](https://developer.mozilla.org/en-US/docs/Web/API/File_System_API/Origin_private_file_system).
+///
+/// The files are created in the root directory and named after the keys, which therefore have to
+/// be valid file names.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OpfsBackend;
+
+impl OpfsBackend {
+    async fn root() -> Result<web_sys::FileSystemDirectoryHandle, JsValue> {
+        Ok(js_fut!(window().navigator().storage().get_directory())
+            .await?
+            .unchecked_into())
+    }
+}
+
+#[async_trait(?Send)]
+impl StorageBackend for OpfsBackend {
+    async fn get(&self, key: &str) -> Result<Option<String>, JsValue> {
+        let handle = match js_fut!(Self::root().await?.get_file_handle(key)).await {
+            Ok(handle) => handle.unchecked_into::<web_sys::FileSystemFileHandle>(),
+            Err(err) if is_not_found(&err) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        let file: web_sys::File = js_fut!(handle.get_file()).await?.unchecked_into();
+
+        Ok(js_fut!(file.text()).await?.as_string())
+    }
+
+    async fn set(&self, key: &str, value: &str) -> Result<(), JsValue> {
+        let options = web_sys::FileSystemGetFileOptions::new();
+        options.set_create(true);
+
+        let handle: web_sys::FileSystemFileHandle = js_fut!(Self::root()
+            .await?
+            .get_file_handle_with_options(key, &options))
+        .await?
+        .unchecked_into();
+
+        let writable: web_sys::FileSystemWritableFileStream =
+            js_fut!(handle.create_writable()).await?.unchecked_into();
+        js_fut!(writable.write_with_str(value)?).await?;
+        js_fut!(writable.close()).await?;
+
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), JsValue> {
+        match js_fut!(Self::root().await?.remove_entry(key)).await {
+            Err(err) if !is_not_found(&err) => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn is_not_found(err: &JsValue) -> bool {
+    err.dyn_ref::<web_sys::DomException>()
+        .is_some_and(|err| err.name() == "NotFoundError")
+}
@@ -0,0 +1,198 @@
+use crate::utils::StringCodec;
+use std::fmt;
+use std::rc::Rc;
+
+/// Decides what happens when another tab writes to a key that is used by
+/// [`fn@crate::storage::use_storage`] on this page.
+///
+/// The strategy is applied when a [`storage`](https://developer.mozilla.org/en-US/docs/Web/API/Window/storage_event)
+/// event is received. The merged value isn't written back to the storage so that tabs with
+/// different strategies don't overwrite each other endlessly. It is only persisted with the next
+/// change on this page.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::storage::{use_local_storage_with_options, StorageMergeStrategy, UseStorageOptions};
+/// # use leptos_use::utils::FromToStringCodec;
+/// #
+/// # pub fn Demo() -> impl IntoView {
+/// // keep the highest count of all tabs
+/// let (count, set_count, _) = use_local_storage_with_options::<i32, FromToStringCodec>(
+///     "my-count",
+///     UseStorageOptions::default()
+///         .merge(StorageMergeStrategy::custom(|local: i32, remote: i32| local.max(remote))),
+/// );
+/// #    view! { }
+/// # }
+/// ```
+#[derive(Default)]
+pub enum StorageMergeStrategy<T> {
+    /// Replace the local value with the one from the other tab. This is the default.
+    #[default]
+    PreferRemote,
+
+    /// Keep the local value and ignore the change of the other tab.
+    PreferLocal,
+
+    /// Both values are encoded as JSON objects and merged recursively. Keys that exist in both
+    /// objects take the value of the other tab. Everything that isn't an object is replaced by
+    /// the remote value. Use this together with [`crate::utils::JsonCodec`] for map-like data.
+    ///
+    /// Requires the feature `serde`.
+    #[cfg(feature = "serde")]
+    MergeJson,
+
+    /// Compute the new value from the local and the remote value (in this order).
+    Custom(Rc<dyn Fn(T, T) -> T>),
+}
+
+impl<T> StorageMergeStrategy<T> {
+    /// Strategy that calls `merge` with the local and the remote value.
+    pub fn custom(merge: impl Fn(T, T) -> T + 'static) -> Self {
+        Self::Custom(Rc::new(merge))
+    }
+
+    /// Merges `local` and `remote`. The stored string of the remote value is passed as
+    /// `remote_encoded`.
+    #[cfg_attr(not(feature = "serde"), allow(unused_variables))]
+    #[cfg_attr(feature = "ssr", allow(dead_code))]
+    pub(crate) fn merge<C>(
+        &self,
+        codec: &C,
+        local: T,
+        remote: T,
+        remote_encoded: &str,
+    ) -> Result<T, C::Error>
+    where
+        C: StringCodec<T>,
+    {
+        match self {
+            Self::PreferRemote => Ok(remote),
+            Self::PreferLocal => Ok(local),
+            #[cfg(feature = "serde")]
+            Self::MergeJson => {
+                let local_encoded = codec.encode(&local)?;
+
+                match merge_json(&local_encoded, remote_encoded) {
+                    Some(merged) => codec.decode(merged),
+                    None => Ok(remote),
+                }
+            }
+            Self::Custom(merge) => Ok(merge(local, remote)),
+        }
+    }
+}
+
+/// Recursively merges the JSON objects `remote` into `local`.
+/// Returns `None` if one of them isn't valid JSON.
+#[cfg(feature = "serde")]
+#[cfg_attr(feature = "ssr", allow(dead_code))]
+fn merge_json(local: &str, remote: &str) -> Option<String> {
+    use serde_json::Value;
+
+    fn merge(local: &mut Value, remote: Value) {
+        match (local, remote) {
+            (Value::Object(local), Value::Object(remote)) => {
+                for (key, remote_value) in remote {
+                    match local.get_mut(&key) {
+                        Some(local_value) => merge(local_value, remote_value),
+                        None => {
+                            local.insert(key, remote_value);
+                        }
+                    }
+                }
+            }
+            (local, remote) => *local = remote,
+        }
+    }
+
+    let mut local = serde_json::from_str::<Value>(local).ok()?;
+    let remote = serde_json::from_str::<Value>(remote).ok()?;
+
+    merge(&mut local, remote);
+
+    Some(local.to_string())
+}
+
+impl<T> Clone for StorageMergeStrategy<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::PreferRemote => Self::PreferRemote,
+            Self::PreferLocal => Self::PreferLocal,
+            #[cfg(feature = "serde")]
+            Self::MergeJson => Self::MergeJson,
+            Self::Custom(merge) => Self::Custom(Rc::clone(merge)),
+        }
+    }
+}
+
+impl<T> fmt::Debug for StorageMergeStrategy<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PreferRemote => f.write_str("PreferRemote"),
+            Self::PreferLocal => f.write_str("PreferLocal"),
+            #[cfg(feature = "serde")]
+            Self::MergeJson => f.write_str("MergeJson"),
+            Self::Custom(_) => f.debug_tuple("Custom").finish_non_exhaustive(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::FromToStringCodec;
+
+    #[test]
+    fn test_prefer_and_custom() {
+        let codec = FromToStringCodec;
+
+        let prefer_remote = StorageMergeStrategy::<i32>::PreferRemote;
+        assert_eq!(prefer_remote.merge(&codec, 1, 2, "2"), Ok(2));
+
+        let prefer_local = StorageMergeStrategy::<i32>::PreferLocal;
+        assert_eq!(prefer_local.merge(&codec, 1, 2, "2"), Ok(1));
+
+        let max = StorageMergeStrategy::custom(|local: i32, remote: i32| local.max(remote));
+        assert_eq!(max.merge(&codec, 3, 2, "2"), Ok(3));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_merge_json() {
+        use crate::utils::JsonCodec;
+        use std::collections::HashMap;
+
+        let mut local = HashMap::new();
+        local.insert("a".to_string(), 1);
+        local.insert("b".to_string(), 2);
+
+        let mut remote = HashMap::new();
+        remote.insert("b".to_string(), 3);
+        remote.insert("c".to_string(), 4);
+
+        let codec = JsonCodec;
+        let remote_encoded = codec.encode(&remote).unwrap();
+
+        let merged = StorageMergeStrategy::MergeJson
+            .merge(&codec, local, remote, &remote_encoded)
+            .unwrap();
+
+        let mut expected = HashMap::new();
+        expected.insert("a".to_string(), 1);
+        expected.insert("b".to_string(), 3);
+        expected.insert("c".to_string(), 4);
+
+        assert_eq!(merged, expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_merge_json_nested() {
+        assert_eq!(
+            merge_json(r#"{"a":{"x":1,"y":2},"b":[1]}"#, r#"{"a":{"y":3},"b":[2]}"#),
+            Some(r#"{"a":{"x":1,"y":3},"b":[2]}"#.to_string())
+        );
+        assert_eq!(merge_json("not json", "{}"), None);
+    }
+}
@@ -0,0 +1,365 @@
+use crate::storage::{StorageBackend, UseStorageOptions};
+use crate::utils::StringCodec;
+use cfg_if::cfg_if;
+use leptos::*;
+
+/// Reactive storage with an asynchronous [`StorageBackend`].
+///
+/// Works like [`fn@crate::storage::use_storage`] but can persist the data in IndexedDB, the
+/// Origin Private File System or the Cache API. Since reading from these takes some time, the
+/// hook returns a `loading` signal that is `true` until the initial value has been read.
+/// Until then `data` contains the `initial_value`. A value that is set while loading is kept and
+/// not replaced by the one that is read.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::storage::{use_async_storage, OpfsBackend, UseAsyncStorageReturn};
+/// # use leptos_use::utils::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseAsyncStorageReturn {
+///     data,
+///     set_data,
+///     remove,
+///     loading,
+/// } = use_async_storage::<i32, FromToStringCodec>(OpfsBackend, "my-count");
+///
+/// view! {
+///     <Show when=move || !loading.get() fallback=|| "Loading...">
+///         {move || data.get()}
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// Available backends are `web_sys::Storage`, [`crate::storage::IndexedDbStore`],
+/// [`crate::storage::CacheStorageBackend`] and [`crate::storage::OpfsBackend`]. You can
+/// implement [`StorageBackend`] yourself to use any other key-value store.
+///
+/// Changes are synced with other hooks that use the same key on this page. Changes from other tabs
+/// are only noticed for `web_sys::Storage`.
+///
+/// Options are the same as for [`fn@crate::storage::use_storage_with_options`] including
+/// `merge` and `version` / `migrate`.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the returned signals will just read/manipulate the `initial_value` without
+/// persistence and `loading` is always `false`.
+pub fn use_async_storage<T, C>(
+    backend: impl StorageBackend + 'static,
+    key: impl AsRef<str>,
+) -> UseAsyncStorageReturn<T, impl Fn() + Clone>
+where
+    T: Default + Clone + PartialEq,
+    C: StringCodec<T> + Default,
+{
+    use_async_storage_with_options::<T, C>(backend, key, UseStorageOptions::default())
+}
+
+/// Version of [`use_async_storage`] that accepts [`UseStorageOptions`].
+pub fn use_async_storage_with_options<T, C>(
+    backend: impl StorageBackend + 'static,
+    key: impl AsRef<str>,
+    options: UseStorageOptions<T, C>,
+) -> UseAsyncStorageReturn<T, impl Fn() + Clone>
+where
+    T: Clone + PartialEq,
+    C: StringCodec<T> + Default,
+{
+    let UseStorageOptions {
+        codec,
+        on_error,
+        listen_to_storage_changes,
+        initial_value,
+        filter,
+        merge,
+        version,
+        migrate,
+    } = options;
+
+    let (data, set_data) = initial_value.into_signal();
+    let default = data.get_untracked();
+
+    cfg_if! { if #[cfg(feature = "ssr")] {
+        let _ = codec;
+        let _ = on_error;
+        let _ = listen_to_storage_changes;
+        let _ = filter;
+        let _ = merge;
+        let _ = version;
+        let _ = migrate;
+        let _ = backend;
+        let _ = key;
+
+        let remove = move || {
+            set_data.set(default.clone());
+        };
+
+        UseAsyncStorageReturn {
+            data,
+            set_data,
+            remove,
+            loading: Signal::derive(|| false),
+        }
+    } else {
+        use crate::storage::use_storage::{
+            dispatch_internal_storage_event, handle_error, version_key, INTERNAL_STORAGE_EVENT,
+        };
+        use crate::storage::UseStorageError;
+        use crate::{use_event_listener, use_window, watch_with_options, WatchOptions};
+        use std::rc::Rc;
+
+        let backend = Rc::new(backend);
+        let key = key.as_ref().to_owned();
+
+        let (loading, set_loading) = create_signal(true);
+
+        // Incremented whenever data is replaced with the value from the backend so that it
+        // isn't written back.
+        let (fetch_id, set_fetch_id) = create_signal(0_usize);
+
+        // Incremented whenever data is changed on this page. A read that was started before a
+        // local write must not replace the written value with the older one from the backend.
+        let local_writes = store_value(0_usize);
+        let _ = watch(
+            move || (fetch_id.get(), data.get()),
+            move |(id, _), prev, _| {
+                if prev.is_some_and(|(prev_id, _)| prev_id == id) {
+                    local_writes.update_value(|writes| *writes += 1);
+                }
+            },
+            false,
+        );
+
+        let dispatch_storage_event = {
+            let key = key.to_owned();
+            let on_error = on_error.to_owned();
+            move || dispatch_internal_storage_event(key.to_owned(), on_error.to_owned())
+        };
+
+        // Reads from the backend and replaces data if changed. Values written by another tab are
+        // merged with the local value if `merge_remote` is true.
+        let fetch_from_backend = {
+            let backend = Rc::clone(&backend);
+            let codec = codec.to_owned();
+            let key = key.to_owned();
+            let on_error = on_error.to_owned();
+            move |merge_remote: bool| {
+                let backend = Rc::clone(&backend);
+                let codec = codec.to_owned();
+                let key = key.to_owned();
+                let on_error = on_error.to_owned();
+                let merge = merge.to_owned();
+                let migrate = Rc::clone(&migrate);
+                let default = default.clone();
+
+                let writes_before = local_writes.get_value();
+
+                spawn_local(async move {
+                    let result = match backend.get(&key).await {
+                        // Upgrade values written by older versions
+                        Ok(Some(encoded)) if version > 0 => {
+                            migrate_item(&*backend, &key, version, &*migrate, encoded)
+                                .await
+                                .map(Some)
+                        }
+                        result => result.map_err(UseStorageError::GetItemFailed),
+                    };
+
+                    let fetched = handle_error(&on_error, result)
+                        .unwrap_or_default() // Drop handled Err(())
+                        .map(|encoded| {
+                            let result = if merge_remote {
+                                codec.decode(encoded.clone()).and_then(|remote| {
+                                    merge.merge(&codec, data.get_untracked(), remote, &encoded)
+                                })
+                            } else {
+                                codec.decode(encoded)
+                            }
+                            .map_err(UseStorageError::ItemCodecError);
+                            handle_error(&on_error, result)
+                        })
+                        .transpose()
+                        .unwrap_or_default() // Drop handled Err(())
+                        .unwrap_or(default);
+
+                    let written_meanwhile = local_writes
+                        .try_get_value()
+                        .is_some_and(|writes| writes != writes_before);
+
+                    if !written_meanwhile && fetched != data.get_untracked() {
+                        batch(|| {
+                            set_fetch_id.update(|id| *id += 1);
+                            set_data.set(fetched);
+                        });
+                    }
+
+                    set_loading.set(false);
+                });
+            }
+        };
+
+        // Fetch initial value
+        fetch_from_backend(false);
+
+        // Write to the backend when data is changed on this page
+        {
+            let backend = Rc::clone(&backend);
+            let codec = codec.to_owned();
+            let key = key.to_owned();
+            let on_error = on_error.to_owned();
+            let dispatch_storage_event = dispatch_storage_event.to_owned();
+            let _ = watch_with_options(
+                move || (fetch_id.get(), data.get()),
+                move |(id, value), prev, _| {
+                    // Skip values that have just been read from the backend
+                    if prev.map(|(prev_id, _)| *prev_id != *id).unwrap_or_default() {
+                        return;
+                    }
+
+                    let result = codec.encode(value).map_err(UseStorageError::ItemCodecError);
+                    let Ok(encoded) = handle_error(&on_error, result) else {
+                        return;
+                    };
+
+                    let backend = Rc::clone(&backend);
+                    let key = key.to_owned();
+                    let on_error = on_error.to_owned();
+                    let dispatch_storage_event = dispatch_storage_event.to_owned();
+
+                    spawn_local(async move {
+                        let mut result = backend.set(&key, &encoded).await;
+                        if result.is_ok() && version > 0 {
+                            result = backend
+                                .set(&version_key(&key), &version.to_string())
+                                .await;
+                        }
+                        let result = result.map_err(UseStorageError::SetItemFailed);
+
+                        if handle_error(&on_error, result).is_ok() {
+                            dispatch_storage_event();
+                        }
+                    });
+                },
+                WatchOptions::default().filter(filter),
+            );
+        }
+
+        if listen_to_storage_changes {
+            // Listen to global storage events which are only sent for `web_sys::Storage`
+            let check_key = key.to_owned();
+            let fetch = fetch_from_backend.to_owned();
+            let _ = use_event_listener(use_window(), leptos::ev::storage, move |ev| {
+                let ev_key = ev.key();
+                // Key matches or all keys deleted (None)
+                if ev_key == Some(check_key.clone()) || ev_key.is_none() {
+                    fetch(true);
+                }
+            });
+
+            // Listen to internal storage events
+            let check_key = key.to_owned();
+            let fetch = fetch_from_backend.to_owned();
+            let _ = use_event_listener(
+                use_window(),
+                ev::Custom::new(INTERNAL_STORAGE_EVENT),
+                move |ev: web_sys::CustomEvent| {
+                    if Some(check_key.clone()) == ev.detail().as_string() {
+                        fetch(false);
+                    }
+                },
+            );
+        }
+
+        // Remove from backend fn
+        let remove = move || {
+            let backend = Rc::clone(&backend);
+            let key = key.to_owned();
+            let on_error = on_error.to_owned();
+            let fetch = fetch_from_backend.to_owned();
+            let dispatch_storage_event = dispatch_storage_event.to_owned();
+
+            spawn_local(async move {
+                let mut result = backend.remove(&key).await;
+                if result.is_ok() && version > 0 {
+                    result = backend.remove(&version_key(&key)).await;
+                }
+                let result = result.map_err(UseStorageError::RemoveItemFailed);
+                let _ = handle_error(&on_error, result);
+
+                fetch(false);
+                dispatch_storage_event();
+            });
+        };
+
+        UseAsyncStorageReturn {
+            data,
+            set_data,
+            remove,
+            loading: loading.into(),
+        }
+    }}
+}
+
+/// Migrates `encoded`, which is the stored value of `key`, if it has been written by an older
+/// version and writes the result back to the backend.
+#[cfg(not(feature = "ssr"))]
+async fn migrate_item<Err>(
+    backend: &impl StorageBackend,
+    key: &str,
+    version: u32,
+    migrate: &dyn Fn(u32, String) -> String,
+    encoded: String,
+) -> Result<String, crate::storage::UseStorageError<Err>> {
+    use crate::storage::use_storage::version_key;
+    use crate::storage::UseStorageError;
+
+    let version_key = version_key(key);
+
+    let stored_version = backend
+        .get(&version_key)
+        .await
+        .map_err(UseStorageError::GetItemFailed)?
+        .and_then(|stored_version| stored_version.parse().ok())
+        .unwrap_or(0);
+
+    if stored_version >= version {
+        return Ok(encoded);
+    }
+
+    let migrated = migrate(stored_version, encoded);
+
+    backend
+        .set(key, &migrated)
+        .await
+        .map_err(UseStorageError::SetItemFailed)?;
+    backend
+        .set(&version_key, &version.to_string())
+        .await
+        .map_err(UseStorageError::SetItemFailed)?;
+
+    Ok(migrated)
+}
+
+/// Return type of [`use_async_storage`].
+pub struct UseAsyncStorageReturn<T, RemoveFn>
+where
+    T: 'static,
+    RemoveFn: Fn() + Clone,
+{
+    /// The stored value or the `initial_value` while loading or if the key isn't set
+    pub data: Signal<T>,
+
+    /// Sets the value and writes it to the backend
+    pub set_data: WriteSignal<T>,
+
+    /// Removes the key from the backend and resets `data` to the `initial_value`
+    pub remove: RemoveFn,
+
+    /// `true` until the initial value has been read from the backend
+    pub loading: Signal<bool>,
+}
@@ -0,0 +1,201 @@
+use crate::{js, js_fut, use_supported};
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Reactive [Cache API](https://developer.mozilla.org/en-US/docs/Web/API/Cache).
+///
+/// Provides access to the cache with the given name and a signal with the URLs of all requests
+/// that are currently cached. This is for example useful to build the UI of an offline download
+/// manager.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::storage::use_cache_storage;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let cache = use_cache_storage("downloads");
+///
+/// let download = move |_| {
+///     spawn_local(async move {
+///         // fetches the url and stores the response
+///         let _ = cache.add("/videos/intro.mp4").await;
+///     });
+/// };
+///
+/// view! {
+///     <button on:click=download>"Download"</button>
+///     <ul>
+///         <For each=move || cache.urls.get() key=|url| url.clone() let:url>
+///             <li>
+///                 {url.clone()}
+///                 <button on:click=move |_| {
+///                     let url = url.clone();
+///                     spawn_local(async move {
+///                         let _ = cache.delete(&url).await;
+///                     });
+///                 }>"Delete"</button>
+///             </li>
+///         </For>
+///     </ul>
+/// }
+/// # }
+/// ```
+///
+/// All operations update `urls` when they're done. Use `refresh` if the cache is changed from
+/// elsewhere, for example by a service worker. The cache name can be a signal in which case
+/// `urls` is read again whenever it changes.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `urls` is always empty and all operations return
+/// `UseCacheStorageError::NotSupported`.
+pub fn use_cache_storage(cache_name: impl Into<MaybeSignal<String>>) -> UseCacheStorageReturn {
+    let cache_name = cache_name.into();
+
+    let is_supported = use_supported(|| js!("caches" in &window()));
+
+    let (urls, set_urls) = create_signal(Vec::<String>::new());
+    let (error, set_error) = create_signal(None::<UseCacheStorageError>);
+
+    let cache_name = Signal::derive(move || cache_name.get());
+
+    let cache = UseCacheStorageReturn {
+        is_supported,
+        urls: urls.into(),
+        error: error.into(),
+        cache_name,
+        set_urls,
+        set_error,
+    };
+
+    let _ = watch(
+        move || cache_name.get(),
+        move |_, _, _| cache.refresh(),
+        true,
+    );
+
+    cache
+}
+
+/// Return type of [`use_cache_storage`].
+#[derive(Clone, Copy)]
+pub struct UseCacheStorageReturn {
+    /// Whether the Cache API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The URLs of all cached requests
+    pub urls: Signal<Vec<String>>,
+
+    /// The last error that occurred while reading the cached requests
+    pub error: Signal<Option<UseCacheStorageError>>,
+
+    cache_name: Signal<String>,
+    set_urls: WriteSignal<Vec<String>>,
+    set_error: WriteSignal<Option<UseCacheStorageError>>,
+}
+
+impl UseCacheStorageReturn {
+    /// Returns the cached response for `url` if there is one.
+    pub async fn get(&self, url: &str) -> Result<Option<web_sys::Response>, UseCacheStorageError> {
+        let response = js_fut!(self.cache().await?.match_with_str(url))
+            .await
+            .map_err(UseCacheStorageError::Failed)?;
+
+        Ok(response.dyn_into().ok())
+    }
+
+    /// Stores `response` for `url`.
+    pub async fn put(
+        &self,
+        url: &str,
+        response: &web_sys::Response,
+    ) -> Result<(), UseCacheStorageError> {
+        js_fut!(self.cache().await?.put_with_str(url, response))
+            .await
+            .map_err(UseCacheStorageError::Failed)?;
+
+        self.refresh();
+
+        Ok(())
+    }
+
+    /// Fetches `url` and stores the response. Fails if the response status isn't ok.
+    pub async fn add(&self, url: &str) -> Result<(), UseCacheStorageError> {
+        js_fut!(self.cache().await?.add_with_str(url))
+            .await
+            .map_err(UseCacheStorageError::Failed)?;
+
+        self.refresh();
+
+        Ok(())
+    }
+
+    /// Deletes the cached response for `url`. Returns `false` if there was none.
+    pub async fn delete(&self, url: &str) -> Result<bool, UseCacheStorageError> {
+        let deleted = js_fut!(self.cache().await?.delete_with_str(url))
+            .await
+            .map_err(UseCacheStorageError::Failed)?;
+
+        self.refresh();
+
+        Ok(deleted.as_bool().unwrap_or_default())
+    }
+
+    /// Reads the URLs of the cached requests again.
+    pub fn refresh(&self) {
+        if !self.is_supported.get_untracked() {
+            return;
+        }
+
+        let cache = *self;
+
+        spawn_local(async move {
+            match cache.read_urls().await {
+                Ok(urls) => {
+                    cache.set_urls.set(urls);
+                    cache.set_error.set(None);
+                }
+                Err(err) => cache.set_error.set(Some(err)),
+            }
+        });
+    }
+
+    async fn read_urls(&self) -> Result<Vec<String>, UseCacheStorageError> {
+        let requests = js_fut!(self.cache().await?.keys())
+            .await
+            .map_err(UseCacheStorageError::Failed)?;
+
+        Ok(requests
+            .unchecked_into::<js_sys::Array>()
+            .iter()
+            .map(|request| request.unchecked_into::<web_sys::Request>().url())
+            .collect())
+    }
+
+    async fn cache(&self) -> Result<web_sys::Cache, UseCacheStorageError> {
+        if !self.is_supported.get_untracked() {
+            return Err(UseCacheStorageError::NotSupported);
+        }
+
+        let caches = window().caches().map_err(UseCacheStorageError::Failed)?;
+
+        let cache = js_fut!(caches.open(&self.cache_name.get_untracked()))
+            .await
+            .map_err(UseCacheStorageError::Failed)?;
+
+        Ok(cache.unchecked_into())
+    }
+}
+
+/// Errors of [`use_cache_storage`].
+#[derive(Error, Debug, Clone)]
+pub enum UseCacheStorageError {
+    #[error("the Cache API is not supported")]
+    NotSupported,
+    #[error("cache operation failed")]
+    Failed(JsValue),
+}
@@ -0,0 +1,474 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::js_fut;
+use crate::utils::StringCodec;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Reactive [IndexedDB](https://developer.mozilla.org/en-US/docs/Web/API/IndexedDB_API).
+///
+/// Opens (and if necessary creates or upgrades) the database with the given name. Values are
+/// kept in object stores and encoded as strings with a [`StringCodec`] like in [`fn@crate::storage::use_storage`].
+/// Other than local storage, IndexedDB can hold large amounts of data which makes it a good fit
+/// for offline-first apps.
+///
+/// ## Usage
+///
+/// List the object stores of your database with the option `stores`. They're created when the
+/// database is created or when its `version` is increased. To change stores that already exist
+/// use `on_upgrade`.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::storage::{use_indexed_db_with_options, UseIndexedDbOptions};
+/// # use leptos_use::utils::JsonCodec;
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// #[derive(Serialize, Deserialize, Clone, PartialEq)]
+/// pub struct Todo {
+///     pub title: String,
+///     pub done: bool,
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let db = use_indexed_db_with_options(
+///     "my-app",
+///     UseIndexedDbOptions::default().stores(vec!["todos".to_string()]),
+/// );
+///
+/// let todos = db.store::<Todo, JsonCodec>("todos");
+///
+/// // re-read whenever a value is written through this hook
+/// let first_todo = todos.live("first");
+///
+/// let add_todo = move |_| {
+///     spawn_local(async move {
+///         let todo = Todo { title: "Buy milk".to_string(), done: false };
+///         let _ = todos.put("first", &todo).await;
+///     });
+/// };
+/// #
+/// # view! { <button on:click=add_todo>"Add"</button> }
+/// # }
+/// ```
+///
+/// `get`, `put` and `delete` wait until the database has been opened, so they can be called right
+/// away.
+///
+/// When `version` is increased while another tab still has the database open with an older
+/// version, the upgrade has to wait until that tab closes it. In the meantime `error` is
+/// `IndexedDbError::Blocked` and the operations wait. The hook itself closes the database when
+/// another tab upgrades it so that it doesn't block others. `error` then becomes
+/// `IndexedDbError::ClosedForUpgrade` and this tab has to be reloaded to use the new version.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the database is never opened. `db` is always `None` and all operations return
+/// `IndexedDbError::NotSupported`.
+pub fn use_indexed_db(name: &str) -> UseIndexedDbReturn {
+    use_indexed_db_with_options(name, UseIndexedDbOptions::default())
+}
+
+/// Version of [`use_indexed_db`] that takes a `UseIndexedDbOptions`. See [`use_indexed_db`] for how to use.
+pub fn use_indexed_db_with_options(name: &str, options: UseIndexedDbOptions) -> UseIndexedDbReturn {
+    let UseIndexedDbOptions {
+        version,
+        stores,
+        on_upgrade,
+        on_error,
+    } = options;
+
+    let (db, set_db) = create_signal(None::<web_sys::IdbDatabase>);
+    let (error, set_error) = create_signal(None::<IndexedDbError>);
+    let changed = create_trigger();
+
+    let report_error = move |err: IndexedDbError| {
+        #[cfg(debug_assertions)]
+        let prev = SpecialNonReactiveZone::enter();
+
+        on_error(err.clone());
+
+        #[cfg(debug_assertions)]
+        SpecialNonReactiveZone::exit(prev);
+
+        set_error.set(Some(err));
+    };
+
+    #[cfg(feature = "ssr")]
+    let opened = None;
+
+    #[cfg(not(feature = "ssr"))]
+    let opened = {
+        on_cleanup(move || {
+            if let Some(db) = db.get_untracked() {
+                db.close();
+            }
+        });
+
+        let on_blocked = {
+            let report_error = report_error.clone();
+            move || report_error(IndexedDbError::Blocked)
+        };
+
+        match open_database(name, version, stores, on_upgrade, on_blocked) {
+            Ok(request) => {
+                let promise = request_promise(&request);
+
+                spawn_local({
+                    let promise = promise.clone();
+
+                    async move {
+                        match js_fut!(promise).await {
+                            Ok(database) => {
+                                let database: web_sys::IdbDatabase = database.unchecked_into();
+
+                                // let other tabs upgrade the database, this tab has to be
+                                // reloaded to use the new version
+                                let on_version_change = Closure::once_into_js({
+                                    let database = database.clone();
+
+                                    move || {
+                                        database.close();
+                                        set_db.set(None);
+                                        report_error(IndexedDbError::ClosedForUpgrade);
+                                    }
+                                });
+                                database
+                                    .set_onversionchange(Some(on_version_change.unchecked_ref()));
+
+                                if error.with_untracked(|error| {
+                                    matches!(error, Some(IndexedDbError::Blocked))
+                                }) {
+                                    set_error.set(None);
+                                }
+                                set_db.set(Some(database));
+                            }
+                            Err(err) => report_error(IndexedDbError::OpenFailed(err)),
+                        }
+                    }
+                });
+
+                Some(promise)
+            }
+            Err(err) => {
+                report_error(err);
+                None
+            }
+        }
+    };
+
+    UseIndexedDbReturn {
+        db: db.into(),
+        error: error.into(),
+        opened: store_value(opened),
+        changed,
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+fn open_database(
+    name: &str,
+    version: u32,
+    stores: Vec<String>,
+    on_upgrade: Rc<dyn Fn(web_sys::IdbDatabase, u32, u32)>,
+    on_blocked: impl FnOnce() + 'static,
+) -> Result<web_sys::IdbOpenDbRequest, IndexedDbError> {
+    let factory = window()
+        .indexed_db()
+        .ok()
+        .flatten()
+        .ok_or(IndexedDbError::NotSupported)?;
+
+    let request = factory
+        .open_with_u32(name, version)
+        .map_err(IndexedDbError::OpenFailed)?;
+
+    let on_upgrade_needed = Closure::once_into_js({
+        let request = request.clone();
+
+        move |e: web_sys::IdbVersionChangeEvent| {
+            let Ok(database) = request.result() else {
+                return;
+            };
+            let database: web_sys::IdbDatabase = database.unchecked_into();
+
+            let store_names = database.object_store_names();
+            for store in &stores {
+                if !store_names.contains(store) {
+                    let _ = database.create_object_store(store);
+                }
+            }
+
+            on_upgrade(
+                database,
+                e.old_version() as u32,
+                e.new_version().unwrap_or(version as f64) as u32,
+            );
+        }
+    });
+    request.set_onupgradeneeded(Some(on_upgrade_needed.unchecked_ref()));
+
+    // another tab still has an older version of the database open
+    let on_blocked = Closure::once_into_js(on_blocked);
+    request.set_onblocked(Some(on_blocked.unchecked_ref()));
+
+    Ok(request)
+}
+
+/// Promise that resolves with the result of the request or rejects with its error.
+fn request_promise(request: &web_sys::IdbRequest) -> js_sys::Promise {
+    js_sys::Promise::new(&mut |resolve, reject| {
+        let on_success = Closure::once_into_js({
+            let request = request.clone();
+
+            move || {
+                let _ = resolve.call1(&JsValue::NULL, &request.result().unwrap_or_default());
+            }
+        });
+        request.set_onsuccess(Some(on_success.unchecked_ref()));
+
+        let on_error = Closure::once_into_js({
+            let request = request.clone();
+
+            move || {
+                let error = request.error().ok().flatten().map(JsValue::from);
+                let _ = reject.call1(&JsValue::NULL, &error.unwrap_or_default());
+            }
+        });
+        request.set_onerror(Some(on_error.unchecked_ref()));
+    })
+}
+
+/// Options for [`use_indexed_db_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseIndexedDbOptions {
+    /// Version of the database. Increase it when you change the stores to run the upgrade.
+    /// Defaults to 1.
+    version: u32,
+
+    /// Names of the object stores that are created on upgrade if they don't exist yet.
+    #[builder(into)]
+    stores: Vec<String>,
+
+    /// Called while the database is upgraded with the database, the old version and the new
+    /// version. The stores listed in `stores` have already been created at this point.
+    /// The old version is `0` if the database didn't exist before.
+    on_upgrade: Rc<dyn Fn(web_sys::IdbDatabase, u32, u32)>,
+
+    /// Called when the database can't be opened, when opening it is blocked by another tab or
+    /// when it has been closed for an upgrade in another tab.
+    on_error: Rc<dyn Fn(IndexedDbError)>,
+}
+
+impl Default for UseIndexedDbOptions {
+    fn default() -> Self {
+        Self {
+            version: 1,
+            stores: vec![],
+            on_upgrade: Rc::new(|_, _, _| {}),
+            on_error: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// Return type of [`use_indexed_db`].
+#[derive(Clone, Copy)]
+pub struct UseIndexedDbReturn {
+    /// The database once it has been opened.
+    pub db: Signal<Option<web_sys::IdbDatabase>>,
+
+    /// Set if the database couldn't be opened or has been closed. See [`IndexedDbError`].
+    pub error: Signal<Option<IndexedDbError>>,
+
+    opened: StoredValue<Option<js_sys::Promise>>,
+    changed: Trigger,
+}
+
+impl UseIndexedDbReturn {
+    /// Access the object store with the given name. Values are encoded with the codec `C`.
+    /// The store has to be listed in [`UseIndexedDbOptions::stores`] or created in `on_upgrade`.
+    pub fn store<T, C>(&self, name: impl Into<String>) -> IndexedDbStore<T, C>
+    where
+        C: StringCodec<T> + Default,
+    {
+        IndexedDbStore {
+            name: store_value(name.into()),
+            opened: self.opened,
+            changed: self.changed,
+            codec: C::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// An object store of a database opened by [`use_indexed_db`]. Created with [`UseIndexedDbReturn::store`].
+pub struct IndexedDbStore<T, C>
+where
+    C: StringCodec<T>,
+{
+    name: StoredValue<String>,
+    opened: StoredValue<Option<js_sys::Promise>>,
+    changed: Trigger,
+    codec: C,
+    _marker: PhantomData<T>,
+}
+
+impl<T, C> Clone for IndexedDbStore<T, C>
+where
+    C: StringCodec<T>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name,
+            opened: self.opened,
+            changed: self.changed,
+            codec: self.codec.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, C> Copy for IndexedDbStore<T, C> where C: StringCodec<T> + Copy {}
+
+impl<T, C> IndexedDbStore<T, C>
+where
+    T: 'static,
+    C: StringCodec<T>,
+{
+    /// Reads the value for `key`. Returns `None` if there is none.
+    pub async fn get(&self, key: &str) -> Result<Option<T>, IndexedDbStoreError<C::Error>> {
+        let request = self
+            .object_store(web_sys::IdbTransactionMode::Readonly)
+            .await?
+            .get(&JsValue::from_str(key))
+            .map_err(IndexedDbError::RequestFailed)?;
+
+        let value = js_fut!(request_promise(&request))
+            .await
+            .map_err(IndexedDbError::RequestFailed)?;
+
+        value
+            .as_string()
+            .map(|encoded| self.codec.decode(encoded))
+            .transpose()
+            .map_err(IndexedDbStoreError::Codec)
+    }
+
+    /// Writes `value` for `key`. Signals returned by [`Self::live`] are updated afterwards.
+    pub async fn put(&self, key: &str, value: &T) -> Result<(), IndexedDbStoreError<C::Error>> {
+        let encoded = self
+            .codec
+            .encode(value)
+            .map_err(IndexedDbStoreError::Codec)?;
+
+        let request = self
+            .object_store(web_sys::IdbTransactionMode::Readwrite)
+            .await?
+            .put_with_key(&JsValue::from_str(&encoded), &JsValue::from_str(key))
+            .map_err(IndexedDbError::RequestFailed)?;
+
+        self.wait_for_write(request).await
+    }
+
+    /// Deletes the value for `key`. Signals returned by [`Self::live`] are updated afterwards.
+    pub async fn delete(&self, key: &str) -> Result<(), IndexedDbStoreError<C::Error>> {
+        let request = self
+            .object_store(web_sys::IdbTransactionMode::Readwrite)
+            .await?
+            .delete(&JsValue::from_str(key))
+            .map_err(IndexedDbError::RequestFailed)?;
+
+        self.wait_for_write(request).await
+    }
+
+    /// Signal of the value for `key` that is read again whenever a value is written or deleted
+    /// through any store of this database. Errors are ignored and leave the signal unchanged.
+    pub fn live(&self, key: impl Into<String>) -> Signal<Option<T>>
+    where
+        T: Clone,
+    {
+        let key = key.into();
+        let (value, set_value) = create_signal(None::<T>);
+        let store = self.clone();
+        let changed = self.changed;
+
+        create_effect(move |_| {
+            changed.track();
+
+            let store = store.clone();
+            let key = key.clone();
+            spawn_local(async move {
+                if let Ok(new_value) = store.get(&key).await {
+                    set_value.set(new_value);
+                }
+            });
+        });
+
+        value.into()
+    }
+
+    async fn wait_for_write(
+        &self,
+        request: web_sys::IdbRequest,
+    ) -> Result<(), IndexedDbStoreError<C::Error>> {
+        js_fut!(request_promise(&request))
+            .await
+            .map_err(IndexedDbError::RequestFailed)?;
+
+        self.changed.notify();
+
+        Ok(())
+    }
+
+    async fn object_store(
+        &self,
+        mode: web_sys::IdbTransactionMode,
+    ) -> Result<web_sys::IdbObjectStore, IndexedDbError> {
+        let opened = self
+            .opened
+            .get_value()
+            .ok_or(IndexedDbError::NotSupported)?;
+
+        let database: web_sys::IdbDatabase = js_fut!(opened)
+            .await
+            .map_err(IndexedDbError::OpenFailed)?
+            .unchecked_into();
+
+        let name = self.name.get_value();
+
+        database
+            .transaction_with_str_and_mode(&name, mode)
+            .and_then(|transaction| transaction.object_store(&name))
+            .map_err(IndexedDbError::RequestFailed)
+    }
+}
+
+/// Errors of [`use_indexed_db`].
+#[derive(Error, Debug, Clone)]
+pub enum IndexedDbError {
+    #[error("IndexedDB is not supported")]
+    NotSupported,
+    #[error("failed to open database")]
+    OpenFailed(JsValue),
+    #[error("request failed")]
+    RequestFailed(JsValue),
+    #[error("opening the database is blocked by another tab that has an older version open")]
+    Blocked,
+    #[error("the database has been closed because another tab upgrades it")]
+    ClosedForUpgrade,
+}
+
+/// Errors of the operations of an [`IndexedDbStore`].
+#[derive(Error, Debug)]
+pub enum IndexedDbStoreError<Err> {
+    #[error(transparent)]
+    Db(#[from] IndexedDbError),
+    #[error("failed to encode / decode item value")]
+    Codec(Err),
+}
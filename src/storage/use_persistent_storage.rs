@@ -0,0 +1,129 @@
+use crate::{js, js_fut, use_supported};
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::JsValue;
+
+/// Reactive [StorageManager.persisted()](https://developer.mozilla.org/en-US/docs/Web/API/StorageManager/persisted)
+/// and [StorageManager.persist()](https://developer.mozilla.org/en-US/docs/Web/API/StorageManager/persist).
+///
+/// By default browsers may evict the data of your origin in IndexedDB, the Cache API and the
+/// like when the device runs low on storage. Persistent storage is only cleared by the user.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::storage::use_persistent_storage;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let storage = use_persistent_storage();
+///
+/// let request = move |_| {
+///     spawn_local(async move {
+///         let _ = storage.request_persistence().await;
+///     });
+/// };
+///
+/// view! {
+///     <Show
+///         when=move || storage.is_persisted.get()
+///         fallback=move || view! { <button on:click=request>"Keep my data offline"</button> }
+///     >
+///         <p>"Your data is stored persistently"</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// `is_persisted` is read once on creation. Browsers decide on their own whether they grant
+/// persistence. Chromium based browsers grant it without asking for installed or frequently
+/// used apps, Firefox asks the user.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_persisted` is always `false` and `request_persistence` returns
+/// `UsePersistentStorageError::NotSupported`.
+pub fn use_persistent_storage() -> UsePersistentStorageReturn {
+    let is_supported = use_supported(|| {
+        js!("storage" in &window().navigator()) && js!("persist" in &window().navigator().storage())
+    });
+
+    let (is_persisted, set_persisted) = create_signal(false);
+    let (error, set_error) = create_signal(None::<UsePersistentStorageError>);
+
+    if is_supported.get_untracked() {
+        spawn_local(async move {
+            let persisted =
+                async { js_fut!(window().navigator().storage().persisted()?).await }.await;
+
+            match persisted {
+                Ok(persisted) => {
+                    let _ = set_persisted.try_set(persisted.as_bool().unwrap_or_default());
+                }
+                Err(err) => {
+                    let _ = set_error.try_set(Some(UsePersistentStorageError::Persisted(err)));
+                }
+            }
+        });
+    }
+
+    UsePersistentStorageReturn {
+        is_supported,
+        is_persisted: is_persisted.into(),
+        error: error.into(),
+        set_persisted,
+        set_error,
+    }
+}
+
+/// Return type of [`use_persistent_storage`].
+#[derive(Clone, Copy)]
+pub struct UsePersistentStorageReturn {
+    /// Whether persistent storage is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// Whether the storage of the origin is persistent
+    pub is_persisted: Signal<bool>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UsePersistentStorageError>>,
+
+    set_persisted: WriteSignal<bool>,
+    set_error: WriteSignal<Option<UsePersistentStorageError>>,
+}
+
+impl UsePersistentStorageReturn {
+    /// Asks the browser to make the storage persistent. Returns whether it has been granted.
+    pub async fn request_persistence(&self) -> Result<bool, UsePersistentStorageError> {
+        if !self.is_supported.get_untracked() {
+            return Err(UsePersistentStorageError::NotSupported);
+        }
+
+        let persisted = async { js_fut!(window().navigator().storage().persist()?).await }
+            .await
+            .map_err(|err| {
+                let err = UsePersistentStorageError::Persist(err);
+                self.set_error.set(Some(err.clone()));
+                err
+            })?
+            .as_bool()
+            .unwrap_or_default();
+
+        self.set_persisted.set(persisted);
+        self.set_error.set(None);
+
+        Ok(persisted)
+    }
+}
+
+/// Errors of [`use_persistent_storage`].
+#[derive(Error, Debug, Clone)]
+pub enum UsePersistentStorageError {
+    #[error("persistent storage is not supported")]
+    NotSupported,
+    #[error("failed to read whether the storage is persistent")]
+    Persisted(JsValue),
+    #[error("failed to request persistent storage")]
+    Persist(JsValue),
+}
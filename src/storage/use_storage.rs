@@ -1,5 +1,6 @@
 use crate::{
     core::{MaybeRwSignal, StorageType},
+    storage::StorageMergeStrategy,
     utils::{FilterOptions, StringCodec},
 };
 use cfg_if::cfg_if;
@@ -8,7 +9,7 @@ use std::rc::Rc;
 use thiserror::Error;
 use wasm_bindgen::JsValue;
 
-const INTERNAL_STORAGE_EVENT: &str = "leptos-use-storage";
+pub(super) const INTERNAL_STORAGE_EVENT: &str = "leptos-use-storage";
 
 /// Reactive [Storage](https://developer.mozilla.org/en-US/docs/Web/API/Storage).
 ///
@@ -30,6 +31,8 @@ const INTERNAL_STORAGE_EVENT: &str = "leptos-use-storage";
 ///
 /// > To use the [`JsonCodec`], you will need to add the `"serde"` feature to your project's `Cargo.toml`.
 /// > To use [`ProstCodec`], add the feature `"prost"`.
+/// > Binary codecs like [`MsgpackCodec`] or [`BincodeCodec`] can be used by wrapping them in
+/// > [`Base64`] which requires the feature `"base64"`, e.g. `Base64<MsgpackCodec>`.
 ///
 /// ## Example
 ///
@@ -79,6 +82,40 @@ const INTERNAL_STORAGE_EVENT: &str = "leptos-use-storage";
 /// }
 /// ```
 ///
+/// ## Migrations
+///
+/// If the format of your stored data changes, set a `version` and a `migrate` function. Values
+/// written by an older version are passed to `migrate` as raw strings before they are decoded
+/// instead of failing to decode and being reset to the initial value.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::storage::{use_local_storage_with_options, UseStorageOptions};
+/// # use leptos_use::utils::FromToStringCodec;
+/// #
+/// # pub fn Demo() -> impl IntoView {
+/// // version 1 stored the temperature as a float, version 2 stores tenths of a degree
+/// let (temperature, set_temperature, _) = use_local_storage_with_options::<i32, FromToStringCodec>(
+///     "temperature",
+///     UseStorageOptions::default()
+///         .version(2)
+///         .migrate(|old_version, value| match old_version {
+///             0 | 1 => value
+///                 .parse::<f64>()
+///                 .map(|degrees| ((degrees * 10.0) as i32).to_string())
+///                 .unwrap_or(value),
+///             _ => value,
+///         }),
+/// );
+/// #    view! { }
+/// # }
+/// ```
+///
+/// ## Changes From Other Tabs
+///
+/// By default a value written by another tab replaces the local value. Use the option `merge` with a
+/// [`StorageMergeStrategy`] to keep the local value or to combine both.
+///
 /// ## Create Your Own Custom Codec
 ///
 /// All you need to do is to implement the [`StringCodec`] trait together with `Default` and `Clone`.
@@ -114,6 +151,9 @@ where
         listen_to_storage_changes,
         initial_value,
         filter,
+        merge,
+        version,
+        migrate,
     } = options;
 
     let (data, set_data) = initial_value.into_signal();
@@ -124,6 +164,9 @@ where
         let _ = on_error;
         let _ = listen_to_storage_changes;
         let _ = filter;
+        let _ = merge;
+        let _ = version;
+        let _ = migrate;
         let _ = storage_type;
         let _ = key;
         let _ = INTERNAL_STORAGE_EVENT;
@@ -144,32 +187,16 @@ where
             .and_then(|s| s.ok_or(UseStorageError::StorageReturnedNone));
         let storage = handle_error(&on_error, storage);
 
-        // Schedules a storage event microtask. Uses a queue to avoid re-entering the runtime
+        // Schedules a storage event microtask
         let dispatch_storage_event = {
             let key = key.as_ref().to_owned();
             let on_error = on_error.to_owned();
-            move || {
-                let key = key.to_owned();
-                let on_error = on_error.to_owned();
-                queue_microtask(move || {
-                    // TODO : better to use a BroadcastChannel (use_broadcast_channel)?
-                    // Note: we cannot construct a full StorageEvent so we _must_ rely on a custom event
-                    let mut custom = web_sys::CustomEventInit::new();
-                    custom.detail(&JsValue::from_str(&key));
-                    let result = window()
-                        .dispatch_event(
-                            &web_sys::CustomEvent::new_with_event_init_dict(
-                                INTERNAL_STORAGE_EVENT,
-                                &custom,
-                            )
-                            .expect("failed to create custom storage event"),
-                        )
-                        .map_err(UseStorageError::NotifyItemChangedFailed);
-                    let _ = handle_error(&on_error, result);
-                })
-            }
+            move || dispatch_internal_storage_event(key.to_owned(), on_error.to_owned())
         };
 
+        // Set before fetching a value that has been changed by another tab
+        let is_remote_change = store_value(false);
+
         // Fetches direct from browser storage and fills set_data if changed (memo)
         let fetch_from_storage = {
             let storage = storage.to_owned();
@@ -177,21 +204,37 @@ where
             let key = key.as_ref().to_owned();
             let on_error = on_error.to_owned();
             move || {
+                let merge_remote = is_remote_change
+                    .try_update_value(std::mem::take)
+                    .unwrap_or_default();
+
                 let fetched = storage
                     .to_owned()
                     .and_then(|storage| {
                         // Get directly from storage
                         let result = storage
                             .get_item(&key)
-                            .map_err(UseStorageError::GetItemFailed);
+                            .map_err(UseStorageError::GetItemFailed)
+                            .and_then(|encoded| match encoded {
+                                // Upgrade values written by older versions
+                                Some(encoded) if version > 0 => {
+                                    migrate_item(&storage, &key, version, &migrate, encoded).map(Some)
+                                }
+                                encoded => Ok(encoded),
+                            });
                         handle_error(&on_error, result)
                     })
                     .unwrap_or_default() // Drop handled Err(())
                     .map(|encoded| {
                         // Decode item
-                        let result = codec
-                            .decode(encoded)
-                            .map_err(UseStorageError::ItemCodecError);
+                        let result = if merge_remote {
+                            codec.decode(encoded.clone()).and_then(|remote| {
+                                merge.merge(&codec, data.get_untracked(), remote, &encoded)
+                            })
+                        } else {
+                            codec.decode(encoded)
+                        }
+                        .map_err(UseStorageError::ItemCodecError);
                         handle_error(&on_error, result)
                     })
                     .transpose()
@@ -254,6 +297,15 @@ where
                                 storage
                                     .set_item(&key, &enc_value)
                                     .map_err(UseStorageError::SetItemFailed)
+                            })
+                            .and_then(|_| {
+                                if version > 0 {
+                                    storage
+                                        .set_item(&version_key(&key), &version.to_string())
+                                        .map_err(UseStorageError::SetItemFailed)
+                                } else {
+                                    Ok(())
+                                }
                             });
                         let result = handle_error(&on_error, result);
                         // Send internal storage event
@@ -273,6 +325,7 @@ where
                 let ev_key = ev.key();
                 // Key matches or all keys deleted (None)
                 if ev_key == Some(check_key.clone()) || ev_key.is_none() {
+                    is_remote_change.set_value(true);
                     notify.notify()
                 }
             });
@@ -297,6 +350,13 @@ where
                     // Delete directly from storage
                     let result = storage
                         .remove_item(&key)
+                        .and_then(|_| {
+                            if version > 0 {
+                                storage.remove_item(&version_key(&key))
+                            } else {
+                                Ok(())
+                            }
+                        })
                         .map_err(UseStorageError::RemoveItemFailed);
                     let _ = handle_error(&on_error, result);
                     notify.notify();
@@ -331,20 +391,85 @@ pub enum UseStorageError<Err> {
 /// Options for use with [`use_local_storage_with_options`], [`use_session_storage_with_options`] and [`use_storage_with_options`].
 pub struct UseStorageOptions<T: 'static, C: StringCodec<T>> {
     // Translates to and from UTF-16 strings
-    codec: C,
+    pub(super) codec: C,
     // Callback for when an error occurs
-    on_error: Rc<dyn Fn(UseStorageError<C::Error>)>,
+    pub(super) on_error: Rc<dyn Fn(UseStorageError<C::Error>)>,
     // Whether to continuously listen to changes from browser storage
-    listen_to_storage_changes: bool,
+    pub(super) listen_to_storage_changes: bool,
     // Initial value to use when the storage key is not set
-    initial_value: MaybeRwSignal<T>,
+    pub(super) initial_value: MaybeRwSignal<T>,
     // Debounce or throttle the writing to storage whenever the value changes
-    filter: FilterOptions,
+    pub(super) filter: FilterOptions,
+    // How to combine the local value with a value written by another tab
+    pub(super) merge: StorageMergeStrategy<T>,
+    // Version of the stored data. 0 means unversioned
+    pub(super) version: u32,
+    // Upgrades a stored value from an older version
+    pub(super) migrate: Rc<dyn Fn(u32, String) -> String>,
+}
+
+/// The key under which the version of the value of `key` is stored.
+#[cfg(not(feature = "ssr"))]
+pub(super) fn version_key(key: &str) -> String {
+    format!("{key}.version")
+}
+
+/// Migrates `encoded`, which is the stored value of `key`, if it has been written by an older
+/// version and writes the result back to the storage.
+#[cfg(not(feature = "ssr"))]
+fn migrate_item<Err>(
+    storage: &web_sys::Storage,
+    key: &str,
+    version: u32,
+    migrate: &Rc<dyn Fn(u32, String) -> String>,
+    encoded: String,
+) -> Result<String, UseStorageError<Err>> {
+    let version_key = version_key(key);
+
+    let stored_version = storage
+        .get_item(&version_key)
+        .map_err(UseStorageError::GetItemFailed)?
+        .and_then(|stored_version| stored_version.parse().ok())
+        .unwrap_or(0);
+
+    if stored_version >= version {
+        return Ok(encoded);
+    }
+
+    let migrated = migrate(stored_version, encoded);
+
+    storage
+        .set_item(key, &migrated)
+        .and_then(|_| storage.set_item(&version_key, &version.to_string()))
+        .map_err(UseStorageError::SetItemFailed)?;
+
+    Ok(migrated)
+}
+
+/// Schedules a storage event microtask. Uses a queue to avoid re-entering the runtime
+#[cfg(not(feature = "ssr"))]
+pub(super) fn dispatch_internal_storage_event<Err: 'static>(
+    key: String,
+    on_error: Rc<dyn Fn(UseStorageError<Err>)>,
+) {
+    queue_microtask(move || {
+        // TODO : better to use a BroadcastChannel (use_broadcast_channel)?
+        // Note: we cannot construct a full StorageEvent so we _must_ rely on a custom event
+        let mut custom = web_sys::CustomEventInit::new();
+        custom.detail(&JsValue::from_str(&key));
+        let result = window()
+            .dispatch_event(
+                &web_sys::CustomEvent::new_with_event_init_dict(INTERNAL_STORAGE_EVENT, &custom)
+                    .expect("failed to create custom storage event"),
+            )
+            .map_err(UseStorageError::NotifyItemChangedFailed);
+        let _ = handle_error(&on_error, result);
+    })
 }
 
 /// Calls the on_error callback with the given error. Removes the error from the Result to avoid double error handling.
 #[cfg(not(feature = "ssr"))]
-fn handle_error<T, Err>(
+pub(super) fn handle_error<T, Err>(
     on_error: &Rc<dyn Fn(UseStorageError<Err>)>,
     result: Result<T, UseStorageError<Err>>,
 ) -> Result<T, ()> {
@@ -359,11 +484,14 @@ impl<T: Default, C: StringCodec<T> + Default> Default for UseStorageOptions<T, C
             listen_to_storage_changes: true,
             initial_value: MaybeRwSignal::default(),
             filter: FilterOptions::default(),
+            merge: StorageMergeStrategy::default(),
+            version: 0,
+            migrate: Rc::new(|_, value| value),
         }
     }
 }
 
-impl<T: Default, C: StringCodec<T>> UseStorageOptions<T, C> {
+impl<T, C: StringCodec<T>> UseStorageOptions<T, C> {
     /// Sets the codec to use for encoding and decoding values to and from UTF-16 strings.
     pub fn codec(self, codec: impl Into<C>) -> Self {
         Self {
@@ -403,4 +531,27 @@ impl<T: Default, C: StringCodec<T>> UseStorageOptions<T, C> {
             ..self
         }
     }
+
+    /// How to combine the local value with a value that has been written by another tab.
+    /// Defaults to [`StorageMergeStrategy::PreferRemote`]. See [`StorageMergeStrategy`] for details.
+    pub fn merge(self, merge: StorageMergeStrategy<T>) -> Self {
+        Self { merge, ..self }
+    }
+
+    /// Version of the stored data. Increase it whenever the encoded format changes and provide
+    /// `migrate` to upgrade values written by older versions. The version is stored under the
+    /// key `"<key>.version"`. Defaults to `0` which disables versioning.
+    pub fn version(self, version: u32) -> Self {
+        Self { version, ..self }
+    }
+
+    /// Upgrades a stored value. Called with the version the value was written with (`0` if it
+    /// was written without a version) and the raw stored string. Returns the value in the format
+    /// of the current `version` which is then written back to the storage.
+    pub fn migrate(self, migrate: impl Fn(u32, String) -> String + 'static) -> Self {
+        Self {
+            migrate: Rc::new(migrate),
+            ..self
+        }
+    }
 }
@@ -0,0 +1,146 @@
+use crate::{js, js_fut, use_interval_fn, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::collections::HashMap;
+use wasm_bindgen::JsCast;
+
+/// Reactive [StorageManager.estimate()](https://developer.mozilla.org/en-US/docs/Web/API/StorageManager/estimate).
+///
+/// Returns how much storage your origin uses and how much is available so you can warn users
+/// before writes to IndexedDB, the Cache API or the Origin Private File System start failing.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::storage::{use_storage_quota_with_options, UseStorageQuotaOptions, UseStorageQuotaReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseStorageQuotaReturn {
+///     usage,
+///     quota,
+///     refresh,
+///     ..
+/// } = use_storage_quota_with_options(UseStorageQuotaOptions::default().interval(10_000));
+///
+/// let almost_full = move || match (usage.get(), quota.get()) {
+///     (Some(usage), Some(quota)) => usage / quota > 0.9,
+///     _ => false,
+/// };
+///
+/// view! {
+///     <Show when=almost_full>
+///         <p>"You're running out of storage space"</p>
+///     </Show>
+///     <button on:click=move |_| refresh()>"Refresh"</button>
+/// }
+/// # }
+/// ```
+///
+/// The estimate is read once on creation. Use the option `interval` to refresh it periodically or
+/// call `refresh` after writing data.
+///
+/// ## Server-Side Rendering
+///
+/// On the server all values stay `None` and `is_supported` is `false`.
+pub fn use_storage_quota() -> UseStorageQuotaReturn<impl Fn() + Clone> {
+    use_storage_quota_with_options(UseStorageQuotaOptions::default())
+}
+
+/// Version of [`use_storage_quota`] that takes a `UseStorageQuotaOptions`. See [`use_storage_quota`] for how to use.
+pub fn use_storage_quota_with_options(
+    options: UseStorageQuotaOptions,
+) -> UseStorageQuotaReturn<impl Fn() + Clone> {
+    let UseStorageQuotaOptions { interval } = options;
+
+    let is_supported = use_supported(|| {
+        js!("storage" in &window().navigator())
+            && js!("estimate" in &window().navigator().storage())
+    });
+
+    let (usage, set_usage) = create_signal(None::<f64>);
+    let (quota, set_quota) = create_signal(None::<f64>);
+    let (usage_details, set_usage_details) = create_signal(HashMap::<String, f64>::new());
+
+    let refresh = move || {
+        if !is_supported.get_untracked() {
+            return;
+        }
+
+        let Ok(promise) = window().navigator().storage().estimate() else {
+            return;
+        };
+
+        spawn_local(async move {
+            let Ok(estimate) = js_fut!(promise).await else {
+                return;
+            };
+            let estimate: web_sys::StorageEstimate = estimate.unchecked_into();
+
+            set_usage.set(estimate.get_usage());
+            set_quota.set(estimate.get_quota());
+
+            // non-standard, only available in Chromium based browsers
+            let details = js!(estimate["usageDetails"])
+                .ok()
+                .filter(|details| details.is_object())
+                .map(|details| {
+                    js_sys::Object::entries(details.unchecked_ref())
+                        .iter()
+                        .filter_map(|entry| {
+                            let entry: js_sys::Array = entry.unchecked_into();
+                            Some((entry.get(0).as_string()?, entry.get(1).as_f64()?))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            set_usage_details.set(details);
+        });
+    };
+
+    refresh();
+
+    if interval > 0 {
+        let _ = use_interval_fn(refresh, interval);
+    }
+
+    UseStorageQuotaReturn {
+        is_supported,
+        usage: usage.into(),
+        quota: quota.into(),
+        usage_details: usage_details.into(),
+        refresh,
+    }
+}
+
+/// Options for [`use_storage_quota_with_options`].
+#[derive(DefaultBuilder, Default)]
+pub struct UseStorageQuotaOptions {
+    /// Refresh the estimate every `interval` milliseconds. Defaults to `0` which only reads the
+    /// estimate once and whenever `refresh` is called.
+    interval: u64,
+}
+
+/// Return type of [`use_storage_quota`].
+pub struct UseStorageQuotaReturn<RefreshFn>
+where
+    RefreshFn: Fn() + Clone,
+{
+    /// Whether the Storage Manager API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The number of bytes used by the origin
+    pub usage: Signal<Option<f64>>,
+
+    /// The number of bytes the origin is allowed to use
+    pub quota: Signal<Option<f64>>,
+
+    /// The bytes used per storage system like `"indexedDB"` or `"caches"`.
+    /// Only available in Chromium based browsers. Empty otherwise.
+    pub usage_details: Signal<HashMap<String, f64>>,
+
+    /// Reads the estimate again
+    pub refresh: RefreshFn,
+}
@@ -0,0 +1,142 @@
+use crate::storage::{use_storage_with_options, StorageType, UseStorageOptions};
+use crate::utils::StringCodec;
+use leptos::*;
+
+/// Persists a single field of a struct signal under its own storage key.
+///
+/// Storing a large struct with [`fn@crate::storage::use_storage`] re-encodes and rewrites the
+/// whole struct whenever one field changes and notifies everything that reads the struct.
+/// Similar to [`create_slice`] this returns a signal and a setter for one field of `signal`.
+/// The field is stored under the key `"<key>/<suffix>"` with its own codec and only written
+/// when it changes.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::storage::{use_storage_slice, StorageType};
+/// # use leptos_use::utils::{FromToStringCodec, JsonCodec};
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// #[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+/// pub struct Settings {
+///     pub volume: u8,
+///     pub recent_files: Vec<String>,
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let settings = create_rw_signal(Settings::default());
+///
+/// // stored as "settings/volume"
+/// let (volume, set_volume, _) = use_storage_slice::<_, _, FromToStringCodec>(
+///     StorageType::Local,
+///     settings,
+///     "settings",
+///     "volume",
+///     |settings| settings.volume,
+///     |settings, volume| settings.volume = volume,
+/// );
+///
+/// // stored as "settings/recent_files"
+/// let (recent_files, set_recent_files, _) = use_storage_slice::<_, _, JsonCodec>(
+///     StorageType::Local,
+///     settings,
+///     "settings",
+///     "recent_files",
+///     |settings| settings.recent_files.clone(),
+///     |settings, recent_files| settings.recent_files = recent_files,
+/// );
+///
+/// // only writes "settings/volume"
+/// set_volume.set(11);
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// Changes of the field that are made directly through `signal` are persisted as well.
+/// On creation the stored value (if any) replaces the field of `signal`.
+pub fn use_storage_slice<T, O, C>(
+    storage_type: StorageType,
+    signal: RwSignal<T>,
+    key: impl AsRef<str>,
+    suffix: &str,
+    getter: impl Fn(&T) -> O + Clone + 'static,
+    setter: impl Fn(&mut T, O) + Clone + 'static,
+) -> (Signal<O>, SignalSetter<O>, impl Fn() + Clone)
+where
+    O: Default + Clone + PartialEq,
+    C: StringCodec<O> + Default,
+{
+    use_storage_slice_with_options(
+        storage_type,
+        signal,
+        key,
+        suffix,
+        getter,
+        setter,
+        UseStorageOptions::<O, C>::default(),
+    )
+}
+
+/// Version of [`use_storage_slice`] that accepts [`UseStorageOptions`]. The `initial_value` of the
+/// options is ignored. The current value of the field is used instead.
+pub fn use_storage_slice_with_options<T, O, C>(
+    storage_type: StorageType,
+    signal: RwSignal<T>,
+    key: impl AsRef<str>,
+    suffix: &str,
+    getter: impl Fn(&T) -> O + Clone + 'static,
+    setter: impl Fn(&mut T, O) + Clone + 'static,
+    options: UseStorageOptions<O, C>,
+) -> (Signal<O>, SignalSetter<O>, impl Fn() + Clone)
+where
+    O: Clone + PartialEq,
+    C: StringCodec<O> + Default,
+{
+    let field = create_memo({
+        let getter = getter.clone();
+        move |_| signal.with(&getter)
+    });
+
+    let (stored, set_stored, remove) = use_storage_with_options::<O, C>(
+        storage_type,
+        format!("{}/{suffix}", key.as_ref()),
+        options.initial_value(field.get_untracked()),
+    );
+
+    let write_field = move |value: O| signal.update(|data| setter(data, value));
+
+    if field.get_untracked() != stored.get_untracked() {
+        write_field(stored.get_untracked());
+    }
+
+    // field changed -> persist it
+    let _ = watch(
+        move || field.get(),
+        move |value, _, _| {
+            if stored.with_untracked(|stored| stored != value) {
+                set_stored.set(value.clone());
+            }
+        },
+        false,
+    );
+
+    // stored value changed (e.g. by another tab or `remove`) -> update the field
+    let _ = {
+        let write_field = write_field.clone();
+
+        watch(
+            move || stored.get(),
+            move |value, _, _| {
+                if field.with_untracked(|field| field != value) {
+                    write_field(value.clone());
+                }
+            },
+            false,
+        )
+    };
+
+    (field.into(), SignalSetter::map(write_field), remove)
+}
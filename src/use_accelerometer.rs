@@ -0,0 +1,69 @@
+use crate::{use_sensor_with_options, SensorReading, UseSensorOptions, UseSensorReturn};
+use wasm_bindgen::JsValue;
+
+/// Reactive [Accelerometer](https://developer.mozilla.org/en-US/docs/Web/API/Accelerometer).
+/// Provides the acceleration of the device along the three axes.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_accelerometer_with_options, UseSensorOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let accelerometer = use_accelerometer_with_options(UseSensorOptions::default().frequency(60.0));
+///
+/// view! {
+///     <Show
+///         when=move || accelerometer.error.get().is_none()
+///         fallback=|| view! { <p>"The accelerometer isn't available"</p> }
+///     >
+///         <p>"x: " {move || accelerometer.reading.get().map(|reading| reading.x)}</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// The sensor is stopped when the component is cleaned up. See [`fn@crate::use_sensor`] for
+/// details.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `reading` is always `None` and `is_supported` is `false`.
+pub fn use_accelerometer() -> UseSensorReturn<AccelerometerReading> {
+    use_accelerometer_with_options(UseSensorOptions::default())
+}
+
+/// Version of [`use_accelerometer`] that takes a `UseSensorOptions`. See [`use_accelerometer`] for how to use.
+pub fn use_accelerometer_with_options(
+    options: UseSensorOptions,
+) -> UseSensorReturn<AccelerometerReading> {
+    use_sensor_with_options(options)
+}
+
+/// Acceleration of the device in m/s² including the effect of gravity.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AccelerometerReading {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl SensorReading for AccelerometerReading {
+    const SENSOR: &'static str = "Accelerometer";
+
+    fn from_sensor(sensor: &JsValue) -> Option<Self> {
+        let value = |name: &str| {
+            js_sys::Reflect::get(sensor, &name.into())
+                .ok()
+                .and_then(|value| value.as_f64())
+        };
+
+        Some(Self {
+            x: value("x")?,
+            y: value("y")?,
+            z: value("z")?,
+        })
+    }
+}
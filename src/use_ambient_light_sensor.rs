@@ -0,0 +1,64 @@
+use crate::{use_sensor_with_options, SensorReading, UseSensorOptions, UseSensorReturn};
+use wasm_bindgen::JsValue;
+
+/// Reactive [AmbientLightSensor](https://developer.mozilla.org/en-US/docs/Web/API/AmbientLightSensor).
+/// Provides the light level around the device.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_ambient_light_sensor;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let light = use_ambient_light_sensor();
+///
+/// let is_dark = move || {
+///     light
+///         .reading
+///         .get()
+///         .is_some_and(|reading| reading.illuminance < 50.0)
+/// };
+///
+/// view! {
+///     <p>{move || if is_dark() { "It's dark" } else { "It's bright" }}</p>
+/// }
+/// # }
+/// ```
+///
+/// Most browsers only provide this sensor behind a flag. The sensor is stopped when the
+/// component is cleaned up. See [`fn@crate::use_sensor`] for details.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `reading` is always `None` and `is_supported` is `false`.
+pub fn use_ambient_light_sensor() -> UseSensorReturn<AmbientLightReading> {
+    use_ambient_light_sensor_with_options(UseSensorOptions::default())
+}
+
+/// Version of [`use_ambient_light_sensor`] that takes a `UseSensorOptions`. See [`use_ambient_light_sensor`] for how to use.
+pub fn use_ambient_light_sensor_with_options(
+    options: UseSensorOptions,
+) -> UseSensorReturn<AmbientLightReading> {
+    use_sensor_with_options(options)
+}
+
+/// Light level around the device.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AmbientLightReading {
+    /// Illuminance in lux
+    pub illuminance: f64,
+}
+
+impl SensorReading for AmbientLightReading {
+    const SENSOR: &'static str = "AmbientLightSensor";
+
+    fn from_sensor(sensor: &JsValue) -> Option<Self> {
+        let illuminance = js_sys::Reflect::get(sensor, &"illuminance".into())
+            .ok()
+            .and_then(|illuminance| illuminance.as_f64())?;
+
+        Some(Self { illuminance })
+    }
+}
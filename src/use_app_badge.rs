@@ -0,0 +1,137 @@
+use crate::{js, js_fut, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Reactive [App Badge](https://developer.mozilla.org/en-US/docs/Web/API/Badging_API).
+/// Shows a number on the icon of an installed web app, for example the count of unread messages.
+///
+/// ## Usage
+///
+/// The badge shows `count` whenever it changes. `None` or `Some(0)` clear the badge.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_app_badge;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (unread, set_unread) = create_signal(Some(3));
+///
+/// let badge = use_app_badge(unread);
+///
+/// view! {
+///     <Show when=move || badge.is_supported.get()>
+///         <button on:click=move |_| set_unread.set(None)>"Mark all as read"</button>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// The badge stays on the icon after the page has been closed. Use `clear_on_cleanup` to
+/// clear it when the component is cleaned up instead.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_app_badge_with_options, UseAppBadgeOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// # let (unread, set_unread) = create_signal(Some(3));
+/// let badge = use_app_badge_with_options(unread, UseAppBadgeOptions::default().clear_on_cleanup(true));
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// Browsers only show the badge for installed web apps and may require the permission to show
+/// notifications. Errors are available in `error`.
+///
+/// ## Server-Side Rendering
+///
+/// On the server this function does nothing.
+pub fn use_app_badge(count: impl Into<MaybeSignal<Option<u32>>>) -> UseAppBadgeReturn {
+    use_app_badge_with_options(count, UseAppBadgeOptions::default())
+}
+
+/// Version of [`use_app_badge`] that takes a `UseAppBadgeOptions`. See [`use_app_badge`] for how to use.
+pub fn use_app_badge_with_options(
+    count: impl Into<MaybeSignal<Option<u32>>>,
+    options: UseAppBadgeOptions,
+) -> UseAppBadgeReturn {
+    let count = count.into();
+    let UseAppBadgeOptions { clear_on_cleanup } = options;
+
+    let is_supported = use_supported(|| js!("setAppBadge" in &window().navigator()));
+
+    let (error, set_error) = create_signal(None::<JsValue>);
+
+    let _ = watch(
+        move || count.get(),
+        move |count, _, _| {
+            if !is_supported.get_untracked() {
+                return;
+            }
+
+            let navigator = navigator();
+            let promise = match count {
+                Some(count) if *count > 0 => navigator.set_app_badge(*count),
+                _ => navigator.clear_app_badge(),
+            };
+
+            spawn_local(async move {
+                let result = js_fut!(promise).await;
+                let _ = set_error.try_set(result.err());
+            });
+        },
+        true,
+    );
+
+    if clear_on_cleanup {
+        on_cleanup(move || {
+            if is_supported.get_untracked() {
+                let _ = navigator().clear_app_badge();
+            }
+        });
+    }
+
+    UseAppBadgeReturn {
+        is_supported,
+        error: error.into(),
+    }
+}
+
+/// Options for [`use_app_badge_with_options`].
+#[derive(DefaultBuilder, Default)]
+pub struct UseAppBadgeOptions {
+    /// If `true` the badge is cleared when the component is cleaned up. Defaults to `false`.
+    clear_on_cleanup: bool,
+}
+
+/// Return type of [`use_app_badge`].
+#[derive(Clone, Copy)]
+pub struct UseAppBadgeReturn {
+    /// Whether the Badging API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The error of the last update of the badge
+    pub error: Signal<Option<JsValue>>,
+}
+
+fn navigator() -> BadgingNavigator {
+    window().navigator().unchecked_into()
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Clone, Debug)]
+    #[wasm_bindgen(extends = web_sys::Navigator, js_name = Navigator)]
+    type BadgingNavigator;
+
+    #[wasm_bindgen(method, js_name = setAppBadge)]
+    fn set_app_badge(this: &BadgingNavigator, contents: u32) -> js_sys::Promise;
+
+    #[wasm_bindgen(method, js_name = clearAppBadge)]
+    fn clear_app_badge(this: &BadgingNavigator) -> js_sys::Promise;
+}
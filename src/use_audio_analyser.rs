@@ -0,0 +1,357 @@
+use crate::core::ElementMaybeSignal;
+use crate::utils::Pausable;
+use crate::{
+    js, use_event_listener, use_raf_fn_with_options, use_supported, UseRafFnCallbackArgs,
+    UseRafFnOptions,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use wasm_bindgen::JsCast;
+use web_sys::{AnalyserNode, AudioContext, AudioNode, HtmlMediaElement, MediaStream};
+
+/// Analyses the audio of an `<audio>` or `<video>` element with the
+/// [Web Audio API](https://developer.mozilla.org/en-US/docs/Web/API/Web_Audio_API) and provides
+/// the frequency and time-domain data of an
+/// [`AnalyserNode`](https://developer.mozilla.org/en-US/docs/Web/API/AnalyserNode) as signals.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Audio;
+/// # use leptos_use::use_audio_analyser;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let audio = create_node_ref::<Audio>();
+///
+/// let analyser = use_audio_analyser(audio);
+///
+/// view! {
+///     <audio node_ref=audio src="music.mp3" controls></audio>
+///     <div class="bars">
+///         {move || {
+///             analyser
+///                 .frequency_data
+///                 .get()
+///                 .into_iter()
+///                 .map(|value| view! { <div style:height=format!("{value}px")></div> })
+///                 .collect_view()
+///         }}
+///     </div>
+/// }
+/// # }
+/// ```
+///
+/// The data is updated on every animation frame while the analyser is active. Use `throttle`
+/// to update less often. `fft_size` controls the number of values: `frequency_data` has
+/// `fft_size / 2` and `time_domain_data` has `fft_size` values between `0` and `255`.
+///
+/// The audio of the element is routed through the analyser to the speakers so it can still be
+/// heard.
+///
+/// ## Streams
+///
+/// Use [`use_stream_audio_analyser`] to analyse a `MediaStream`, for example the microphone from
+/// [`fn@crate::use_user_media`]. The stream isn't played through the speakers to avoid feedback.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_stream_audio_analyser_with_options, use_user_media, UseAudioAnalyserOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let microphone = use_user_media();
+///
+/// let analyser = use_stream_audio_analyser_with_options(
+///     microphone.stream,
+///     UseAudioAnalyserOptions::default().fft_size(256).throttle(50.0),
+/// );
+///
+/// let volume = move || {
+///     analyser
+///         .time_domain_data
+///         .get()
+///         .iter()
+///         .map(|value| (*value as i32 - 128).unsigned_abs())
+///         .max()
+///         .unwrap_or_default()
+/// };
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server the data is always empty and `is_supported` is `false`.
+pub fn use_audio_analyser<El, T>(
+    target: El,
+) -> UseAudioAnalyserReturn<impl Fn() + Clone, impl Fn() + Clone>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    use_audio_analyser_with_options(target, UseAudioAnalyserOptions::default())
+}
+
+/// Version of [`use_audio_analyser`] that takes a `UseAudioAnalyserOptions`. See [`use_audio_analyser`] for how to use.
+pub fn use_audio_analyser_with_options<El, T>(
+    target: El,
+    options: UseAudioAnalyserOptions,
+) -> UseAudioAnalyserReturn<impl Fn() + Clone, impl Fn() + Clone>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let target = target.into();
+
+    let source = Signal::derive(move || {
+        target
+            .get()
+            .and_then(|element| element.into().dyn_into::<HtmlMediaElement>().ok())
+            .map(AudioSource::Element)
+    });
+
+    use_analyser(source, options)
+}
+
+/// Version of [`use_audio_analyser`] that analyses a `MediaStream`.
+pub fn use_stream_audio_analyser(
+    stream: impl Into<MaybeSignal<Option<MediaStream>>>,
+) -> UseAudioAnalyserReturn<impl Fn() + Clone, impl Fn() + Clone> {
+    use_stream_audio_analyser_with_options(stream, UseAudioAnalyserOptions::default())
+}
+
+/// Version of [`use_stream_audio_analyser`] that takes a `UseAudioAnalyserOptions`. See [`use_audio_analyser`] for how to use.
+pub fn use_stream_audio_analyser_with_options(
+    stream: impl Into<MaybeSignal<Option<MediaStream>>>,
+    options: UseAudioAnalyserOptions,
+) -> UseAudioAnalyserReturn<impl Fn() + Clone, impl Fn() + Clone> {
+    let stream = stream.into();
+
+    let source = Signal::derive(move || stream.get().map(AudioSource::Stream));
+
+    use_analyser(source, options)
+}
+
+#[derive(Clone, PartialEq)]
+enum AudioSource {
+    Element(HtmlMediaElement),
+    Stream(MediaStream),
+}
+
+fn use_analyser(
+    source: Signal<Option<AudioSource>>,
+    options: UseAudioAnalyserOptions,
+) -> UseAudioAnalyserReturn<impl Fn() + Clone, impl Fn() + Clone> {
+    let UseAudioAnalyserOptions {
+        fft_size,
+        smoothing_time_constant,
+        throttle,
+        immediate,
+    } = options;
+
+    let is_supported = use_supported(|| js!("AudioContext" in &window()));
+
+    let (analyser, set_analyser) = create_signal(None::<AnalyserNode>);
+    let (frequency_data, set_frequency_data) = create_signal(Vec::<u8>::new());
+    let (time_domain_data, set_time_domain_data) = create_signal(Vec::<u8>::new());
+
+    let context = store_value(None::<AudioContext>);
+    // a media element can only be connected to an audio context once
+    let element_nodes = store_value(Vec::<(HtmlMediaElement, AudioNode)>::new());
+    let connected = store_value(None::<AudioNode>);
+
+    let connect = move |source: &AudioSource| -> Result<(), wasm_bindgen::JsValue> {
+        let ctx = match context.get_value() {
+            Some(ctx) => ctx,
+            None => {
+                let ctx = AudioContext::new()?;
+                context.set_value(Some(ctx.clone()));
+                ctx
+            }
+        };
+
+        let analyser = match analyser.get_untracked() {
+            Some(analyser) => analyser,
+            None => {
+                let analyser = ctx.create_analyser()?;
+                analyser.set_fft_size(fft_size);
+                analyser.set_smoothing_time_constant(smoothing_time_constant);
+                set_analyser.set(Some(analyser.clone()));
+                analyser
+            }
+        };
+
+        let node: AudioNode = match source {
+            AudioSource::Element(element) => {
+                let cached = element_nodes.with_value(|nodes| {
+                    nodes
+                        .iter()
+                        .find(|(cached, _)| cached == element)
+                        .map(|(_, node)| node.clone())
+                });
+
+                match cached {
+                    Some(node) => node,
+                    None => {
+                        let node: AudioNode = ctx.create_media_element_source(element)?.into();
+                        element_nodes
+                            .update_value(|nodes| nodes.push((element.clone(), node.clone())));
+                        node
+                    }
+                }
+            }
+            AudioSource::Stream(stream) => ctx.create_media_stream_source(stream)?.into(),
+        };
+
+        node.connect_with_audio_node(&analyser)?;
+
+        let _ = analyser.disconnect();
+        if matches!(source, AudioSource::Element(_)) {
+            analyser.connect_with_audio_node(&ctx.destination())?;
+        }
+
+        connected.set_value(Some(node));
+
+        Ok(())
+    };
+
+    let disconnect = move || {
+        if let Some(node) = connected.get_value() {
+            let _ = node.disconnect();
+        }
+
+        connected.set_value(None);
+    };
+
+    let _ = watch(
+        move || source.get(),
+        move |source, _, _| {
+            if !is_supported.get_untracked() {
+                return;
+            }
+
+            disconnect();
+
+            if let Some(source) = source {
+                if let Err(err) = connect(source) {
+                    logging::debug_warn!("Connecting the audio analyser failed: {err:?}");
+                }
+            }
+        },
+        true,
+    );
+
+    // browsers only start an audio context after a user interaction
+    let element = Signal::derive(move || match source.get() {
+        Some(AudioSource::Element(element)) => Some(element),
+        _ => None,
+    });
+
+    let _ = use_event_listener(element, ev::play, move |_| {
+        if let Some(ctx) = context.get_value() {
+            let _ = ctx.resume();
+        }
+    });
+
+    let last_update = store_value(0.0);
+
+    let Pausable {
+        pause,
+        resume,
+        is_active,
+    } = use_raf_fn_with_options(
+        move |UseRafFnCallbackArgs { timestamp, .. }| {
+            if timestamp - last_update.get_value() < throttle {
+                return;
+            }
+            last_update.set_value(timestamp);
+
+            if let Some(analyser) = analyser.get_untracked() {
+                let mut frequency = vec![0; analyser.frequency_bin_count() as usize];
+                analyser.get_byte_frequency_data(&mut frequency);
+                set_frequency_data.set(frequency);
+
+                let mut time_domain = vec![0; analyser.fft_size() as usize];
+                analyser.get_byte_time_domain_data(&mut time_domain);
+                set_time_domain_data.set(time_domain);
+            }
+        },
+        UseRafFnOptions::default().immediate(immediate),
+    );
+
+    on_cleanup(move || {
+        if let Some(Some(ctx)) = context.try_get_value() {
+            let _ = ctx.close();
+        }
+    });
+
+    UseAudioAnalyserReturn {
+        is_supported,
+        analyser: analyser.into(),
+        frequency_data: frequency_data.into(),
+        time_domain_data: time_domain_data.into(),
+        is_active,
+        pause,
+        resume,
+    }
+}
+
+/// Options for [`use_audio_analyser_with_options`] and [`use_stream_audio_analyser_with_options`].
+#[derive(DefaultBuilder, Clone, Copy)]
+pub struct UseAudioAnalyserOptions {
+    /// The size of the FFT. Must be a power of 2 between `32` and `32768`. Defaults to `2048`.
+    fft_size: u32,
+
+    /// How much the frequency data is averaged over time between `0.0` and `1.0`.
+    /// Defaults to `0.8`.
+    smoothing_time_constant: f64,
+
+    /// The minimum time in ms between two updates of the data. Defaults to `0.0` which updates
+    /// on every animation frame.
+    throttle: f64,
+
+    /// Start updating the data immediately. Defaults to `true`.
+    immediate: bool,
+}
+
+impl Default for UseAudioAnalyserOptions {
+    fn default() -> Self {
+        Self {
+            fft_size: 2048,
+            smoothing_time_constant: 0.8,
+            throttle: 0.0,
+            immediate: true,
+        }
+    }
+}
+
+/// Return type of [`use_audio_analyser`].
+pub struct UseAudioAnalyserReturn<PauseFn, ResumeFn>
+where
+    PauseFn: Fn() + Clone,
+    ResumeFn: Fn() + Clone,
+{
+    /// Whether the Web Audio API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The `AnalyserNode` once a source has been connected
+    pub analyser: Signal<Option<AnalyserNode>>,
+
+    /// The current frequency data with `fft_size / 2` values
+    pub frequency_data: Signal<Vec<u8>>,
+
+    /// The current waveform with `fft_size` values
+    pub time_domain_data: Signal<Vec<u8>>,
+
+    /// Whether the data is being updated
+    pub is_active: Signal<bool>,
+
+    /// Stop updating the data
+    pub pause: PauseFn,
+
+    /// Start updating the data again
+    pub resume: ResumeFn,
+}
@@ -0,0 +1,181 @@
+use crate::utils::ready_service_worker_registration;
+use crate::{js, js_fut, use_supported};
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Reactive [Background Synchronization API](https://developer.mozilla.org/en-US/docs/Web/API/Background_Synchronization_API).
+/// Defers work until the user has a stable network connection, even if the page has been closed
+/// in the meantime.
+///
+/// A service worker has to be registered, for example with
+/// [`use_service_worker`](crate::use_service_worker).
+///
+/// ## Usage
+///
+/// `register` asks the browser to fire a `sync` event with the given tag in the service worker as
+/// soon as the device is online. Registering a tag that is already pending does nothing.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_background_sync;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let background_sync = use_background_sync();
+///
+/// let send = move |_| {
+///     // store the message, for example with `use_indexed_db`, and let the service worker send it
+///     spawn_local(async move {
+///         let _ = background_sync.register("send-messages").await;
+///     });
+/// };
+///
+/// view! {
+///     <button on:click=send>"Send"</button>
+///     <p>"Pending: " {move || background_sync.tags.get().join(", ")}</p>
+/// }
+/// # }
+/// ```
+///
+/// The service worker handles the event. If the returned promise rejects, the browser retries
+/// the sync later.
+///
+/// ```js
+/// self.addEventListener("sync", (event) => {
+///   if (event.tag === "send-messages") {
+///     event.waitUntil(sendStoredMessages());
+///   }
+/// });
+/// ```
+///
+/// `tags` contains the tags that haven't been synced yet. It's loaded initially and updated by
+/// `register`. Call `refresh` to update it after the service worker has handled a sync.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `tags` is always empty and all operations return
+/// `UseBackgroundSyncError::NotSupported`.
+pub fn use_background_sync() -> UseBackgroundSyncReturn {
+    let is_supported = use_supported(|| {
+        js!("SyncManager" in &window()) && js!("serviceWorker" in &window().navigator())
+    });
+
+    let (tags, set_tags) = create_signal(Vec::<String>::new());
+    let (error, set_error) = create_signal(None::<UseBackgroundSyncError>);
+
+    let background_sync = UseBackgroundSyncReturn {
+        is_supported,
+        tags: tags.into(),
+        error: error.into(),
+        set_tags,
+        set_error,
+    };
+
+    if is_supported.get_untracked() {
+        spawn_local(async move {
+            let _ = background_sync.refresh().await;
+        });
+    }
+
+    background_sync
+}
+
+/// Return type of [`use_background_sync`].
+#[derive(Clone, Copy)]
+pub struct UseBackgroundSyncReturn {
+    /// Whether the Background Synchronization API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The tags that are registered but haven't been synced yet
+    pub tags: Signal<Vec<String>>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseBackgroundSyncError>>,
+
+    set_tags: WriteSignal<Vec<String>>,
+    set_error: WriteSignal<Option<UseBackgroundSyncError>>,
+}
+
+impl UseBackgroundSyncReturn {
+    /// Registers a one-off sync with the given tag.
+    pub async fn register(&self, tag: &str) -> Result<(), UseBackgroundSyncError> {
+        let sync_manager = self.sync_manager().await?;
+
+        async { js_fut!(sync_manager.register(tag)?).await }
+            .await
+            .map_err(|err| self.fail(UseBackgroundSyncError::Register(err)))?;
+
+        self.refresh().await?;
+
+        Ok(())
+    }
+
+    /// Loads the tags that haven't been synced yet into `tags` and returns them.
+    pub async fn refresh(&self) -> Result<Vec<String>, UseBackgroundSyncError> {
+        let sync_manager = self.sync_manager().await?;
+
+        let tags = async { js_fut!(sync_manager.get_tags()?).await }
+            .await
+            .map_err(|err| self.fail(UseBackgroundSyncError::GetTags(err)))?
+            .unchecked_into::<js_sys::Array>()
+            .iter()
+            .filter_map(|tag| tag.as_string())
+            .collect::<Vec<_>>();
+
+        let _ = self.set_tags.try_set(tags.clone());
+        let _ = self.set_error.try_set(None);
+
+        Ok(tags)
+    }
+
+    async fn sync_manager(&self) -> Result<SyncManager, UseBackgroundSyncError> {
+        if !self.is_supported.get_untracked() {
+            return Err(UseBackgroundSyncError::NotSupported);
+        }
+
+        let registration = ready_service_worker_registration()
+            .await
+            .map_err(|err| self.fail(UseBackgroundSyncError::Registration(err)))?;
+
+        Ok(registration.unchecked_into::<SyncRegistration>().sync())
+    }
+
+    fn fail(&self, err: UseBackgroundSyncError) -> UseBackgroundSyncError {
+        let _ = self.set_error.try_set(Some(err.clone()));
+        err
+    }
+}
+
+/// Errors of [`use_background_sync`].
+#[derive(Error, Debug, Clone)]
+pub enum UseBackgroundSyncError {
+    #[error("the Background Synchronization API is not supported")]
+    NotSupported,
+    #[error("failed to get the service worker registration")]
+    Registration(JsValue),
+    #[error("failed to register the sync")]
+    Register(JsValue),
+    #[error("failed to get the registered tags")]
+    GetTags(JsValue),
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Clone, Debug)]
+    #[wasm_bindgen(extends = web_sys::ServiceWorkerRegistration, js_name = ServiceWorkerRegistration)]
+    type SyncRegistration;
+
+    #[wasm_bindgen(method, getter)]
+    fn sync(this: &SyncRegistration) -> SyncManager;
+
+    #[derive(Clone, Debug)]
+    type SyncManager;
+
+    #[wasm_bindgen(catch, method)]
+    fn register(this: &SyncManager, tag: &str) -> Result<js_sys::Promise, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = getTags)]
+    fn get_tags(this: &SyncManager) -> Result<js_sys::Promise, JsValue>;
+}
@@ -0,0 +1,117 @@
+use crate::{js, js_fut, use_event_listener, use_supported};
+use leptos::*;
+use wasm_bindgen::JsCast;
+
+/// Reactive [Battery Status API](https://developer.mozilla.org/en-US/docs/Web/API/Battery_Status_API).
+/// Provides information about the battery charge level and whether the device is charging.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_battery, UseBatteryReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseBatteryReturn {
+///     is_supported,
+///     charging,
+///     level,
+///     ..
+/// } = use_battery();
+///
+/// view! {
+///     <Show
+///         when=move || is_supported.get()
+///         fallback=|| view! { <p>"Your browser doesn't support the Battery Status API"</p> }
+///     >
+///         <p>{move || format!("{:.0}%", level.get() * 100.0)}</p>
+///         <Show when=move || charging.get()>"Charging"</Show>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server and in browsers without the API `is_supported` is `false`, `charging` is
+/// `false`, `level` is `1.0` and both times are `0.0`.
+pub fn use_battery() -> UseBatteryReturn {
+    let is_supported = use_supported(|| js!("getBattery" in &window().navigator()));
+
+    let (battery, set_battery) = create_signal(None::<web_sys::BatteryManager>);
+
+    let (charging, set_charging) = create_signal(false);
+    let (charging_time, set_charging_time) = create_signal(0.0);
+    let (discharging_time, set_discharging_time) = create_signal(0.0);
+    let (level, set_level) = create_signal(1.0);
+
+    let update = move || {
+        if let Some(battery) = battery.get_untracked() {
+            set_charging.set(battery.charging());
+            set_charging_time.set(battery.charging_time());
+            set_discharging_time.set(battery.discharging_time());
+            set_level.set(battery.level());
+        }
+    };
+
+    if is_supported.get_untracked() {
+        spawn_local(async move {
+            let navigator = window().navigator();
+
+            let Some(promise) = js!(navigator["getBattery"])
+                .ok()
+                .and_then(|get_battery| get_battery.dyn_into::<js_sys::Function>().ok())
+                .and_then(|get_battery| get_battery.call0(&navigator).ok())
+            else {
+                return;
+            };
+
+            if let Ok(manager) = js_fut!(js_sys::Promise::from(promise)).await {
+                set_battery.set(Some(manager.unchecked_into()));
+                update();
+            }
+        });
+    }
+
+    for event in [
+        "chargingchange",
+        "chargingtimechange",
+        "dischargingtimechange",
+        "levelchange",
+    ] {
+        let _ = use_event_listener(
+            battery,
+            ev::Custom::<web_sys::Event>::new(event),
+            move |_| update(),
+        );
+    }
+
+    UseBatteryReturn {
+        is_supported,
+        charging: charging.into(),
+        charging_time: charging_time.into(),
+        discharging_time: discharging_time.into(),
+        level: level.into(),
+    }
+}
+
+/// Return type of [`use_battery`].
+#[derive(Clone, Copy)]
+pub struct UseBatteryReturn {
+    /// Whether the Battery Status API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// Whether the battery is charging
+    pub charging: Signal<bool>,
+
+    /// Seconds until the battery is fully charged. `0.0` if it's full and
+    /// `f64::INFINITY` if it isn't charging.
+    pub charging_time: Signal<f64>,
+
+    /// Seconds until the battery is empty. `f64::INFINITY` if it's charging.
+    pub discharging_time: Signal<f64>,
+
+    /// The charge level between `0.0` and `1.0`
+    pub level: Signal<f64>,
+}
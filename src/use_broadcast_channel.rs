@@ -66,6 +66,41 @@ use wasm_bindgen::JsValue;
 /// # }
 /// ```
 ///
+/// Messages that can't be decoded are reported in `parse_error` while `error` only contains
+/// errors of the channel itself.
+///
+/// ## Raw Messages
+///
+/// Every received message is also available undecoded in `message_raw`. Together with `channel`
+/// this allows to exchange any value that the browser can clone, for example with code that
+/// doesn't use the codec.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_broadcast_channel, UseBroadcastChannelReturn};
+/// # use leptos_use::utils::FromToStringCodec;
+/// # use wasm_bindgen::JsValue;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseBroadcastChannelReturn {
+///     channel,
+///     message_raw,
+///     ..
+/// } = use_broadcast_channel::<String, FromToStringCodec>("some-channel-name");
+///
+/// let post_raw = move |value: JsValue| {
+///     if let Some(channel) = channel.get_untracked() {
+///         let _ = channel.post_message(&value);
+///     }
+/// };
+///
+/// let is_number = move || message_raw.with(|raw| raw.as_ref().and_then(JsValue::as_f64).is_some());
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
 /// ## Create Your Own Custom Codec
 ///
 /// All you need to do is to implement the [`StringCodec`] trait together with `Default` and `Clone`.
@@ -80,7 +115,9 @@ where
     let (is_closed, set_closed) = create_signal(false);
     let (channel, set_channel) = create_signal(None::<web_sys::BroadcastChannel>);
     let (message, set_message) = create_signal(None::<T>);
+    let (message_raw, set_message_raw) = create_signal(None::<JsValue>);
     let (error, set_error) = create_signal(None::<UseBroadcastChannelError<C::Error>>);
+    let (parse_error, set_parse_error) = create_signal(None::<C::Error>);
 
     let codec = C::default();
 
@@ -124,16 +161,23 @@ where
                 channel.clone(),
                 ev::message,
                 move |event| {
-                    if let Some(data) = event.data().as_string() {
+                    let data = event.data();
+
+                    // values that aren't strings have been posted raw and are only available in
+                    // `message_raw`
+                    if let Some(data) = data.as_string() {
                         match codec.decode(data) {
                             Ok(msg) => {
+                                if parse_error.with_untracked(Option::is_some) {
+                                    set_parse_error.set(None);
+                                }
                                 set_message.set(Some(msg));
                             }
-                            Err(err) => set_error.set(Some(UseBroadcastChannelError::Decode(err))),
+                            Err(err) => set_parse_error.set(Some(err)),
                         }
-                    } else {
-                        set_error.set(Some(UseBroadcastChannelError::ValueNotString));
                     }
+
+                    set_message_raw.set(Some(data));
                 },
                 UseEventListenerOptions::default().passive(true),
             );
@@ -159,9 +203,11 @@ where
         is_supported,
         channel: channel.into(),
         message: message.into(),
+        message_raw: message_raw.into(),
         post,
         close,
         error: error.into(),
+        parse_error: parse_error.into(),
         is_closed: is_closed.into(),
     }
 }
@@ -183,6 +229,9 @@ where
     /// Latest message received from the channel
     pub message: Signal<Option<T>>,
 
+    /// Latest message received from the channel before decoding
+    pub message_raw: Signal<Option<JsValue>>,
+
     /// Sends a message through the channel
     pub post: PFn,
 
@@ -192,6 +241,10 @@ where
     /// Latest error as reported by the `messageerror` event.
     pub error: Signal<Option<UseBroadcastChannelError<Err>>>,
 
+    /// The error of the latest received message that couldn't be decoded. Reset to `None` as soon
+    /// as a message is decoded successfully.
+    pub parse_error: Signal<Option<Err>>,
+
     /// Wether the channel is closed
     pub is_closed: Signal<bool>,
 }
@@ -204,8 +257,4 @@ pub enum UseBroadcastChannelError<Err> {
     MessageEvent(web_sys::MessageEvent),
     #[error("failed to encode value")]
     Encode(Err),
-    #[error("failed to decode value")]
-    Decode(Err),
-    #[error("received value is not a string")]
-    ValueNotString,
 }
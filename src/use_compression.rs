@@ -0,0 +1,206 @@
+use crate::{js, js_fut, use_supported};
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{
+    Blob, CompressionFormat, CompressionStream, DecompressionStream, ReadableStream,
+    ReadableWritablePair, Response,
+};
+
+/// Compresses and decompresses data with the browser's native
+/// [Compression Streams API](https://developer.mozilla.org/en-US/docs/Web/API/Compression_Streams_API).
+///
+/// > This function requires `--cfg=web_sys_unstable_apis` to be activated as
+/// > [described in the wasm-bindgen guide](https://rustwasm.github.io/docs/wasm-bindgen/web-sys/unstable-apis.html).
+///
+/// ## Usage
+///
+/// `compress` and `decompress` work on complete byte payloads, for example before sending them
+/// over a WebSocket or storing them.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_compression;
+/// # use web_sys::CompressionFormat;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let gzip = use_compression(CompressionFormat::Gzip);
+///
+/// spawn_local(async move {
+///     let payload = "Hello, World! ".repeat(100).into_bytes();
+///
+///     if let Ok(compressed) = gzip.compress(&payload).await {
+///         // send the compressed bytes
+///
+///         let restored = gzip.decompress(&compressed).await;
+///         assert_eq!(restored.ok(), Some(payload));
+///     }
+/// });
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Streams
+///
+/// `compress_stream` and `decompress_stream` transform a `ReadableStream` chunk by chunk without
+/// keeping the whole payload in memory. This plugs into the body of fetch requests and responses.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{js_fut, use_compression};
+/// # use wasm_bindgen::JsCast;
+/// # use web_sys::CompressionFormat;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let gzip = use_compression(CompressionFormat::Gzip);
+///
+/// spawn_local(async move {
+///     let response = js_fut!(window().fetch_with_str("/data.json.gz"))
+///         .await
+///         .unwrap()
+///         .unchecked_into::<web_sys::Response>();
+///
+///     if let Some(Ok(stream)) = response.body().map(|body| gzip.decompress_stream(&body)) {
+///         let decompressed = web_sys::Response::new_with_opt_readable_stream(Some(&stream));
+///         // read the decompressed response with `.text()` or `.json()`
+///     }
+/// });
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server all operations return `UseCompressionError::NotSupported`.
+pub fn use_compression(format: CompressionFormat) -> UseCompressionReturn {
+    let is_supported = use_supported(|| {
+        js!("CompressionStream" in &window()) && js!("DecompressionStream" in &window())
+    });
+
+    let (is_pending, set_pending) = create_signal(false);
+    let (error, set_error) = create_signal(None::<UseCompressionError>);
+
+    UseCompressionReturn {
+        is_supported,
+        is_pending: is_pending.into(),
+        error: error.into(),
+        format,
+        set_pending,
+        set_error,
+    }
+}
+
+/// Return type of [`use_compression`].
+#[derive(Clone, Copy)]
+pub struct UseCompressionReturn {
+    /// Whether the Compression Streams API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// Whether `compress` or `decompress` is running
+    pub is_pending: Signal<bool>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseCompressionError>>,
+
+    format: CompressionFormat,
+    set_pending: WriteSignal<bool>,
+    set_error: WriteSignal<Option<UseCompressionError>>,
+}
+
+impl UseCompressionReturn {
+    /// Compresses the bytes.
+    pub async fn compress(&self, data: &[u8]) -> Result<Vec<u8>, UseCompressionError> {
+        self.check_supported()?;
+
+        let stream = self.compress_stream(&Self::stream_bytes(data)?)?;
+
+        self.collect(stream).await
+    }
+
+    /// Decompresses the bytes. Fails if they aren't valid data of the format.
+    pub async fn decompress(&self, data: &[u8]) -> Result<Vec<u8>, UseCompressionError> {
+        self.check_supported()?;
+
+        let stream = self.decompress_stream(&Self::stream_bytes(data)?)?;
+
+        self.collect(stream).await
+    }
+
+    /// Returns a stream of the compressed chunks of `stream`.
+    pub fn compress_stream(
+        &self,
+        stream: &ReadableStream,
+    ) -> Result<ReadableStream, UseCompressionError> {
+        self.check_supported()?;
+
+        let transform = CompressionStream::new(self.format)
+            .map_err(|err| self.fail(UseCompressionError::Failed(err)))?;
+
+        Ok(stream.pipe_through(transform.unchecked_ref::<ReadableWritablePair>()))
+    }
+
+    /// Returns a stream of the decompressed chunks of `stream`.
+    pub fn decompress_stream(
+        &self,
+        stream: &ReadableStream,
+    ) -> Result<ReadableStream, UseCompressionError> {
+        self.check_supported()?;
+
+        let transform = DecompressionStream::new(self.format)
+            .map_err(|err| self.fail(UseCompressionError::Failed(err)))?;
+
+        Ok(stream.pipe_through(transform.unchecked_ref::<ReadableWritablePair>()))
+    }
+
+    fn stream_bytes(data: &[u8]) -> Result<ReadableStream, UseCompressionError> {
+        let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(data));
+
+        Blob::new_with_u8_array_sequence(&parts)
+            .map(|blob| blob.stream())
+            .map_err(UseCompressionError::Failed)
+    }
+
+    async fn collect(&self, stream: ReadableStream) -> Result<Vec<u8>, UseCompressionError> {
+        self.set_pending.set(true);
+
+        let result = async {
+            let buffer = Response::new_with_opt_readable_stream(Some(&stream))?.array_buffer()?;
+
+            js_fut!(buffer).await
+        }
+        .await;
+
+        self.set_pending.set(false);
+
+        let buffer = result.map_err(|err| self.fail(UseCompressionError::Failed(err)))?;
+        self.set_error.set(None);
+
+        Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+    }
+
+    fn check_supported(&self) -> Result<(), UseCompressionError> {
+        if self.is_supported.get_untracked() {
+            Ok(())
+        } else {
+            Err(UseCompressionError::NotSupported)
+        }
+    }
+
+    fn fail(&self, err: UseCompressionError) -> UseCompressionError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// Errors of [`use_compression`].
+#[derive(Error, Debug, Clone)]
+pub enum UseCompressionError {
+    #[error("the Compression Streams API is not supported")]
+    NotSupported,
+    #[error("compressing or decompressing failed")]
+    Failed(JsValue),
+}
@@ -85,7 +85,8 @@ use std::rc::Rc;
 /// This works equally well on the server or the client.
 /// On the server this function reads the cookie from the HTTP request header and writes it back into
 /// the HTTP response header according to options (if provided).
-/// The returned `WriteSignal` will not affect the cookie headers on the server.
+/// Setting the returned `WriteSignal` on the server adds a `Set-Cookie` header for the new value as
+/// long as the response headers haven't been sent yet.
 ///
 /// > If you're using `axum` you have to enable the `"axum"` feature in your Cargo.toml.
 /// > In case it's `actix-web` enable the feature `"actix"`, for `spin` enable `"spin"`.
@@ -130,6 +131,38 @@ use std::rc::Rc;
 /// # }
 /// ```
 ///
+/// Instead of the two functions you can also implement [`SsrCookieHeaders`] for the integration
+/// with your server framework and pass it to [`UseCookieOptions::ssr_cookie_headers`].
+///
+/// ```
+/// # use cookie::Cookie;
+/// # use leptos::*;
+/// # use leptos_use::{use_cookie_with_options, SsrCookieHeaders, UseCookieOptions};
+/// # use leptos_use::utils::FromToStringCodec;
+/// #
+/// struct MyServerCookies;
+///
+/// impl SsrCookieHeaders for MyServerCookies {
+///     fn cookies_header(&self) -> Option<String> {
+///         // somehow get the value of the cookie header
+///         None
+///     }
+///
+///     fn set_cookie(&self, cookie: &Cookie) {
+///         // somehow insert the Set-Cookie header for this cookie
+///     }
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (theme, set_theme) = use_cookie_with_options::<String, FromToStringCodec>(
+///     "theme",
+///     UseCookieOptions::default().ssr_cookie_headers(MyServerCookies),
+/// );
+/// # view! {}
+/// # }
+/// ```
+///
 /// ## Create Your Own Custom Codec
 ///
 /// All you need to do is to implement the [`StringCodec`] trait together with `Default` and `Clone`.
@@ -356,27 +389,33 @@ where
     #[cfg(feature = "ssr")]
     {
         if !readonly {
-            let value = cookie
-                .with_untracked(|cookie| {
-                    cookie
-                        .as_ref()
-                        .map(|cookie| codec.encode(&cookie).map_err(|err| on_error(err)).ok())
-                })
-                .flatten();
-            jar.update_value(|jar| {
-                write_server_cookie(
-                    cookie_name,
-                    value,
-                    jar,
-                    max_age,
-                    expires,
-                    domain,
-                    path,
-                    same_site,
-                    secure,
-                    http_only,
-                    ssr_set_cookie,
-                )
+            let cookie_name = cookie_name.to_owned();
+
+            // Runs on the server as well. Writes the initial value and every change while rendering.
+            create_isomorphic_effect(move |_| {
+                let value = cookie
+                    .with(|cookie| {
+                        cookie
+                            .as_ref()
+                            .map(|cookie| codec.encode(cookie).map_err(|err| on_error(err)).ok())
+                    })
+                    .flatten();
+
+                jar.update_value(|jar| {
+                    write_server_cookie(
+                        &cookie_name,
+                        value,
+                        jar,
+                        max_age,
+                        expires,
+                        domain.clone(),
+                        path.clone(),
+                        same_site,
+                        secure,
+                        http_only,
+                        Rc::clone(&ssr_set_cookie),
+                    )
+                });
             });
         }
     }
@@ -611,6 +650,33 @@ impl<T, Err> Default for UseCookieOptions<T, Err> {
     }
 }
 
+impl<T, Err> UseCookieOptions<T, Err> {
+    /// Uses `headers` to read the cookie header of the request and to add `Set-Cookie` headers to
+    /// the response on the server. This replaces `ssr_cookies_header_getter` and `ssr_set_cookie`.
+    pub fn ssr_cookie_headers(self, headers: impl SsrCookieHeaders + 'static) -> Self {
+        let headers = Rc::new(headers);
+
+        Self {
+            ssr_cookies_header_getter: Rc::new({
+                let headers = Rc::clone(&headers);
+                move || headers.cookies_header()
+            }),
+            ssr_set_cookie: Rc::new(move |cookie: &Cookie| headers.set_cookie(cookie)),
+            ..self
+        }
+    }
+}
+
+/// Integration of [`use_cookie`] with a server framework. Reads the cookies of the request and
+/// writes the cookies of the response. See [`UseCookieOptions::ssr_cookie_headers`].
+pub trait SsrCookieHeaders {
+    /// The value of the `Cookie` header of the current request.
+    fn cookies_header(&self) -> Option<String>;
+
+    /// Adds a `Set-Cookie` header for `cookie` to the current response.
+    fn set_cookie(&self, cookie: &Cookie);
+}
+
 fn read_cookies_string(
     ssr_cookies_header_getter: Rc<dyn Fn() -> Option<String>>,
 ) -> Option<String> {
@@ -0,0 +1,334 @@
+use crate::{js, js_fut, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Reactive [Credential Management API](https://developer.mozilla.org/en-US/docs/Web/API/Credential_Management_API)
+/// for password credentials. Lets the browser's password manager sign the user in.
+///
+/// ## Usage
+///
+/// Store the credential after a successful sign-in so that the browser offers to save it.
+/// `get` returns a stored credential afterwards.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_credential_management, CredentialMediation, PasswordCredentialInfo};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let credentials = use_credential_management();
+///
+/// let on_signed_in = move |username: String, password: String| {
+///     spawn_local(async move {
+///         let _ = credentials.store(&PasswordCredentialInfo::new(username, password)).await;
+///     });
+/// };
+///
+/// let sign_in = move |_| {
+///     spawn_local(async move {
+///         if let Ok(Some(credential)) = credentials.get(CredentialMediation::Optional).await {
+///             // sign in with `credential.id` and `credential.password`
+///         }
+///     });
+/// };
+///
+/// let sign_out = move |_| {
+///     spawn_local(async move {
+///         // keeps the browser from signing the user in again automatically
+///         let _ = credentials.prevent_silent_access().await;
+///     });
+/// };
+///
+/// view! {
+///     <button on:click=sign_in>"Sign in"</button>
+///     <button on:click=sign_out>"Sign out"</button>
+/// }
+/// # }
+/// ```
+///
+/// ## Automatic sign-in
+///
+/// With `immediate` enabled a credential is requested without any user interaction as soon as
+/// the hook is created. `is_available` becomes `true` if the browser returned one.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{
+/// #     use_credential_management_with_options, PasswordCredentialInfo, UseCredentialManagementOptions,
+/// # };
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let credentials = use_credential_management_with_options(
+///     UseCredentialManagementOptions::default().immediate(true),
+/// );
+///
+/// let _ = watch(
+///     move || credentials.credential.get(),
+///     |credential: &Option<PasswordCredentialInfo>, _, _| {
+///         if let Some(credential) = credential {
+///             // sign in with the stored credential
+///         }
+///     },
+///     false,
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// Only Chromium based browsers support password credentials.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_available` is always `false` and all methods return
+/// `UseCredentialManagementError::NotSupported`.
+pub fn use_credential_management() -> UseCredentialManagementReturn {
+    use_credential_management_with_options(UseCredentialManagementOptions::default())
+}
+
+/// Version of [`use_credential_management`] that takes a `UseCredentialManagementOptions`. See [`use_credential_management`] for how to use.
+pub fn use_credential_management_with_options(
+    options: UseCredentialManagementOptions,
+) -> UseCredentialManagementReturn {
+    let UseCredentialManagementOptions { immediate } = options;
+
+    let is_supported = use_supported(|| {
+        js!("PasswordCredential" in &window()) && js!("credentials" in &window().navigator())
+    });
+
+    let (credential, set_credential) = create_signal(None::<PasswordCredentialInfo>);
+    let (error, set_error) = create_signal(None::<UseCredentialManagementError>);
+
+    let ret = UseCredentialManagementReturn {
+        is_supported,
+        is_available: Signal::derive(move || credential.with(Option::is_some)),
+        credential: credential.into(),
+        error: error.into(),
+        set_credential,
+        set_error,
+    };
+
+    if immediate && is_supported.get_untracked() {
+        spawn_local(async move {
+            let _ = ret.get(CredentialMediation::Silent).await;
+        });
+    }
+
+    ret
+}
+
+/// Options for [`use_credential_management_with_options`].
+#[derive(DefaultBuilder, Default)]
+pub struct UseCredentialManagementOptions {
+    /// If `true` a credential is requested with [`CredentialMediation::Silent`] when the hook is
+    /// created. Defaults to `false`.
+    immediate: bool,
+}
+
+/// Return type of [`use_credential_management`].
+#[derive(Clone, Copy)]
+pub struct UseCredentialManagementReturn {
+    /// Whether password credentials are supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// Whether `get` has returned a credential
+    pub is_available: Signal<bool>,
+
+    /// The credential that `get` has returned last
+    pub credential: Signal<Option<PasswordCredentialInfo>>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseCredentialManagementError>>,
+
+    set_credential: WriteSignal<Option<PasswordCredentialInfo>>,
+    set_error: WriteSignal<Option<UseCredentialManagementError>>,
+}
+
+impl UseCredentialManagementReturn {
+    /// Requests a stored password credential. Returns `None` if there is none or the user didn't
+    /// choose one.
+    pub async fn get(
+        &self,
+        mediation: CredentialMediation,
+    ) -> Result<Option<PasswordCredentialInfo>, UseCredentialManagementError> {
+        self.check_supported()?;
+
+        let options = js_sys::Object::new();
+        js!(options["password"] = true);
+        js!(options["mediation"] = mediation.as_str());
+
+        let credential = async {
+            js_fut!(window()
+                .navigator()
+                .credentials()
+                .get_with_options(options.unchecked_ref())?)
+            .await
+        }
+        .await
+        .map_err(|err| self.fail(UseCredentialManagementError::Get(err)))?;
+
+        let credential = credential
+            .dyn_into::<PasswordCredential>()
+            .ok()
+            .map(|credential| PasswordCredentialInfo {
+                id: credential.id(),
+                password: credential.password(),
+                name: credential.name().filter(|name| !name.is_empty()),
+                icon_url: credential.icon_url().filter(|url| !url.is_empty()),
+            });
+
+        self.set_credential.set(credential.clone());
+        self.set_error.set(None);
+
+        Ok(credential)
+    }
+
+    /// Stores the credential in the browser's password manager. The browser usually asks the
+    /// user to confirm this.
+    pub async fn store(
+        &self,
+        credential: &PasswordCredentialInfo,
+    ) -> Result<(), UseCredentialManagementError> {
+        self.check_supported()?;
+
+        let data = js_sys::Object::new();
+        js!(data["id"] = &credential.id);
+        js!(data["password"] = &credential.password);
+        if let Some(name) = &credential.name {
+            js!(data["name"] = name);
+        }
+        if let Some(icon_url) = &credential.icon_url {
+            js!(data["iconURL"] = icon_url);
+        }
+
+        async {
+            let password_credential = PasswordCredential::new(&data)?;
+
+            js_fut!(window()
+                .navigator()
+                .credentials()
+                .store(&password_credential)?)
+            .await
+        }
+        .await
+        .map_err(|err| self.fail(UseCredentialManagementError::Store(err)))?;
+
+        self.set_error.set(None);
+
+        Ok(())
+    }
+
+    /// Keeps the browser from returning a credential for [`CredentialMediation::Silent`] until
+    /// the user signs in again. Call this when the user signs out.
+    pub async fn prevent_silent_access(&self) -> Result<(), UseCredentialManagementError> {
+        self.check_supported()?;
+
+        async { js_fut!(window().navigator().credentials().prevent_silent_access()?).await }
+            .await
+            .map_err(|err| self.fail(UseCredentialManagementError::PreventSilentAccess(err)))?;
+
+        self.set_credential.set(None);
+        self.set_error.set(None);
+
+        Ok(())
+    }
+
+    fn check_supported(&self) -> Result<(), UseCredentialManagementError> {
+        if self.is_supported.get_untracked() {
+            Ok(())
+        } else {
+            Err(UseCredentialManagementError::NotSupported)
+        }
+    }
+
+    fn fail(&self, err: UseCredentialManagementError) -> UseCredentialManagementError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// Whether the user has to interact with the browser when a credential is requested.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CredentialMediation {
+    /// Returns a credential without asking the user or `None`
+    Silent,
+    /// Asks the user only if necessary, for example if there are several credentials
+    #[default]
+    Optional,
+    /// Always asks the user
+    Required,
+}
+
+impl CredentialMediation {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Silent => "silent",
+            Self::Optional => "optional",
+            Self::Required => "required",
+        }
+    }
+}
+
+/// A username and password stored in the browser's password manager.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PasswordCredentialInfo {
+    /// The username, for example the email address
+    pub id: String,
+
+    /// The password
+    pub password: String,
+
+    /// The name of the user that the browser shows
+    pub name: Option<String>,
+
+    /// The URL of the avatar of the user that the browser shows
+    pub icon_url: Option<String>,
+}
+
+impl PasswordCredentialInfo {
+    /// Creates a credential without a name and icon.
+    pub fn new(id: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            password: password.into(),
+            name: None,
+            icon_url: None,
+        }
+    }
+}
+
+/// Errors of [`use_credential_management`].
+#[derive(Error, Debug, Clone)]
+pub enum UseCredentialManagementError {
+    #[error("password credentials are not supported")]
+    NotSupported,
+    #[error("getting the credential failed")]
+    Get(JsValue),
+    #[error("storing the credential failed")]
+    Store(JsValue),
+    #[error("preventing silent access failed")]
+    PreventSilentAccess(JsValue),
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Clone, Debug)]
+    #[wasm_bindgen(extends = web_sys::Credential, js_name = PasswordCredential)]
+    type PasswordCredential;
+
+    #[wasm_bindgen(catch, constructor, js_class = PasswordCredential)]
+    fn new(data: &js_sys::Object) -> Result<PasswordCredential, JsValue>;
+
+    #[wasm_bindgen(method, getter)]
+    fn password(this: &PasswordCredential) -> String;
+
+    #[wasm_bindgen(method, getter)]
+    fn name(this: &PasswordCredential) -> Option<String>;
+
+    #[wasm_bindgen(method, getter = iconURL)]
+    fn icon_url(this: &PasswordCredential) -> Option<String>;
+}
@@ -0,0 +1,206 @@
+use crate::{use_document, use_event_listener};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+use web_sys::{SecurityPolicyViolationEvent, SecurityPolicyViolationEventDisposition};
+
+/// Reactive log of [Content Security Policy](https://developer.mozilla.org/en-US/docs/Web/HTTP/CSP)
+/// violations from the
+/// [`securitypolicyviolation`](https://developer.mozilla.org/en-US/docs/Web/API/Element/securitypolicyviolation_event)
+/// event.
+///
+/// ## Usage
+///
+/// Every resource or inline script that is blocked by the CSP of the page is added to
+/// `violations`. This also includes violations of a `Content-Security-Policy-Report-Only`
+/// header which makes it easy to try a new policy.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_csp_violation;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let csp = use_csp_violation();
+///
+/// view! {
+///     <ul>
+///         <For each=move || csp.violations.get() key=|violation| violation.clone() let:violation>
+///             <li>{violation.effective_directive} " blocked " {violation.blocked_uri}</li>
+///         </For>
+///     </ul>
+///     <button on:click=move |_| csp.clear()>"Clear"</button>
+/// }
+/// # }
+/// ```
+///
+/// ## Reporting
+///
+/// To send the violations to your monitoring in production, use `on_violation`. Set
+/// `max_violations` to `0` to not keep any of them in `violations`.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_csp_violation_with_options, UseCspViolationOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// use_csp_violation_with_options(
+///     UseCspViolationOptions::default()
+///         .max_violations(0)
+///         .on_violation(|violation| {
+///             // send `violation` to your server
+///         }),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// With the feature `serde` enabled, [`CspViolation`] implements `Serialize` and `Deserialize`.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `violations` is always empty.
+pub fn use_csp_violation() -> UseCspViolationReturn {
+    use_csp_violation_with_options(UseCspViolationOptions::default())
+}
+
+/// Version of [`use_csp_violation`] that takes a `UseCspViolationOptions`. See [`use_csp_violation`] for how to use.
+pub fn use_csp_violation_with_options(options: UseCspViolationOptions) -> UseCspViolationReturn {
+    let UseCspViolationOptions {
+        max_violations,
+        on_violation,
+    } = options;
+
+    let (violations, set_violations) = create_signal(Vec::<CspViolation>::new());
+
+    let _ = use_event_listener(
+        use_document(),
+        ev::Custom::<SecurityPolicyViolationEvent>::new("securitypolicyviolation"),
+        move |event| {
+            let violation = CspViolation::from(&event);
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_violation(&violation);
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+
+            if max_violations == 0 {
+                return;
+            }
+
+            set_violations.update(|violations| {
+                if violations.len() >= max_violations {
+                    violations.remove(0);
+                }
+                violations.push(violation);
+            });
+        },
+    );
+
+    UseCspViolationReturn {
+        violations: violations.into(),
+        set_violations,
+    }
+}
+
+/// Options for [`use_csp_violation_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseCspViolationOptions {
+    /// How many violations are kept in `violations`. The oldest one is dropped when a new one
+    /// occurs. Defaults to `100`.
+    max_violations: usize,
+
+    /// Called for every violation, for example to report it.
+    on_violation: Rc<dyn Fn(&CspViolation)>,
+}
+
+impl Default for UseCspViolationOptions {
+    fn default() -> Self {
+        Self {
+            max_violations: 100,
+            on_violation: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// Return type of [`use_csp_violation`].
+#[derive(Clone, Copy)]
+pub struct UseCspViolationReturn {
+    /// The violations that occurred, the oldest first
+    pub violations: Signal<Vec<CspViolation>>,
+
+    set_violations: WriteSignal<Vec<CspViolation>>,
+}
+
+impl UseCspViolationReturn {
+    /// Removes all violations from `violations`.
+    pub fn clear(&self) {
+        self.set_violations.set(Vec::new());
+    }
+}
+
+/// A violation of the Content Security Policy of the page.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct CspViolation {
+    /// The directive whose enforcement caused the violation, for example `"script-src-elem"`
+    pub effective_directive: String,
+
+    /// The directive as it's written in the policy, for example `"script-src"`
+    pub violated_directive: String,
+
+    /// The URL of the blocked resource or `"inline"`, `"eval"` and the like
+    pub blocked_uri: String,
+
+    /// The URL of the document in which the violation occurred
+    pub document_uri: String,
+
+    /// The URL of the script that caused the violation, if known
+    pub source_file: Option<String>,
+
+    /// The line in `source_file`
+    pub line_number: Option<u32>,
+
+    /// The column in `source_file`
+    pub column_number: Option<u32>,
+
+    /// The first 40 characters of the blocked inline script or style if the policy contains
+    /// `'report-sample'`
+    pub sample: Option<String>,
+
+    /// The whole policy that was violated
+    pub original_policy: String,
+
+    /// `true` if the resource was only reported by a `Content-Security-Policy-Report-Only` policy
+    /// but not blocked
+    pub is_report_only: bool,
+}
+
+impl From<&SecurityPolicyViolationEvent> for CspViolation {
+    fn from(event: &SecurityPolicyViolationEvent) -> Self {
+        let non_empty = |value: String| Some(value).filter(|value| !value.is_empty());
+        let positive = |value: i32| u32::try_from(value).ok().filter(|value| *value > 0);
+
+        Self {
+            effective_directive: event.effective_directive(),
+            violated_directive: event.violated_directive(),
+            blocked_uri: event.blocked_uri(),
+            document_uri: event.document_uri(),
+            source_file: non_empty(event.source_file()),
+            line_number: positive(event.line_number()),
+            column_number: positive(event.column_number()),
+            sample: non_empty(event.sample()),
+            original_policy: event.original_policy(),
+            is_report_only: event.disposition() == SecurityPolicyViolationEventDisposition::Report,
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use crate::utils::request_event_permission;
+use crate::PermissionState;
+use cfg_if::cfg_if;
+use leptos::*;
+use wasm_bindgen::JsValue;
+
+/// Reactive [DeviceMotionEvent](https://developer.mozilla.org/en-US/docs/Web/API/DeviceMotionEvent).
+/// Provides information about the acceleration and the rotation rate of the device running the
+/// web page.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_device_motion, UseDeviceMotionReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseDeviceMotionReturn {
+///     acceleration,
+///     rotation_rate,
+///     ..
+/// } = use_device_motion();
+///
+/// view! {
+///     <p>"x: " {move || acceleration.get().x}</p>
+///     <p>"alpha: " {move || rotation_rate.get().alpha}</p>
+/// }
+/// # }
+/// ```
+///
+/// ## Permission
+///
+/// iOS Safari only sends motion events after the user granted permission. Call
+/// `request_permission` from a user gesture like a click to show the prompt. In all other
+/// browsers it resolves to `PermissionState::Granted` right away.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_device_motion;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let motion = use_device_motion();
+///
+/// let enable = move |_| {
+///     spawn_local(async move {
+///         let _ = motion.request_permission().await;
+///     });
+/// };
+/// #
+/// # view! { <button on:click=enable>"Enable"</button> }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server this function returns values as if motion events were not supported by the
+/// device.
+pub fn use_device_motion() -> UseDeviceMotionReturn {
+    cfg_if! { if #[cfg(feature = "ssr")] {
+        let is_supported = Signal::derive(|| false);
+        let acceleration = || DeviceAcceleration::default();
+        let acceleration_including_gravity = || DeviceAcceleration::default();
+        let rotation_rate = || DeviceRotationRate::default();
+        let interval = || None;
+    } else {
+        use crate::{use_event_listener_with_options, UseEventListenerOptions, use_supported, js};
+        use leptos::ev::devicemotion;
+
+        let is_supported = use_supported(|| js!("DeviceMotionEvent" in &window()));
+        let (acceleration, set_acceleration) = create_signal(DeviceAcceleration::default());
+        let (acceleration_including_gravity, set_acceleration_including_gravity) =
+            create_signal(DeviceAcceleration::default());
+        let (rotation_rate, set_rotation_rate) = create_signal(DeviceRotationRate::default());
+        let (interval, set_interval) = create_signal(None);
+
+        if is_supported.get_untracked() {
+            let cleanup = use_event_listener_with_options(
+                window(),
+                devicemotion,
+                move |event: web_sys::DeviceMotionEvent| {
+                    set_acceleration.set(
+                        event
+                            .acceleration()
+                            .as_ref()
+                            .map(DeviceAcceleration::from)
+                            .unwrap_or_default(),
+                    );
+                    set_acceleration_including_gravity.set(
+                        event
+                            .acceleration_including_gravity()
+                            .as_ref()
+                            .map(DeviceAcceleration::from)
+                            .unwrap_or_default(),
+                    );
+                    set_rotation_rate.set(
+                        event
+                            .rotation_rate()
+                            .as_ref()
+                            .map(DeviceRotationRate::from)
+                            .unwrap_or_default(),
+                    );
+                    set_interval.set(event.interval());
+                },
+                UseEventListenerOptions::default().passive(true),
+            );
+
+            on_cleanup(cleanup);
+        }
+    }}
+
+    UseDeviceMotionReturn {
+        is_supported,
+        acceleration: acceleration.into(),
+        acceleration_including_gravity: acceleration_including_gravity.into(),
+        rotation_rate: rotation_rate.into(),
+        interval: interval.into(),
+    }
+}
+
+/// Acceleration of the device in m/s² along the three axes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DeviceAcceleration {
+    pub x: Option<f64>,
+    pub y: Option<f64>,
+    pub z: Option<f64>,
+}
+
+impl From<&web_sys::DeviceAcceleration> for DeviceAcceleration {
+    fn from(acceleration: &web_sys::DeviceAcceleration) -> Self {
+        Self {
+            x: acceleration.x(),
+            y: acceleration.y(),
+            z: acceleration.z(),
+        }
+    }
+}
+
+/// Rate of rotation of the device in degrees per second around the three axes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DeviceRotationRate {
+    pub alpha: Option<f64>,
+    pub beta: Option<f64>,
+    pub gamma: Option<f64>,
+}
+
+impl From<&web_sys::DeviceRotationRate> for DeviceRotationRate {
+    fn from(rotation_rate: &web_sys::DeviceRotationRate) -> Self {
+        Self {
+            alpha: rotation_rate.alpha(),
+            beta: rotation_rate.beta(),
+            gamma: rotation_rate.gamma(),
+        }
+    }
+}
+
+/// Return type of [`use_device_motion`].
+#[derive(Clone, Copy)]
+pub struct UseDeviceMotionReturn {
+    pub is_supported: Signal<bool>,
+
+    /// Acceleration of the device without the effect of gravity
+    pub acceleration: Signal<DeviceAcceleration>,
+
+    /// Acceleration of the device including the effect of gravity
+    pub acceleration_including_gravity: Signal<DeviceAcceleration>,
+
+    pub rotation_rate: Signal<DeviceRotationRate>,
+
+    /// Interval in ms at which the device reports motion events
+    pub interval: Signal<Option<f64>>,
+}
+
+impl UseDeviceMotionReturn {
+    /// Requests the permission to receive motion events. Has to be called from a user gesture.
+    /// Resolves to `PermissionState::Denied` if motion events aren't supported.
+    pub async fn request_permission(&self) -> Result<PermissionState, JsValue> {
+        if !self.is_supported.get_untracked() {
+            return Ok(PermissionState::Denied);
+        }
+
+        request_event_permission("DeviceMotionEvent").await
+    }
+}
@@ -1,5 +1,8 @@
+use crate::utils::request_event_permission;
+use crate::PermissionState;
 use cfg_if::cfg_if;
 use leptos::*;
+use wasm_bindgen::JsValue;
 
 /// Reactive [DeviceOrientationEvent](https://developer.mozilla.org/en-US/docs/Web/API/DeviceOrientationEvent).
 /// Provide web developers with information from the physical orientation of
@@ -29,6 +32,30 @@ use leptos::*;
 /// # }
 /// ```
 ///
+/// ## Permission
+///
+/// iOS Safari only sends orientation events after the user granted permission. Call
+/// `request_permission` from a user gesture like a click to show the prompt. In all other
+/// browsers it resolves to `PermissionState::Granted` right away.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_device_orientation;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let orientation = use_device_orientation();
+///
+/// let enable = move |_| {
+///     spawn_local(async move {
+///         let _ = orientation.request_permission().await;
+///     });
+/// };
+/// #
+/// # view! { <button on:click=enable>"Enable"</button> }
+/// # }
+/// ```
+///
 /// ## Server-Side Rendering
 ///
 /// On the server this function returns values as if the orientation
@@ -80,7 +107,7 @@ pub fn use_device_orientation() -> UseDeviceOrientationReturn {
 }
 
 /// Return type of [`use_device_orientation`].
-#[derive(Clone)]
+#[derive(Clone, Copy)]
 pub struct UseDeviceOrientationReturn {
     pub is_supported: Signal<bool>,
     pub absolute: Signal<bool>,
@@ -88,3 +115,15 @@ pub struct UseDeviceOrientationReturn {
     pub beta: Signal<Option<f64>>,
     pub gamma: Signal<Option<f64>>,
 }
+
+impl UseDeviceOrientationReturn {
+    /// Requests the permission to receive orientation events. Has to be called from a user
+    /// gesture. Resolves to `PermissionState::Denied` if orientation events aren't supported.
+    pub async fn request_permission(&self) -> Result<PermissionState, JsValue> {
+        if !self.is_supported.get_untracked() {
+            return Ok(PermissionState::Denied);
+        }
+
+        request_event_permission("DeviceOrientationEvent").await
+    }
+}
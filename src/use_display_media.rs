@@ -2,6 +2,7 @@ use crate::core::MaybeRwSignal;
 use cfg_if::cfg_if;
 use default_struct_builder::DefaultBuilder;
 use leptos::*;
+use std::fmt::Display;
 use wasm_bindgen::{JsCast, JsValue};
 
 /// Reactive [`mediaDevices.getDisplayMedia`](https://developer.mozilla.org/en-US/docs/Web/API/MediaDevices/getDisplayMedia) streaming.
@@ -38,6 +39,30 @@ use wasm_bindgen::{JsCast, JsValue};
 /// # }
 /// ```
 ///
+/// The options let you suggest which kind of surface the user should share and whether the
+/// current tab should be offered at all.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_display_media_with_options, DisplaySurface, UseDisplayMediaOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let display_media = use_display_media_with_options(
+///     UseDisplayMediaOptions::default()
+///         .display_surface(DisplaySurface::Window)
+///         .exclude_self_browser_surface(true)
+///         .audio(true),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// Capturing stops when `stop` is called, when the user ends the capture in the browser UI or
+/// when the component is cleaned up. In all cases every track of the stream is stopped and
+/// `enabled` becomes `false`.
+///
 /// ## Server-Side Rendering
 ///
 /// On the server calls to `start` or any other way to enable the stream will be ignored
@@ -50,7 +75,12 @@ pub fn use_display_media() -> UseDisplayMediaReturn<impl Fn() + Clone, impl Fn()
 pub fn use_display_media_with_options(
     options: UseDisplayMediaOptions,
 ) -> UseDisplayMediaReturn<impl Fn() + Clone, impl Fn() + Clone> {
-    let UseDisplayMediaOptions { enabled, audio } = options;
+    let UseDisplayMediaOptions {
+        enabled,
+        audio,
+        display_surface,
+        exclude_self_browser_surface,
+    } = options;
 
     let (enabled, set_enabled) = enabled.into_signal();
 
@@ -62,11 +92,13 @@ pub fn use_display_media_with_options(
                 return;
             }
 
-            let stream = create_media(audio).await;
+            let stream = create_media(audio, display_surface, exclude_self_browser_surface).await;
 
             set_stream.update(|s| *s = Some(stream));
         } else {
             let _ = audio;
+            let _ = display_surface;
+            let _ = exclude_self_browser_surface;
         }}
     };
 
@@ -112,6 +144,35 @@ pub fn use_display_media_with_options(
         true,
     );
 
+    // the user can end the capture from the browser UI
+    #[cfg(not(feature = "ssr"))]
+    {
+        use crate::use_event_listener;
+
+        let video_track = Signal::derive(move || {
+            stream.with(|stream| {
+                stream
+                    .as_ref()
+                    .and_then(|stream| stream.as_ref().ok())
+                    .and_then(|stream| {
+                        stream
+                            .get_video_tracks()
+                            .get(0)
+                            .dyn_into::<web_sys::MediaStreamTrack>()
+                            .ok()
+                    })
+            })
+        });
+
+        let _ = use_event_listener(
+            video_track,
+            leptos::ev::Custom::<web_sys::Event>::new("ended"),
+            move |_| stop(),
+        );
+    }
+
+    on_cleanup(_stop);
+
     UseDisplayMediaReturn {
         stream: stream.into(),
         start,
@@ -122,7 +183,11 @@ pub fn use_display_media_with_options(
 }
 
 #[cfg(not(feature = "ssr"))]
-async fn create_media(audio: bool) -> Result<web_sys::MediaStream, JsValue> {
+async fn create_media(
+    audio: bool,
+    display_surface: Option<DisplaySurface>,
+    exclude_self_browser_surface: bool,
+) -> Result<web_sys::MediaStream, JsValue> {
     use crate::js_fut;
     use crate::use_window::use_window;
 
@@ -131,11 +196,23 @@ async fn create_media(audio: bool) -> Result<web_sys::MediaStream, JsValue> {
         .ok_or_else(|| JsValue::from_str("Failed to access window.navigator"))
         .and_then(|n| n.media_devices())?;
 
+    use crate::js;
+
     let mut constraints = web_sys::DisplayMediaStreamConstraints::new();
     if audio {
         constraints.audio(&JsValue::from(true));
     }
 
+    if let Some(display_surface) = display_surface {
+        let video = js_sys::Object::new();
+        js!(video["displaySurface"] = display_surface.to_string());
+        constraints.set_video(&video);
+    }
+
+    if exclude_self_browser_surface {
+        js!(constraints["selfBrowserSurface"] = "exclude");
+    }
+
     let promise = media.get_display_media_with_constraints(&constraints)?;
     let res = js_fut!(promise).await?;
 
@@ -153,6 +230,15 @@ pub struct UseDisplayMediaOptions {
     /// will contain an audio track, if audio is supported and available for the display surface chosen by the user.
     /// The default value is `false`.
     audio: bool,
+
+    /// The kind of surface that is preselected in the browser's share dialog. The user can still
+    /// choose another one. Defaults to `None` which leaves the choice to the browser.
+    #[builder(into)]
+    display_surface: Option<DisplaySurface>,
+
+    /// If `true` the current tab isn't offered in the share dialog. This avoids the hall of
+    /// mirrors effect when the captured stream is shown on the page. Defaults to `false`.
+    exclude_self_browser_surface: bool,
 }
 
 impl Default for UseDisplayMediaOptions {
@@ -160,6 +246,31 @@ impl Default for UseDisplayMediaOptions {
         Self {
             enabled: false.into(),
             audio: false,
+            display_surface: None,
+            exclude_self_browser_surface: false,
+        }
+    }
+}
+
+/// The kind of surface to [capture](https://developer.mozilla.org/en-US/docs/Web/API/MediaTrackConstraints/displaySurface).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DisplaySurface {
+    /// A browser tab
+    Browser,
+
+    /// A single window
+    Window,
+
+    /// A whole screen
+    Monitor,
+}
+
+impl Display for DisplaySurface {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            DisplaySurface::Browser => write!(f, "browser"),
+            DisplaySurface::Window => write!(f, "window"),
+            DisplaySurface::Monitor => write!(f, "monitor"),
         }
     }
 }
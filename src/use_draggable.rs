@@ -1,4 +1,4 @@
-use crate::core::{ElementMaybeSignal, MaybeRwSignal, PointerType, Position};
+use crate::core::{ElementMaybeSignal, MaybeRwSignal, PointerType, Position, Size};
 use crate::{use_event_listener_with_options, use_window, UseEventListenerOptions, UseWindow};
 use default_struct_builder::DefaultBuilder;
 use leptos::ev::{pointerdown, pointermove, pointerup};
@@ -44,6 +44,67 @@ use web_sys::PointerEvent;
 /// }
 /// # }
 /// ```
+///
+/// ## Constraints
+///
+/// Dragging can be restricted to one `axis`, kept inside `bounds` and snapped to a `grid`.
+/// With a `handle` the element can only be dragged by that part of it.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_draggable_with_options, UseDraggableAxis, UseDraggableBounds, UseDraggableOptions, UseDraggableReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let container = create_node_ref::<Div>();
+/// let el = create_node_ref::<Div>();
+/// let handle = create_node_ref::<Div>();
+///
+/// let UseDraggableReturn { style, .. } = use_draggable_with_options(
+///     el,
+///     UseDraggableOptions::default()
+///         .axis(UseDraggableAxis::X)
+///         .bounds(UseDraggableBounds::container(container))
+///         .grid(Some(20.0))
+///         .handle(Some(handle)),
+/// );
+///
+/// view! {
+///     <div node_ref=container style="position: relative; width: 400px; height: 100px">
+///         <div node_ref=el style=move || format!("position: absolute; {}", style.get())>
+///             <div node_ref=handle>"Drag here"</div>
+///         </div>
+///     </div>
+/// }
+/// # }
+/// ```
+///
+/// `on_move` receives the constrained position. It can return a different position or `None`
+/// to not move the element at all.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_draggable_with_options, UseDraggableOptions};
+/// # use leptos_use::core::Position;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+///
+/// let draggable = use_draggable_with_options(
+///     el,
+///     UseDraggableOptions::default().on_move(|args| {
+///         // keep the element on a diagonal
+///         let x = args.position.x.max(args.position.y);
+///         Some(Position { x, y: x })
+///     }),
+/// );
+/// #
+/// # view! { <div node_ref=el></div> }
+/// # }
+/// ```
 pub fn use_draggable<El, T>(target: El) -> UseDraggableReturn
 where
     El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
@@ -81,6 +142,9 @@ where
         handle,
         pointer_types,
         initial_value,
+        axis,
+        bounds,
+        grid,
         on_start,
         on_move,
         on_end,
@@ -100,6 +164,27 @@ where
     let (position, set_position) = initial_value.into_signal();
     let (start_position, set_start_position) = create_signal(None::<Position>);
 
+    let target_size = {
+        let target = target.clone();
+
+        move || {
+            target
+                .get_untracked()
+                .map(|target| {
+                    let rect = target
+                        .into()
+                        .unchecked_into::<web_sys::Element>()
+                        .get_bounding_client_rect();
+
+                    Size {
+                        width: rect.width(),
+                        height: rect.height(),
+                    }
+                })
+                .unwrap_or_default()
+        }
+    };
+
     let filter_event = move |event: &PointerEvent| {
         let ty = event.pointer_type();
         pointer_types.iter().any(|p| p.to_string() == ty)
@@ -164,23 +249,78 @@ where
                 return;
             }
             if let Some(start_position) = start_position.get_untracked() {
-                let position = Position {
+                let mut new_position = Position {
                     x: event.client_x() as f64 - start_position.x,
                     y: event.client_y() as f64 - start_position.y,
                 };
-                set_position.set(position);
+
+                let container = match &bounds {
+                    UseDraggableBounds::Container(container) => container.get_untracked(),
+                    _ => None,
+                };
+
+                // inside a container the position is relative to its content
+                if let Some(container) = &container {
+                    let rect = container.get_bounding_client_rect();
+                    new_position.x += container.scroll_left() as f64
+                        - rect.left()
+                        - container.client_left() as f64;
+                    new_position.y +=
+                        container.scroll_top() as f64 - rect.top() - container.client_top() as f64;
+                }
+
+                let current_position = position.get_untracked();
+                match axis.get_untracked() {
+                    UseDraggableAxis::Both => {}
+                    UseDraggableAxis::X => new_position.y = current_position.y,
+                    UseDraggableAxis::Y => new_position.x = current_position.x,
+                }
+
+                if let Some(grid) = grid.get_untracked().filter(|grid| *grid > 0.0) {
+                    new_position.x = (new_position.x / grid).round() * grid;
+                    new_position.y = (new_position.y / grid).round() * grid;
+                }
+
+                let area = match (&bounds, &container) {
+                    (
+                        UseDraggableBounds::Rect {
+                            left,
+                            top,
+                            right,
+                            bottom,
+                        },
+                        _,
+                    ) => Some((*left, *top, *right, *bottom)),
+                    (_, Some(container)) => Some((
+                        0.0,
+                        0.0,
+                        container.client_width() as f64,
+                        container.client_height() as f64,
+                    )),
+                    _ => None,
+                };
+
+                if let Some((left, top, right, bottom)) = area {
+                    let size = target_size();
+                    new_position.x = new_position.x.min(right - size.width).max(left);
+                    new_position.y = new_position.y.min(bottom - size.height).max(top);
+                }
 
                 #[cfg(debug_assertions)]
                 let prev = SpecialNonReactiveZone::enter();
 
-                on_move(UseDraggableCallbackArgs {
-                    position,
+                let new_position = on_move(UseDraggableCallbackArgs {
+                    position: new_position,
                     event: event.clone(),
                 });
 
                 #[cfg(debug_assertions)]
                 SpecialNonReactiveZone::exit(prev);
 
+                if let Some(new_position) = new_position {
+                    set_position.set(new_position);
+                }
+
                 handle_event(event);
             }
         }
@@ -277,11 +417,24 @@ where
     #[builder(into)]
     initial_value: MaybeRwSignal<Position>,
 
+    /// Restrict dragging to one axis. Defaults to `UseDraggableAxis::Both`.
+    #[builder(into)]
+    axis: MaybeSignal<UseDraggableAxis>,
+
+    /// Keep the element inside an area. Defaults to `UseDraggableBounds::None`.
+    bounds: UseDraggableBounds,
+
+    /// Snap the position to a grid of this size in pixels. Defaults to `None`.
+    #[builder(into)]
+    grid: MaybeSignal<Option<f64>>,
+
     /// Callback when the dragging starts. Return `false` to prevent dragging.
     on_start: Rc<dyn Fn(UseDraggableCallbackArgs) -> bool>,
 
-    /// Callback during dragging.
-    on_move: Rc<dyn Fn(UseDraggableCallbackArgs)>,
+    /// Callback during dragging with the position after all constraints have been applied.
+    /// Return the position the element should move to or `None` to keep it where it is.
+    /// Defaults to returning the given position.
+    on_move: Rc<dyn Fn(UseDraggableCallbackArgs) -> Option<Position>>,
 
     /// Callback when dragging end.
     on_end: Rc<dyn Fn(UseDraggableCallbackArgs)>,
@@ -304,8 +457,11 @@ impl Default
             handle: None,
             pointer_types: vec![PointerType::Mouse, PointerType::Touch, PointerType::Pen],
             initial_value: MaybeRwSignal::default(),
+            axis: MaybeSignal::default(),
+            bounds: UseDraggableBounds::None,
+            grid: MaybeSignal::default(),
             on_start: Rc::new(|_| true),
-            on_move: Rc::new(|_| {}),
+            on_move: Rc::new(|args| Some(args.position)),
             on_end: Rc::new(|_| {}),
             _marker1: PhantomData,
             _marker2: PhantomData,
@@ -313,6 +469,53 @@ impl Default
     }
 }
 
+/// Axis along which an element can be dragged. See [`UseDraggableOptions`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UseDraggableAxis {
+    /// Horizontally and vertically
+    #[default]
+    Both,
+    /// Only horizontally
+    X,
+    /// Only vertically
+    Y,
+}
+
+/// Area an element is kept inside while dragging. See [`UseDraggableOptions`].
+#[derive(Clone)]
+pub enum UseDraggableBounds {
+    /// The element can be dragged anywhere
+    None,
+
+    /// The element stays inside this rectangle. The edges are in the same coordinates as the
+    /// position, i.e. client coordinates.
+    Rect {
+        left: f64,
+        top: f64,
+        right: f64,
+        bottom: f64,
+    },
+
+    /// The element stays inside this element. The position is relative to the content of the
+    /// container which fits an element that is positioned `absolute` inside of it.
+    /// Create it with [`UseDraggableBounds::container`].
+    Container(Signal<Option<web_sys::Element>>),
+}
+
+impl UseDraggableBounds {
+    /// Keeps the dragged element inside `container`.
+    pub fn container<El, T>(container: El) -> Self
+    where
+        El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+        T: Into<web_sys::Element> + Clone + 'static,
+    {
+        let container = container.into();
+        Self::Container(Signal::derive(move || {
+            container.get().map(|container| container.into())
+        }))
+    }
+}
+
 /// Argument for the `on_...` handler functions of [`UseDraggableOptions`].
 pub struct UseDraggableCallbackArgs {
     /// Position of the `target` element
@@ -1,13 +1,16 @@
 use crate::core::ElementMaybeSignal;
+use crate::{js, js_fut};
 use cfg_if::cfg_if;
 use default_struct_builder::DefaultBuilder;
 use leptos::*;
 use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
+use wasm_bindgen::{JsCast, JsValue};
 
 cfg_if! { if #[cfg(not(feature = "ssr"))] {
     use crate::use_event_listener;
     use leptos::ev::{dragenter, dragleave, dragover, drop};
+    use std::collections::VecDeque;
 }}
 
 /// Create a zone where files can be dropped.
@@ -47,6 +50,81 @@ cfg_if! { if #[cfg(not(feature = "ssr"))] {
 /// # }
 /// ```
 ///
+/// ## Folders
+///
+/// When a folder is dropped, all files inside of it and its subfolders end up in `files`. Reading
+/// the folders happens asynchronously so `on_drop` is only called once all files are known.
+/// Meanwhile `is_loading` is `true`. Set `directories` to `false` to get the files exactly as the
+/// browser reports them.
+///
+/// ## Accepting only some files
+///
+/// `accept` works like the [`accept`](https://developer.mozilla.org/en-US/docs/Web/HTML/Attributes/accept)
+/// attribute of a file input. It takes MIME types like `"image/png"`, wildcards like `"image/*"`
+/// and file extensions like `".pdf"`. Files that don't match end up in `rejected_files`.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_drop_zone_with_options, UseDropZoneOptions, UseDropZoneReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let drop_zone_el = create_node_ref::<Div>();
+///
+/// let UseDropZoneReturn {
+///     files,
+///     rejected_files,
+///     ..
+/// } = use_drop_zone_with_options(
+///     drop_zone_el,
+///     UseDropZoneOptions::default().accept(vec!["image/*".to_string(), ".pdf".to_string()]),
+/// );
+///
+/// view! {
+///     <div node_ref=drop_zone_el>"Drop images or PDFs here"</div>
+///     <Show when=move || !rejected_files.with(Vec::is_empty)>
+///         <p>{move || rejected_files.with(Vec::len)} " files have been ignored"</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// ## Reading large files
+///
+/// Use [`read_file_with_progress`] to read the content of a dropped file and show the progress.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{read_file_with_progress, use_drop_zone_with_options, UseDropZoneOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let drop_zone_el = create_node_ref::<Div>();
+/// let (progress, set_progress) = create_signal(0.0);
+///
+/// use_drop_zone_with_options(
+///     drop_zone_el,
+///     UseDropZoneOptions::default().on_drop(move |event| {
+///         for file in event.files {
+///             spawn_local(async move {
+///                 let content = read_file_with_progress(&file, move |progress| {
+///                     set_progress.set(progress);
+///                 })
+///                 .await;
+///             });
+///         }
+///     }),
+/// );
+///
+/// view! {
+///     <div node_ref=drop_zone_el>"Drop a file here"</div>
+///     <progress value=progress max="1"></progress>
+/// }
+/// # }
+/// ```
+///
 /// ## Server-Side Rendering
 ///
 /// On the server the returned `file` signal always contains an empty `Vec` and
@@ -73,10 +151,14 @@ where
 {
     let (is_over_drop_zone, set_over_drop_zone) = create_signal(false);
     let (files, set_files) = create_signal(Vec::<web_sys::File>::new());
+    let (rejected_files, set_rejected_files) = create_signal(Vec::<web_sys::File>::new());
+    let (is_loading, set_loading) = create_signal(false);
 
     #[cfg(not(feature = "ssr"))]
     {
         let UseDropZoneOptions {
+            directories,
+            accept,
             on_drop,
             on_enter,
             on_leave,
@@ -84,6 +166,23 @@ where
         } = options;
 
         let counter = store_value(0_usize);
+        let accept = store_value(accept);
+
+        // returns the accepted files
+        let set_filtered_files = move |all_files: Vec<web_sys::File>| {
+            let (accepted, rejected): (Vec<_>, Vec<_>) = accept
+                .try_with_value(|accept| {
+                    all_files
+                        .into_iter()
+                        .partition(|file| is_accepted(file, accept))
+                })
+                .unwrap_or_default();
+
+            let _ = set_files.try_set(accepted.clone());
+            let _ = set_rejected_files.try_set(rejected);
+
+            accepted
+        };
 
         let update_files = move |event: &web_sys::DragEvent| {
             if let Some(data_transfer) = event.data_transfer() {
@@ -95,7 +194,7 @@ where
                     .map(web_sys::File::from)
                     .collect();
 
-                set_files.update(move |f| *f = files);
+                set_filtered_files(files);
             }
         };
 
@@ -160,31 +259,201 @@ where
             counter.update_value(|counter| *counter = 0);
             set_over_drop_zone.set(false);
 
-            update_files(&event);
+            let call_on_drop = {
+                let on_drop = Rc::clone(&on_drop);
 
-            #[cfg(debug_assertions)]
-            let prev = SpecialNonReactiveZone::enter();
+                move |files: Vec<web_sys::File>, event: web_sys::DragEvent| {
+                    #[cfg(debug_assertions)]
+                    let prev = SpecialNonReactiveZone::enter();
 
-            on_drop(UseDropZoneEvent {
-                files: files.get_untracked(),
-                event,
-            });
+                    on_drop(UseDropZoneEvent { files, event });
 
-            #[cfg(debug_assertions)]
-            SpecialNonReactiveZone::exit(prev);
+                    #[cfg(debug_assertions)]
+                    SpecialNonReactiveZone::exit(prev);
+                }
+            };
+
+            let (dropped_files, directory_entries) = if directories {
+                dropped_entries(&event)
+            } else {
+                (vec![], vec![])
+            };
+
+            if directory_entries.is_empty() {
+                update_files(&event);
+                call_on_drop(files.get_untracked(), event);
+                return;
+            }
+
+            // the entries are only accessible during the event so they have been collected above
+            set_loading.set(true);
+
+            spawn_local(async move {
+                let mut all_files = dropped_files;
+                all_files.extend(files_in_directories(directory_entries).await);
+
+                let accepted = set_filtered_files(all_files);
+                let _ = set_loading.try_set(false);
+
+                call_on_drop(accepted, event);
+            });
         });
     }
 
     UseDropZoneReturn {
         files: files.into(),
+        rejected_files: rejected_files.into(),
         is_over_drop_zone: is_over_drop_zone.into(),
+        is_loading: is_loading.into(),
+    }
+}
+
+/// Splits the items of a drop event into the dropped files and folders.
+#[cfg(not(feature = "ssr"))]
+fn dropped_entries(
+    event: &web_sys::DragEvent,
+) -> (Vec<web_sys::File>, Vec<web_sys::FileSystemDirectoryEntry>) {
+    let mut files = vec![];
+    let mut directory_entries = vec![];
+
+    let Some(items) = event
+        .data_transfer()
+        .map(|data_transfer| data_transfer.items())
+    else {
+        return (files, directory_entries);
+    };
+
+    for item in (0..items.length()).filter_map(|index| items.get(index)) {
+        if item.kind() != "file" {
+            continue;
+        }
+
+        match item.webkit_get_as_entry() {
+            Ok(Some(entry)) if entry.is_directory() => {
+                directory_entries.push(entry.unchecked_into());
+            }
+            _ => files.extend(item.get_as_file().ok().flatten()),
+        }
+    }
+
+    (files, directory_entries)
+}
+
+/// Recursively collects all files of the folders.
+#[cfg(not(feature = "ssr"))]
+async fn files_in_directories(
+    directory_entries: Vec<web_sys::FileSystemDirectoryEntry>,
+) -> Vec<web_sys::File> {
+    let mut files = vec![];
+    let mut pending = directory_entries
+        .into_iter()
+        .map(web_sys::FileSystemEntry::from)
+        .collect::<VecDeque<_>>();
+
+    while let Some(entry) = pending.pop_front() {
+        if entry.is_file() {
+            let entry = entry.unchecked_into::<web_sys::FileSystemFileEntry>();
+            let file = js_sys::Promise::new(&mut |resolve, reject| {
+                entry.file_with_callback_and_callback(&resolve, &reject)
+            });
+
+            if let Ok(file) = js_fut!(file).await {
+                files.push(file.unchecked_into());
+            }
+        } else if entry.is_directory() {
+            let reader = entry
+                .unchecked_into::<web_sys::FileSystemDirectoryEntry>()
+                .create_reader();
+
+            // `readEntries` returns the entries in batches until an empty one
+            loop {
+                let batch = js_sys::Promise::new(&mut |resolve, reject| {
+                    let _ = reader.read_entries_with_callback_and_callback(&resolve, &reject);
+                });
+
+                let Ok(batch) = js_fut!(batch).await else {
+                    break;
+                };
+                let batch = js_sys::Array::from(&batch);
+                if batch.length() == 0 {
+                    break;
+                }
+
+                pending.extend(batch.iter().map(JsCast::unchecked_into));
+            }
+        }
     }
+
+    files
+}
+
+/// Whether `file` matches one of the entries of `accept`. An empty `accept` accepts all files.
+#[cfg(not(feature = "ssr"))]
+fn is_accepted(file: &web_sys::File, accept: &[String]) -> bool {
+    if accept.is_empty() {
+        return true;
+    }
+
+    let name = file.name().to_lowercase();
+    let mime_type = file.type_().to_lowercase();
+
+    accept.iter().any(|pattern| {
+        let pattern = pattern.trim().to_lowercase();
+
+        if pattern.starts_with('.') {
+            name.ends_with(&pattern)
+        } else if let Some(prefix) = pattern.strip_suffix("/*") {
+            mime_type.split('/').next() == Some(prefix)
+        } else {
+            mime_type == pattern
+        }
+    })
+}
+
+/// Reads the content of `file` chunk by chunk. `on_progress` is called after every chunk with the
+/// share of the file that has been read so far, from `0.0` to `1.0`.
+pub async fn read_file_with_progress(
+    file: &web_sys::File,
+    on_progress: impl Fn(f64),
+) -> Result<Vec<u8>, JsValue> {
+    let size = file.size();
+    let reader = file
+        .stream()
+        .get_reader()
+        .unchecked_into::<web_sys::ReadableStreamDefaultReader>();
+
+    let mut content = Vec::with_capacity(size as usize);
+    on_progress(0.0);
+
+    loop {
+        let result = js_fut!(reader.read()).await?;
+
+        if js!(result["done"])?.as_bool().unwrap_or(true) {
+            break;
+        }
+
+        let chunk = js!(result["value"])?.unchecked_into::<js_sys::Uint8Array>();
+        content.extend(chunk.to_vec());
+
+        if size > 0.0 {
+            on_progress((content.len() as f64 / size).min(1.0));
+        }
+    }
+
+    on_progress(1.0);
+
+    Ok(content)
 }
 
 /// Options for [`use_drop_zone_with_options`].
 #[derive(DefaultBuilder, Clone)]
 #[cfg_attr(feature = "ssr", allow(dead_code))]
 pub struct UseDropZoneOptions {
+    /// Whether the files inside of dropped folders are collected recursively. Defaults to `true`.
+    directories: bool,
+    /// MIME types like `"image/png"` or `"image/*"` and file extensions like `".pdf"` of the
+    /// files that are accepted. Defaults to an empty `Vec` which accepts all files.
+    accept: Vec<String>,
     /// Event handler for the [`drop`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLElement/drop_event) event
     on_drop: Rc<dyn Fn(UseDropZoneEvent)>,
     /// Event handler for the [`dragenter`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLElement/dragenter_event) event
@@ -198,6 +467,8 @@ pub struct UseDropZoneOptions {
 impl Default for UseDropZoneOptions {
     fn default() -> Self {
         Self {
+            directories: true,
+            accept: vec![],
             on_drop: Rc::new(|_| {}),
             on_enter: Rc::new(|_| {}),
             on_leave: Rc::new(|_| {}),
@@ -225,6 +496,10 @@ pub struct UseDropZoneEvent {
 pub struct UseDropZoneReturn {
     /// Files being handled
     pub files: Signal<Vec<web_sys::File>>,
+    /// Files that have been dropped but don't match `accept`
+    pub rejected_files: Signal<Vec<web_sys::File>>,
     /// Whether the files (dragged by the pointer) are over the drop zone
     pub is_over_drop_zone: Signal<bool>,
+    /// Whether the content of dropped folders is still being read
+    pub is_loading: Signal<bool>,
 }
@@ -26,6 +26,32 @@ use leptos::*;
 /// view! { <div node_ref=el></div> }
 /// # }
 /// ```
+///
+/// ## Triggers
+///
+/// By default the values are updated when the element is resized, the window is scrolled or
+/// resized and when the `style` or `class` attribute of the element changes. Each of these
+/// triggers can be turned off in the options. You can always call `update` manually.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_element_bounding_with_options, UseElementBoundingOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+/// let bounding = use_element_bounding_with_options(
+///     el,
+///     UseElementBoundingOptions::default()
+///         .window_scroll(false)
+///         .mutation(false),
+/// );
+///
+/// view! { <div node_ref=el></div> }
+/// # }
+/// ```
+///
 /// ## Server-Side Rendering
 ///
 /// On the server the returned signals always are `0.0` and `update` is a no-op.
@@ -71,8 +97,8 @@ where
         let update = move || ();
     } else {
         use crate::{
-            use_event_listener_with_options, use_resize_observer, use_window,
-            UseEventListenerOptions,
+            use_event_listener_with_options, use_mutation_observer_with_options,
+            use_resize_observer, use_window, UseEventListenerOptions, UseMutationObserverOptions,
         };
         use leptos::ev::{resize, scroll};
 
@@ -80,6 +106,8 @@ where
             reset,
             window_resize,
             window_scroll,
+            element_resize,
+            mutation,
             immediate,
         } = options;
 
@@ -115,13 +143,28 @@ where
             }
         };
 
-        use_resize_observer(target.clone(), {
-            let update = update.clone();
+        if element_resize {
+            use_resize_observer(target.clone(), {
+                let update = update.clone();
+
+                move |_, _| {
+                    update();
+                }
+            });
+        }
 
-            move |_, _| {
-                update();
-            }
-        });
+        if mutation {
+            use_mutation_observer_with_options(
+                target.clone(),
+                {
+                    let update = update.clone();
+                    move |_, _| update()
+                },
+                UseMutationObserverOptions::default()
+                    .attributes(true)
+                    .attribute_filter(vec!["style".to_string(), "class".to_string()]),
+            );
+        }
 
         let _ = watch(
             move || target.get(),
@@ -196,6 +239,17 @@ pub struct UseElementBoundingOptions {
     /// Default: `true`
     pub window_scroll: bool,
 
+    /// Listen to size changes of the element with a `ResizeObserver`
+    ///
+    /// Default: `true`
+    pub element_resize: bool,
+
+    /// Listen to changes of the `style` and `class` attributes of the element with a
+    /// `MutationObserver`
+    ///
+    /// Default: `true`
+    pub mutation: bool,
+
     /// Immediately call update
     ///
     /// Default: `true`
@@ -208,6 +262,8 @@ impl Default for UseElementBoundingOptions {
             reset: true,
             window_resize: true,
             window_scroll: true,
+            element_resize: true,
+            mutation: true,
             immediate: true,
         }
     }
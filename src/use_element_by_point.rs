@@ -0,0 +1,174 @@
+use crate::utils::Pausable;
+use crate::{js, use_raf_fn_with_options, use_supported, UseRafFnOptions};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use wasm_bindgen::JsCast;
+
+/// Reactive [`document.elementFromPoint`](https://developer.mozilla.org/en-US/docs/Web/API/Document/elementFromPoint)
+/// and [`document.elementsFromPoint`](https://developer.mozilla.org/en-US/docs/Web/API/Document/elementsFromPoint).
+///
+/// ## Usage
+///
+/// The element is looked up on every animation frame so it also changes when the page changes
+/// under a pointer that stands still. `x` and `y` are in client coordinates.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_element_by_point, use_mouse, UseMouseReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseMouseReturn { x, y, .. } = use_mouse();
+/// let by_point = use_element_by_point(x, y);
+///
+/// view! {
+///     <p>"Hovering " {move || by_point.element.get().map(|el| el.tag_name())}</p>
+/// }
+/// # }
+/// ```
+///
+/// ## Multiple Elements
+///
+/// With `multiple` enabled `elements` contains all elements at the point from the topmost to
+/// the bottommost. This is useful for custom drag and drop where the dragged element itself
+/// is under the pointer.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_element_by_point_with_options, use_mouse, UseElementByPointOptions, UseMouseReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseMouseReturn { x, y, .. } = use_mouse();
+/// let by_point =
+///     use_element_by_point_with_options(x, y, UseElementByPointOptions::default().multiple(true));
+///
+/// let drop_zone = move || {
+///     by_point.elements.get().into_iter().find(|el| el.has_attribute("data-drop-zone"))
+/// };
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// The lookup can be suspended with `pause` and continued with `resume`.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `element` is always `None` and `elements` is always empty.
+pub fn use_element_by_point(
+    x: impl Into<MaybeSignal<f64>>,
+    y: impl Into<MaybeSignal<f64>>,
+) -> UseElementByPointReturn<impl Fn() + Clone, impl Fn() + Clone> {
+    use_element_by_point_with_options(x, y, UseElementByPointOptions::default())
+}
+
+/// Version of [`use_element_by_point`] that takes a `UseElementByPointOptions`. See [`use_element_by_point`] for how to use.
+pub fn use_element_by_point_with_options(
+    x: impl Into<MaybeSignal<f64>>,
+    y: impl Into<MaybeSignal<f64>>,
+    options: UseElementByPointOptions,
+) -> UseElementByPointReturn<impl Fn() + Clone, impl Fn() + Clone> {
+    let UseElementByPointOptions {
+        multiple,
+        immediate,
+    } = options;
+
+    let x = x.into();
+    let y = y.into();
+
+    let is_supported = use_supported(|| js!("elementFromPoint" in &document()));
+
+    let (element, set_element) = create_signal(None::<web_sys::Element>);
+    let (elements, set_elements) = create_signal(Vec::<web_sys::Element>::new());
+
+    let update = move |_| {
+        if !is_supported.get_untracked() {
+            return;
+        }
+
+        let x = x.get_untracked() as f32;
+        let y = y.get_untracked() as f32;
+
+        let new_elements: Vec<web_sys::Element> = if multiple {
+            document()
+                .elements_from_point(x, y)
+                .iter()
+                .map(JsCast::unchecked_into::<web_sys::Element>)
+                .collect()
+        } else {
+            document().element_from_point(x, y).into_iter().collect()
+        };
+        let new_element = new_elements.first().cloned();
+
+        // only notify subscribers on changes because this runs on every frame
+        if element.with_untracked(|element| *element != new_element) {
+            set_element.set(new_element);
+        }
+        if elements.with_untracked(|elements| *elements != new_elements) {
+            set_elements.set(new_elements);
+        }
+    };
+
+    let Pausable {
+        pause,
+        resume,
+        is_active,
+    } = use_raf_fn_with_options(update, UseRafFnOptions::default().immediate(immediate));
+
+    UseElementByPointReturn {
+        is_supported,
+        element: element.into(),
+        elements: elements.into(),
+        is_active,
+        pause,
+        resume,
+    }
+}
+
+/// Options for [`use_element_by_point_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseElementByPointOptions {
+    /// If `true`, `elements` contains all elements at the point. Otherwise only the topmost one.
+    /// Defaults to `false`.
+    multiple: bool,
+
+    /// Start looking up the element immediately. If `false` you have to call `resume` first.
+    /// Defaults to `true`.
+    immediate: bool,
+}
+
+impl Default for UseElementByPointOptions {
+    fn default() -> Self {
+        Self {
+            multiple: false,
+            immediate: true,
+        }
+    }
+}
+
+/// Return type of [`use_element_by_point`].
+pub struct UseElementByPointReturn<PauseFn, ResumeFn>
+where
+    PauseFn: Fn() + Clone,
+    ResumeFn: Fn() + Clone,
+{
+    /// Whether `elementFromPoint` is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The topmost element at the point
+    pub element: Signal<Option<web_sys::Element>>,
+
+    /// All elements at the point from the topmost to the bottommost if `multiple` is enabled.
+    /// Otherwise only the topmost element.
+    pub elements: Signal<Vec<web_sys::Element>>,
+
+    /// Whether the element is looked up on every animation frame
+    pub is_active: Signal<bool>,
+
+    /// Stops looking up the element
+    pub pause: PauseFn,
+
+    /// Continues looking up the element
+    pub resume: ResumeFn,
+}
@@ -3,10 +3,10 @@ use crate::{use_event_listener_with_options, UseEventListenerOptions};
 use cfg_if::cfg_if;
 use default_struct_builder::DefaultBuilder;
 use leptos::ev::{mouseenter, mouseleave};
-use leptos::leptos_dom::helpers::TimeoutHandle;
 use leptos::*;
 
 cfg_if! { if #[cfg(not(feature = "ssr"))] {
+    use leptos::leptos_dom::helpers::TimeoutHandle;
     use std::time::Duration;
 }}
 
@@ -34,6 +34,36 @@ cfg_if! { if #[cfg(not(feature = "ssr"))] {
 /// # }
 /// ```
 ///
+/// ## Delays
+///
+/// For dropdown menus and the like you often want the hover state to only change after the
+/// mouse has rested on or left the element for some time. A pending change is canceled when the
+/// mouse quickly passes through the element or comes back.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_element_hover_with_options, UseElementHoverOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+/// let is_open = use_element_hover_with_options(
+///     el,
+///     UseElementHoverOptions::default().delay_enter(200).delay_leave(300),
+/// );
+///
+/// view! {
+///     <div node_ref=el>
+///         "Menu"
+///         <Show when=move || is_open.get()>
+///             <ul>"..."</ul>
+///         </Show>
+///     </div>
+/// }
+/// # }
+/// ```
+///
 /// ## Server-Side Rendering
 ///
 /// On the server this returns a `Signal` that always contains the value `false`.
@@ -47,7 +77,6 @@ where
 }
 
 /// Version of [`use_element_hover`] that takes a `UseElementHoverOptions`. See [`use_element_hover`] for how to use.
-
 #[cfg_attr(feature = "ssr", allow(unused_variables, unused_mut))]
 pub fn use_element_hover_with_options<El, T>(
     el: El,
@@ -65,27 +94,42 @@ where
 
     let (is_hovered, set_hovered) = create_signal(false);
 
-    let mut timer: Option<TimeoutHandle> = None;
+    cfg_if! { if #[cfg(not(feature = "ssr"))] {
+        // shared by both listeners so that leaving cancels a pending enter and vice versa
+        let timer = store_value(None::<TimeoutHandle>);
+
+        let clear_timer = move || {
+            if let Some(Some(handle)) = timer.try_update_value(Option::take) {
+                handle.clear();
+            }
+        };
 
-    let mut toggle = move |entering: bool| {
-        cfg_if! { if #[cfg(not(feature = "ssr"))] {
+        let toggle = move |entering: bool| {
             let delay = if entering { delay_enter } else { delay_leave };
 
-            if let Some(handle) = timer.take() {
-                handle.clear();
+            clear_timer();
+
+            if is_hovered.get_untracked() == entering {
+                return;
             }
 
             if delay > 0 {
-                timer = set_timeout_with_handle(
-                    move || set_hovered.set(entering),
-                    Duration::from_millis(delay),
-                )
-                .ok();
+                timer.set_value(
+                    set_timeout_with_handle(
+                        move || set_hovered.set(entering),
+                        Duration::from_millis(delay),
+                    )
+                    .ok(),
+                );
             } else {
                 set_hovered.set(entering);
             }
-        }}
-    };
+        };
+
+        on_cleanup(clear_timer);
+    } else {
+        let toggle = move |_: bool| {};
+    }}
 
     let listener_options = UseEventListenerOptions::default().passive(true);
 
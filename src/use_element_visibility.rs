@@ -18,13 +18,13 @@ use crate::{use_intersection_observer_with_options, UseIntersectionObserverOptio
 /// ```
 /// # use leptos::*;
 /// # use leptos::html::Div;
-/// # use leptos_use::use_element_visibility;
+/// # use leptos_use::{use_element_visibility, UseElementVisibilityReturn};
 /// #
 /// # #[component]
 /// # fn Demo() -> impl IntoView {
 /// let el = create_node_ref::<Div>();
 ///
-/// let is_visible = use_element_visibility(el);
+/// let UseElementVisibilityReturn { is_visible, .. } = use_element_visibility(el);
 ///
 /// view! {
 ///     <div node_ref=el>
@@ -34,14 +34,44 @@ use crate::{use_intersection_observer_with_options, UseIntersectionObserverOptio
 /// # }
 /// ```
 ///
+/// ## Intersection Ratio
+///
+/// `intersection_ratio` tells how much of the element is visible, from `0.0` to `1.0`. It's only
+/// updated when the ratio crosses one of the `thresholds`. So to drive an animation
+/// proportionally, pass a list of evenly spaced thresholds.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_element_visibility_with_options, UseElementVisibilityOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+///
+/// let visibility = use_element_visibility_with_options(
+///     el,
+///     UseElementVisibilityOptions::default()
+///         .thresholds((0..=20).map(|i| i as f64 / 20.0).collect::<Vec<_>>())
+///         .root_margin("0px 0px -100px 0px"),
+/// );
+///
+/// view! {
+///     <div node_ref=el style:opacity=move || visibility.intersection_ratio.get().to_string()>
+///         "Fades in"
+///     </div>
+/// }
+/// # }
+/// ```
+///
 /// ## Server-Side Rendering
 ///
-/// On the server this returns a `Signal` that always contains the value `false`.
+/// On the server `is_visible` is always `false` and `intersection_ratio` is always `0.0`.
 ///
 /// ## See also
 ///
 /// * [`use_intersection_observer`]
-pub fn use_element_visibility<El, T>(target: El) -> Signal<bool>
+pub fn use_element_visibility<El, T>(target: El) -> UseElementVisibilityReturn
 where
     El: Into<ElementMaybeSignal<T, web_sys::Element>>,
     T: Into<web_sys::Element> + Clone + 'static,
@@ -57,7 +87,7 @@ where
 pub fn use_element_visibility_with_options<El, T, ContainerEl, ContainerT>(
     target: El,
     options: UseElementVisibilityOptions<ContainerEl, ContainerT>,
-) -> Signal<bool>
+) -> UseElementVisibilityReturn
 where
     El: Into<ElementMaybeSignal<T, web_sys::Element>>,
     T: Into<web_sys::Element> + Clone + 'static,
@@ -65,8 +95,16 @@ where
     ContainerT: Into<web_sys::Element> + Clone + 'static,
 {
     let (is_visible, set_visible) = create_signal(false);
+    let (intersection_ratio, set_intersection_ratio) = create_signal(0.0);
 
     cfg_if! { if #[cfg(not(feature = "ssr"))] {
+        let UseElementVisibilityOptions {
+            viewport,
+            root_margin,
+            thresholds,
+            ..
+        } = options;
+
         use_intersection_observer_with_options(
             target.into(),
             move |entries, _| {
@@ -77,13 +115,20 @@ where
                     rect.width() > 0.0 || rect.height() > 0.0
                 }) {
                     set_visible.set(entry.is_intersecting());
+                    set_intersection_ratio.set(entry.intersection_ratio());
                 }
             },
-            UseIntersectionObserverOptions::default().root(options.viewport),
+            UseIntersectionObserverOptions::default()
+                .root(viewport)
+                .root_margin(root_margin)
+                .thresholds(thresholds),
         );
     }}
 
-    is_visible.into()
+    UseElementVisibilityReturn {
+        is_visible: is_visible.into(),
+        intersection_ratio: intersection_ratio.into(),
+    }
 }
 
 /// Options for [`use_element_visibility_with_options`].
@@ -102,6 +147,20 @@ where
     #[cfg_attr(feature = "ssr", allow(dead_code))]
     viewport: Option<El>,
 
+    /// A string which specifies a set of offsets to add to the bounding box of the `viewport`
+    /// when calculating intersections. The syntax is approximately the same as that for the CSS
+    /// [`margin`](https://developer.mozilla.org/en-US/docs/Web/CSS/margin) property.
+    /// The default is `"0px"`.
+    #[cfg_attr(feature = "ssr", allow(dead_code))]
+    #[builder(into)]
+    root_margin: String,
+
+    /// A `Vec` of numbers between 0.0 and 1.0. The signals are updated whenever the visible
+    /// ratio of the element crosses one of them.
+    /// The default is a single threshold of `[0.0]`.
+    #[cfg_attr(feature = "ssr", allow(dead_code))]
+    thresholds: Vec<f64>,
+
     #[builder(skip)]
     _marker: PhantomData<T>,
 }
@@ -110,7 +169,19 @@ impl Default for UseElementVisibilityOptions<web_sys::Element, web_sys::Element>
     fn default() -> Self {
         Self {
             viewport: None,
+            root_margin: "0px".into(),
+            thresholds: vec![0.0],
             _marker: PhantomData,
         }
     }
 }
+
+/// Return type of [`use_element_visibility`].
+#[derive(Clone, Copy)]
+pub struct UseElementVisibilityReturn {
+    /// Whether the element is inside the viewport
+    pub is_visible: Signal<bool>,
+
+    /// The ratio of the element that is inside the viewport from `0.0` to `1.0`
+    pub intersection_ratio: Signal<f64>,
+}
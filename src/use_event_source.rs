@@ -1,13 +1,15 @@
 use crate::core::ConnectionReadyState;
-use crate::utils::StringCodec;
-use crate::{js, use_event_listener};
+use crate::utils::{EventStreamParser, StringCodec};
+use crate::{js, js_fut, use_event_listener};
 use default_struct_builder::DefaultBuilder;
 use leptos::*;
 use std::cell::Cell;
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::rc::Rc;
 use std::time::Duration;
 use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
 
 /// Reactive [EventSource](https://developer.mozilla.org/en-US/docs/Web/API/EventSource)
 ///
@@ -18,7 +20,9 @@ use thiserror::Error;
 ///
 /// ## Usage
 ///
-/// Values are decoded via the given [`Codec`].
+/// Values are decoded via the given [`Codec`]. With [`JsonCodec`] any type that implements
+/// `serde::Deserialize` can be received. Data that can't be decoded is reported in `parse_error`
+/// while `error` only contains connection errors.
 ///
 /// > To use the [`JsonCodec`], you will need to add the `"serde"` feature to your project's `Cargo.toml`.
 /// > To use [`ProstCodec`], add the feature `"prost"`.
@@ -37,8 +41,10 @@ use thiserror::Error;
 /// # #[component]
 /// # fn Demo() -> impl IntoView {
 /// let UseEventSourceReturn {
-///     ready_state, data, error, close, ..
+///     ready_state, data, error, parse_error, close, ..
 /// } = use_event_source::<EventSourceData, JsonCodec>("https://event-source-url");
+///
+/// let priority = move || data.get().map(|data| data.priority);
 /// #
 /// # view! { }
 /// # }
@@ -51,6 +57,8 @@ use thiserror::Error;
 /// ### Named Events
 ///
 /// You can define named events when using `use_event_source_with_options`.
+/// `data` always contains the latest data regardless of the event. The latest data of a
+/// specific event is available through `event_data`.
 ///
 /// ```
 /// # use leptos::*;
@@ -58,13 +66,14 @@ use thiserror::Error;
 /// #
 /// # #[component]
 /// # fn Demo() -> impl IntoView {
-/// let UseEventSourceReturn {
-///     ready_state, data, error, close, ..
-/// } = use_event_source_with_options::<String, FromToStringCodec>(
+/// let event_source = use_event_source_with_options::<String, FromToStringCodec>(
 ///     "https://event-source-url",
 ///     UseEventSourceOptions::default()
 ///         .named_events(["notice".to_string(), "update".to_string()])
 /// );
+///
+/// let notice = event_source.event_data("notice");
+/// let update = event_source.event_data("update");
 /// #
 /// # view! { }
 /// # }
@@ -104,6 +113,35 @@ use thiserror::Error;
 ///
 /// To disable auto-reconnection, set `reconnect_limit` to `0`.
 ///
+/// ### Custom Headers and Resuming Streams
+///
+/// The native `EventSource` can't send custom headers. Enable `use_fetch` to connect with `fetch`
+/// instead which supports `headers` like `Authorization`. When the stream ends or fails, the hook
+/// reconnects and sends the `Last-Event-ID` header with the id of the latest received event
+/// so the server can continue where it left off. The id is also available as `last_event_id`.
+/// A response with a status other than `200` or a content type other than `text/event-stream`
+/// fails the connection without reconnecting: `error` becomes `UseEventSourceError::Response`
+/// and `on_failed` is called.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_event_source_with_options, UseEventSourceReturn, UseEventSourceOptions, utils::FromToStringCodec};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseEventSourceReturn {
+///     data, last_event_id, ..
+/// } = use_event_source_with_options::<String, FromToStringCodec>(
+///     "https://event-source-url",
+///     UseEventSourceOptions::default()
+///         .use_fetch(true)
+///         .headers(vec![("Authorization".to_string(), "Bearer token".to_string())])
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
 /// ## Server-Side Rendering
 ///
 /// On the server-side, `use_event_source` will always return `ready_state` as `ConnectionReadyState::Closed`,
@@ -135,6 +173,8 @@ where
         immediate,
         named_events,
         with_credentials,
+        use_fetch,
+        headers,
         _marker,
     } = options;
 
@@ -144,16 +184,66 @@ where
     let (data, set_data) = create_signal(None::<T>);
     let (ready_state, set_ready_state) = create_signal(ConnectionReadyState::Closed);
     let (event_source, set_event_source) = create_signal(None::<web_sys::EventSource>);
-    let (error, set_error) = create_signal(None::<UseEventSourceError<C::Error>>);
+    let (error, set_error) = create_signal(None::<UseEventSourceError>);
+    let (parse_error, set_parse_error) = create_signal(None::<C::Error>);
+    let (events, set_events) = create_signal(HashMap::<String, T>::new());
+    let (last_event_id, set_last_event_id) = create_signal(None::<String>);
 
     let explicitly_closed = Rc::new(Cell::new(false));
     let retried = Rc::new(Cell::new(0));
+    let abort_controller = store_value(None::<web_sys::AbortController>);
 
-    let set_data_from_string = move |data_string: Option<String>| {
-        if let Some(data_string) = data_string {
-            match codec.decode(data_string) {
-                Ok(data) => set_data.set(Some(data)),
-                Err(err) => set_error.set(Some(UseEventSourceError::Deserialize(err))),
+    let decode = move |data_string: Option<String>| -> Option<T> {
+        match codec.decode(data_string?) {
+            Ok(data) => {
+                if parse_error.with_untracked(Option::is_some) {
+                    set_parse_error.set(None);
+                }
+                Some(data)
+            }
+            Err(err) => {
+                set_parse_error.set(Some(err));
+                None
+            }
+        }
+    };
+
+    let update_last_event_id = move |id: String| {
+        let id = Some(id).filter(|id| !id.is_empty());
+        if last_event_id.get_untracked() != id {
+            set_last_event_id.set(id);
+        }
+    };
+
+    let handle_message = {
+        let decode = decode.clone();
+
+        move |data_string: Option<String>, id: String| {
+            update_last_event_id(id);
+            if let Some(data) = decode(data_string) {
+                set_data.set(Some(data));
+            }
+        }
+    };
+
+    let handle_named_event = {
+        let decode = decode.clone();
+
+        move |event_name: &str, e: web_sys::Event| {
+            set_event.set(Some(e.clone()));
+
+            let id = js!(e["lastEventId"])
+                .ok()
+                .and_then(|id| id.as_string())
+                .unwrap_or_default();
+            update_last_event_id(id);
+
+            let data_string = js!(e["data"]).ok().and_then(|d| d.as_string());
+            if let Some(data) = decode(data_string) {
+                set_events.update(|events| {
+                    events.insert(event_name.to_string(), data.clone());
+                });
+                set_data.set(Some(data));
             }
         }
     };
@@ -168,11 +258,51 @@ where
                 set_ready_state.set(ConnectionReadyState::Closed);
                 explicitly_closed.set(true);
             }
+
+            if let Some(controller) = abort_controller.get_value() {
+                controller.abort();
+                abort_controller.set_value(None);
+                set_ready_state.set(ConnectionReadyState::Closed);
+                explicitly_closed.set(true);
+            }
         }
     };
 
     let init = store_value(None::<Rc<dyn Fn()>>);
 
+    let reconnect = {
+        let explicitly_closed = Rc::clone(&explicitly_closed);
+        let retried = Rc::clone(&retried);
+        let on_failed = Rc::clone(&on_failed);
+
+        move |interval: u64| {
+            if explicitly_closed.get() || reconnect_limit == 0 {
+                return;
+            }
+
+            retried.set(retried.get() + 1);
+
+            if retried.get() < reconnect_limit {
+                set_timeout(
+                    move || {
+                        if let Some(init) = init.get_value() {
+                            init();
+                        }
+                    },
+                    Duration::from_millis(interval),
+                );
+            } else {
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                on_failed();
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+            }
+        }
+    };
+
     init.set_value(Some(Rc::new({
         let explicitly_closed = Rc::clone(&explicitly_closed);
         let retried = Rc::clone(&retried);
@@ -184,14 +314,138 @@ where
                 return;
             }
 
+            set_ready_state.set(ConnectionReadyState::Connecting);
+
+            if use_fetch {
+                let controller = web_sys::AbortController::new().unwrap_throw();
+                abort_controller.set_value(Some(controller.clone()));
+
+                let request_init = web_sys::RequestInit::new();
+                request_init.set_method("GET");
+                request_init.set_signal(Some(&controller.signal()));
+                request_init.set_credentials(if with_credentials {
+                    web_sys::RequestCredentials::Include
+                } else {
+                    web_sys::RequestCredentials::SameOrigin
+                });
+
+                let request_headers = web_sys::Headers::new().unwrap_throw();
+                let _ = request_headers.set("Accept", "text/event-stream");
+                if let Some(id) = last_event_id.get_untracked() {
+                    let _ = request_headers.set("Last-Event-ID", &id);
+                }
+                for (name, value) in &headers {
+                    let _ = request_headers.set(name, value);
+                }
+                request_init.set_headers(&request_headers);
+
+                let request = window().fetch_with_str_and_init(&url, &request_init);
+
+                spawn_local({
+                    let retried = Rc::clone(&retried);
+                    let handle_message = handle_message.clone();
+                    let handle_named_event = handle_named_event.clone();
+                    let reconnect = reconnect.clone();
+                    let named_events = named_events.clone();
+                    let on_failed = Rc::clone(&on_failed);
+
+                    async move {
+                        let response = js_fut!(request)
+                            .await
+                            .and_then(|response| response.dyn_into::<web_sys::Response>());
+
+                        let mut parser = EventStreamParser::new(
+                            last_event_id.get_untracked().unwrap_or_default(),
+                        );
+
+                        let result = match response {
+                            Ok(response) if !is_event_stream(&response) => {
+                                if controller.signal().aborted() {
+                                    return;
+                                }
+
+                                // like the native `EventSource` fail the connection without
+                                // reconnecting. Sending the same request again won't help.
+                                set_ready_state.set(ConnectionReadyState::Closed);
+                                set_error.set(Some(UseEventSourceError::Response {
+                                    status: response.status(),
+                                    content_type: response
+                                        .headers()
+                                        .get("Content-Type")
+                                        .ok()
+                                        .flatten(),
+                                }));
+
+                                #[cfg(debug_assertions)]
+                                let prev = SpecialNonReactiveZone::enter();
+
+                                on_failed();
+
+                                #[cfg(debug_assertions)]
+                                SpecialNonReactiveZone::exit(prev);
+
+                                return;
+                            }
+                            Ok(response) => match response.body() {
+                                Some(body) => {
+                                    set_ready_state.set(ConnectionReadyState::Open);
+                                    set_error.set(None);
+                                    retried.set(0);
+
+                                    read_event_stream(body, |chunk| {
+                                        for event in parser.feed(&chunk) {
+                                            if event.event == "message" {
+                                                handle_message(
+                                                    Some(event.data),
+                                                    event.last_event_id,
+                                                );
+                                            } else if named_events.contains(&event.event) {
+                                                let init = web_sys::MessageEventInit::new();
+                                                init.set_data(&JsValue::from(event.data));
+                                                init.set_last_event_id(&event.last_event_id);
+
+                                                if let Ok(e) =
+                                                    web_sys::MessageEvent::new_with_event_init_dict(
+                                                        &event.event,
+                                                        &init,
+                                                    )
+                                                {
+                                                    handle_named_event(&event.event, e.into());
+                                                }
+                                            }
+                                        }
+                                    })
+                                    .await
+                                }
+                                None => Err(JsValue::from_str("Response has no body")),
+                            },
+                            Err(err) => Err(err),
+                        };
+
+                        if controller.signal().aborted() {
+                            return;
+                        }
+
+                        set_ready_state.set(ConnectionReadyState::Closed);
+                        if let Err(err) = result {
+                            set_error.set(Some(UseEventSourceError::Request(err)));
+                        }
+
+                        // like the native `EventSource` reconnect when the server ends the stream
+                        // or the network fails
+                        reconnect(parser.retry().unwrap_or(reconnect_interval));
+                    }
+                });
+
+                return;
+            }
+
             let mut event_src_opts = web_sys::EventSourceInit::new();
             event_src_opts.with_credentials(with_credentials);
 
             let es = web_sys::EventSource::new_with_event_source_init_dict(&url, &event_src_opts)
                 .unwrap_throw();
 
-            set_ready_state.set(ConnectionReadyState::Connecting);
-
             set_event_source.set(Some(es.clone()));
 
             let on_open = Closure::wrap(Box::new(move |_: web_sys::Event| {
@@ -202,9 +456,7 @@ where
             on_open.forget();
 
             let on_error = Closure::wrap(Box::new({
-                let explicitly_closed = Rc::clone(&explicitly_closed);
-                let retried = Rc::clone(&retried);
-                let on_failed = Rc::clone(&on_failed);
+                let reconnect = reconnect.clone();
                 let es = es.clone();
 
                 move |e: web_sys::Event| {
@@ -213,29 +465,9 @@ where
 
                     // only reconnect if EventSource isn't reconnecting by itself
                     // this is the case when the connection is closed (readyState is 2)
-                    if es.ready_state() == 2 && !explicitly_closed.get() && reconnect_limit > 0 {
+                    if es.ready_state() == 2 {
                         es.close();
-
-                        retried.set(retried.get() + 1);
-
-                        if retried.get() < reconnect_limit {
-                            set_timeout(
-                                move || {
-                                    if let Some(init) = init.get_value() {
-                                        init();
-                                    }
-                                },
-                                Duration::from_millis(reconnect_interval),
-                            );
-                        } else {
-                            #[cfg(debug_assertions)]
-                            let prev = SpecialNonReactiveZone::enter();
-
-                            on_failed();
-
-                            #[cfg(debug_assertions)]
-                            SpecialNonReactiveZone::exit(prev);
-                        }
+                        reconnect(reconnect_interval);
                     }
                 }
             }) as Box<dyn FnMut(web_sys::Event)>);
@@ -243,26 +475,22 @@ where
             on_error.forget();
 
             let on_message = Closure::wrap(Box::new({
-                let set_data_from_string = set_data_from_string.clone();
+                let handle_message = handle_message.clone();
 
                 move |e: web_sys::MessageEvent| {
-                    set_data_from_string(e.data().as_string());
+                    handle_message(e.data().as_string(), e.last_event_id());
                 }
             }) as Box<dyn FnMut(web_sys::MessageEvent)>);
             es.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
             on_message.forget();
 
             for event_name in named_events.clone() {
-                let set_data_from_string = set_data_from_string.clone();
+                let handle_named_event = handle_named_event.clone();
 
                 let _ = use_event_listener(
                     es.clone(),
-                    ev::Custom::<ev::Event>::new(event_name),
-                    move |e| {
-                        set_event.set(Some(e.clone()));
-                        let data_string = js!(e["data"]).ok().and_then(|d| d.as_string());
-                        set_data_from_string(data_string);
-                    },
+                    ev::Custom::<ev::Event>::new(event_name.clone()),
+                    move |e| handle_named_event(&event_name, e),
                 );
             }
         }
@@ -302,14 +530,53 @@ where
     UseEventSourceReturn {
         event_source: event_source.into(),
         event: event.into(),
+        events: events.into(),
         data: data.into(),
+        last_event_id: last_event_id.into(),
         ready_state: ready_state.into(),
         error: error.into(),
+        parse_error: parse_error.into(),
         open,
         close,
     }
 }
 
+/// Whether the server accepted the connection. See
+/// [the spec](https://html.spec.whatwg.org/multipage/server-sent-events.html#sse-processing-model).
+fn is_event_stream(response: &web_sys::Response) -> bool {
+    response.status() == 200
+        && response
+            .headers()
+            .get("Content-Type")
+            .ok()
+            .flatten()
+            .is_some_and(|content_type| {
+                content_type
+                    .split(';')
+                    .next()
+                    .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("text/event-stream"))
+            })
+}
+
+/// Reads the body chunk by chunk until the stream is done.
+async fn read_event_stream(
+    body: web_sys::ReadableStream,
+    mut on_chunk: impl FnMut(Vec<u8>),
+) -> Result<(), JsValue> {
+    let reader: web_sys::ReadableStreamDefaultReader = body.get_reader().unchecked_into();
+
+    loop {
+        let result = js_fut!(reader.read()).await?;
+
+        if js!(result["done"])?.as_bool().unwrap_or(true) {
+            return Ok(());
+        }
+
+        let value = js!(result["value"])?;
+        on_chunk(js_sys::Uint8Array::new(&value).to_vec());
+    }
+}
+
 /// Options for [`use_event_source_with_options`].
 #[derive(DefaultBuilder)]
 pub struct UseEventSourceOptions<T, C>
@@ -326,7 +593,8 @@ where
     /// Retry interval in ms. Defaults to 3000.
     reconnect_interval: u64,
 
-    /// On maximum retry times reached.
+    /// On maximum retry times reached. With `use_fetch` also when the server responds with an
+    /// error status or not with an event stream.
     on_failed: Rc<dyn Fn()>,
 
     /// If `true` the `EventSource` connection will immediately be opened when calling this function.
@@ -341,6 +609,16 @@ where
     /// If CORS should be set to `include` credentials. Defaults to `false`.
     with_credentials: bool,
 
+    /// Use `fetch` instead of the native `EventSource`. This is required to send custom `headers`.
+    /// In this mode the hook reconnects by itself and sends the `Last-Event-ID` header so the
+    /// server can resume the stream. Defaults to `false`.
+    use_fetch: bool,
+
+    /// Additional request headers like `Authorization`. Only used if `use_fetch` is `true`
+    /// because the native `EventSource` can't send headers.
+    #[builder(into)]
+    headers: Vec<(String, String)>,
+
     _marker: PhantomData<T>,
 }
 
@@ -354,6 +632,8 @@ impl<T, C: StringCodec<T> + Default> Default for UseEventSourceOptions<T, C> {
             immediate: true,
             named_events: vec![],
             with_credentials: false,
+            use_fetch: false,
+            headers: vec![],
             _marker: PhantomData,
         }
     }
@@ -370,14 +650,25 @@ where
     /// Latest data received via the `EventSource`
     pub data: Signal<Option<T>>,
 
+    /// The id of the latest event that has been sent by the server. `None` if the server
+    /// didn't send any.
+    pub last_event_id: Signal<Option<String>>,
+
     /// The current state of the connection,
     pub ready_state: Signal<ConnectionReadyState>,
 
     /// The latest named event
     pub event: Signal<Option<web_sys::Event>>,
 
-    /// The current error
-    pub error: Signal<Option<UseEventSourceError<Err>>>,
+    /// The latest data of every named event by event name. See also [`Self::event_data`].
+    pub events: Signal<HashMap<String, T>>,
+
+    /// The current connection error
+    pub error: Signal<Option<UseEventSourceError>>,
+
+    /// The error of the latest received data that couldn't be decoded. Reset to `None` as soon
+    /// as data is decoded successfully.
+    pub parse_error: Signal<Option<Err>>,
 
     /// (Re-)Opens the `EventSource` connection
     /// If the current one is active, will close it before opening a new one.
@@ -390,11 +681,34 @@ where
     pub event_source: Signal<Option<web_sys::EventSource>>,
 }
 
+impl<T, Err, OpenFn, CloseFn> UseEventSourceReturn<T, Err, OpenFn, CloseFn>
+where
+    Err: 'static,
+    T: Clone + 'static,
+    OpenFn: Fn() + Clone + 'static,
+    CloseFn: Fn() + Clone + 'static,
+{
+    /// The latest data received with the named event `name`. The event has to be listed in
+    /// [`UseEventSourceOptions::named_events`].
+    pub fn event_data(&self, name: impl Into<String>) -> Signal<Option<T>> {
+        let name = name.into();
+        let events = self.events;
+
+        Signal::derive(move || events.with(|events| events.get(&name).cloned()))
+    }
+}
+
 #[derive(Error, Debug)]
-pub enum UseEventSourceError<Err> {
+pub enum UseEventSourceError {
     #[error("Error event: {0:?}")]
     Event(web_sys::Event),
 
-    #[error("Error decoding value")]
-    Deserialize(Err),
+    #[error("Request failed: {0:?}")]
+    Request(JsValue),
+
+    #[error("Server responded with status {status} and content type {content_type:?} instead of an event stream")]
+    Response {
+        status: u16,
+        content_type: Option<String>,
+    },
 }
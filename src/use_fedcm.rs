@@ -0,0 +1,313 @@
+use crate::{js, js_fut, use_supported, CredentialMediation};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Reactive [Federated Credential Management API](https://developer.mozilla.org/en-US/docs/Web/API/FedCM_API).
+/// Signs the user in with an identity provider like Google through a dialog of the browser
+/// instead of redirects and third-party cookies.
+///
+/// ## Usage
+///
+/// `get` shows the dialog of the browser with the accounts of the user at the identity provider.
+/// Once the user has chosen one, the token of the identity provider is available in `state` and
+/// can be sent to your server.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_fedcm, FedCmProvider, FedCmState};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let fedcm = use_fedcm(
+///     FedCmProvider::new("https://idp.example/fedcm.json", "my-client-id")
+///         .nonce("random-nonce"),
+/// );
+///
+/// let sign_in = move |_| {
+///     spawn_local(async move {
+///         if let Ok(credential) = fedcm.get().await {
+///             // send `credential.token` to your server
+///         }
+///     });
+/// };
+///
+/// view! {
+///     <Show when=move || fedcm.is_supported.get()>
+///         <button on:click=sign_in>"Sign in with IdP"</button>
+///     </Show>
+///     {move || match fedcm.state.get() {
+///         FedCmState::Pending => "Signing in...".to_string(),
+///         FedCmState::Resolved(_) => "Signed in".to_string(),
+///         FedCmState::Failed(err) => err.to_string(),
+///         FedCmState::Idle => String::new(),
+///     }}
+/// }
+/// # }
+/// ```
+///
+/// ## Cancellation
+///
+/// A running request is aborted with an `AbortController` when `cancel` is called or the
+/// component is cleaned up. `get` returns `UseFedCmError::Aborted` then and `state` goes back to
+/// `FedCmState::Idle`.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `state` is always `FedCmState::Idle` and `get` returns
+/// `UseFedCmError::NotSupported`.
+pub fn use_fedcm(provider: FedCmProvider) -> UseFedCmReturn {
+    use_fedcm_with_options(provider, UseFedCmOptions::default())
+}
+
+/// Version of [`use_fedcm`] that takes a `UseFedCmOptions`. See [`use_fedcm`] for how to use.
+pub fn use_fedcm_with_options(provider: FedCmProvider, options: UseFedCmOptions) -> UseFedCmReturn {
+    let is_supported = use_supported(|| {
+        js!("IdentityCredential" in &window()) && js!("credentials" in &window().navigator())
+    });
+
+    let (state, set_state) = create_signal(FedCmState::Idle);
+
+    let fedcm = UseFedCmReturn {
+        is_supported,
+        state: state.into(),
+        set_state,
+        provider: store_value(provider),
+        options: store_value(options),
+        abort_controller: store_value(None),
+    };
+
+    on_cleanup(move || fedcm.cancel());
+
+    fedcm
+}
+
+/// Options for [`use_fedcm_with_options`].
+#[derive(DefaultBuilder, Clone, Debug)]
+pub struct UseFedCmOptions {
+    /// Whether the user has to interact with the dialog. With [`CredentialMediation::Silent`]
+    /// a returning user is signed in automatically. Defaults to [`CredentialMediation::Optional`].
+    mediation: CredentialMediation,
+
+    /// The wording of the dialog. Defaults to [`FedCmContext::SignIn`].
+    context: FedCmContext,
+}
+
+impl Default for UseFedCmOptions {
+    fn default() -> Self {
+        Self {
+            mediation: CredentialMediation::Optional,
+            context: FedCmContext::SignIn,
+        }
+    }
+}
+
+/// An identity provider that [`use_fedcm`] signs in with.
+#[derive(DefaultBuilder, Default, Clone, Debug, PartialEq, Eq)]
+pub struct FedCmProvider {
+    /// The URL of the config file of the identity provider
+    #[builder(into)]
+    config_url: String,
+
+    /// The id of your website that you registered with the identity provider
+    #[builder(into)]
+    client_id: String,
+
+    /// A random value that the identity provider includes in the token to prevent replay attacks
+    #[builder(into)]
+    nonce: Option<String>,
+
+    /// Shows only the account with this id or email address
+    #[builder(into)]
+    login_hint: Option<String>,
+
+    /// Shows only accounts of this domain
+    #[builder(into)]
+    domain_hint: Option<String>,
+}
+
+impl FedCmProvider {
+    /// Creates a provider with the URL of its config file and your client id.
+    pub fn new(config_url: impl Into<String>, client_id: impl Into<String>) -> Self {
+        Self {
+            config_url: config_url.into(),
+            client_id: client_id.into(),
+            ..Default::default()
+        }
+    }
+
+    fn to_js(&self) -> js_sys::Object {
+        let obj = js_sys::Object::new();
+        js!(obj["configURL"] = &self.config_url);
+        js!(obj["clientId"] = &self.client_id);
+        if let Some(nonce) = &self.nonce {
+            js!(obj["nonce"] = nonce);
+        }
+        if let Some(login_hint) = &self.login_hint {
+            js!(obj["loginHint"] = login_hint);
+        }
+        if let Some(domain_hint) = &self.domain_hint {
+            js!(obj["domainHint"] = domain_hint);
+        }
+        obj
+    }
+}
+
+/// The wording of the FedCM dialog.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FedCmContext {
+    /// "Sign in to ... with ..."
+    #[default]
+    SignIn,
+    /// "Sign up to ... with ..."
+    SignUp,
+    /// "Use ... with ..."
+    Use,
+    /// "Continue to ... with ..."
+    Continue,
+}
+
+impl FedCmContext {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::SignIn => "signin",
+            Self::SignUp => "signup",
+            Self::Use => "use",
+            Self::Continue => "continue",
+        }
+    }
+}
+
+/// The state of the sign-in of [`use_fedcm`].
+#[derive(Clone, Debug, Default)]
+pub enum FedCmState {
+    /// No sign-in has been started or it has been cancelled
+    #[default]
+    Idle,
+    /// The dialog is shown
+    Pending,
+    /// The user has signed in
+    Resolved(FedCmCredential),
+    /// The sign-in failed
+    Failed(UseFedCmError),
+}
+
+/// The result of a FedCM sign-in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FedCmCredential {
+    /// The token of the identity provider that your server verifies
+    pub token: String,
+
+    /// Whether the user was signed in automatically without choosing the account
+    pub is_auto_selected: bool,
+}
+
+/// Return type of [`use_fedcm`].
+#[derive(Clone, Copy)]
+pub struct UseFedCmReturn {
+    /// Whether FedCM is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The state of the current or last sign-in
+    pub state: Signal<FedCmState>,
+
+    set_state: WriteSignal<FedCmState>,
+    provider: StoredValue<FedCmProvider>,
+    options: StoredValue<UseFedCmOptions>,
+    abort_controller: StoredValue<Option<web_sys::AbortController>>,
+}
+
+impl UseFedCmReturn {
+    /// Shows the dialog of the browser and returns the token of the identity provider once the
+    /// user has signed in. Cancels a sign-in that is already running.
+    pub async fn get(&self) -> Result<FedCmCredential, UseFedCmError> {
+        if !self.is_supported.get_untracked() {
+            return Err(UseFedCmError::NotSupported);
+        }
+
+        self.cancel();
+
+        let controller =
+            web_sys::AbortController::new().map_err(|err| self.fail(UseFedCmError::Get(err)))?;
+        let signal = controller.signal();
+
+        let UseFedCmOptions { mediation, context } = self.options.get_value();
+
+        let identity = js_sys::Object::new();
+        js!(identity["providers"] =
+            js_sys::Array::of1(&self.provider.with_value(FedCmProvider::to_js)));
+        js!(identity["context"] = context.as_str());
+
+        let options = js_sys::Object::new();
+        js!(options["identity"] = identity);
+        js!(options["mediation"] = mediation.as_str());
+        js!(options["signal"] = &signal);
+
+        self.abort_controller.set_value(Some(controller));
+        self.set_state.set(FedCmState::Pending);
+
+        let result = async {
+            js_fut!(window()
+                .navigator()
+                .credentials()
+                .get_with_options(options.unchecked_ref())?)
+            .await
+        }
+        .await;
+
+        if signal.aborted() {
+            // `cancel` has already reset the state
+            return Err(UseFedCmError::Aborted);
+        }
+        let _ = self.abort_controller.try_set_value(None);
+
+        let credential = result
+            .map_err(UseFedCmError::Get)
+            .and_then(|credential| {
+                Ok(FedCmCredential {
+                    token: js!(credential["token"])
+                        .ok()
+                        .and_then(|token| token.as_string())
+                        .ok_or(UseFedCmError::NoCredential)?,
+                    is_auto_selected: js!(credential["isAutoSelected"])
+                        .ok()
+                        .and_then(|auto_selected| auto_selected.as_bool())
+                        .unwrap_or_default(),
+                })
+            })
+            .map_err(|err| self.fail(err))?;
+
+        let _ = self
+            .set_state
+            .try_set(FedCmState::Resolved(credential.clone()));
+
+        Ok(credential)
+    }
+
+    /// Aborts the running sign-in, if any.
+    pub fn cancel(&self) {
+        if let Some(Some(controller)) = self.abort_controller.try_update_value(Option::take) {
+            controller.abort();
+            let _ = self.set_state.try_set(FedCmState::Idle);
+        }
+    }
+
+    fn fail(&self, err: UseFedCmError) -> UseFedCmError {
+        let _ = self.set_state.try_set(FedCmState::Failed(err.clone()));
+        err
+    }
+}
+
+/// Errors of [`use_fedcm`].
+#[derive(Error, Debug, Clone)]
+pub enum UseFedCmError {
+    #[error("FedCM is not supported")]
+    NotSupported,
+    #[error("the sign-in has been cancelled")]
+    Aborted,
+    #[error("the sign-in failed")]
+    Get(JsValue),
+    #[error("the identity provider returned no token")]
+    NoCredential,
+}
@@ -0,0 +1,368 @@
+use crate::utils::Pausable;
+use crate::{
+    js, use_event_listener, use_raf_fn_with_options, use_supported, use_window, UseRafFnOptions,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{gamepadconnected, gamepaddisconnected};
+use leptos::*;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+
+/// Reactive [Gamepad API](https://developer.mozilla.org/en-US/docs/Web/API/Gamepad_API).
+/// Provides the state of all connected gamepads and controllers.
+///
+/// The Gamepad API doesn't fire events when buttons are pressed or sticks are moved so the
+/// gamepads are polled with `requestAnimationFrame`. The signals are only updated if something
+/// changed.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_gamepad;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let gamepad = use_gamepad();
+///
+/// // button and axis of the first gamepad. These only notify when they change.
+/// let a_pressed = gamepad.button(0, 0);
+/// let left_stick_x = gamepad.axis(0, 0);
+///
+/// view! {
+///     <Show
+///         when=move || !gamepad.gamepads.get().is_empty()
+///         fallback=|| view! { <p>"Connect a gamepad and press any button"</p> }
+///     >
+///         <p>"A: " {move || a_pressed.get().map(|button| button.pressed)}</p>
+///         <p>"Left stick: " {left_stick_x}</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// Browsers only report gamepads after the user pressed a button while the page is focused.
+///
+/// ## Deadzone and threshold
+///
+/// Analog sticks rarely rest exactly at `0.0`. Axis values with an absolute value below
+/// `deadzone` are reported as `0.0`. To avoid updates for tiny movements, changes of an axis
+/// that are smaller than `threshold` are ignored as well.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_gamepad_with_options, GamepadState, UseGamepadOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let gamepad = use_gamepad_with_options(
+///     UseGamepadOptions::default()
+///         .deadzone(0.1)
+///         .threshold(0.01)
+///         .on_connected(|gamepad: GamepadState| logging::log!("{} connected", gamepad.id)),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server `gamepads` is always empty and `is_supported` is `false`.
+pub fn use_gamepad() -> UseGamepadReturn<impl Fn() + Clone, impl Fn() + Clone> {
+    use_gamepad_with_options(UseGamepadOptions::default())
+}
+
+/// Version of [`use_gamepad`] that takes a `UseGamepadOptions`. See [`use_gamepad`] for how to use.
+pub fn use_gamepad_with_options(
+    options: UseGamepadOptions,
+) -> UseGamepadReturn<impl Fn() + Clone, impl Fn() + Clone> {
+    let UseGamepadOptions {
+        deadzone,
+        threshold,
+        immediate,
+        on_connected,
+        on_disconnected,
+    } = options;
+
+    let is_supported = use_supported(|| js!("getGamepads" in &window().navigator()));
+
+    let (gamepads, set_gamepads) = create_signal(Vec::<GamepadState>::new());
+
+    let update = move || {
+        if !is_supported.get_untracked() {
+            return;
+        }
+
+        let Ok(connected) = window().navigator().get_gamepads() else {
+            return;
+        };
+
+        let next = connected
+            .iter()
+            .filter_map(|gamepad| gamepad.dyn_into::<web_sys::Gamepad>().ok())
+            .map(|gamepad| GamepadState::new(&gamepad, deadzone))
+            .collect::<Vec<_>>();
+
+        let changed = gamepads.with_untracked(|gamepads| {
+            gamepads.len() != next.len()
+                || gamepads
+                    .iter()
+                    .zip(next.iter())
+                    .any(|(prev, next)| prev.differs_from(next, threshold))
+        });
+
+        if changed {
+            set_gamepads.set(next);
+        }
+    };
+
+    let Pausable {
+        pause,
+        resume,
+        is_active,
+    } = use_raf_fn_with_options(
+        move |_| update(),
+        UseRafFnOptions::default().immediate(immediate),
+    );
+
+    let _ = use_event_listener(use_window(), gamepadconnected, move |event| {
+        update();
+
+        if let Some(gamepad) = event.gamepad() {
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_connected(GamepadState::new(&gamepad, deadzone));
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        }
+    });
+
+    let _ = use_event_listener(use_window(), gamepaddisconnected, move |event| {
+        update();
+
+        if let Some(gamepad) = event.gamepad() {
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_disconnected(GamepadState::new(&gamepad, deadzone));
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        }
+    });
+
+    UseGamepadReturn {
+        is_supported,
+        gamepads: gamepads.into(),
+        is_active,
+        pause,
+        resume,
+    }
+}
+
+/// Options for [`use_gamepad_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseGamepadOptions {
+    /// Axis values with an absolute value below this are reported as `0.0`. Defaults to `0.0`.
+    deadzone: f64,
+
+    /// Axis changes smaller than this don't update the signals. Defaults to `0.0`.
+    threshold: f64,
+
+    /// Start polling immediately. Defaults to `true`.
+    immediate: bool,
+
+    /// Called when a gamepad is connected.
+    on_connected: Rc<dyn Fn(GamepadState)>,
+
+    /// Called when a gamepad is disconnected.
+    on_disconnected: Rc<dyn Fn(GamepadState)>,
+}
+
+impl Default for UseGamepadOptions {
+    fn default() -> Self {
+        Self {
+            deadzone: 0.0,
+            threshold: 0.0,
+            immediate: true,
+            on_connected: Rc::new(|_| {}),
+            on_disconnected: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// Snapshot of the state of a gamepad.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GamepadState {
+    /// The index of the gamepad. It stays the same while the gamepad is connected.
+    pub index: u32,
+
+    /// Identifies the model of the gamepad
+    pub id: String,
+
+    /// `"standard"` if the buttons and axes follow the
+    /// [standard layout](https://w3c.github.io/gamepad/#remapping), otherwise an empty string
+    pub mapping: String,
+
+    /// Whether the gamepad is still connected
+    pub connected: bool,
+
+    /// The time the state was last updated
+    pub timestamp: f64,
+
+    /// The state of all buttons
+    pub buttons: Vec<GamepadButtonState>,
+
+    /// The values of all axes between `-1.0` and `1.0` with the deadzone applied
+    pub axes: Vec<f64>,
+}
+
+impl GamepadState {
+    fn new(gamepad: &web_sys::Gamepad, deadzone: f64) -> Self {
+        Self {
+            index: gamepad.index(),
+            id: gamepad.id(),
+            mapping: js!(gamepad["mapping"])
+                .ok()
+                .and_then(|mapping| mapping.as_string())
+                .unwrap_or_default(),
+            connected: gamepad.connected(),
+            timestamp: gamepad.timestamp(),
+            buttons: gamepad
+                .buttons()
+                .iter()
+                .map(|button| {
+                    let button: web_sys::GamepadButton = button.unchecked_into();
+
+                    GamepadButtonState {
+                        pressed: button.pressed(),
+                        touched: button.touched(),
+                        value: button.value(),
+                    }
+                })
+                .collect(),
+            axes: gamepad
+                .axes()
+                .iter()
+                .map(|axis| {
+                    let axis = axis.as_f64().unwrap_or_default();
+
+                    if axis.abs() < deadzone {
+                        0.0
+                    } else {
+                        axis
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    fn differs_from(&self, other: &Self, threshold: f64) -> bool {
+        self.index != other.index
+            || self.id != other.id
+            || self.connected != other.connected
+            || self.buttons != other.buttons
+            || self.axes.len() != other.axes.len()
+            || self
+                .axes
+                .iter()
+                .zip(other.axes.iter())
+                // always report when a stick returns to the center or leaves it
+                .any(|(prev, next)| {
+                    (prev - next).abs() > threshold || (*prev == 0.0) != (*next == 0.0)
+                })
+    }
+}
+
+/// State of a gamepad button.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GamepadButtonState {
+    /// Whether the button is pressed
+    pub pressed: bool,
+
+    /// Whether the button is touched. Always `true` if `pressed` is `true`.
+    pub touched: bool,
+
+    /// How far the button is pressed between `0.0` and `1.0`. Digital buttons only report
+    /// `0.0` or `1.0`.
+    pub value: f64,
+}
+
+/// Return type of [`use_gamepad`].
+pub struct UseGamepadReturn<PauseFn, ResumeFn>
+where
+    PauseFn: Fn() + Clone,
+    ResumeFn: Fn() + Clone,
+{
+    /// Whether the Gamepad API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// All connected gamepads
+    pub gamepads: Signal<Vec<GamepadState>>,
+
+    /// Whether the gamepads are being polled
+    pub is_active: Signal<bool>,
+
+    /// Stop polling the gamepads
+    pub pause: PauseFn,
+
+    /// Start polling the gamepads again
+    pub resume: ResumeFn,
+}
+
+impl<PauseFn, ResumeFn> UseGamepadReturn<PauseFn, ResumeFn>
+where
+    PauseFn: Fn() + Clone,
+    ResumeFn: Fn() + Clone,
+{
+    /// Signal of the gamepad with the given `index`. Only notifies when this gamepad changes.
+    pub fn gamepad(&self, index: u32) -> Signal<Option<GamepadState>> {
+        let gamepads = self.gamepads;
+
+        create_memo(move |_| {
+            gamepads.with(|gamepads| {
+                gamepads
+                    .iter()
+                    .find(|gamepad| gamepad.index == index)
+                    .cloned()
+            })
+        })
+        .into()
+    }
+
+    /// Signal of one button of the gamepad with the given `index`. Only notifies when this
+    /// button changes.
+    pub fn button(&self, index: u32, button: usize) -> Signal<Option<GamepadButtonState>> {
+        let gamepads = self.gamepads;
+
+        create_memo(move |_| {
+            gamepads.with(|gamepads| {
+                gamepads
+                    .iter()
+                    .find(|gamepad| gamepad.index == index)
+                    .and_then(|gamepad| gamepad.buttons.get(button).copied())
+            })
+        })
+        .into()
+    }
+
+    /// Signal of one axis of the gamepad with the given `index`. The value is `0.0` if the
+    /// gamepad isn't connected. Only notifies when this axis changes.
+    pub fn axis(&self, index: u32, axis: usize) -> Signal<f64> {
+        let gamepads = self.gamepads;
+
+        create_memo(move |_| {
+            gamepads.with(|gamepads| {
+                gamepads
+                    .iter()
+                    .find(|gamepad| gamepad.index == index)
+                    .and_then(|gamepad| gamepad.axes.get(axis).copied())
+                    .unwrap_or_default()
+            })
+        })
+        .into()
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{use_sensor_with_options, SensorReading, UseSensorOptions, UseSensorReturn};
+use wasm_bindgen::JsValue;
+
+/// Reactive [Gyroscope](https://developer.mozilla.org/en-US/docs/Web/API/Gyroscope).
+/// Provides the angular velocity of the device around the three axes.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_gyroscope_with_options, UseSensorOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let gyroscope = use_gyroscope_with_options(UseSensorOptions::default().frequency(60.0));
+///
+/// view! {
+///     <Show
+///         when=move || gyroscope.error.get().is_none()
+///         fallback=|| view! { <p>"The gyroscope isn't available"</p> }
+///     >
+///         <p>"x: " {move || gyroscope.reading.get().map(|reading| reading.x)}</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// The sensor is stopped when the component is cleaned up. See [`fn@crate::use_sensor`] for
+/// details.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `reading` is always `None` and `is_supported` is `false`.
+pub fn use_gyroscope() -> UseSensorReturn<GyroscopeReading> {
+    use_gyroscope_with_options(UseSensorOptions::default())
+}
+
+/// Version of [`use_gyroscope`] that takes a `UseSensorOptions`. See [`use_gyroscope`] for how to use.
+pub fn use_gyroscope_with_options(options: UseSensorOptions) -> UseSensorReturn<GyroscopeReading> {
+    use_sensor_with_options(options)
+}
+
+/// Angular velocity of the device in rad/s.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GyroscopeReading {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl SensorReading for GyroscopeReading {
+    const SENSOR: &'static str = "Gyroscope";
+
+    fn from_sensor(sensor: &JsValue) -> Option<Self> {
+        let value = |name: &str| {
+            js_sys::Reflect::get(sensor, &name.into())
+                .ok()
+                .and_then(|value| value.as_f64())
+        };
+
+        Some(Self {
+            x: value("x")?,
+            y: value("y")?,
+            z: value("z")?,
+        })
+    }
+}
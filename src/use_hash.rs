@@ -0,0 +1,118 @@
+use crate::utils::{digest, encode_hex, HashAlgorithm};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use leptos::*;
+use wasm_bindgen::JsValue;
+
+/// Reactive hash of some data computed with the browser's native
+/// [Web Crypto API](https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/digest).
+///
+/// ## Usage
+///
+/// The hash is recomputed whenever `data` or `algorithm` change. It's available as bytes, hex
+/// and base64.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_hash, UseHashReturn};
+/// # use leptos_use::utils::HashAlgorithm;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (text, set_text) = create_signal("Hello, World!".to_string());
+///
+/// let UseHashReturn { hex, .. } = use_hash::<String>(text, HashAlgorithm::Sha256);
+///
+/// view! {
+///     <input on:input=move |ev| set_text.set(event_target_value(&ev)) prop:value=text />
+///     <p>"SHA-256: " {hex}</p>
+/// }
+/// # }
+/// ```
+///
+/// `data` can be anything that can be viewed as bytes like `String` or `Vec<u8>`. Its type
+/// usually has to be specified as in the example above. The hash is `None` until the first one
+/// has been computed.
+///
+/// For HMAC signatures and keys see [`hmac_sign`] and the other helpers in [`crate::utils`].
+///
+/// [`hmac_sign`]: crate::utils::hmac_sign
+///
+/// ## Server-Side Rendering
+///
+/// On the server the hash is always `None`.
+pub fn use_hash<T>(
+    data: impl Into<MaybeSignal<T>>,
+    algorithm: impl Into<MaybeSignal<HashAlgorithm>>,
+) -> UseHashReturn
+where
+    T: AsRef<[u8]> + Clone + 'static,
+{
+    let data = data.into();
+    let algorithm = algorithm.into();
+
+    let (hash, set_hash) = create_signal(None::<Vec<u8>>);
+    let (is_pending, set_pending) = create_signal(false);
+    let (error, set_error) = create_signal(None::<JsValue>);
+
+    // Incremented for every new computation so that a slower previous one doesn't overwrite it.
+    let generation = store_value(0_usize);
+
+    let _ = watch(
+        move || (data.get(), algorithm.get()),
+        move |(data, algorithm): &(T, HashAlgorithm), _, _| {
+            generation.update_value(|generation| *generation += 1);
+            let current_generation = generation.get_value();
+
+            let data = data.as_ref().to_vec();
+            let algorithm = *algorithm;
+
+            set_pending.set(true);
+
+            spawn_local(async move {
+                let result = digest(algorithm, &data).await;
+
+                if generation.try_get_value() != Some(current_generation) {
+                    return;
+                }
+
+                match result {
+                    Ok(result) => {
+                        set_hash.set(Some(result));
+                        set_error.set(None);
+                    }
+                    Err(err) => set_error.set(Some(err)),
+                }
+                set_pending.set(false);
+            });
+        },
+        true,
+    );
+
+    UseHashReturn {
+        hash: hash.into(),
+        hex: Signal::derive(move || hash.with(|hash| hash.as_deref().map(encode_hex))),
+        base64: Signal::derive(move || hash.with(|hash| hash.as_deref().map(|hash| STANDARD.encode(hash)))),
+        is_pending: is_pending.into(),
+        error: error.into(),
+    }
+}
+
+/// Return type of [`use_hash`].
+#[derive(Clone, Copy)]
+pub struct UseHashReturn {
+    /// The bytes of the hash
+    pub hash: Signal<Option<Vec<u8>>>,
+
+    /// The hash encoded as lowercase hex
+    pub hex: Signal<Option<String>>,
+
+    /// The hash encoded as base64
+    pub base64: Signal<Option<String>>,
+
+    /// Whether the hash is being computed
+    pub is_pending: Signal<bool>,
+
+    /// The error of the last computation
+    pub error: Signal<Option<JsValue>>,
+}
@@ -0,0 +1,396 @@
+use crate::{js, js_fut, use_event_listener, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::JsValue;
+use web_sys::{HidConnectionEvent, HidDevice, HidDeviceFilter, HidInputReportEvent};
+
+/// Reactive [WebHID API](https://developer.mozilla.org/en-US/docs/Web/API/WebHID_API).
+/// Lets the user grant access to HID devices like keyboards or game controllers, keeps a
+/// signal of the connected devices up to date and provides the input reports of the opened
+/// device.
+///
+/// > This function requires `--cfg=web_sys_unstable_apis` to be activated as
+/// > [described in the wasm-bindgen guide](https://rustwasm.github.io/docs/wasm-bindgen/web-sys/unstable-apis.html).
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_hid_with_options, HidInputReport, UseHidOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let filter = web_sys::HidDeviceFilter::new();
+/// filter.set_vendor_id(0xfeed);
+///
+/// let hid = use_hid_with_options(
+///     UseHidOptions::default()
+///         .filters(vec![filter])
+///         .on_input_report(|report: HidInputReport| {
+///             logging::log!("report {}: {} bytes", report.report_id, report.data.byte_length())
+///         }),
+/// );
+///
+/// let connect = move |_| {
+///     spawn_local(async move {
+///         // shows the browser's device chooser
+///         if let Ok(Some(device)) = hid.request_device().await.map(|devices| devices.into_iter().next()) {
+///             let _ = hid.open(&device).await;
+///         }
+///     });
+/// };
+///
+/// let set_backlight = move |_| {
+///     spawn_local(async move {
+///         let _ = hid.send_report(0x01, &[0x0a, 0xff]).await;
+///     });
+/// };
+///
+/// view! {
+///     <Show
+///         when=move || hid.is_open.get()
+///         fallback=move || view! { <button on:click=connect>"Connect keyboard"</button> }
+///     >
+///         <button on:click=set_backlight>"Backlight on"</button>
+///         <p>"Last report id: " {move || hid.input_report.get().map(|report| report.report_id)}</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// `request_device` has to be called from a user gesture like a click. Devices the user has
+/// granted access to before are part of `devices` and can be opened without asking again.
+///
+/// Only one device can be open at a time. Opening another device closes the previous one.
+/// Every input report of the open device is passed to `on_input_report` and is available in
+/// the `input_report` signal.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `devices` is always empty and all operations return
+/// `UseHidError::NotSupported`.
+pub fn use_hid() -> UseHidReturn {
+    use_hid_with_options(UseHidOptions::default())
+}
+
+/// Version of [`use_hid`] that takes a `UseHidOptions`. See [`use_hid`] for how to use.
+pub fn use_hid_with_options(options: UseHidOptions) -> UseHidReturn {
+    let UseHidOptions {
+        filters,
+        on_input_report,
+        on_connect,
+        on_disconnect,
+    } = options;
+
+    let is_supported = use_supported(|| js!("hid" in &window().navigator()));
+
+    let (devices, set_devices) = create_signal(Vec::<HidDevice>::new());
+    let (device, set_device) = create_signal(None::<HidDevice>);
+    let (input_report, set_input_report) = create_signal(None::<HidInputReport>);
+    let (error, set_error) = create_signal(None::<UseHidError>);
+
+    let hid = UseHidReturn {
+        is_supported,
+        devices: devices.into(),
+        device: device.into(),
+        is_open: Signal::derive(move || device.with(Option::is_some)),
+        input_report: input_report.into(),
+        error: error.into(),
+        set_devices,
+        set_device,
+        set_error,
+        filters: store_value(filters),
+    };
+
+    hid.refresh();
+
+    let navigator_hid = if is_supported.get_untracked() {
+        Some(window().navigator().hid())
+    } else {
+        None
+    };
+
+    let _ = use_event_listener(
+        navigator_hid.clone(),
+        ev::Custom::<HidConnectionEvent>::new("connect"),
+        move |event| {
+            hid.refresh();
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_connect(event.device());
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        },
+    );
+
+    let _ = use_event_listener(
+        navigator_hid,
+        ev::Custom::<HidConnectionEvent>::new("disconnect"),
+        move |event| {
+            hid.refresh();
+
+            let disconnected = event.device();
+            if device.with_untracked(|device| device.as_ref() == Some(&disconnected)) {
+                set_device.set(None);
+            }
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_disconnect(disconnected);
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        },
+    );
+
+    let _ = use_event_listener(
+        device,
+        ev::Custom::<HidInputReportEvent>::new("inputreport"),
+        move |event| {
+            let report = HidInputReport {
+                device: event.device(),
+                report_id: event.report_id(),
+                data: event.data(),
+            };
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_input_report(report.clone());
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+
+            set_input_report.set(Some(report));
+        },
+    );
+
+    hid
+}
+
+/// Options for [`use_hid_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseHidOptions {
+    /// Only devices that match one of these filters are shown in the chooser. If empty (the
+    /// default) all devices are shown.
+    #[builder(into)]
+    filters: Vec<HidDeviceFilter>,
+
+    /// Called for every input report of the open device.
+    on_input_report: Rc<dyn Fn(HidInputReport)>,
+
+    /// Called when a device the user has granted access to is plugged in.
+    on_connect: Rc<dyn Fn(HidDevice)>,
+
+    /// Called when a device the user has granted access to is unplugged.
+    on_disconnect: Rc<dyn Fn(HidDevice)>,
+}
+
+impl Default for UseHidOptions {
+    fn default() -> Self {
+        Self {
+            filters: vec![],
+            on_input_report: Rc::new(|_| {}),
+            on_connect: Rc::new(|_| {}),
+            on_disconnect: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// An input report received from a HID device.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HidInputReport {
+    /// The device that sent the report
+    pub device: HidDevice,
+
+    /// The id of the report or `0` if the device doesn't use report ids
+    pub report_id: u8,
+
+    /// The data of the report without the report id
+    pub data: js_sys::DataView,
+}
+
+/// Return type of [`use_hid`].
+#[derive(Clone, Copy)]
+pub struct UseHidReturn {
+    /// Whether the WebHID API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The connected devices the user has granted access to
+    pub devices: Signal<Vec<HidDevice>>,
+
+    /// The device that is currently open
+    pub device: Signal<Option<HidDevice>>,
+
+    /// Whether a device is open
+    pub is_open: Signal<bool>,
+
+    /// The latest input report of the open device
+    pub input_report: Signal<Option<HidInputReport>>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseHidError>>,
+
+    set_devices: WriteSignal<Vec<HidDevice>>,
+    set_device: WriteSignal<Option<HidDevice>>,
+    set_error: WriteSignal<Option<UseHidError>>,
+    filters: StoredValue<Vec<HidDeviceFilter>>,
+}
+
+impl UseHidReturn {
+    /// Shows the browser's device chooser and returns the selected devices. Composite devices
+    /// can show up as multiple devices with different collections.
+    pub async fn request_device(&self) -> Result<Vec<HidDevice>, UseHidError> {
+        let hid = self.navigator_hid()?;
+
+        let options = self
+            .filters
+            .with_value(|filters| web_sys::HidDeviceRequestOptions::new(filters));
+
+        let devices = js_fut!(hid.request_device(&options))
+            .await
+            .map_err(|err| self.fail(UseHidError::RequestDevice(err)))?;
+
+        self.refresh();
+
+        Ok(devices.iter().collect())
+    }
+
+    /// Opens `device` and starts receiving its input reports. A previously opened device is
+    /// closed.
+    pub async fn open(&self, device: &HidDevice) -> Result<(), UseHidError> {
+        self.navigator_hid()?;
+
+        if let Some(previous) = self.device.get_untracked() {
+            if &previous == device {
+                return Ok(());
+            }
+
+            self.close().await?;
+        }
+
+        if !device.opened() {
+            js_fut!(device.open())
+                .await
+                .map_err(|err| self.fail(UseHidError::Open(err)))?;
+        }
+
+        self.set_error.set(None);
+        self.set_device.set(Some(device.clone()));
+
+        Ok(())
+    }
+
+    /// Closes the open device.
+    pub async fn close(&self) -> Result<(), UseHidError> {
+        let Some(device) = self.device.get_untracked() else {
+            return Ok(());
+        };
+
+        self.set_device.set(None);
+
+        js_fut!(device.close())
+            .await
+            .map_err(|err| self.fail(UseHidError::Failed(err)))?;
+
+        Ok(())
+    }
+
+    /// Sends an output report to the open device. Use `0` as `report_id` if the device doesn't
+    /// use report ids.
+    pub async fn send_report(&self, report_id: u8, data: &[u8]) -> Result<(), UseHidError> {
+        let device = self.open_device()?;
+
+        let promise = device
+            .send_report_with_u8_slice(report_id, &mut data.to_vec())
+            .map_err(|err| self.fail(UseHidError::Failed(err)))?;
+
+        js_fut!(promise)
+            .await
+            .map_err(|err| self.fail(UseHidError::Failed(err)))?;
+
+        Ok(())
+    }
+
+    /// Sends a feature report to the open device.
+    pub async fn send_feature_report(&self, report_id: u8, data: &[u8]) -> Result<(), UseHidError> {
+        let device = self.open_device()?;
+
+        let promise = device
+            .send_feature_report_with_u8_slice(report_id, &mut data.to_vec())
+            .map_err(|err| self.fail(UseHidError::Failed(err)))?;
+
+        js_fut!(promise)
+            .await
+            .map_err(|err| self.fail(UseHidError::Failed(err)))?;
+
+        Ok(())
+    }
+
+    /// Reads a feature report from the open device.
+    pub async fn receive_feature_report(
+        &self,
+        report_id: u8,
+    ) -> Result<js_sys::DataView, UseHidError> {
+        let device = self.open_device()?;
+
+        js_fut!(device.receive_feature_report(report_id))
+            .await
+            .map_err(|err| self.fail(UseHidError::Failed(err)))
+    }
+
+    /// Reads the connected devices again.
+    pub fn refresh(&self) {
+        let Ok(hid) = self.navigator_hid() else {
+            return;
+        };
+
+        let set_devices = self.set_devices;
+        let set_error = self.set_error;
+
+        spawn_local(async move {
+            match js_fut!(hid.get_devices()).await {
+                Ok(devices) => set_devices.set(devices.iter().collect()),
+                Err(err) => set_error.set(Some(UseHidError::Failed(err))),
+            }
+        });
+    }
+
+    fn open_device(&self) -> Result<HidDevice, UseHidError> {
+        self.device.get_untracked().ok_or(UseHidError::NotOpen)
+    }
+
+    fn navigator_hid(&self) -> Result<web_sys::Hid, UseHidError> {
+        if !self.is_supported.get_untracked() {
+            return Err(UseHidError::NotSupported);
+        }
+
+        Ok(window().navigator().hid())
+    }
+
+    fn fail(&self, err: UseHidError) -> UseHidError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// Errors of [`use_hid`].
+#[derive(Error, Debug, Clone)]
+pub enum UseHidError {
+    #[error("WebHID is not supported")]
+    NotSupported,
+    #[error("no device is open")]
+    NotOpen,
+    #[error("requesting a device failed")]
+    RequestDevice(JsValue),
+    #[error("opening the device failed")]
+    Open(JsValue),
+    #[error("HID operation failed")]
+    Failed(JsValue),
+}
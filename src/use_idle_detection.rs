@@ -0,0 +1,304 @@
+use crate::{js, js_fut, use_event_listener, use_permission, use_supported, PermissionState};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+
+/// Reactive [Idle Detection API](https://developer.mozilla.org/en-US/docs/Web/API/Idle_Detection_API).
+/// Tells if the user is idle on the whole device and if the screen is locked.
+///
+/// Unlike [`fn@crate::use_idle`] which only listens to events on the page, this also knows if
+/// the user is active in other apps. It requires the `"idle-detection"` permission though.
+///
+/// ## Usage
+///
+/// `request_permission` has to be called from a user gesture like a click. Afterwards `start`
+/// begins detecting.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_idle_detection, PermissionState, ScreenIdleState};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let idle = use_idle_detection();
+///
+/// let start = move |_| {
+///     spawn_local(async move {
+///         if let Ok(PermissionState::Granted) = idle.request_permission().await {
+///             let _ = idle.start().await;
+///         }
+///     });
+/// };
+///
+/// view! {
+///     <button on:click=start disabled=idle.is_running>"Start"</button>
+///     <button on:click=move |_| idle.stop()>"Stop"</button>
+///     <p>"User: " {move || format!("{:?}", idle.user_state.get())}</p>
+///     <Show when=move || idle.screen_state.get() == Some(ScreenIdleState::Locked)>
+///         <p>"The screen is locked"</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// The user counts as idle after `threshold` milliseconds without interaction, which has to be
+/// at least one minute.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_idle_detection_with_options, UseIdleDetectionOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let idle = use_idle_detection_with_options(UseIdleDetectionOptions::default().threshold(300_000));
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// Detection stops when `stop` is called or the component is cleaned up.
+///
+/// Only Chromium based browsers support this.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the states are always `None` and `start` returns
+/// `UseIdleDetectionError::NotSupported`.
+pub fn use_idle_detection() -> UseIdleDetectionReturn {
+    use_idle_detection_with_options(UseIdleDetectionOptions::default())
+}
+
+/// Version of [`use_idle_detection`] that takes a `UseIdleDetectionOptions`. See [`use_idle_detection`] for how to use.
+pub fn use_idle_detection_with_options(options: UseIdleDetectionOptions) -> UseIdleDetectionReturn {
+    let UseIdleDetectionOptions { threshold } = options;
+
+    let is_supported = use_supported(|| js!("IdleDetector" in &window()));
+    let permission = use_permission("idle-detection");
+
+    let (detector, set_detector) = create_signal(None::<IdleDetector>);
+    let (user_state, set_user_state) = create_signal(None::<UserIdleState>);
+    let (screen_state, set_screen_state) = create_signal(None::<ScreenIdleState>);
+    let (error, set_error) = create_signal(None::<UseIdleDetectionError>);
+
+    let _ = use_event_listener(
+        detector,
+        ev::Custom::<web_sys::Event>::new("change"),
+        move |_| {
+            detector.with_untracked(|detector| {
+                if let Some(detector) = detector {
+                    set_user_state.set(detector.user_state().as_deref().map(UserIdleState::from));
+                    set_screen_state.set(
+                        detector
+                            .screen_state()
+                            .as_deref()
+                            .map(ScreenIdleState::from),
+                    );
+                }
+            });
+        },
+    );
+
+    let idle = UseIdleDetectionReturn {
+        is_supported,
+        is_running: Signal::derive(move || detector.with(Option::is_some)),
+        permission,
+        user_state: user_state.into(),
+        screen_state: screen_state.into(),
+        error: error.into(),
+        threshold,
+        set_detector,
+        set_user_state,
+        set_screen_state,
+        set_error,
+        abort_controller: store_value(None),
+    };
+
+    on_cleanup(move || idle.stop());
+
+    idle
+}
+
+/// Options for [`use_idle_detection_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseIdleDetectionOptions {
+    /// After how many milliseconds without interaction the user counts as idle. Has to be at
+    /// least `60_000`. Defaults to `60_000`.
+    threshold: u64,
+}
+
+impl Default for UseIdleDetectionOptions {
+    fn default() -> Self {
+        Self { threshold: 60_000 }
+    }
+}
+
+/// Return type of [`use_idle_detection`].
+#[derive(Clone, Copy)]
+pub struct UseIdleDetectionReturn {
+    /// Whether the Idle Detection API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// Whether the detection is running
+    pub is_running: Signal<bool>,
+
+    /// The state of the `"idle-detection"` permission
+    pub permission: Signal<PermissionState>,
+
+    /// Whether the user is active. `None` while the detection isn't running.
+    pub user_state: Signal<Option<UserIdleState>>,
+
+    /// Whether the screen is locked. `None` while the detection isn't running.
+    pub screen_state: Signal<Option<ScreenIdleState>>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseIdleDetectionError>>,
+
+    threshold: u64,
+    set_detector: WriteSignal<Option<IdleDetector>>,
+    set_user_state: WriteSignal<Option<UserIdleState>>,
+    set_screen_state: WriteSignal<Option<ScreenIdleState>>,
+    set_error: WriteSignal<Option<UseIdleDetectionError>>,
+    abort_controller: StoredValue<Option<web_sys::AbortController>>,
+}
+
+impl UseIdleDetectionReturn {
+    /// Asks the user for the `"idle-detection"` permission. Has to be called from a user gesture.
+    pub async fn request_permission(&self) -> Result<PermissionState, UseIdleDetectionError> {
+        if !self.is_supported.get_untracked() {
+            return Err(UseIdleDetectionError::NotSupported);
+        }
+
+        let permission = async { js_fut!(IdleDetector::request_permission()?).await }
+            .await
+            .map_err(|err| self.fail(UseIdleDetectionError::Permission(err)))?;
+
+        Ok(match permission.as_string().as_deref() {
+            Some("granted") => PermissionState::Granted,
+            _ => PermissionState::Denied,
+        })
+    }
+
+    /// Starts detecting. Does nothing if already running. Fails if the permission hasn't been
+    /// granted.
+    pub async fn start(&self) -> Result<(), UseIdleDetectionError> {
+        if !self.is_supported.get_untracked() {
+            return Err(UseIdleDetectionError::NotSupported);
+        }
+
+        if self.is_running.get_untracked() {
+            return Ok(());
+        }
+
+        let detector =
+            IdleDetector::new().map_err(|err| self.fail(UseIdleDetectionError::Start(err)))?;
+
+        let controller = web_sys::AbortController::new()
+            .map_err(|err| self.fail(UseIdleDetectionError::Start(err)))?;
+
+        let options = js_sys::Object::new();
+        js!(options["threshold"] = self.threshold as f64);
+        js!(options["signal"] = controller.signal());
+
+        js_fut!(detector.start(&options))
+            .await
+            .map_err(|err| self.fail(UseIdleDetectionError::Start(err)))?;
+
+        self.abort_controller.set_value(Some(controller));
+        self.set_error.set(None);
+        self.set_user_state
+            .set(detector.user_state().as_deref().map(UserIdleState::from));
+        self.set_screen_state.set(
+            detector
+                .screen_state()
+                .as_deref()
+                .map(ScreenIdleState::from),
+        );
+        self.set_detector.set(Some(detector));
+
+        Ok(())
+    }
+
+    /// Stops detecting.
+    pub fn stop(&self) {
+        if let Some(Some(controller)) = self.abort_controller.try_update_value(Option::take) {
+            controller.abort();
+        }
+
+        let _ = self.set_detector.try_set(None);
+        let _ = self.set_user_state.try_set(None);
+        let _ = self.set_screen_state.try_set(None);
+    }
+
+    fn fail(&self, err: UseIdleDetectionError) -> UseIdleDetectionError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// Whether the user interacts with the device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UserIdleState {
+    /// The user has interacted with the device within the threshold
+    Active,
+    /// The user hasn't interacted with the device for longer than the threshold
+    Idle,
+}
+
+impl From<&str> for UserIdleState {
+    fn from(state: &str) -> Self {
+        match state {
+            "idle" => Self::Idle,
+            _ => Self::Active,
+        }
+    }
+}
+
+/// Whether the screen of the device is locked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScreenIdleState {
+    Locked,
+    Unlocked,
+}
+
+impl From<&str> for ScreenIdleState {
+    fn from(state: &str) -> Self {
+        match state {
+            "locked" => Self::Locked,
+            _ => Self::Unlocked,
+        }
+    }
+}
+
+/// Errors of [`use_idle_detection`].
+#[derive(Error, Debug, Clone)]
+pub enum UseIdleDetectionError {
+    #[error("the Idle Detection API is not supported")]
+    NotSupported,
+    #[error("requesting the permission failed")]
+    Permission(JsValue),
+    #[error("starting the detection failed")]
+    Start(JsValue),
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Clone, Debug, PartialEq)]
+    #[wasm_bindgen(extends = web_sys::EventTarget)]
+    type IdleDetector;
+
+    #[wasm_bindgen(catch, constructor)]
+    fn new() -> Result<IdleDetector, JsValue>;
+
+    #[wasm_bindgen(catch, static_method_of = IdleDetector, js_name = requestPermission)]
+    fn request_permission() -> Result<js_sys::Promise, JsValue>;
+
+    #[wasm_bindgen(method, getter = userState)]
+    fn user_state(this: &IdleDetector) -> Option<String>;
+
+    #[wasm_bindgen(method, getter = screenState)]
+    fn screen_state(this: &IdleDetector) -> Option<String>;
+
+    #[wasm_bindgen(method)]
+    fn start(this: &IdleDetector, options: &js_sys::Object) -> js_sys::Promise;
+}
@@ -124,7 +124,7 @@ where
         })
     });
 
-    let is_element_visible = use_element_visibility(observed_element);
+    let is_element_visible = use_element_visibility(observed_element).is_visible;
 
     let check_and_load = store_value(None::<Rc<dyn Fn()>>);
 
@@ -0,0 +1,155 @@
+use crate::{js, js_fut, use_event_listener, use_window};
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+
+/// Lets the user install the web app with a custom install button instead of the browser's
+/// own install prompt.
+///
+/// ## Usage
+///
+/// The [`beforeinstallprompt`](https://developer.mozilla.org/en-US/docs/Web/API/BeforeInstallPromptEvent)
+/// event is captured as soon as the browser considers the app installable. Then `can_install`
+/// becomes `true` and `prompt` shows the install dialog.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_install_prompt, InstallPromptOutcome};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let install_prompt = use_install_prompt();
+///
+/// let install = move |_| {
+///     spawn_local(async move {
+///         if let Ok(InstallPromptOutcome::Accepted) = install_prompt.prompt().await {
+///             // the app is being installed
+///         }
+///     });
+/// };
+///
+/// view! {
+///     <Show when=move || install_prompt.can_install.get()>
+///         <button on:click=install>"Install app"</button>
+///     </Show>
+///     <Show when=move || install_prompt.installed.get()>
+///         <p>"Thanks for installing!"</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// The dialog can only be shown once per event. Afterwards `can_install` is `false` until the
+/// browser fires the event again.
+///
+/// `installed` becomes `true` when the app has been installed while the page is open, no matter
+/// if through `prompt` or the browser's menu.
+///
+/// Only Chromium based browsers support this. In other browsers `can_install` stays `false`.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `can_install` and `installed` are always `false`.
+pub fn use_install_prompt() -> UseInstallPromptReturn {
+    let (event, set_event) = create_signal(None::<BeforeInstallPromptEvent>);
+    let (installed, set_installed) = create_signal(false);
+
+    let _ = use_event_listener(
+        use_window(),
+        ev::Custom::<BeforeInstallPromptEvent>::new("beforeinstallprompt"),
+        move |event| {
+            // Keeps the browser from showing its own prompt.
+            event.prevent_default();
+            set_event.set(Some(event));
+        },
+    );
+
+    let _ = use_event_listener(
+        use_window(),
+        ev::Custom::<web_sys::Event>::new("appinstalled"),
+        move |_| {
+            set_event.set(None);
+            set_installed.set(true);
+        },
+    );
+
+    UseInstallPromptReturn {
+        can_install: Signal::derive(move || event.with(Option::is_some)),
+        installed: installed.into(),
+        event,
+        set_event,
+    }
+}
+
+/// Return type of [`use_install_prompt`].
+#[derive(Clone, Copy)]
+pub struct UseInstallPromptReturn {
+    /// Whether the install dialog can be shown with `prompt`
+    pub can_install: Signal<bool>,
+
+    /// Whether the app has been installed while the page is open
+    pub installed: Signal<bool>,
+
+    event: ReadSignal<Option<BeforeInstallPromptEvent>>,
+    set_event: WriteSignal<Option<BeforeInstallPromptEvent>>,
+}
+
+impl UseInstallPromptReturn {
+    /// Shows the install dialog and returns whether the user accepted or dismissed it.
+    pub async fn prompt(&self) -> Result<InstallPromptOutcome, UseInstallPromptError> {
+        let event = self
+            .event
+            .get_untracked()
+            .ok_or(UseInstallPromptError::NotAvailable)?;
+
+        // The event can only be used once.
+        self.set_event.set(None);
+
+        let choice = async {
+            js_fut!(event.prompt()?).await?;
+            js_fut!(event.user_choice()).await
+        }
+        .await
+        .map_err(UseInstallPromptError::Prompt)?;
+
+        let outcome = js!(choice["outcome"])
+            .ok()
+            .and_then(|outcome| outcome.as_string());
+
+        Ok(match outcome.as_deref() {
+            Some("accepted") => InstallPromptOutcome::Accepted,
+            _ => InstallPromptOutcome::Dismissed,
+        })
+    }
+}
+
+/// The choice of the user in the install dialog.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InstallPromptOutcome {
+    /// The app is installed
+    Accepted,
+    /// The dialog was closed without installing the app
+    Dismissed,
+}
+
+/// Errors of [`UseInstallPromptReturn::prompt`].
+#[derive(Error, Debug, Clone)]
+pub enum UseInstallPromptError {
+    #[error("the app can't be installed right now")]
+    NotAvailable,
+    #[error("showing the install dialog failed")]
+    Prompt(JsValue),
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Clone, Debug, PartialEq)]
+    #[wasm_bindgen(extends = web_sys::Event)]
+    type BeforeInstallPromptEvent;
+
+    #[wasm_bindgen(catch, method)]
+    fn prompt(this: &BeforeInstallPromptEvent) -> Result<js_sys::Promise, JsValue>;
+
+    #[wasm_bindgen(method, getter = userChoice)]
+    fn user_choice(this: &BeforeInstallPromptEvent) -> js_sys::Promise;
+}
@@ -46,6 +46,40 @@ cfg_if! { if #[cfg(not(feature = "ssr"))] {
 /// # }
 /// ```
 ///
+/// ## Multiple Targets
+///
+/// One observer can watch many elements, for example all items of a list. The targets can be
+/// a reactive `Vec` so elements can be added and removed. `entries` contains the latest entry
+/// of every target. With `pause` and `resume` the observation can be suspended, for example
+/// while an animation is running, without creating a new observer.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_intersection_observer, UseIntersectionObserverReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (targets, set_targets) = create_signal(Vec::<web_sys::Element>::new());
+///
+/// let UseIntersectionObserverReturn {
+///     entries,
+///     pause,
+///     resume,
+///     ..
+/// } = use_intersection_observer(Signal::from(targets), |_, _| {});
+///
+/// let visible_count = move || {
+///     entries.with(|entries| entries.iter().filter(|entry| entry.is_intersecting()).count())
+/// };
+///
+/// view! {
+///     <p>{visible_count} " items are visible"</p>
+///     <button on:click=move |_| pause()>"Pause"</button>
+///     <button on:click=move |_| resume()>"Resume"</button>
+/// }
+/// # }
+/// ```
+///
 /// ## Server-Side Rendering
 ///
 /// On the server this amounts to a no-op.
@@ -92,25 +126,42 @@ where
     } = options;
 
     let (is_active, set_active) = create_signal(immediate);
+    let (entries, set_entries) = create_signal(Vec::<web_sys::IntersectionObserverEntry>::new());
 
     cfg_if! { if #[cfg(feature = "ssr")] {
-        let pause = || {};
-        let cleanup = || {};
+        let _ = set_entries;
+
+        let pause = move || set_active.set(false);
+        let resume = move || set_active.set(true);
         let stop = || {};
     } else {
         let closure_js = Closure::<dyn FnMut(js_sys::Array, web_sys::IntersectionObserver)>::new(
             move |entries: js_sys::Array, observer| {
+                let entries = entries
+                    .to_vec()
+                    .into_iter()
+                    .map(|v| v.unchecked_into::<web_sys::IntersectionObserverEntry>())
+                    .collect::<Vec<_>>();
+
+                let _ = set_entries.try_update(|latest_entries| {
+                    for entry in &entries {
+                        let target = entry.target();
+
+                        if let Some(latest_entry) = latest_entries
+                            .iter_mut()
+                            .find(|latest_entry| latest_entry.target() == target)
+                        {
+                            *latest_entry = entry.clone();
+                        } else {
+                            latest_entries.push(entry.clone());
+                        }
+                    }
+                });
+
                 #[cfg(debug_assertions)]
                 let prev = SpecialNonReactiveZone::enter();
 
-                callback(
-                    entries
-                        .to_vec()
-                        .into_iter()
-                        .map(|v| v.unchecked_into::<web_sys::IntersectionObserverEntry>())
-                        .collect(),
-                    observer,
-                );
+                callback(entries, observer);
 
                 #[cfg(debug_assertions)]
                 SpecialNonReactiveZone::exit(prev);
@@ -118,15 +169,18 @@ where
         )
         .into_js_value();
 
+        // The observer is only recreated when the root changes. Pausing or changing the targets
+        // only disconnects it.
         let observer: Rc<RefCell<Option<web_sys::IntersectionObserver>>> =
             Rc::new(RefCell::new(None));
+        let observer_root: Rc<RefCell<Option<web_sys::Element>>> = Rc::new(RefCell::new(None));
 
-        let cleanup = {
-            let obsserver = Rc::clone(&observer);
+        let disconnect = {
+            let observer = Rc::clone(&observer);
 
             move || {
-                if let Some(o) = obsserver.take() {
-                    o.disconnect();
+                if let Some(observer) = observer.borrow().as_ref() {
+                    observer.disconnect();
                 }
             }
         };
@@ -135,7 +189,7 @@ where
         let root = root.map(|root| (root).into());
 
         let stop_watch = {
-            let cleanup = cleanup.clone();
+            let disconnect = disconnect.clone();
 
             watch_with_options(
                 move || {
@@ -148,71 +202,79 @@ where
                 move |values, _, _| {
                     let (targets, root, is_active) = values;
 
-                    cleanup();
+                    disconnect();
+
+                    let targets = targets
+                        .iter()
+                        .flatten()
+                        .map(|target| target.clone().into())
+                        .collect::<Vec<web_sys::Element>>();
+
+                    set_entries.update(|entries| {
+                        entries.retain(|entry| targets.contains(&entry.target()));
+                    });
 
                     if !is_active {
                         return;
                     }
 
-                    let mut options = web_sys::IntersectionObserverInit::new();
-                    options.root_margin(&root_margin).threshold(
-                        &thresholds
-                            .iter()
-                            .copied()
-                            .map(JsValue::from)
-                            .collect::<js_sys::Array>(),
-                    );
-
-                    if let Some(Some(root)) = root {
-                        let root: web_sys::Element = root.clone().into();
-                        options.root(Some(&root));
+                    let root = root
+                        .as_ref()
+                        .and_then(|root| root.clone())
+                        .map(|root| -> web_sys::Element { root.into() });
+
+                    let mut observer = observer.borrow_mut();
+
+                    if observer.is_none() || *observer_root.borrow() != root {
+                        let mut options = web_sys::IntersectionObserverInit::new();
+                        options.root_margin(&root_margin).threshold(
+                            &thresholds
+                                .iter()
+                                .copied()
+                                .map(JsValue::from)
+                                .collect::<js_sys::Array>(),
+                        );
+
+                        if let Some(root) = &root {
+                            options.root(Some(root));
+                        }
+
+                        let obs = web_sys::IntersectionObserver::new_with_options(
+                            closure_js.clone().as_ref().unchecked_ref(),
+                            &options,
+                        )
+                        .expect("failed to create IntersectionObserver");
+
+                        observer.replace(obs);
+                        observer_root.replace(root);
                     }
 
-                    let obs = web_sys::IntersectionObserver::new_with_options(
-                        closure_js.clone().as_ref().unchecked_ref(),
-                        &options,
-                    )
-                    .expect("failed to create IntersectionObserver");
-
-                    for target in targets.iter().flatten() {
-                        let target: web_sys::Element = target.clone().into();
-                        obs.observe(&target);
+                    if let Some(observer) = observer.as_ref() {
+                        for target in &targets {
+                            observer.observe(target);
+                        }
                     }
-
-                    observer.replace(Some(obs));
                 },
                 WatchOptions::default().immediate(immediate),
             )
         };
 
-        let stop = {
-            let cleanup = cleanup.clone();
-
-            move || {
-                cleanup();
-                stop_watch();
-            }
+        let stop = move || {
+            disconnect();
+            stop_watch();
         };
 
         on_cleanup(stop.clone());
 
-        let pause = {
-            let cleanup = cleanup.clone();
-
-            move || {
-                cleanup();
-                set_active.set(false);
-            }
-        };
+        let pause = move || set_active.set(false);
+        let resume = move || set_active.set(true);
     }}
 
     UseIntersectionObserverReturn {
         is_active: is_active.into(),
+        entries: entries.into(),
         pause,
-        resume: move || {
-            cleanup();
-            set_active.set(true);
-        },
+        resume,
         stop,
     }
 }
@@ -277,7 +339,7 @@ where
     PauseFn: Fn() + Clone,
     ResumeFn: Fn() + Clone,
 {
-    /// Pauses the `IntersectionObserver` observations. Will cause `is_active = false`.
+    /// Pauses the `IntersectionObserver` observations without destroying it. Will cause `is_active = false`.
     pub pause: PauseFn,
     /// Resumes the `IntersectionObserver` observations. Will cause `is_active = true`.
     pub resume: ResumeFn,
@@ -285,4 +347,7 @@ where
     pub stop: StopFn,
     /// A signal which is `true` when the `IntersectionObserver` is active, and `false` when paused or stopped.
     pub is_active: Signal<bool>,
+    /// The latest entry of every observed target. Entries of targets that are no longer
+    /// observed are removed.
+    pub entries: Signal<Vec<web_sys::IntersectionObserverEntry>>,
 }
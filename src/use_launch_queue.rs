@@ -0,0 +1,118 @@
+use crate::{js, use_supported};
+use leptos::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::FileSystemFileHandle;
+
+/// Reactive [Launch Queue](https://developer.mozilla.org/en-US/docs/Web/API/LaunchQueue) of the
+/// File Handling API. Provides the files that the operating system opened the installed web app
+/// with.
+///
+/// ## Usage
+///
+/// The app has to declare the file types it handles in the `file_handlers` of its web app
+/// manifest. When the user opens such a file with the app, its handle is available in `files`.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{js_fut, use_launch_queue, UseLaunchQueueReturn};
+/// # use wasm_bindgen::JsCast;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseLaunchQueueReturn { files, .. } = use_launch_queue();
+///
+/// create_effect(move |_| {
+///     for handle in files.get() {
+///         spawn_local(async move {
+///             if let Ok(file) = js_fut!(handle.get_file()).await {
+///                 let file = file.unchecked_into::<web_sys::File>();
+///                 // read the file
+///             }
+///         });
+///     }
+/// });
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// `target_url` is the URL the app was launched with, including any query parameters. It's also
+/// set when the app is launched without files, for example through a shortcut or a link.
+///
+/// Both signals are updated on every launch. If the manifest uses `"launch_handler":
+/// {"client_mode": "focus-existing"}`, files that are opened while the app is running are
+/// passed to the open window instead of a new one.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `files` is always empty and `target_url` is always `None`.
+pub fn use_launch_queue() -> UseLaunchQueueReturn {
+    let is_supported = use_supported(|| js!("launchQueue" in &window()));
+
+    let (files, set_files) = create_signal(Vec::<FileSystemFileHandle>::new());
+    let (target_url, set_target_url) = create_signal(None::<String>);
+
+    if is_supported.get_untracked() {
+        let consumer = Closure::<dyn Fn(LaunchParams)>::new(move |params: LaunchParams| {
+            let _ = set_target_url.try_set(params.target_url());
+            let _ = set_files.try_set(
+                params
+                    .files()
+                    .iter()
+                    .map(JsCast::unchecked_into::<FileSystemFileHandle>)
+                    .collect(),
+            );
+        })
+        .into_js_value();
+
+        window()
+            .unchecked_into::<LaunchQueueWindow>()
+            .launch_queue()
+            .set_consumer(consumer.unchecked_ref());
+    }
+
+    UseLaunchQueueReturn {
+        is_supported,
+        files: files.into(),
+        target_url: target_url.into(),
+    }
+}
+
+/// Return type of [`use_launch_queue`].
+#[derive(Clone, Copy)]
+pub struct UseLaunchQueueReturn {
+    /// Whether the File Handling API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The handles of the files the app was launched with
+    pub files: Signal<Vec<FileSystemFileHandle>>,
+
+    /// The URL the app was launched with
+    pub target_url: Signal<Option<String>>,
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Clone, Debug)]
+    #[wasm_bindgen(extends = web_sys::Window, js_name = Window)]
+    type LaunchQueueWindow;
+
+    #[wasm_bindgen(method, getter = launchQueue)]
+    fn launch_queue(this: &LaunchQueueWindow) -> LaunchQueue;
+
+    #[derive(Clone, Debug)]
+    type LaunchQueue;
+
+    #[wasm_bindgen(method, js_name = setConsumer)]
+    fn set_consumer(this: &LaunchQueue, consumer: &js_sys::Function);
+
+    #[derive(Clone, Debug)]
+    type LaunchParams;
+
+    #[wasm_bindgen(method, getter)]
+    fn files(this: &LaunchParams) -> js_sys::Array;
+
+    #[wasm_bindgen(method, getter = targetURL)]
+    fn target_url(this: &LaunchParams) -> Option<String>;
+}
@@ -0,0 +1,294 @@
+use crate::core::{now, ElementMaybeSignal, Position};
+use crate::utils::Pausable;
+use crate::{
+    use_event_listener_with_options, use_raf_fn_with_options, UseEventListenerOptions,
+    UseRafFnOptions,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{pointercancel, pointerdown, pointerleave, pointermove, pointerup};
+use leptos::leptos_dom::helpers::TimeoutHandle;
+use leptos::*;
+use std::rc::Rc;
+use std::time::Duration;
+use web_sys::PointerEvent;
+
+/// Detect when an element is pressed and held for some time.
+///
+/// ## Usage
+///
+/// `on_long_press` is called once the pointer has been held down on the target for `delay`
+/// milliseconds. The press is cancelled when the pointer is released, leaves the target or moves
+/// further than `distance_threshold` pixels.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Button;
+/// # use leptos_use::use_long_press;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Button>();
+/// let (is_deleted, set_deleted) = create_signal(false);
+///
+/// use_long_press(el, move |_| set_deleted.set(true));
+///
+/// view! {
+///     <button node_ref=el>"Hold to delete"</button>
+///     <Show when=move || is_deleted.get()>"Deleted"</Show>
+/// }
+/// # }
+/// ```
+///
+/// ## Progress
+///
+/// `pressing_progress` goes from `0.0` to `1.0` while the pointer is held down. It can be used to
+/// show how long the user still has to hold.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Button;
+/// # use leptos_use::{use_long_press_with_options, UseLongPressOptions, UseLongPressReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Button>();
+///
+/// let UseLongPressReturn {
+///     pressing_progress,
+///     ..
+/// } = use_long_press_with_options(
+///     el,
+///     |_| leptos::logging::log!("Long pressed"),
+///     UseLongPressOptions::default()
+///         .delay(1000)
+///         .distance_threshold(None),
+/// );
+///
+/// view! {
+///     <button node_ref=el>
+///         "Hold me"
+///         <progress value=pressing_progress max="1"></progress>
+///     </button>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_pressing` is always `false` and `pressing_progress` is always `0.0`.
+pub fn use_long_press<El, T, F>(
+    target: El,
+    on_long_press: F,
+) -> UseLongPressReturn<impl Fn() + Clone>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+    F: Fn(PointerEvent) + 'static,
+{
+    use_long_press_with_options(target, on_long_press, UseLongPressOptions::default())
+}
+
+/// Version of [`use_long_press`] that takes a `UseLongPressOptions`. See [`use_long_press`] for how to use.
+pub fn use_long_press_with_options<El, T, F>(
+    target: El,
+    on_long_press: F,
+    options: UseLongPressOptions,
+) -> UseLongPressReturn<impl Fn() + Clone>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+    F: Fn(PointerEvent) + 'static,
+{
+    let UseLongPressOptions {
+        delay,
+        distance_threshold,
+    } = options;
+
+    let on_long_press = Rc::new(on_long_press);
+
+    let (is_pressing, set_pressing) = create_signal(false);
+    let (pressing_progress, set_pressing_progress) = create_signal(0.0);
+
+    // where and when the current press started
+    let press_start = store_value(None::<(Position, f64)>);
+    let timer = store_value(None::<TimeoutHandle>);
+
+    let Pausable {
+        pause: pause_progress,
+        resume: resume_progress,
+        ..
+    } = use_raf_fn_with_options(
+        move |_| {
+            if let Some((_, started_at)) = press_start.get_value() {
+                set_pressing_progress.set(((now() - started_at) / delay as f64).min(1.0));
+            }
+        },
+        UseRafFnOptions::default().immediate(false),
+    );
+
+    let cancel = {
+        let pause_progress = pause_progress.clone();
+
+        move || {
+            if let Some(Some(handle)) = timer.try_update_value(Option::take) {
+                handle.clear();
+            }
+            pause_progress();
+
+            let _ = press_start.try_set_value(None);
+            let _ = set_pressing.try_set(false);
+            let _ = set_pressing_progress.try_set(0.0);
+        }
+    };
+
+    on_cleanup(cancel.clone());
+
+    let listener_options = UseEventListenerOptions::default().passive(true);
+
+    let target = target.into();
+
+    let stop_down = use_event_listener_with_options(
+        target.clone(),
+        pointerdown,
+        {
+            let cancel = cancel.clone();
+
+            move |event| {
+                // only the main button of a mouse starts a press
+                if !event.is_primary() || event.button() != 0 {
+                    return;
+                }
+
+                cancel();
+
+                press_start.set_value(Some((
+                    Position {
+                        x: event.client_x() as f64,
+                        y: event.client_y() as f64,
+                    },
+                    now(),
+                )));
+                set_pressing.set(true);
+                resume_progress();
+
+                let on_long_press = Rc::clone(&on_long_press);
+                let pause_progress = pause_progress.clone();
+
+                timer.set_value(
+                    set_timeout_with_handle(
+                        move || {
+                            timer.set_value(None);
+                            pause_progress();
+                            set_pressing_progress.set(1.0);
+
+                            #[cfg(debug_assertions)]
+                            let prev = SpecialNonReactiveZone::enter();
+
+                            on_long_press(event);
+
+                            #[cfg(debug_assertions)]
+                            SpecialNonReactiveZone::exit(prev);
+                        },
+                        Duration::from_millis(delay),
+                    )
+                    .ok(),
+                );
+            }
+        },
+        listener_options,
+    );
+
+    let stop_move = use_event_listener_with_options(
+        target.clone(),
+        pointermove,
+        {
+            let cancel = cancel.clone();
+
+            move |event| {
+                let (Some(threshold), Some((start, _))) =
+                    (distance_threshold, press_start.get_value())
+                else {
+                    return;
+                };
+
+                let distance =
+                    (event.client_x() as f64 - start.x).hypot(event.client_y() as f64 - start.y);
+
+                if distance > threshold {
+                    cancel();
+                }
+            }
+        },
+        listener_options,
+    );
+
+    let stop_up = use_event_listener_with_options(
+        target.clone(),
+        pointerup,
+        {
+            let cancel = cancel.clone();
+            move |_| cancel()
+        },
+        listener_options,
+    );
+    let stop_leave = use_event_listener_with_options(
+        target.clone(),
+        pointerleave,
+        {
+            let cancel = cancel.clone();
+            move |_| cancel()
+        },
+        listener_options,
+    );
+    let stop_cancel =
+        use_event_listener_with_options(target, pointercancel, move |_| cancel(), listener_options);
+
+    let stop = move || {
+        stop_down();
+        stop_move();
+        stop_up();
+        stop_leave();
+        stop_cancel();
+    };
+
+    UseLongPressReturn {
+        is_pressing: is_pressing.into(),
+        pressing_progress: pressing_progress.into(),
+        stop,
+    }
+}
+
+/// Options for [`use_long_press_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseLongPressOptions {
+    /// How long the pointer has to be held down in milliseconds. Defaults to `500`.
+    delay: u64,
+
+    /// How many pixels the pointer may move before the press is cancelled. `None` means the
+    /// press is never cancelled by moving. Defaults to `Some(10.0)`.
+    distance_threshold: Option<f64>,
+}
+
+impl Default for UseLongPressOptions {
+    fn default() -> Self {
+        Self {
+            delay: 500,
+            distance_threshold: Some(10.0),
+        }
+    }
+}
+
+/// Return type of [`use_long_press`].
+pub struct UseLongPressReturn<F>
+where
+    F: Fn() + Clone,
+{
+    /// Whether the pointer is held down on the target
+    pub is_pressing: Signal<bool>,
+
+    /// How far the press has progressed from `0.0` to `1.0`. `on_long_press` is called at `1.0`.
+    pub pressing_progress: Signal<f64>,
+
+    /// Removes the event listeners
+    pub stop: F,
+}
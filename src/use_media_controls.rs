@@ -0,0 +1,448 @@
+use crate::core::ElementMaybeSignal;
+use crate::{js_fut, use_event_listener};
+use leptos::*;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlMediaElement, TextTrack, TextTrackKind, TextTrackList, TextTrackMode};
+
+/// Reactive controls for `<audio>` and `<video>` elements.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Video;
+/// # use leptos_use::{use_media_controls, UseMediaControlsReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let video = create_node_ref::<Video>();
+///
+/// let UseMediaControlsReturn {
+///     playing,
+///     set_playing,
+///     current_time,
+///     set_current_time,
+///     duration,
+///     volume,
+///     set_volume,
+///     ..
+/// } = use_media_controls(video);
+///
+/// view! {
+///     <video node_ref=video src="movie.mp4"></video>
+///     <button on:click=move |_| set_playing.update(|playing| *playing = !*playing)>
+///         {move || if playing.get() { "Pause" } else { "Play" }}
+///     </button>
+///     <input
+///         type="range"
+///         min="0"
+///         prop:max=duration
+///         prop:value=current_time
+///         on:input=move |ev| set_current_time.set(event_target_value(&ev).parse().unwrap_or_default())
+///     />
+///     <input
+///         type="range"
+///         min="0"
+///         max="1"
+///         step="0.01"
+///         prop:value=volume
+///         on:input=move |ev| set_volume.set(event_target_value(&ev).parse().unwrap_or(1.0))
+///     />
+/// }
+/// # }
+/// ```
+///
+/// The signals are updated from the events of the element, so they also follow the native
+/// controls of the browser or changes made by other code. Setting `playing`, `current_time`,
+/// `volume`, `muted` or `rate` updates the element. If the browser refuses to play, for example
+/// because autoplay with sound isn't allowed, `playing` is set back to `false`.
+///
+/// ## Text Tracks
+///
+/// The `<track>`s of the element are available in `tracks`. `enable_track` shows one of them and
+/// hides the other subtitles and captions.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Video;
+/// # use leptos_use::use_media_controls;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let video = create_node_ref::<Video>();
+///
+/// let controls = use_media_controls(video);
+///
+/// view! {
+///     <video node_ref=video src="movie.mp4">
+///         <track kind="subtitles" src="en.vtt" srclang="en" label="English" />
+///         <track kind="subtitles" src="de.vtt" srclang="de" label="Deutsch" />
+///     </video>
+///     <button on:click=move |_| controls.disable_tracks()>"Off"</button>
+///     <For each=move || controls.tracks.get() key=|track| track.index let:track>
+///         <button on:click=move |_| controls.enable_track(track.index)>{track.label}</button>
+///     </For>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server the signals keep their initial values.
+pub fn use_media_controls<El, T>(target: El) -> UseMediaControlsReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let target = target.into();
+
+    let media = Signal::derive(move || {
+        target
+            .get()
+            .and_then(|element| element.into().dyn_into::<HtmlMediaElement>().ok())
+    });
+
+    let (playing, set_playing) = create_signal(false);
+    let (current_time, set_current_time) = create_signal(0.0);
+    let (duration, set_duration) = create_signal(0.0);
+    let (buffered, set_buffered) = create_signal(Vec::<(f64, f64)>::new());
+    let (volume, set_volume) = create_signal(1.0);
+    let (muted, set_muted) = create_signal(false);
+    let (rate, set_rate) = create_signal(1.0);
+    let (seeking, set_seeking) = create_signal(false);
+    let (waiting, set_waiting) = create_signal(false);
+    let (ended, set_ended) = create_signal(false);
+    let (tracks, set_tracks) = create_signal(Vec::<UseMediaTextTrack>::new());
+
+    let text_tracks = Signal::derive(move || media.get().and_then(|media| media.text_tracks()));
+
+    let update_tracks = move || {
+        set_tracks.set(
+            text_tracks
+                .get_untracked()
+                .map(|list| {
+                    (0..list.length())
+                        .filter_map(|index| {
+                            list.get(index)
+                                .map(|track| UseMediaTextTrack::new(index, &track))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        )
+    };
+
+    let update_buffered = move |media: &HtmlMediaElement| {
+        let ranges = media.buffered();
+
+        set_buffered.set(
+            (0..ranges.length())
+                .filter_map(|index| Some((ranges.start(index).ok()?, ranges.end(index).ok()?)))
+                .collect(),
+        );
+    };
+
+    let _ = watch(
+        move || media.get(),
+        move |media, _, _| {
+            if let Some(media) = media {
+                set_playing.set(!media.paused());
+                set_current_time.set(media.current_time());
+                set_duration.set(media.duration());
+                update_buffered(media);
+                set_volume.set(media.volume());
+                set_muted.set(media.muted());
+                set_rate.set(media.playback_rate());
+                set_seeking.set(media.seeking());
+                set_ended.set(media.ended());
+            }
+
+            update_tracks();
+        },
+        true,
+    );
+
+    on_media_event(media, &["timeupdate"], move |media| {
+        set_current_time.set(media.current_time())
+    });
+
+    on_media_event(media, &["durationchange", "loadedmetadata"], move |media| {
+        set_duration.set(media.duration())
+    });
+
+    on_media_event(media, &["progress", "loadeddata"], update_buffered);
+
+    on_media_event(media, &["volumechange"], move |media| {
+        set_volume.set(media.volume());
+        set_muted.set(media.muted());
+    });
+
+    on_media_event(media, &["ratechange"], move |media| {
+        set_rate.set(media.playback_rate())
+    });
+
+    on_media_event(
+        media,
+        &["play", "pause", "ended", "emptied"],
+        move |media| {
+            set_playing.set(!media.paused());
+            set_ended.set(media.ended());
+        },
+    );
+
+    on_media_event(media, &["seeking", "seeked"], move |media| {
+        set_seeking.set(media.seeking())
+    });
+
+    on_media_event(media, &["waiting"], move |_| set_waiting.set(true));
+
+    on_media_event(
+        media,
+        &["playing", "canplay", "pause", "emptied"],
+        move |_| set_waiting.set(false),
+    );
+
+    for event in ["addtrack", "removetrack", "change"] {
+        let _ = use_event_listener(
+            text_tracks,
+            ev::Custom::<web_sys::Event>::new(event),
+            move |_| update_tracks(),
+        );
+    }
+
+    // The signals are also updated from the events of the element. Only if they differ from the
+    // element the change has been made by the user.
+    let _ = watch(
+        move || playing.get(),
+        move |playing, _, _| {
+            let Some(media) = media.get_untracked() else {
+                return;
+            };
+
+            if *playing && media.paused() {
+                match media.play() {
+                    Ok(promise) => spawn_local(async move {
+                        if js_fut!(promise).await.is_err() {
+                            set_playing.set(false);
+                        }
+                    }),
+                    Err(_) => set_playing.set(false),
+                }
+            } else if !*playing && !media.paused() {
+                let _ = media.pause();
+            }
+        },
+        false,
+    );
+
+    let _ = watch(
+        move || current_time.get(),
+        move |current_time, _, _| {
+            if let Some(media) = media.get_untracked() {
+                if media.current_time() != *current_time {
+                    media.set_current_time(*current_time);
+                }
+            }
+        },
+        false,
+    );
+
+    let _ = watch(
+        move || volume.get(),
+        move |volume, _, _| {
+            if let Some(media) = media.get_untracked() {
+                if media.volume() != *volume {
+                    media.set_volume(*volume);
+                }
+            }
+        },
+        false,
+    );
+
+    let _ = watch(
+        move || muted.get(),
+        move |muted, _, _| {
+            if let Some(media) = media.get_untracked() {
+                if media.muted() != *muted {
+                    media.set_muted(*muted);
+                }
+            }
+        },
+        false,
+    );
+
+    let _ = watch(
+        move || rate.get(),
+        move |rate, _, _| {
+            if let Some(media) = media.get_untracked() {
+                if media.playback_rate() != *rate {
+                    media.set_playback_rate(*rate);
+                }
+            }
+        },
+        false,
+    );
+
+    let selected_track = Signal::derive(move || {
+        tracks.with(|tracks| {
+            tracks
+                .iter()
+                .find(|track| track.mode == TextTrackMode::Showing)
+                .map(|track| track.index)
+        })
+    });
+
+    UseMediaControlsReturn {
+        playing: playing.into(),
+        set_playing,
+        current_time: current_time.into(),
+        set_current_time,
+        duration: duration.into(),
+        buffered: buffered.into(),
+        volume: volume.into(),
+        set_volume,
+        muted: muted.into(),
+        set_muted,
+        rate: rate.into(),
+        set_rate,
+        seeking: seeking.into(),
+        waiting: waiting.into(),
+        ended: ended.into(),
+        tracks: tracks.into(),
+        selected_track,
+        text_tracks,
+    }
+}
+
+fn on_media_event(
+    media: Signal<Option<HtmlMediaElement>>,
+    events: &[&'static str],
+    handler: impl Fn(&HtmlMediaElement) + Clone + 'static,
+) {
+    for event in events {
+        let handler = handler.clone();
+
+        let _ = use_event_listener(
+            media,
+            ev::Custom::<web_sys::Event>::new(*event),
+            move |_| {
+                if let Some(media) = media.get_untracked() {
+                    handler(&media);
+                }
+            },
+        );
+    }
+}
+
+/// A text track of the media element like subtitles or captions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UseMediaTextTrack {
+    /// The position of the track in the element
+    pub index: u32,
+    pub id: String,
+    pub label: String,
+    pub language: String,
+    pub kind: TextTrackKind,
+    pub mode: TextTrackMode,
+}
+
+impl UseMediaTextTrack {
+    fn new(index: u32, track: &TextTrack) -> Self {
+        Self {
+            index,
+            id: track.id(),
+            label: track.label(),
+            language: track.language(),
+            kind: track.kind(),
+            mode: track.mode(),
+        }
+    }
+}
+
+/// Return type of [`use_media_controls`].
+#[derive(Clone, Copy)]
+pub struct UseMediaControlsReturn {
+    /// Whether the media is playing
+    pub playing: Signal<bool>,
+
+    /// Plays or pauses the media
+    pub set_playing: WriteSignal<bool>,
+
+    /// The playback position in seconds
+    pub current_time: Signal<f64>,
+
+    /// Seeks to the position in seconds
+    pub set_current_time: WriteSignal<f64>,
+
+    /// The length of the media in seconds. `NaN` until it's known and infinite for streams.
+    pub duration: Signal<f64>,
+
+    /// The ranges of the media that have been loaded as `(start, end)` in seconds
+    pub buffered: Signal<Vec<(f64, f64)>>,
+
+    /// The volume between `0.0` and `1.0`
+    pub volume: Signal<f64>,
+
+    /// Sets the volume
+    pub set_volume: WriteSignal<f64>,
+
+    /// Whether the media is muted
+    pub muted: Signal<bool>,
+
+    /// Mutes or unmutes the media
+    pub set_muted: WriteSignal<bool>,
+
+    /// The playback rate where `1.0` is the normal speed
+    pub rate: Signal<f64>,
+
+    /// Sets the playback rate
+    pub set_rate: WriteSignal<f64>,
+
+    /// Whether the media is seeking to a new position
+    pub seeking: Signal<bool>,
+
+    /// Whether playback has stopped because the next frame isn't loaded yet
+    pub waiting: Signal<bool>,
+
+    /// Whether the media has played to the end
+    pub ended: Signal<bool>,
+
+    /// The text tracks of the media
+    pub tracks: Signal<Vec<UseMediaTextTrack>>,
+
+    /// The index of the track that is shown
+    pub selected_track: Signal<Option<u32>>,
+
+    text_tracks: Signal<Option<TextTrackList>>,
+}
+
+impl UseMediaControlsReturn {
+    /// Shows the text track at `index` and hides all other subtitles and captions.
+    pub fn enable_track(&self, index: u32) {
+        self.for_each_track(|track_index, track| {
+            if track_index == index {
+                track.set_mode(TextTrackMode::Showing);
+            } else if matches!(
+                track.kind(),
+                TextTrackKind::Subtitles | TextTrackKind::Captions
+            ) {
+                track.set_mode(TextTrackMode::Disabled);
+            }
+        });
+    }
+
+    /// Hides all text tracks.
+    pub fn disable_tracks(&self) {
+        self.for_each_track(|_, track| track.set_mode(TextTrackMode::Disabled));
+    }
+
+    fn for_each_track(&self, f: impl Fn(u32, &TextTrack)) {
+        if let Some(list) = self.text_tracks.get_untracked() {
+            for index in 0..list.length() {
+                if let Some(track) = list.get(index) {
+                    f(index, &track);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use crate::{js, js_fut, use_event_listener, use_permission, use_supported, PermissionState};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{MediaDeviceInfo, MediaDeviceKind};
+
+/// Reactive [`mediaDevices.enumerateDevices`](https://developer.mozilla.org/en-US/docs/Web/API/MediaDevices/enumerateDevices).
+/// Lists the available cameras, microphones and speakers.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_media_devices;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let devices = use_media_devices();
+///
+/// let ask = move |_| {
+///     spawn_local(async move {
+///         let _ = devices.ensure_permissions().await;
+///     });
+/// };
+///
+/// view! {
+///     <button on:click=ask>"Show device names"</button>
+///     <select>
+///         <For each=move || devices.video_inputs.get() key=|device| device.device_id() let:device>
+///             <option value=device.device_id()>{device.label()}</option>
+///         </For>
+///     </select>
+/// }
+/// # }
+/// ```
+///
+/// The lists are updated whenever a device is plugged in or removed.
+///
+/// Browsers only report the labels of the devices after the user granted access to the camera
+/// or microphone. Call `ensure_permissions` to ask for it. The lists are also enumerated again
+/// when the permission is granted in any other way, for example by [`fn@crate::use_user_media`].
+/// Set `request_permissions` to `true` to ask right away.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_media_devices_with_options, UseMediaDevicesOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let devices = use_media_devices_with_options(
+///     UseMediaDevicesOptions::default()
+///         .request_permissions(true)
+///         .video(false),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server all lists are empty and `ensure_permissions` returns
+/// `UseMediaDevicesError::NotSupported`.
+pub fn use_media_devices() -> UseMediaDevicesReturn {
+    use_media_devices_with_options(UseMediaDevicesOptions::default())
+}
+
+/// Version of [`use_media_devices`] that takes a `UseMediaDevicesOptions`. See [`use_media_devices`] for how to use.
+pub fn use_media_devices_with_options(options: UseMediaDevicesOptions) -> UseMediaDevicesReturn {
+    let UseMediaDevicesOptions {
+        request_permissions,
+        audio,
+        video,
+    } = options;
+
+    let is_supported = use_supported(|| {
+        js!("mediaDevices" in &window().navigator())
+            && window()
+                .navigator()
+                .media_devices()
+                .is_ok_and(|media_devices| js!("enumerateDevices" in &media_devices))
+    });
+
+    let (devices, set_devices) = create_signal(Vec::<MediaDeviceInfo>::new());
+    let (permission_granted, set_permission_granted) = create_signal(false);
+    let (error, set_error) = create_signal(None::<UseMediaDevicesError>);
+
+    let of_kind = move |kind: MediaDeviceKind| {
+        Signal::derive(move || {
+            devices.with(|devices| {
+                devices
+                    .iter()
+                    .filter(|device| device.kind() == kind)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+        })
+    };
+
+    let ret = UseMediaDevicesReturn {
+        is_supported,
+        devices: devices.into(),
+        video_inputs: of_kind(MediaDeviceKind::Videoinput),
+        audio_inputs: of_kind(MediaDeviceKind::Audioinput),
+        audio_outputs: of_kind(MediaDeviceKind::Audiooutput),
+        permission_granted: permission_granted.into(),
+        error: error.into(),
+        set_devices,
+        set_permission_granted,
+        set_error,
+        audio,
+        video,
+    };
+
+    ret.refresh();
+
+    let media_devices = if is_supported.get_untracked() {
+        window().navigator().media_devices().ok()
+    } else {
+        None
+    };
+
+    let _ = use_event_listener(
+        media_devices,
+        ev::Custom::<web_sys::Event>::new("devicechange"),
+        move |_| ret.refresh(),
+    );
+
+    let camera = use_permission("camera");
+    let microphone = use_permission("microphone");
+
+    let _ = watch(
+        move || {
+            (video && camera.get() == PermissionState::Granted)
+                || (audio && microphone.get() == PermissionState::Granted)
+        },
+        move |granted, _, _| {
+            if *granted {
+                set_permission_granted.set(true);
+                ret.refresh();
+            }
+        },
+        false,
+    );
+
+    if request_permissions && is_supported.get_untracked() {
+        spawn_local(async move {
+            let _ = ret.ensure_permissions().await;
+        });
+    }
+
+    ret
+}
+
+/// Options for [`use_media_devices_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseMediaDevicesOptions {
+    /// Ask for the permissions right away. Defaults to `false`.
+    request_permissions: bool,
+
+    /// Ask for the permission to access the microphone in `ensure_permissions`. Defaults to `true`.
+    audio: bool,
+
+    /// Ask for the permission to access the camera in `ensure_permissions`. Defaults to `true`.
+    video: bool,
+}
+
+impl Default for UseMediaDevicesOptions {
+    fn default() -> Self {
+        Self {
+            request_permissions: false,
+            audio: true,
+            video: true,
+        }
+    }
+}
+
+/// Return type of [`use_media_devices`].
+#[derive(Clone, Copy)]
+pub struct UseMediaDevicesReturn {
+    /// Whether `enumerateDevices` is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// All media devices
+    pub devices: Signal<Vec<MediaDeviceInfo>>,
+
+    /// The cameras
+    pub video_inputs: Signal<Vec<MediaDeviceInfo>>,
+
+    /// The microphones
+    pub audio_inputs: Signal<Vec<MediaDeviceInfo>>,
+
+    /// The speakers and headphones
+    pub audio_outputs: Signal<Vec<MediaDeviceInfo>>,
+
+    /// Whether the user granted access to the devices. Only then the devices have labels.
+    pub permission_granted: Signal<bool>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseMediaDevicesError>>,
+
+    set_devices: WriteSignal<Vec<MediaDeviceInfo>>,
+    set_permission_granted: WriteSignal<bool>,
+    set_error: WriteSignal<Option<UseMediaDevicesError>>,
+    audio: bool,
+    video: bool,
+}
+
+impl UseMediaDevicesReturn {
+    /// Asks for the permission to access the camera and microphone and enumerates the devices
+    /// again. Resolves right away if the permission has already been granted.
+    pub async fn ensure_permissions(&self) -> Result<(), UseMediaDevicesError> {
+        if self.permission_granted.get_untracked() {
+            return Ok(());
+        }
+
+        let media_devices = self.media_devices()?;
+
+        let constraints = web_sys::MediaStreamConstraints::new();
+        constraints.set_audio_bool(self.audio);
+        constraints.set_video_bool(self.video);
+
+        let promise = media_devices
+            .get_user_media_with_constraints(&constraints)
+            .map_err(|err| self.fail(UseMediaDevicesError::PermissionDenied(err)))?;
+
+        let stream: web_sys::MediaStream = js_fut!(promise)
+            .await
+            .map_err(|err| self.fail(UseMediaDevicesError::PermissionDenied(err)))?
+            .unchecked_into();
+
+        // the stream was only needed to trigger the permission prompt
+        for track in stream.get_tracks() {
+            track.unchecked_ref::<web_sys::MediaStreamTrack>().stop();
+        }
+
+        self.set_error.set(None);
+        self.set_permission_granted.set(true);
+        self.refresh();
+
+        Ok(())
+    }
+
+    /// Enumerates the devices again.
+    pub fn refresh(&self) {
+        let Ok(media_devices) = self.media_devices() else {
+            return;
+        };
+
+        let set_devices = self.set_devices;
+        let set_error = self.set_error;
+
+        spawn_local(async move {
+            let result = match media_devices.enumerate_devices() {
+                Ok(promise) => js_fut!(promise).await,
+                Err(err) => Err(err),
+            };
+
+            match result {
+                Ok(devices) => set_devices.set(
+                    devices
+                        .unchecked_into::<js_sys::Array>()
+                        .iter()
+                        .map(JsCast::unchecked_into)
+                        .collect(),
+                ),
+                Err(err) => set_error.set(Some(UseMediaDevicesError::Enumerate(err))),
+            }
+        });
+    }
+
+    fn media_devices(&self) -> Result<web_sys::MediaDevices, UseMediaDevicesError> {
+        if !self.is_supported.get_untracked() {
+            return Err(UseMediaDevicesError::NotSupported);
+        }
+
+        window()
+            .navigator()
+            .media_devices()
+            .map_err(|_| UseMediaDevicesError::NotSupported)
+    }
+
+    fn fail(&self, err: UseMediaDevicesError) -> UseMediaDevicesError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// Errors of [`use_media_devices`].
+#[derive(Error, Debug, Clone)]
+pub enum UseMediaDevicesError {
+    #[error("enumerating media devices is not supported")]
+    NotSupported,
+    #[error("the permission to access the media devices has been denied")]
+    PermissionDenied(JsValue),
+    #[error("enumerating the media devices failed")]
+    Enumerate(JsValue),
+}
@@ -0,0 +1,389 @@
+use crate::{js, use_event_listener, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::JsValue;
+use web_sys::{Blob, BlobEvent, MediaRecorder, MediaStream, RecordingState};
+
+/// Reactive [MediaRecorder](https://developer.mozilla.org/en-US/docs/Web/API/MediaRecorder).
+/// Records a `MediaStream`, for example from [`fn@crate::use_user_media`] or
+/// [`fn@crate::use_display_media`].
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_media_recorder, use_user_media, UseUserMediaReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseUserMediaReturn { stream, start, .. } = use_user_media();
+///
+/// let recorder = use_media_recorder(stream);
+///
+/// start();
+///
+/// let download_url = move || {
+///     recorder
+///         .blob
+///         .get()
+///         .and_then(|blob| web_sys::Url::create_object_url_with_blob(&blob).ok())
+/// };
+///
+/// view! {
+///     <button on:click=move |_| { let _ = recorder.start(); }>"Record"</button>
+///     <button on:click=move |_| { let _ = recorder.pause(); }>"Pause"</button>
+///     <button on:click=move |_| { let _ = recorder.resume(); }>"Resume"</button>
+///     <button on:click=move |_| { let _ = recorder.stop(); }>"Stop"</button>
+///     <p>"State: " {move || format!("{:?}", recorder.state.get())}</p>
+///     <a href=download_url download="recording.webm">"Download"</a>
+/// }
+/// # }
+/// ```
+///
+/// While recording the data is collected in `chunks`. Once the recording is stopped all chunks
+/// are combined into a single `Blob` that is available in `blob`. Starting a new recording
+/// clears both.
+///
+/// ## Timeslice and mime type
+///
+/// By default the browser delivers all data at once when the recording stops. With `timeslice`
+/// a chunk is delivered every `timeslice` ms which is useful if you want to upload the recording
+/// while it's still going. Every chunk is passed to `on_data_available`.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_media_recorder_with_options, use_user_media, UseMediaRecorderOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// # let stream = use_user_media().stream;
+/// #
+/// let recorder = use_media_recorder_with_options(
+///     stream,
+///     UseMediaRecorderOptions::default()
+///         .mime_type("video/webm;codecs=vp9")
+///         .timeslice(1000)
+///         .on_data_available(|chunk: web_sys::Blob| {
+///             logging::log!("received {} bytes", chunk.size());
+///         }),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server `state` is always `RecordingState::Inactive` and all operations return
+/// `UseMediaRecorderError::NotSupported`.
+pub fn use_media_recorder(
+    stream: impl Into<MaybeSignal<Option<MediaStream>>>,
+) -> UseMediaRecorderReturn {
+    use_media_recorder_with_options(stream, UseMediaRecorderOptions::default())
+}
+
+/// Version of [`use_media_recorder`] that takes a `UseMediaRecorderOptions`. See [`use_media_recorder`] for how to use.
+pub fn use_media_recorder_with_options(
+    stream: impl Into<MaybeSignal<Option<MediaStream>>>,
+    options: UseMediaRecorderOptions,
+) -> UseMediaRecorderReturn {
+    let UseMediaRecorderOptions {
+        mime_type,
+        timeslice,
+        audio_bits_per_second,
+        video_bits_per_second,
+        on_data_available,
+    } = options;
+
+    let stream = stream.into();
+
+    let is_supported = use_supported(|| js!("MediaRecorder" in &window()));
+
+    let (recorder, set_recorder) = create_signal(None::<MediaRecorder>);
+    let (state, set_state) = create_signal(RecordingState::Inactive);
+    let (chunks, set_chunks) = create_signal(Vec::<Blob>::new());
+    let (blob, set_blob) = create_signal(None::<Blob>);
+    let (error, set_error) = create_signal(None::<UseMediaRecorderError>);
+
+    let ret = UseMediaRecorderReturn {
+        is_supported,
+        state: state.into(),
+        chunks: chunks.into(),
+        blob: blob.into(),
+        error: error.into(),
+        stream: Signal::derive(move || stream.get()),
+        recorder,
+        set_recorder,
+        set_chunks,
+        set_blob,
+        set_error,
+        options: store_value(RecorderOptions {
+            mime_type,
+            timeslice,
+            audio_bits_per_second,
+            video_bits_per_second,
+        }),
+    };
+
+    let update_state = move || {
+        if let Some(recorder) = recorder.get_untracked() {
+            set_state.set(recorder.state());
+        }
+    };
+
+    for event in ["start", "pause", "resume"] {
+        let _ = use_event_listener(
+            recorder,
+            ev::Custom::<web_sys::Event>::new(event),
+            move |_| update_state(),
+        );
+    }
+
+    let _ = use_event_listener(
+        recorder,
+        ev::Custom::<BlobEvent>::new("dataavailable"),
+        move |event| {
+            let Some(chunk) = event.data() else {
+                return;
+            };
+
+            if chunk.size() == 0.0 {
+                return;
+            }
+
+            set_chunks.update(|chunks| chunks.push(chunk.clone()));
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_data_available(chunk);
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        },
+    );
+
+    let _ = use_event_listener(
+        recorder,
+        ev::Custom::<web_sys::Event>::new("stop"),
+        move |_| {
+            update_state();
+
+            let Some(recorder) = recorder.get_untracked() else {
+                return;
+            };
+
+            let parts = chunks.with_untracked(|chunks| chunks.iter().collect::<js_sys::Array>());
+
+            let options = web_sys::BlobPropertyBag::new();
+            options.set_type(&recorder.mime_type());
+
+            match Blob::new_with_blob_sequence_and_options(&parts, &options) {
+                Ok(recording) => set_blob.set(Some(recording)),
+                Err(err) => set_error.set(Some(UseMediaRecorderError::Failed(err))),
+            }
+        },
+    );
+
+    let _ = use_event_listener(
+        recorder,
+        ev::Custom::<web_sys::Event>::new("error"),
+        move |event| {
+            update_state();
+
+            let err = js!(event["error"]).unwrap_or(JsValue::UNDEFINED);
+            set_error.set(Some(UseMediaRecorderError::Failed(err)));
+        },
+    );
+
+    on_cleanup(move || {
+        let _ = ret.stop();
+    });
+
+    ret
+}
+
+/// Options for [`use_media_recorder_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseMediaRecorderOptions {
+    /// The mime type of the recording like `"video/webm;codecs=vp9"`. Use
+    /// `MediaRecorder::is_type_supported` to check if the browser supports it. Defaults to `None`
+    /// which lets the browser choose.
+    #[builder(into)]
+    mime_type: Option<String>,
+
+    /// If set, a chunk of data is delivered every `timeslice` ms. Otherwise all data is delivered
+    /// when the recording stops. Defaults to `None`.
+    #[builder(into)]
+    timeslice: Option<i32>,
+
+    /// The bitrate of the audio track. Defaults to `None` which lets the browser choose.
+    #[builder(into)]
+    audio_bits_per_second: Option<u32>,
+
+    /// The bitrate of the video track. Defaults to `None` which lets the browser choose.
+    #[builder(into)]
+    video_bits_per_second: Option<u32>,
+
+    /// Called with every chunk of recorded data.
+    on_data_available: Rc<dyn Fn(Blob)>,
+}
+
+impl Default for UseMediaRecorderOptions {
+    fn default() -> Self {
+        Self {
+            mime_type: None,
+            timeslice: None,
+            audio_bits_per_second: None,
+            video_bits_per_second: None,
+            on_data_available: Rc::new(|_| {}),
+        }
+    }
+}
+
+struct RecorderOptions {
+    mime_type: Option<String>,
+    timeslice: Option<i32>,
+    audio_bits_per_second: Option<u32>,
+    video_bits_per_second: Option<u32>,
+}
+
+/// Return type of [`use_media_recorder`].
+#[derive(Clone, Copy)]
+pub struct UseMediaRecorderReturn {
+    /// Whether the MediaRecorder API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The state of the recorder
+    pub state: Signal<RecordingState>,
+
+    /// The chunks of data recorded so far
+    pub chunks: Signal<Vec<Blob>>,
+
+    /// The whole recording once it has been stopped
+    pub blob: Signal<Option<Blob>>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseMediaRecorderError>>,
+
+    stream: Signal<Option<MediaStream>>,
+    recorder: ReadSignal<Option<MediaRecorder>>,
+    set_recorder: WriteSignal<Option<MediaRecorder>>,
+    set_chunks: WriteSignal<Vec<Blob>>,
+    set_blob: WriteSignal<Option<Blob>>,
+    set_error: WriteSignal<Option<UseMediaRecorderError>>,
+    options: StoredValue<RecorderOptions>,
+}
+
+impl UseMediaRecorderReturn {
+    /// Starts a new recording of the current stream. Does nothing if it's already recording.
+    pub fn start(&self) -> Result<(), UseMediaRecorderError> {
+        if !self.is_supported.get_untracked() {
+            return Err(UseMediaRecorderError::NotSupported);
+        }
+
+        if self.state.get_untracked() != RecordingState::Inactive {
+            return Ok(());
+        }
+
+        let stream = self
+            .stream
+            .get_untracked()
+            .ok_or_else(|| self.fail(UseMediaRecorderError::NoStream))?;
+
+        let recorder_options = web_sys::MediaRecorderOptions::new();
+        self.options.with_value(|options| {
+            if let Some(mime_type) = &options.mime_type {
+                recorder_options.set_mime_type(mime_type);
+            }
+            if let Some(audio_bits_per_second) = options.audio_bits_per_second {
+                recorder_options.set_audio_bits_per_second(audio_bits_per_second);
+            }
+            if let Some(video_bits_per_second) = options.video_bits_per_second {
+                recorder_options.set_video_bits_per_second(video_bits_per_second);
+            }
+        });
+
+        let recorder = MediaRecorder::new_with_media_stream_and_media_recorder_options(
+            &stream,
+            &recorder_options,
+        )
+        .map_err(|err| self.fail(UseMediaRecorderError::Failed(err)))?;
+
+        self.set_chunks.set(vec![]);
+        self.set_blob.set(None);
+        self.set_error.set(None);
+        self.set_recorder.set(Some(recorder.clone()));
+
+        match self.options.with_value(|options| options.timeslice) {
+            Some(timeslice) => recorder.start_with_time_slice(timeslice),
+            None => recorder.start(),
+        }
+        .map_err(|err| self.fail(UseMediaRecorderError::Failed(err)))
+    }
+
+    /// Pauses the recording.
+    pub fn pause(&self) -> Result<(), UseMediaRecorderError> {
+        match self.active_recorder() {
+            Some(recorder) if recorder.state() == RecordingState::Recording => recorder
+                .pause()
+                .map_err(|err| self.fail(UseMediaRecorderError::Failed(err))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Resumes a paused recording.
+    pub fn resume(&self) -> Result<(), UseMediaRecorderError> {
+        match self.active_recorder() {
+            Some(recorder) if recorder.state() == RecordingState::Paused => recorder
+                .resume()
+                .map_err(|err| self.fail(UseMediaRecorderError::Failed(err))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Stops the recording. The whole recording is available in `blob` afterwards.
+    pub fn stop(&self) -> Result<(), UseMediaRecorderError> {
+        match self.active_recorder() {
+            Some(recorder) => recorder
+                .stop()
+                .map_err(|err| self.fail(UseMediaRecorderError::Failed(err))),
+            None => Ok(()),
+        }
+    }
+
+    /// Asks the recorder to deliver the data recorded so far as a chunk.
+    pub fn request_data(&self) -> Result<(), UseMediaRecorderError> {
+        match self.active_recorder() {
+            Some(recorder) => recorder
+                .request_data()
+                .map_err(|err| self.fail(UseMediaRecorderError::Failed(err))),
+            None => Ok(()),
+        }
+    }
+
+    fn active_recorder(&self) -> Option<MediaRecorder> {
+        self.recorder
+            .try_get_untracked()
+            .flatten()
+            .filter(|recorder| recorder.state() != RecordingState::Inactive)
+    }
+
+    fn fail(&self, err: UseMediaRecorderError) -> UseMediaRecorderError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// Errors of [`use_media_recorder`].
+#[derive(Error, Debug, Clone)]
+pub enum UseMediaRecorderError {
+    #[error("MediaRecorder is not supported")]
+    NotSupported,
+    #[error("there is no stream to record")]
+    NoStream,
+    #[error("recording failed")]
+    Failed(JsValue),
+}
@@ -0,0 +1,421 @@
+use crate::{js, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    MediaImage, MediaMetadata, MediaPositionState, MediaSession, MediaSessionAction,
+    MediaSessionActionDetails, MediaSessionPlaybackState,
+};
+
+/// Reactive [Media Session API](https://developer.mozilla.org/en-US/docs/Web/API/Media_Session_API).
+/// Shows what is playing in the media controls of the operating system and reacts to its
+/// buttons.
+///
+/// > This function requires `--cfg=web_sys_unstable_apis` to be activated as
+/// > [described in the wasm-bindgen guide](https://rustwasm.github.io/docs/wasm-bindgen/web-sys/unstable-apis.html).
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{
+/// #     use_media_session_with_options, MediaSessionArtwork, MediaSessionMetadata,
+/// #     UseMediaSessionOptions,
+/// # };
+/// # use web_sys::{MediaSessionAction, MediaSessionPlaybackState};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (track, set_track) = create_signal(0);
+/// let (playing, set_playing) = create_signal(false);
+///
+/// let session = use_media_session_with_options(
+///     UseMediaSessionOptions::default()
+///         .metadata(Signal::derive(move || {
+///             Some(MediaSessionMetadata {
+///                 title: format!("Track {}", track.get() + 1),
+///                 artist: "Leptos".to_string(),
+///                 artwork: vec![MediaSessionArtwork::new("cover.png").sizes("512x512")],
+///                 ..Default::default()
+///             })
+///         }))
+///         .playback_state(Signal::derive(move || {
+///             if playing.get() {
+///                 MediaSessionPlaybackState::Playing
+///             } else {
+///                 MediaSessionPlaybackState::Paused
+///             }
+///         })),
+/// );
+///
+/// let _ = session.set_action_handler(MediaSessionAction::Play, move |_| set_playing.set(true));
+/// let _ = session.set_action_handler(MediaSessionAction::Pause, move |_| set_playing.set(false));
+/// let _ = session.set_action_handler(MediaSessionAction::Nexttrack, move |_| {
+///     set_track.update(|track| *track += 1)
+/// });
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// The metadata, playback state and position are updated whenever the signals change. Action
+/// handlers receive the `MediaSessionActionDetails` which contain the `seek_time` for
+/// `MediaSessionAction::Seekto` and the `seek_offset` for `Seekbackward` and `Seekforward`.
+/// Setting a handler for an action replaces the previous one. Not every browser supports every
+/// action, in which case `set_action_handler` returns `UseMediaSessionError::UnsupportedAction`.
+///
+/// The position is shown as a progress bar by most operating systems.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_media_session_with_options, MediaSessionPosition, UseMediaSessionOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (current_time, set_current_time) = create_signal(0.0);
+///
+/// let session = use_media_session_with_options(
+///     UseMediaSessionOptions::default().position(Signal::derive(move || {
+///         Some(MediaSessionPosition {
+///             duration: 180.0,
+///             position: current_time.get(),
+///             playback_rate: 1.0,
+///         })
+///     })),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// When the component is unmounted the metadata and all action handlers are removed.
+///
+/// ## Server-Side Rendering
+///
+/// On the server this does nothing and `set_action_handler` returns
+/// `UseMediaSessionError::NotSupported`.
+pub fn use_media_session() -> UseMediaSessionReturn {
+    use_media_session_with_options(UseMediaSessionOptions::default())
+}
+
+/// Version of [`use_media_session`] that takes a `UseMediaSessionOptions`. See [`use_media_session`] for how to use.
+pub fn use_media_session_with_options(options: UseMediaSessionOptions) -> UseMediaSessionReturn {
+    let UseMediaSessionOptions {
+        metadata,
+        playback_state,
+        position,
+    } = options;
+
+    let is_supported = use_supported(|| js!("mediaSession" in &window().navigator()));
+
+    let session = if is_supported.get_untracked() {
+        Some(window().navigator().media_session())
+    } else {
+        None
+    };
+
+    if let Some(session) = session.clone() {
+        let _ = watch(
+            move || metadata.get(),
+            {
+                let session = session.clone();
+
+                move |metadata: &Option<MediaSessionMetadata>, _, _| match metadata {
+                    Some(metadata) => match metadata.to_js() {
+                        Ok(metadata) => session.set_metadata(Some(&metadata)),
+                        Err(err) => {
+                            logging::debug_warn!("Creating the media metadata failed: {err:?}")
+                        }
+                    },
+                    None => session.set_metadata(None),
+                }
+            },
+            true,
+        );
+
+        let _ = watch(
+            move || playback_state.get(),
+            {
+                let session = session.clone();
+                move |playback_state, _, _| session.set_playback_state(*playback_state)
+            },
+            true,
+        );
+
+        let _ = watch(
+            move || position.get(),
+            move |position, _, _| {
+                let result = match position {
+                    Some(position) => session
+                        .unchecked_ref::<FallibleMediaSession>()
+                        .set_position_state(&position.to_js()),
+                    None => session
+                        .unchecked_ref::<FallibleMediaSession>()
+                        .clear_position_state(),
+                };
+
+                if let Err(err) = result {
+                    logging::debug_warn!("Setting the media position state failed: {err:?}");
+                }
+            },
+            true,
+        );
+    }
+
+    let ret = UseMediaSessionReturn {
+        is_supported,
+        session: store_value(session),
+        handlers: store_value(vec![]),
+    };
+
+    on_cleanup(move || {
+        if let Some(Some(session)) = ret.session.try_get_value() {
+            session.set_metadata(None);
+            ret.remove_all_action_handlers();
+        }
+    });
+
+    ret
+}
+
+/// Options for [`use_media_session_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseMediaSessionOptions {
+    /// What is playing right now. Defaults to `None` which shows nothing.
+    #[builder(into)]
+    metadata: MaybeSignal<Option<MediaSessionMetadata>>,
+
+    /// Whether the media is playing. Defaults to `MediaSessionPlaybackState::None` which lets
+    /// the browser infer it from the media elements on the page.
+    #[builder(into)]
+    playback_state: MaybeSignal<MediaSessionPlaybackState>,
+
+    /// The duration, playback position and rate of the media. Defaults to `None`.
+    #[builder(into)]
+    position: MaybeSignal<Option<MediaSessionPosition>>,
+}
+
+impl Default for UseMediaSessionOptions {
+    fn default() -> Self {
+        Self {
+            metadata: None.into(),
+            playback_state: MediaSessionPlaybackState::None.into(),
+            position: None.into(),
+        }
+    }
+}
+
+/// The metadata of the media that is playing. Shown by the media controls of the operating
+/// system.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MediaSessionMetadata {
+    pub title: String,
+    pub artist: String,
+    pub album: String,
+    pub artwork: Vec<MediaSessionArtwork>,
+}
+
+impl MediaSessionMetadata {
+    fn to_js(&self) -> Result<MediaMetadata, JsValue> {
+        let metadata = MediaMetadata::new()?;
+        metadata.set_title(&self.title);
+        metadata.set_artist(&self.artist);
+        metadata.set_album(&self.album);
+        metadata.set_artwork(
+            &self
+                .artwork
+                .iter()
+                .map(MediaSessionArtwork::to_js)
+                .collect::<Vec<_>>(),
+        );
+
+        Ok(metadata)
+    }
+}
+
+/// An image of [`MediaSessionMetadata`] like the cover of an album.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MediaSessionArtwork {
+    /// The URL of the image
+    pub src: String,
+
+    /// The sizes of the image like `"96x96"` or `"128x128 256x256"`
+    pub sizes: Option<String>,
+
+    /// The MIME type of the image like `"image/png"`
+    pub mime_type: Option<String>,
+}
+
+impl MediaSessionArtwork {
+    pub fn new(src: impl Into<String>) -> Self {
+        Self {
+            src: src.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn sizes(mut self, sizes: impl Into<String>) -> Self {
+        self.sizes = Some(sizes.into());
+        self
+    }
+
+    pub fn mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = Some(mime_type.into());
+        self
+    }
+
+    fn to_js(&self) -> MediaImage {
+        let image = MediaImage::new(&self.src);
+
+        if let Some(sizes) = &self.sizes {
+            image.set_sizes(sizes);
+        }
+        if let Some(mime_type) = &self.mime_type {
+            image.set_type(mime_type);
+        }
+
+        image
+    }
+}
+
+/// The playback position of the media. All values are in seconds. The `position` has to be
+/// between `0.0` and `duration`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MediaSessionPosition {
+    pub duration: f64,
+    pub position: f64,
+    pub playback_rate: f64,
+}
+
+impl Default for MediaSessionPosition {
+    fn default() -> Self {
+        Self {
+            duration: 0.0,
+            position: 0.0,
+            playback_rate: 1.0,
+        }
+    }
+}
+
+impl MediaSessionPosition {
+    fn to_js(self) -> MediaPositionState {
+        let state = MediaPositionState::new();
+        state.set_duration(self.duration);
+        state.set_position(self.position);
+        state.set_playback_rate(self.playback_rate);
+        state
+    }
+}
+
+type ActionHandler = Rc<Closure<dyn Fn(MediaSessionActionDetails)>>;
+
+/// Return type of [`use_media_session`].
+#[derive(Clone, Copy)]
+pub struct UseMediaSessionReturn {
+    /// Whether the Media Session API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    session: StoredValue<Option<MediaSession>>,
+    handlers: StoredValue<Vec<(MediaSessionAction, ActionHandler)>>,
+}
+
+impl UseMediaSessionReturn {
+    /// Calls `handler` when the user triggers `action` in the media controls of the operating
+    /// system. Replaces the previous handler of `action`.
+    pub fn set_action_handler(
+        &self,
+        action: MediaSessionAction,
+        handler: impl Fn(MediaSessionActionDetails) + 'static,
+    ) -> Result<(), UseMediaSessionError> {
+        let session = self
+            .session
+            .get_value()
+            .ok_or(UseMediaSessionError::NotSupported)?;
+
+        let handler = Rc::new(Closure::<dyn Fn(MediaSessionActionDetails)>::new(
+            move |details: MediaSessionActionDetails| {
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                handler(details);
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+            },
+        ));
+
+        session
+            .unchecked_ref::<FallibleMediaSession>()
+            .set_action_handler(action, Some(handler.as_ref().as_ref().unchecked_ref()))
+            .map_err(|_| UseMediaSessionError::UnsupportedAction(action))?;
+
+        self.handlers.update_value(|handlers| {
+            handlers.retain(|(other, _)| *other != action);
+            handlers.push((action, handler));
+        });
+
+        Ok(())
+    }
+
+    /// Removes the handler of `action` so the media controls fall back to their default
+    /// behaviour.
+    pub fn remove_action_handler(&self, action: MediaSessionAction) {
+        if let Some(session) = self.session.get_value() {
+            let _ = session
+                .unchecked_ref::<FallibleMediaSession>()
+                .set_action_handler(action, None);
+        }
+
+        self.handlers
+            .update_value(|handlers| handlers.retain(|(other, _)| *other != action));
+    }
+
+    fn remove_all_action_handlers(&self) {
+        let actions = self.handlers.with_value(|handlers| {
+            handlers
+                .iter()
+                .map(|(action, _)| *action)
+                .collect::<Vec<_>>()
+        });
+
+        for action in actions {
+            self.remove_action_handler(action);
+        }
+    }
+}
+
+/// Errors of [`use_media_session`].
+#[derive(Error, Debug, Clone)]
+pub enum UseMediaSessionError {
+    #[error("the media session API is not supported")]
+    NotSupported,
+    #[error("the action {0:?} is not supported by the browser")]
+    UnsupportedAction(MediaSessionAction),
+}
+
+// The bindings of web-sys don't catch the exceptions that are thrown for unsupported actions
+// and invalid positions.
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = MediaSession)]
+    type FallibleMediaSession;
+
+    #[wasm_bindgen(method, catch, js_name = setActionHandler)]
+    fn set_action_handler(
+        this: &FallibleMediaSession,
+        action: MediaSessionAction,
+        handler: Option<&js_sys::Function>,
+    ) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = setPositionState)]
+    fn set_position_state(
+        this: &FallibleMediaSession,
+        state: &MediaPositionState,
+    ) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = setPositionState)]
+    fn clear_position_state(this: &FallibleMediaSession) -> Result<(), JsValue>;
+}
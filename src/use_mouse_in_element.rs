@@ -34,6 +34,9 @@ use std::marker::PhantomData;
 /// # }
 /// ```
 ///
+/// The values are also updated when the page is scrolled or the element is resized or moved
+/// so that `element_x`, `element_y` and `is_outside` stay correct even if the mouse doesn't move.
+///
 /// ## Server-Side Rendering
 ///
 /// On the server this returns simple Signals with the `initial_value` for `x` and `y`,
@@ -68,6 +71,8 @@ where
         ..
     } = options;
 
+    let is_client_coord_type = matches!(coord_type, UseMouseCoordType::Client);
+
     let UseMouseReturn {
         x, y, source_type, ..
     } = use_mouse_with_options(
@@ -100,46 +105,67 @@ where
         let _ = set_element_height;
         let _ = set_outside;
         let _ = target;
+        let _ = is_client_coord_type;
     } else {
-        use crate::use_event_listener;
+        use crate::{use_element_bounding, use_event_listener, UseElementBoundingReturn};
         use leptos::ev::mouseleave;
 
-        let target = target.into();
+        let UseElementBoundingReturn {
+            left,
+            top,
+            width,
+            height,
+            ..
+        } = use_element_bounding(target);
+
         let window = window();
+        let scroll_offset = move || {
+            (
+                window.page_x_offset().unwrap_or_default(),
+                window.page_y_offset().unwrap_or_default(),
+            )
+        };
+
+        // Page coordinates of the pointer change when scrolling, but there is no mouse event.
+        // So the offset at the time of the last pointer update is used to get the current
+        // client coordinates.
+        let scroll_offset_at_pointer = store_value((0.0, 0.0));
 
         let stop = watch(
-            move || (target.get(), x.get(), y.get()),
-            move |(el, x, y), _, _| {
-                if let Some(el) = el {
-                    let el: web_sys::Element = el.clone().into();
-                    let rect = el.get_bounding_client_rect();
-                    let left = rect.left();
-                    let top = rect.top();
-                    let width = rect.width();
-                    let height = rect.height();
-
-                    set_element_position_x.set(left + window.page_x_offset().unwrap_or_default());
-                    set_element_position_y.set(top + window.page_y_offset().unwrap_or_default());
-
-                    set_element_height.set(height);
-                    set_element_width.set(width);
-
-                    let el_x = *x - element_position_x.get_untracked();
-                    let el_y = *y - element_position_y.get_untracked();
-
-                    set_outside.set(
-                        width == 0.0
-                            || height == 0.0
-                            || el_x <= 0.0
-                            || el_y <= 0.0
-                            || el_x > width
-                            || el_y > height,
-                    );
-
-                    if handle_outside || !is_outside.get_untracked() {
-                        set_element_x.set(el_x);
-                        set_element_y.set(el_y);
-                    }
+            move || (left.get(), top.get(), width.get(), height.get(), x.get(), y.get()),
+            move |(left, top, width, height, x, y), prev, _| {
+                let (left, top, width, height) = (*left, *top, *width, *height);
+                let (scroll_x, scroll_y) = scroll_offset();
+
+                if prev.map(|(.., prev_x, prev_y)| (prev_x, prev_y)) != Some((x, y)) {
+                    scroll_offset_at_pointer.set_value((scroll_x, scroll_y));
+                }
+
+                set_element_position_x.set(left + scroll_x);
+                set_element_position_y.set(top + scroll_y);
+
+                set_element_height.set(height);
+                set_element_width.set(width);
+
+                let (el_x, el_y) = if is_client_coord_type {
+                    (*x - left, *y - top)
+                } else {
+                    let (pointer_scroll_x, pointer_scroll_y) = scroll_offset_at_pointer.get_value();
+                    (*x - pointer_scroll_x - left, *y - pointer_scroll_y - top)
+                };
+
+                set_outside.set(
+                    width == 0.0
+                        || height == 0.0
+                        || el_x <= 0.0
+                        || el_y <= 0.0
+                        || el_x > width
+                        || el_y > height,
+                );
+
+                if handle_outside || !is_outside.get_untracked() {
+                    set_element_x.set(el_x);
+                    set_element_y.set(el_y);
                 }
             },
             false,
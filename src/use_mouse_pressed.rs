@@ -0,0 +1,276 @@
+use crate::core::{ElementMaybeSignal, PointerType, Position};
+use crate::{use_event_listener, use_window, UseWindow};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{dragend, drop, pointercancel, pointerdown, pointermove, pointerup};
+use leptos::*;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use web_sys::PointerEvent;
+
+/// Reactive mouse pressing state. Works for touch and pen input as well.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_mouse_pressed, UseMousePressedReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseMousePressedReturn {
+///     pressed,
+///     source_type,
+///     ..
+/// } = use_mouse_pressed();
+///
+/// view! {
+///     <p>{move || pressed.get().then(|| format!("Pressed with {:?}", source_type.get()))}</p>
+/// }
+/// # }
+/// ```
+///
+/// ## Target, Buttons and Origin
+///
+/// Only presses that start on `target` are tracked. The release is listened to on the window so it
+/// is noticed even when the pointer has been dragged outside of the target.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_mouse_pressed_with_options, MouseButton, UseMousePressedOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+///
+/// let mouse_pressed = use_mouse_pressed_with_options(
+///     UseMousePressedOptions::default()
+///         .target(el)
+///         .on_released(|_| leptos::logging::log!("Released")),
+/// );
+///
+/// let is_context_drag = move || mouse_pressed.buttons.get().contains(&MouseButton::Secondary);
+/// let origin = move || mouse_pressed.origin.get().map(|origin| format!("{}, {}", origin.x, origin.y));
+///
+/// view! { <div node_ref=el>"Press me"</div> }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server `pressed` is always `initial_value` and everything else is empty.
+pub fn use_mouse_pressed() -> UseMousePressedReturn {
+    use_mouse_pressed_with_options(UseMousePressedOptions::default())
+}
+
+/// Version of [`use_mouse_pressed`] that takes a `UseMousePressedOptions`. See [`use_mouse_pressed`] for how to use.
+pub fn use_mouse_pressed_with_options<El, T>(
+    options: UseMousePressedOptions<El, T>,
+) -> UseMousePressedReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+{
+    let UseMousePressedOptions {
+        target,
+        pointer_types,
+        drag,
+        initial_value,
+        on_pressed,
+        on_released,
+        ..
+    } = options;
+
+    let (pressed, set_pressed) = create_signal(initial_value);
+    let (buttons, set_buttons) = create_signal(Vec::<MouseButton>::new());
+    let (origin, set_origin) = create_signal(None::<Position>);
+    let (source_type, set_source_type) = create_signal(None::<PointerType>);
+
+    // other pointers, like a hovering mouse during a touch, must not change the press
+    let pressing_pointer_id = store_value(None::<i32>);
+    let is_pressing_pointer =
+        move |event: &PointerEvent| pressing_pointer_id.get_value() == Some(event.pointer_id());
+
+    let _ = use_event_listener(target, pointerdown, move |event| {
+        let pointer_type = event.pointer_type().parse::<PointerType>().ok();
+        if !pointer_type.is_some_and(|pointer_type| pointer_types.contains(&pointer_type)) {
+            return;
+        }
+
+        pressing_pointer_id.set_value(Some(event.pointer_id()));
+        set_pressed.set(true);
+        set_buttons.set(MouseButton::from_buttons(event.buttons()));
+        set_origin.set(Some(Position {
+            x: event.client_x() as f64,
+            y: event.client_y() as f64,
+        }));
+        set_source_type.set(pointer_type);
+
+        #[cfg(debug_assertions)]
+        let prev = SpecialNonReactiveZone::enter();
+
+        on_pressed(event);
+
+        #[cfg(debug_assertions)]
+        SpecialNonReactiveZone::exit(prev);
+    });
+
+    let release = move |event: web_sys::Event| {
+        if !pressed.get_untracked() {
+            return;
+        }
+
+        pressing_pointer_id.set_value(None);
+        set_pressed.set(false);
+        set_buttons.set(Vec::new());
+        set_origin.set(None);
+        set_source_type.set(None);
+
+        #[cfg(debug_assertions)]
+        let prev = SpecialNonReactiveZone::enter();
+
+        on_released(event);
+
+        #[cfg(debug_assertions)]
+        SpecialNonReactiveZone::exit(prev);
+    };
+
+    // pressing or releasing another mouse button while one is already pressed only fires
+    // `pointermove` and not `pointerdown` or `pointerup`
+    let on_buttons_change = {
+        let release = release.clone();
+
+        move |event: PointerEvent| {
+            if !is_pressing_pointer(&event) {
+                return;
+            }
+
+            let new_buttons = MouseButton::from_buttons(event.buttons());
+            if new_buttons.is_empty() {
+                release(event.into());
+            } else if buttons.with_untracked(|buttons| *buttons != new_buttons) {
+                set_buttons.set(new_buttons);
+            }
+        }
+    };
+
+    let _ = use_event_listener(use_window(), pointermove, on_buttons_change.clone());
+    let _ = use_event_listener(use_window(), pointerup, on_buttons_change);
+    let _ = use_event_listener(use_window(), pointercancel, {
+        let release = release.clone();
+
+        move |event| {
+            if is_pressing_pointer(&event) {
+                release(event.into());
+            }
+        }
+    });
+
+    if drag {
+        // the browser doesn't send pointer events anymore once a native drag has started
+        let _ = use_event_listener(use_window(), dragend, {
+            let release = release.clone();
+            move |event| release(event.into())
+        });
+        let _ = use_event_listener(use_window(), drop, move |event| release(event.into()));
+    }
+
+    UseMousePressedReturn {
+        pressed: pressed.into(),
+        buttons: buttons.into(),
+        origin: origin.into(),
+        source_type: source_type.into(),
+    }
+}
+
+/// Options for [`use_mouse_pressed_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseMousePressedOptions<El, T>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+{
+    /// On which element a press has to start. Defaults to `window`.
+    target: El,
+
+    /// Which kinds of pointers can press. Defaults to all pointer types.
+    pointer_types: Vec<PointerType>,
+
+    /// Whether the end of a native drag and drop operation releases the press. Defaults to `true`.
+    drag: bool,
+
+    /// Initial value of `pressed`. Defaults to `false`.
+    initial_value: bool,
+
+    /// Called when the target is pressed.
+    on_pressed: Rc<dyn Fn(PointerEvent)>,
+
+    /// Called when the press is released, wherever this happens.
+    on_released: Rc<dyn Fn(web_sys::Event)>,
+
+    #[builder(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl Default for UseMousePressedOptions<UseWindow, web_sys::Window> {
+    fn default() -> Self {
+        Self {
+            target: use_window(),
+            pointer_types: vec![PointerType::Mouse, PointerType::Touch, PointerType::Pen],
+            drag: true,
+            initial_value: false,
+            on_pressed: Rc::new(|_| {}),
+            on_released: Rc::new(|_| {}),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Return type of [`use_mouse_pressed`].
+#[derive(Clone, Copy)]
+pub struct UseMousePressedReturn {
+    /// Whether the target is pressed
+    pub pressed: Signal<bool>,
+
+    /// The buttons that are pressed. A finger or a pen that touches the screen counts as
+    /// `MouseButton::Main`.
+    pub buttons: Signal<Vec<MouseButton>>,
+
+    /// Where the press started in client coordinates
+    pub origin: Signal<Option<Position>>,
+
+    /// What kind of pointer is pressing
+    pub source_type: Signal<Option<PointerType>>,
+}
+
+/// A button of a mouse. See
+/// [MouseEvent.buttons](https://developer.mozilla.org/en-US/docs/Web/API/MouseEvent/buttons).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    /// Usually the left button
+    Main,
+    /// Usually the right button
+    Secondary,
+    /// Usually the wheel or the middle button
+    Auxiliary,
+    /// The "browser back" button
+    Back,
+    /// The "browser forward" button
+    Forward,
+}
+
+impl MouseButton {
+    fn from_buttons(buttons: u16) -> Vec<Self> {
+        [
+            (1, Self::Main),
+            (2, Self::Secondary),
+            (4, Self::Auxiliary),
+            (8, Self::Back),
+            (16, Self::Forward),
+        ]
+        .into_iter()
+        .filter(|(bit, _)| buttons & bit != 0)
+        .map(|(_, button)| button)
+        .collect()
+    }
+}
@@ -34,7 +34,7 @@ cfg_if! { if #[cfg(not(feature = "ssr"))] {
 ///     el,
 ///     move |mutations, _| {
 ///         if let Some(mutation) = mutations.first() {
-///             set_text.update(|text| *text = format!("{text}\n{:?}", mutation.attribute_name()));
+///             set_text.update(|text| *text = format!("{text}\n{:?}", mutation.attribute_name));
 ///         }
 ///     },
 ///     UseMutationObserverOptions::default().attributes(true),
@@ -46,17 +46,57 @@ cfg_if! { if #[cfg(not(feature = "ssr"))] {
 /// # }
 /// ```
 ///
+/// The callback receives [`MutationRecord`]s. With `attribute_old_value` or
+/// `character_data_old_value` enabled their `old_value` contains the value before the change.
+///
+/// ## Taking Records
+///
+/// Mutations are delivered asynchronously. `take_records` returns the mutations that have
+/// been detected but not yet passed to the callback, for example right before you stop
+/// observing.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Ul;
+/// # use leptos_use::{use_mutation_observer_with_options, UseMutationObserverOptions, UseMutationObserverReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Ul>();
+///
+/// let UseMutationObserverReturn { stop, take_records, .. } = use_mutation_observer_with_options(
+///     el,
+///     move |mutations, _| {
+///         for mutation in mutations {
+///             logging::log!("{} nodes added", mutation.added_nodes.len());
+///         }
+///     },
+///     UseMutationObserverOptions::default().child_list(true).subtree(true),
+/// );
+///
+/// let finish = move |_| {
+///     let pending = take_records();
+///     stop();
+/// };
+///
+/// view! {
+///     <ul node_ref=el></ul>
+///     <button on:click=finish>"Finish"</button>
+/// }
+/// # }
+/// ```
+///
 /// ## Server-Side Rendering
 ///
-/// On the server this amounts to a no-op.
+/// On the server this amounts to a no-op and `take_records` always returns an empty `Vec`.
 pub fn use_mutation_observer<El, T, F>(
     target: El,
     callback: F,
-) -> UseMutationObserverReturn<impl Fn() + Clone>
+) -> UseMutationObserverReturn<impl Fn() + Clone, impl Fn() -> Vec<MutationRecord> + Clone>
 where
     El: Into<ElementsMaybeSignal<T, web_sys::Element>>,
     T: Into<web_sys::Element> + Clone + 'static,
-    F: FnMut(Vec<web_sys::MutationRecord>, web_sys::MutationObserver) + 'static,
+    F: FnMut(Vec<MutationRecord>, web_sys::MutationObserver) + 'static,
 {
     use_mutation_observer_with_options(target, callback, UseMutationObserverOptions::default())
 }
@@ -67,17 +107,18 @@ pub fn use_mutation_observer_with_options<El, T, F>(
     target: El,
     mut callback: F,
     options: UseMutationObserverOptions,
-) -> UseMutationObserverReturn<impl Fn() + Clone>
+) -> UseMutationObserverReturn<impl Fn() + Clone, impl Fn() -> Vec<MutationRecord> + Clone>
 where
     El: Into<ElementsMaybeSignal<T, web_sys::Element>>,
     T: Into<web_sys::Element> + Clone + 'static,
-    F: FnMut(Vec<web_sys::MutationRecord>, web_sys::MutationObserver) + 'static,
+    F: FnMut(Vec<MutationRecord>, web_sys::MutationObserver) + 'static,
 {
     #[cfg(feature = "ssr")]
     {
         UseMutationObserverReturn {
             is_supported: Signal::derive(|| true),
             stop: || {},
+            take_records: Vec::new,
         }
     }
 
@@ -90,14 +131,7 @@ where
                 #[cfg(debug_assertions)]
                 let prev = SpecialNonReactiveZone::enter();
 
-                callback(
-                    entries
-                        .to_vec()
-                        .into_iter()
-                        .map(|v| v.unchecked_into::<web_sys::MutationRecord>())
-                        .collect(),
-                    observer,
-                );
+                callback(records_from_array(&entries), observer);
 
                 #[cfg(debug_assertions)]
                 SpecialNonReactiveZone::exit(prev);
@@ -121,6 +155,18 @@ where
             }
         };
 
+        let take_records = {
+            let observer = Rc::clone(&observer);
+
+            move || {
+                observer
+                    .borrow()
+                    .as_ref()
+                    .map(|observer| records_from_array(&observer.take_records()))
+                    .unwrap_or_default()
+            }
+        };
+
         let targets = target.into();
 
         let stop_watch = {
@@ -155,7 +201,11 @@ where
 
         on_cleanup(stop.clone());
 
-        UseMutationObserverReturn { is_supported, stop }
+        UseMutationObserverReturn {
+            is_supported,
+            stop,
+            take_records,
+        }
     }
 }
 
@@ -175,7 +225,8 @@ pub struct UseMutationObserverOptions {
     /// Set to `true` to watch for changes to the value of attributes on the node or nodes being
     /// monitored. The default value is `true` if either of `attribute_filter` or
     /// `attribute_old_value` is specified, otherwise the default value is `false`.
-    attributes: bool,
+    #[builder(into)]
+    attributes: Option<bool>,
 
     /// An array of specific attribute names to be monitored. If this property isn't included,
     /// changes to all attributes cause mutation notifications.
@@ -218,10 +269,12 @@ impl From<UseMutationObserverOptions> for web_sys::MutationObserverInit {
 
         init.subtree(subtree)
             .child_list(child_list)
-            .attributes(attributes)
             .attribute_old_value(attribute_old_value)
             .character_data_old_value(character_data_old_value);
 
+        if let Some(attributes) = attributes {
+            init.attributes(attributes);
+        }
         if let Some(attribute_filter) = attribute_filter {
             let array = js_sys::Array::from_iter(attribute_filter.into_iter().map(JsValue::from));
             init.attribute_filter(array.unchecked_ref());
@@ -235,9 +288,88 @@ impl From<UseMutationObserverOptions> for web_sys::MutationObserverInit {
 }
 
 /// The return value of [`use_mutation_observer`].
-pub struct UseMutationObserverReturn<F: Fn() + Clone> {
+pub struct UseMutationObserverReturn<StopFn, TakeRecordsFn>
+where
+    StopFn: Fn() + Clone,
+    TakeRecordsFn: Fn() -> Vec<MutationRecord> + Clone,
+{
     /// Whether the browser supports the MutationObserver API
     pub is_supported: Signal<bool>,
     /// A function to stop and detach the MutationObserver
-    pub stop: F,
+    pub stop: StopFn,
+    /// Returns the pending mutations that haven't been passed to the callback yet and removes
+    /// them from the queue of the MutationObserver
+    pub take_records: TakeRecordsFn,
+}
+
+/// A single change to the DOM. See
+/// [MutationRecord](https://developer.mozilla.org/en-US/docs/Web/API/MutationRecord) for details.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MutationRecord {
+    /// The kind of the change
+    pub mutation_type: MutationType,
+    /// The node the mutation affected. For `CharacterData` this is the text node.
+    pub target: web_sys::Node,
+    /// The nodes that have been added for `ChildList` mutations
+    pub added_nodes: Vec<web_sys::Node>,
+    /// The nodes that have been removed for `ChildList` mutations
+    pub removed_nodes: Vec<web_sys::Node>,
+    /// The previous sibling of the added or removed nodes
+    pub previous_sibling: Option<web_sys::Node>,
+    /// The next sibling of the added or removed nodes
+    pub next_sibling: Option<web_sys::Node>,
+    /// The name of the changed attribute for `Attributes` mutations
+    pub attribute_name: Option<String>,
+    /// The namespace of the changed attribute for `Attributes` mutations
+    pub attribute_namespace: Option<String>,
+    /// The value before the change if `attribute_old_value` or `character_data_old_value` is
+    /// enabled
+    pub old_value: Option<String>,
+}
+
+/// The kind of a [`MutationRecord`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MutationType {
+    /// An attribute has been changed
+    Attributes,
+    /// The text of a text node has been changed
+    CharacterData,
+    /// Child nodes have been added or removed
+    ChildList,
+}
+
+impl From<web_sys::MutationRecord> for MutationRecord {
+    fn from(record: web_sys::MutationRecord) -> Self {
+        let nodes = |list: web_sys::NodeList| {
+            (0..list.length())
+                .filter_map(|index| list.item(index))
+                .collect()
+        };
+
+        Self {
+            mutation_type: match record.type_().as_str() {
+                "attributes" => MutationType::Attributes,
+                "characterData" => MutationType::CharacterData,
+                _ => MutationType::ChildList,
+            },
+            target: record
+                .target()
+                .expect("a mutation record always has a target"),
+            added_nodes: nodes(record.added_nodes()),
+            removed_nodes: nodes(record.removed_nodes()),
+            previous_sibling: record.previous_sibling(),
+            next_sibling: record.next_sibling(),
+            attribute_name: record.attribute_name(),
+            attribute_namespace: record.attribute_namespace(),
+            old_value: record.old_value(),
+        }
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+fn records_from_array(records: &js_sys::Array) -> Vec<MutationRecord> {
+    records
+        .iter()
+        .map(|record| record.unchecked_into::<web_sys::MutationRecord>().into())
+        .collect()
 }
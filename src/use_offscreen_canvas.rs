@@ -0,0 +1,370 @@
+use crate::utils::JsCodec;
+use crate::{
+    js, use_device_pixel_ratio, use_element_size, use_event_listener, use_supported,
+    UseElementSizeReturn,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::JsValue;
+use web_sys::{ErrorEvent, HtmlCanvasElement, MessageEvent, Worker, WorkerOptions, WorkerType};
+
+/// Renders a `<canvas>` in a [Web Worker](https://developer.mozilla.org/en-US/docs/Web/API/Web_Workers_API)
+/// with an [OffscreenCanvas](https://developer.mozilla.org/en-US/docs/Web/API/OffscreenCanvas)
+/// so that heavy rendering doesn't block the main thread.
+///
+/// ## Usage
+///
+/// As soon as the canvas is mounted its control is transferred to an `OffscreenCanvas` that is
+/// sent to a new worker started from `worker_url`. Draw commands of type `T` are sent with `post`
+/// and messages of type `R` from the worker are available in `message`. Both are converted with
+/// the given [`JsCodec`].
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Canvas;
+/// # use leptos_use::{use_offscreen_canvas, UseOffscreenCanvasReturn};
+/// # use leptos_use::utils::SerdeWasmCodec;
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// #[derive(Serialize, Deserialize, Clone)]
+/// enum Command {
+///     SetColor(String),
+///     Clear,
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let canvas = create_node_ref::<Canvas>();
+///
+/// let UseOffscreenCanvasReturn { post, .. } =
+///     use_offscreen_canvas::<Command, (), SerdeWasmCodec>(canvas, "render-worker.js");
+///
+/// let set_color = post.clone();
+///
+/// view! {
+///     <canvas node_ref=canvas style="width: 100%; height: 400px"></canvas>
+///     <button on:click=move |_| set_color(&Command::SetColor("tomato".to_string()))>"Red"</button>
+///     <button on:click=move |_| post(&Command::Clear)>"Clear"</button>
+/// }
+/// # }
+/// ```
+///
+/// The worker receives three kinds of messages which can be told apart by their `type`.
+/// `width` and `height` are the size of the canvas element in CSS pixels. Multiply them with
+/// `devicePixelRatio` to get a sharp image. They are sent again whenever the element is resized
+/// or the pixel ratio changes, for example when the window is moved to another screen.
+///
+/// ```js
+/// let canvas, context;
+///
+/// self.onmessage = (event) => {
+///   const message = event.data;
+///
+///   switch (message.type) {
+///     case "init":
+///       canvas = message.canvas;
+///       context = canvas.getContext("2d");
+///     // falls through
+///     case "resize":
+///       canvas.width = message.width * message.devicePixelRatio;
+///       canvas.height = message.height * message.devicePixelRatio;
+///       break;
+///     case "message":
+///       // the draw command that was passed to `post`
+///       draw(message.data);
+///       break;
+///   }
+/// };
+/// ```
+///
+/// The worker is terminated when the component is cleaned up.
+///
+/// ## Server-Side Rendering
+///
+/// On the server no worker is started and `post` does nothing.
+pub fn use_offscreen_canvas<T, R, C>(
+    target: NodeRef<html::Canvas>,
+    worker_url: &str,
+) -> UseOffscreenCanvasReturn<
+    R,
+    <C as JsCodec<R>>::Error,
+    impl Fn(&T) + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    T: 'static,
+    R: 'static,
+    C: JsCodec<T, Error = <C as JsCodec<R>>::Error> + JsCodec<R> + Default,
+{
+    use_offscreen_canvas_with_options(
+        target,
+        worker_url,
+        UseOffscreenCanvasOptions::<R, C>::default(),
+    )
+}
+
+/// Version of [`use_offscreen_canvas`] that takes `UseOffscreenCanvasOptions`. See [`use_offscreen_canvas`] for how to use.
+pub fn use_offscreen_canvas_with_options<T, R, C>(
+    target: NodeRef<html::Canvas>,
+    worker_url: &str,
+    options: UseOffscreenCanvasOptions<R, C>,
+) -> UseOffscreenCanvasReturn<
+    R,
+    <C as JsCodec<R>>::Error,
+    impl Fn(&T) + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    T: 'static,
+    R: 'static,
+    C: JsCodec<T, Error = <C as JsCodec<R>>::Error> + JsCodec<R>,
+{
+    let UseOffscreenCanvasOptions {
+        codec,
+        worker_type,
+        on_message,
+        on_error,
+    } = options;
+
+    let worker_url = worker_url.to_string();
+
+    let is_supported =
+        use_supported(|| js!("OffscreenCanvas" in &window()) && js!("Worker" in &window()));
+
+    let UseElementSizeReturn { width, height } = use_element_size(target);
+    let pixel_ratio = use_device_pixel_ratio();
+
+    let canvas =
+        Signal::derive(move || target.get().map(|canvas| HtmlCanvasElement::clone(&canvas)));
+
+    let (worker, set_worker) = create_signal(None::<Worker>);
+    let (message, set_message) = create_signal(None::<R>);
+    let (error, set_error) =
+        create_signal(None::<UseOffscreenCanvasError<<C as JsCodec<R>>::Error>>);
+
+    let size_message = move |kind: &str| {
+        let message = js_sys::Object::new();
+        js!(message["type"] = kind);
+        js!(message["width"] = width.get_untracked());
+        js!(message["height"] = height.get_untracked());
+        js!(message["devicePixelRatio"] = pixel_ratio.get_untracked());
+        message
+    };
+
+    let terminate = move || {
+        if let Some(Some(worker)) = worker.try_get_untracked() {
+            worker.terminate();
+            set_worker.set(None);
+        }
+    };
+
+    let start = move |canvas: &HtmlCanvasElement| -> Result<Worker, UseOffscreenCanvasError<_>> {
+        let offscreen = canvas
+            .transfer_control_to_offscreen()
+            .map_err(UseOffscreenCanvasError::Transfer)?;
+
+        let worker_options = WorkerOptions::new();
+        worker_options.set_type(worker_type);
+
+        let worker = Worker::new_with_options(&worker_url, &worker_options)
+            .map_err(UseOffscreenCanvasError::Create)?;
+
+        let message = size_message("init");
+        js!(message["canvas"] = &offscreen);
+
+        worker
+            .post_message_with_transfer(&message, &js_sys::Array::of1(&offscreen))
+            .map_err(|err| {
+                worker.terminate();
+                UseOffscreenCanvasError::PostMessage(err)
+            })?;
+
+        Ok(worker)
+    };
+
+    let _ = watch(
+        move || canvas.get(),
+        move |canvas, _, _| {
+            terminate();
+
+            let Some(canvas) = canvas else {
+                return;
+            };
+
+            if !is_supported.get_untracked() {
+                return;
+            }
+
+            match start(canvas) {
+                Ok(worker) => set_worker.set(Some(worker)),
+                Err(err) => set_error.set(Some(err)),
+            }
+        },
+        true,
+    );
+
+    let _ = watch(
+        move || (width.get(), height.get(), pixel_ratio.get()),
+        move |_, _, _| {
+            if let Some(worker) = worker.get_untracked() {
+                if let Err(err) = worker.post_message(&size_message("resize")) {
+                    set_error.set(Some(UseOffscreenCanvasError::PostMessage(err)));
+                }
+            }
+        },
+        false,
+    );
+
+    let _ = use_event_listener(worker, ev::message, {
+        let codec = codec.clone();
+
+        move |event| match JsCodec::<R>::decode(&codec, event.data()) {
+            Ok(val) => {
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                on_message(&val);
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+
+                set_message.set(Some(val));
+            }
+            Err(err) => set_error.set(Some(UseOffscreenCanvasError::Decode(err))),
+        }
+    });
+
+    let _ = use_event_listener(
+        worker,
+        ev::Custom::<MessageEvent>::new("messageerror"),
+        move |event| set_error.set(Some(UseOffscreenCanvasError::MessageEvent(event))),
+    );
+
+    let _ = use_event_listener(
+        worker,
+        ev::Custom::<ErrorEvent>::new("error"),
+        move |event| {
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_error(event.clone());
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+
+            set_error.set(Some(UseOffscreenCanvasError::Worker(event)));
+        },
+    );
+
+    let post = move |val: &T| {
+        if let Some(worker) = worker.get_untracked() {
+            match JsCodec::<T>::encode(&codec, val) {
+                Ok(data) => {
+                    let message = js_sys::Object::new();
+                    js!(message["type"] = "message");
+                    js!(message["data"] = data);
+
+                    worker
+                        .post_message(&message)
+                        .map_err(|err| {
+                            set_error.set(Some(UseOffscreenCanvasError::PostMessage(err)))
+                        })
+                        .ok();
+                }
+                Err(err) => set_error.set(Some(UseOffscreenCanvasError::Encode(err))),
+            }
+        }
+    };
+
+    on_cleanup(terminate);
+
+    UseOffscreenCanvasReturn {
+        is_supported,
+        worker: worker.into(),
+        message: message.into(),
+        error: error.into(),
+        post,
+        terminate,
+    }
+}
+
+/// Options for [`use_offscreen_canvas_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseOffscreenCanvasOptions<R, C>
+where
+    R: 'static,
+    C: JsCodec<R>,
+{
+    /// Encodes the draw commands and decodes the messages from the worker.
+    #[builder(keep_type)]
+    codec: C,
+
+    /// Whether the script is a classic script or an ES module. Defaults to `WorkerType::Classic`.
+    worker_type: WorkerType,
+
+    /// Called for every successfully decoded message from the worker.
+    #[builder(keep_type)]
+    on_message: Rc<dyn Fn(&R)>,
+
+    /// Called when an uncaught error occurs in the worker or the script can't be loaded.
+    on_error: Rc<dyn Fn(ErrorEvent)>,
+}
+
+impl<R, C: JsCodec<R> + Default> Default for UseOffscreenCanvasOptions<R, C> {
+    fn default() -> Self {
+        Self {
+            codec: C::default(),
+            worker_type: WorkerType::Classic,
+            on_message: Rc::new(|_| {}),
+            on_error: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// Return type of [`use_offscreen_canvas`].
+#[derive(Clone)]
+pub struct UseOffscreenCanvasReturn<R, Err, PostFn, TerminateFn>
+where
+    R: 'static,
+    Err: 'static,
+    PostFn: Clone + 'static,
+    TerminateFn: Fn() + Clone + 'static,
+{
+    /// `true` if this browser supports `OffscreenCanvas` and web workers.
+    pub is_supported: Signal<bool>,
+
+    /// The worker that renders the canvas
+    pub worker: Signal<Option<Worker>>,
+
+    /// Latest message received from the worker
+    pub message: Signal<Option<R>>,
+
+    /// Latest error
+    pub error: Signal<Option<UseOffscreenCanvasError<Err>>>,
+
+    /// Sends a draw command to the worker
+    pub post: PostFn,
+
+    /// Stops the worker immediately. The canvas can't be drawn to afterwards.
+    pub terminate: TerminateFn,
+}
+
+#[derive(Debug, Error, Clone)]
+pub enum UseOffscreenCanvasError<Err> {
+    #[error("failed to transfer the canvas to an OffscreenCanvas")]
+    Transfer(JsValue),
+    #[error("failed to create worker")]
+    Create(JsValue),
+    #[error("worker error")]
+    Worker(ErrorEvent),
+    #[error("failed to post message")]
+    PostMessage(JsValue),
+    #[error("worker message error")]
+    MessageEvent(MessageEvent),
+    #[error("failed to encode value")]
+    Encode(Err),
+    #[error("failed to decode value")]
+    Decode(Err),
+}
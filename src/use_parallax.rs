@@ -0,0 +1,254 @@
+use crate::core::ElementMaybeSignal;
+use crate::{
+    use_device_orientation, use_mouse_in_element_with_options, UseDeviceOrientationReturn,
+    UseMouseInElementOptions, UseMouseInElementReturn,
+};
+use cfg_if::cfg_if;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+use web_sys::OrientationType;
+
+/// Create parallax effect easily. It uses [`fn@crate::use_device_orientation`] and falls back to
+/// [`fn@crate::use_mouse_in_element`] if orientation is not supported.
+///
+/// ## Usage
+///
+/// `tilt` and `roll` are `0.0` when the device is held straight or the mouse is in the center of
+/// the element. For the mouse they range from `-0.5` to `0.5` at the edges of the element. For the
+/// device orientation `1.0` corresponds to a rotation of 90 degrees.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_parallax, UseParallaxReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let container = create_node_ref::<Div>();
+///
+/// let UseParallaxReturn { tilt, roll, .. } = use_parallax(container);
+///
+/// let transform = move || {
+///     format!(
+///         "perspective(300px) rotateX({}deg) rotateY({}deg)",
+///         roll.get() * 20.0,
+///         tilt.get() * 20.0
+///     )
+/// };
+///
+/// view! {
+///     <div node_ref=container>
+///         <img src="hero.png" style:transform=transform />
+///     </div>
+/// }
+/// # }
+/// ```
+///
+/// ## Adjusting the values
+///
+/// The values can be adjusted separately for both sources, for example to invert the direction
+/// or to make the effect stronger on mobile devices.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_parallax_with_options, UseParallaxOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let container = create_node_ref::<Div>();
+///
+/// let parallax = use_parallax_with_options(
+///     container,
+///     UseParallaxOptions::default()
+///         .mouse_tilt_adjust(|tilt| -tilt)
+///         .device_orientation_roll_adjust(|roll| roll * 2.0),
+/// );
+/// #
+/// # view! { <div node_ref=container></div> }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server `tilt` and `roll` are always `0.0` and `source` is `UseParallaxSource::Mouse`.
+pub fn use_parallax<El, T>(target: El) -> UseParallaxReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>> + Clone,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    use_parallax_with_options(target, UseParallaxOptions::default())
+}
+
+/// Version of [`use_parallax`] that takes a `UseParallaxOptions`. See [`use_parallax`] for how to use.
+pub fn use_parallax_with_options<El, T>(
+    target: El,
+    options: UseParallaxOptions,
+) -> UseParallaxReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>> + Clone,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let UseParallaxOptions {
+        device_orientation_tilt_adjust,
+        device_orientation_roll_adjust,
+        mouse_tilt_adjust,
+        mouse_roll_adjust,
+    } = options;
+
+    let UseDeviceOrientationReturn {
+        is_supported,
+        beta,
+        gamma,
+        ..
+    } = use_device_orientation();
+
+    let UseMouseInElementReturn {
+        element_x,
+        element_y,
+        element_width,
+        element_height,
+        ..
+    } = use_mouse_in_element_with_options(
+        target,
+        UseMouseInElementOptions::default().handle_outside(false),
+    );
+
+    let orientation_type = use_screen_orientation_type();
+
+    let source = Signal::derive(move || {
+        let is_moved = |angle: Option<f64>| angle.is_some_and(|angle| angle != 0.0);
+
+        if is_supported.get() && (is_moved(beta.get()) || is_moved(gamma.get())) {
+            UseParallaxSource::DeviceOrientation
+        } else {
+            UseParallaxSource::Mouse
+        }
+    });
+
+    let roll = Signal::derive(move || match source.get() {
+        UseParallaxSource::DeviceOrientation => {
+            let beta = beta.get().unwrap_or_default();
+            let gamma = gamma.get().unwrap_or_default();
+
+            device_orientation_roll_adjust(match orientation_type.get() {
+                Some(OrientationType::LandscapePrimary) => gamma / 90.0,
+                Some(OrientationType::LandscapeSecondary) => -gamma / 90.0,
+                Some(OrientationType::PortraitSecondary) => beta / 90.0,
+                _ => -beta / 90.0,
+            })
+        }
+        UseParallaxSource::Mouse => {
+            let height = element_height.get();
+            if height == 0.0 {
+                return mouse_roll_adjust(0.0);
+            }
+
+            mouse_roll_adjust(-(element_y.get() - height / 2.0) / height)
+        }
+    });
+
+    let tilt = Signal::derive(move || match source.get() {
+        UseParallaxSource::DeviceOrientation => {
+            let beta = beta.get().unwrap_or_default();
+            let gamma = gamma.get().unwrap_or_default();
+
+            device_orientation_tilt_adjust(match orientation_type.get() {
+                Some(OrientationType::LandscapePrimary) => beta / 90.0,
+                Some(OrientationType::LandscapeSecondary) => -beta / 90.0,
+                Some(OrientationType::PortraitSecondary) => -gamma / 90.0,
+                _ => gamma / 90.0,
+            })
+        }
+        UseParallaxSource::Mouse => {
+            let width = element_width.get();
+            if width == 0.0 {
+                return mouse_tilt_adjust(0.0);
+            }
+
+            mouse_tilt_adjust((element_x.get() - width / 2.0) / width)
+        }
+    });
+
+    UseParallaxReturn { tilt, roll, source }
+}
+
+fn use_screen_orientation_type() -> Signal<Option<OrientationType>> {
+    cfg_if! { if #[cfg(feature = "ssr")] {
+        Signal::derive(|| None)
+    } else {
+        use crate::use_event_listener;
+
+        let screen_orientation = window().screen().ok().map(|screen| screen.orientation());
+
+        let (orientation_type, set_orientation_type) = create_signal(
+            screen_orientation
+                .as_ref()
+                .and_then(|orientation| orientation.type_().ok()),
+        );
+
+        let _ = use_event_listener(
+            screen_orientation.clone(),
+            ev::Custom::<web_sys::Event>::new("change"),
+            move |_| {
+                set_orientation_type.set(
+                    screen_orientation
+                        .as_ref()
+                        .and_then(|orientation| orientation.type_().ok()),
+                );
+            },
+        );
+
+        orientation_type.into()
+    }}
+}
+
+/// Options for [`use_parallax_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseParallaxOptions {
+    /// Adjusts `tilt` when it comes from the device orientation. Defaults to no adjustment.
+    device_orientation_tilt_adjust: Rc<dyn Fn(f64) -> f64>,
+
+    /// Adjusts `roll` when it comes from the device orientation. Defaults to no adjustment.
+    device_orientation_roll_adjust: Rc<dyn Fn(f64) -> f64>,
+
+    /// Adjusts `tilt` when it comes from the mouse. Defaults to no adjustment.
+    mouse_tilt_adjust: Rc<dyn Fn(f64) -> f64>,
+
+    /// Adjusts `roll` when it comes from the mouse. Defaults to no adjustment.
+    mouse_roll_adjust: Rc<dyn Fn(f64) -> f64>,
+}
+
+impl Default for UseParallaxOptions {
+    fn default() -> Self {
+        Self {
+            device_orientation_tilt_adjust: Rc::new(|tilt| tilt),
+            device_orientation_roll_adjust: Rc::new(|roll| roll),
+            mouse_tilt_adjust: Rc::new(|tilt| tilt),
+            mouse_roll_adjust: Rc::new(|roll| roll),
+        }
+    }
+}
+
+/// Return type of [`use_parallax`].
+#[derive(Clone, Copy)]
+pub struct UseParallaxReturn {
+    /// Tilt to the left (negative) or right (positive), around the vertical axis
+    pub tilt: Signal<f64>,
+
+    /// Roll forward (negative) or backward (positive), around the horizontal axis
+    pub roll: Signal<f64>,
+
+    /// Where the values come from
+    pub source: Signal<UseParallaxSource>,
+}
+
+/// Source of the values of [`use_parallax`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UseParallaxSource {
+    /// The device orientation of a mobile device
+    DeviceOrientation,
+    /// The position of the mouse in the element
+    Mouse,
+}
@@ -0,0 +1,252 @@
+use crate::utils::ready_service_worker_registration;
+use crate::{js, js_fut, use_permission, use_supported, PermissionState};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Reactive [Periodic Background Synchronization API](https://developer.mozilla.org/en-US/docs/Web/API/Web_Periodic_Background_Synchronization_API).
+/// Lets the service worker refresh content regularly in the background so that it's up to date
+/// when the user opens the app.
+///
+/// A service worker has to be registered, for example with
+/// [`use_service_worker`](crate::use_service_worker).
+///
+/// ## Usage
+///
+/// `register` asks the browser to fire a `periodicsync` event with the given tag in the service
+/// worker at most every `min_interval` milliseconds. The browser decides how often this actually
+/// happens depending on how much the app is used.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_periodic_background_sync_with_options, UsePeriodicBackgroundSyncOptions, PermissionState};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let periodic_sync = use_periodic_background_sync_with_options(
+///     UsePeriodicBackgroundSyncOptions::default().min_interval(24 * 60 * 60 * 1000),
+/// );
+///
+/// let register = move |_| {
+///     spawn_local(async move {
+///         let _ = periodic_sync.register("refresh-articles").await;
+///     });
+/// };
+///
+/// view! {
+///     <button
+///         on:click=register
+///         disabled=move || periodic_sync.permission.get() != PermissionState::Granted
+///     >
+///         "Refresh articles daily"
+///     </button>
+///     <p>"Registered: " {move || periodic_sync.tags.get().join(", ")}</p>
+/// }
+/// # }
+/// ```
+///
+/// The service worker handles the event:
+///
+/// ```js
+/// self.addEventListener("periodicsync", (event) => {
+///   if (event.tag === "refresh-articles") {
+///     event.waitUntil(refreshArticles());
+///   }
+/// });
+/// ```
+///
+/// Browsers only grant the permission for installed web apps. It's available in `permission`.
+/// `tags` contains all registered tags. It's loaded initially and updated by `register` and
+/// `unregister`.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `tags` is always empty and all operations return
+/// `UsePeriodicBackgroundSyncError::NotSupported`.
+pub fn use_periodic_background_sync() -> UsePeriodicBackgroundSyncReturn {
+    use_periodic_background_sync_with_options(UsePeriodicBackgroundSyncOptions::default())
+}
+
+/// Version of [`use_periodic_background_sync`] that takes a `UsePeriodicBackgroundSyncOptions`. See [`use_periodic_background_sync`] for how to use.
+pub fn use_periodic_background_sync_with_options(
+    options: UsePeriodicBackgroundSyncOptions,
+) -> UsePeriodicBackgroundSyncReturn {
+    let UsePeriodicBackgroundSyncOptions { min_interval } = options;
+
+    let is_supported = use_supported(|| {
+        js!("PeriodicSyncManager" in &window()) && js!("serviceWorker" in &window().navigator())
+    });
+
+    let permission = use_permission("periodic-background-sync");
+
+    let (tags, set_tags) = create_signal(Vec::<String>::new());
+    let (error, set_error) = create_signal(None::<UsePeriodicBackgroundSyncError>);
+
+    let periodic_sync = UsePeriodicBackgroundSyncReturn {
+        is_supported,
+        permission,
+        tags: tags.into(),
+        error: error.into(),
+        min_interval,
+        set_tags,
+        set_error,
+    };
+
+    if is_supported.get_untracked() {
+        spawn_local(async move {
+            let _ = periodic_sync.refresh().await;
+        });
+    }
+
+    periodic_sync
+}
+
+/// Options for [`use_periodic_background_sync_with_options`].
+#[derive(DefaultBuilder, Default)]
+pub struct UsePeriodicBackgroundSyncOptions {
+    /// The minimum time in milliseconds between two syncs of a tag registered with `register`.
+    /// Defaults to `0` which lets the browser decide.
+    min_interval: u64,
+}
+
+/// Return type of [`use_periodic_background_sync`].
+#[derive(Clone, Copy)]
+pub struct UsePeriodicBackgroundSyncReturn {
+    /// Whether the Periodic Background Synchronization API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The permission to run periodic background syncs
+    pub permission: Signal<PermissionState>,
+
+    /// The registered tags
+    pub tags: Signal<Vec<String>>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UsePeriodicBackgroundSyncError>>,
+
+    min_interval: u64,
+    set_tags: WriteSignal<Vec<String>>,
+    set_error: WriteSignal<Option<UsePeriodicBackgroundSyncError>>,
+}
+
+impl UsePeriodicBackgroundSyncReturn {
+    /// Registers a periodic sync with the given tag and the `min_interval` of the options.
+    /// Registering an existing tag again updates its interval.
+    pub async fn register(&self, tag: &str) -> Result<(), UsePeriodicBackgroundSyncError> {
+        self.register_with_min_interval(tag, self.min_interval)
+            .await
+    }
+
+    /// Registers a periodic sync with the given tag and minimum interval in milliseconds.
+    pub async fn register_with_min_interval(
+        &self,
+        tag: &str,
+        min_interval: u64,
+    ) -> Result<(), UsePeriodicBackgroundSyncError> {
+        let sync_manager = self.sync_manager().await?;
+
+        let register_options = js_sys::Object::new();
+        js!(register_options["minInterval"] = min_interval as f64);
+
+        async { js_fut!(sync_manager.register(tag, &register_options)?).await }
+            .await
+            .map_err(|err| self.fail(UsePeriodicBackgroundSyncError::Register(err)))?;
+
+        self.refresh().await?;
+
+        Ok(())
+    }
+
+    /// Removes the periodic sync with the given tag.
+    pub async fn unregister(&self, tag: &str) -> Result<(), UsePeriodicBackgroundSyncError> {
+        let sync_manager = self.sync_manager().await?;
+
+        async { js_fut!(sync_manager.unregister(tag)?).await }
+            .await
+            .map_err(|err| self.fail(UsePeriodicBackgroundSyncError::Unregister(err)))?;
+
+        self.refresh().await?;
+
+        Ok(())
+    }
+
+    /// Loads the registered tags into `tags` and returns them.
+    pub async fn refresh(&self) -> Result<Vec<String>, UsePeriodicBackgroundSyncError> {
+        let sync_manager = self.sync_manager().await?;
+
+        let tags = async { js_fut!(sync_manager.get_tags()?).await }
+            .await
+            .map_err(|err| self.fail(UsePeriodicBackgroundSyncError::GetTags(err)))?
+            .unchecked_into::<js_sys::Array>()
+            .iter()
+            .filter_map(|tag| tag.as_string())
+            .collect::<Vec<_>>();
+
+        let _ = self.set_tags.try_set(tags.clone());
+        let _ = self.set_error.try_set(None);
+
+        Ok(tags)
+    }
+
+    async fn sync_manager(&self) -> Result<PeriodicSyncManager, UsePeriodicBackgroundSyncError> {
+        if !self.is_supported.get_untracked() {
+            return Err(UsePeriodicBackgroundSyncError::NotSupported);
+        }
+
+        let registration = ready_service_worker_registration()
+            .await
+            .map_err(|err| self.fail(UsePeriodicBackgroundSyncError::Registration(err)))?;
+
+        Ok(registration
+            .unchecked_into::<PeriodicSyncRegistration>()
+            .periodic_sync())
+    }
+
+    fn fail(&self, err: UsePeriodicBackgroundSyncError) -> UsePeriodicBackgroundSyncError {
+        let _ = self.set_error.try_set(Some(err.clone()));
+        err
+    }
+}
+
+/// Errors of [`use_periodic_background_sync`].
+#[derive(Error, Debug, Clone)]
+pub enum UsePeriodicBackgroundSyncError {
+    #[error("the Periodic Background Synchronization API is not supported")]
+    NotSupported,
+    #[error("failed to get the service worker registration")]
+    Registration(JsValue),
+    #[error("failed to register the periodic sync")]
+    Register(JsValue),
+    #[error("failed to unregister the periodic sync")]
+    Unregister(JsValue),
+    #[error("failed to get the registered tags")]
+    GetTags(JsValue),
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Clone, Debug)]
+    #[wasm_bindgen(extends = web_sys::ServiceWorkerRegistration, js_name = ServiceWorkerRegistration)]
+    type PeriodicSyncRegistration;
+
+    #[wasm_bindgen(method, getter = periodicSync)]
+    fn periodic_sync(this: &PeriodicSyncRegistration) -> PeriodicSyncManager;
+
+    #[derive(Clone, Debug)]
+    type PeriodicSyncManager;
+
+    #[wasm_bindgen(catch, method)]
+    fn register(
+        this: &PeriodicSyncManager,
+        tag: &str,
+        options: &js_sys::Object,
+    ) -> Result<js_sys::Promise, JsValue>;
+
+    #[wasm_bindgen(catch, method)]
+    fn unregister(this: &PeriodicSyncManager, tag: &str) -> Result<js_sys::Promise, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = getTags)]
+    fn get_tags(this: &PeriodicSyncManager) -> Result<js_sys::Promise, JsValue>;
+}
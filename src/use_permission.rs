@@ -1,5 +1,11 @@
+use crate::use_web_notification::request_web_notification_permission;
+use crate::{js, js_fut, NotificationPermission};
 use leptos::*;
+use std::collections::HashMap;
 use std::fmt::Display;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
 
 /// Reactive [Permissions API](https://developer.mozilla.org/en-US/docs/Web/API/Permissions_API).
 ///
@@ -21,6 +27,8 @@ use std::fmt::Display;
 /// # }
 /// ```
 ///
+/// To query several permissions at once and to ask the user for them see [`use_permissions`].
+///
 /// ## Server-Side Rendering
 ///
 /// On the server the returned signal will always be `PermissionState::Unknown`.
@@ -72,6 +80,179 @@ pub fn use_permission(permission_name: &str) -> Signal<PermissionState> {
     state.into()
 }
 
+/// Reactive states of several permissions at once. See [`use_permission`] for a single one.
+///
+/// ## Usage
+///
+/// `request` asks the user for a permission by calling the API that needs it, because the
+/// Permissions API itself can't show a prompt. The states are updated automatically afterwards.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_permissions, PermissionState};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let permissions = use_permissions(&["camera", "microphone", "geolocation"]);
+///
+/// let camera = permissions.states["camera"];
+///
+/// let request_camera = move |_| {
+///     let permissions = permissions.clone();
+///     spawn_local(async move {
+///         let _ = permissions.request("camera").await;
+///     });
+/// };
+///
+/// view! {
+///     <p>"Camera: " {move || camera.get().to_string()}</p>
+///     <Show when=move || camera.get() == PermissionState::Prompt>
+///         <button on:click=request_camera.clone()>"Allow camera"</button>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// The permissions `"camera"`, `"microphone"`, `"geolocation"` and `"notifications"` can be
+/// requested. See [`request_permission`] for details.
+///
+/// ## Server-Side Rendering
+///
+/// On the server all states are always `PermissionState::Unknown`.
+pub fn use_permissions(permission_names: &[&str]) -> UsePermissionsReturn {
+    UsePermissionsReturn {
+        states: permission_names
+            .iter()
+            .map(|name| (name.to_string(), use_permission(name)))
+            .collect(),
+    }
+}
+
+/// Return type of [`use_permissions`].
+#[derive(Clone)]
+pub struct UsePermissionsReturn {
+    /// The reactive state of every permission by its name
+    pub states: HashMap<String, Signal<PermissionState>>,
+}
+
+impl UsePermissionsReturn {
+    /// The current state of the permission. Returns `PermissionState::Unknown` for a permission
+    /// that hasn't been passed to [`use_permissions`].
+    pub fn get(&self, permission_name: &str) -> PermissionState {
+        self.states
+            .get(permission_name)
+            .map(|state| state.get())
+            .unwrap_or_default()
+    }
+
+    /// Asks the user for the permission. See [`request_permission`].
+    pub async fn request(
+        &self,
+        permission_name: &str,
+    ) -> Result<PermissionState, RequestPermissionError> {
+        request_permission(permission_name).await
+    }
+}
+
+/// Asks the user for a permission by calling the API that needs it and returns the new state.
+///
+/// | Permission        | API                                      |
+/// |-------------------|------------------------------------------|
+/// | `"camera"`        | `navigator.mediaDevices.getUserMedia()`  |
+/// | `"microphone"`    | `navigator.mediaDevices.getUserMedia()`  |
+/// | `"geolocation"`   | `navigator.geolocation.getCurrentPosition()` |
+/// | `"notifications"` | `Notification.requestPermission()`       |
+///
+/// The media stream and the position are discarded right away. Most browsers only show a prompt
+/// in response to a user gesture like a click.
+///
+/// Other permissions return `RequestPermissionError::NotRequestable`.
+pub async fn request_permission(
+    permission_name: &str,
+) -> Result<PermissionState, RequestPermissionError> {
+    let granted = match permission_name {
+        "camera" => request_user_media(true, false).await,
+        "microphone" => request_user_media(false, true).await,
+        "geolocation" => request_geolocation().await,
+        "notifications" => {
+            return Ok(match request_web_notification_permission().await {
+                NotificationPermission::Granted => PermissionState::Granted,
+                NotificationPermission::Denied => PermissionState::Denied,
+                NotificationPermission::Default => PermissionState::Prompt,
+            });
+        }
+        _ => {
+            return Err(RequestPermissionError::NotRequestable(
+                permission_name.to_string(),
+            ))
+        }
+    };
+
+    // The outcome of the API only tells if the user denied the prompt, the permission may still
+    // be granted temporarily for example.
+    Ok(match query_permission(permission_name.to_owned()).await {
+        Ok(status) => status.state().into(),
+        Err(_) if granted => PermissionState::Granted,
+        Err(_) => PermissionState::Denied,
+    })
+}
+
+/// Errors of [`request_permission`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RequestPermissionError {
+    #[error("the permission `{0}` can't be requested")]
+    NotRequestable(String),
+}
+
+async fn request_user_media(video: bool, audio: bool) -> bool {
+    let stream = async {
+        let constraints = web_sys::MediaStreamConstraints::new();
+        constraints.set_video_bool(video);
+        constraints.set_audio_bool(audio);
+
+        js_fut!(window()
+            .navigator()
+            .media_devices()?
+            .get_user_media_with_constraints(&constraints)?)
+        .await
+    }
+    .await;
+
+    match stream {
+        Ok(stream) => {
+            for track in stream.unchecked_into::<web_sys::MediaStream>().get_tracks() {
+                track.unchecked_ref::<web_sys::MediaStreamTrack>().stop();
+            }
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+async fn request_geolocation() -> bool {
+    let position = js_sys::Promise::new(&mut |resolve, reject| {
+        let on_success = Closure::once_into_js(move |position: JsValue| {
+            let _ = resolve.call1(&JsValue::NULL, &position);
+        });
+        let on_error = Closure::once_into_js(move |err: JsValue| {
+            let _ = reject.call1(&JsValue::NULL, &err);
+        });
+
+        // called dynamically because the signature differs with `web_sys_unstable_apis`
+        if let Err(err) = window().navigator().geolocation().and_then(|geolocation| {
+            js_sys::Reflect::get(&geolocation, &"getCurrentPosition".into())?
+                .unchecked_into::<js_sys::Function>()
+                .call2(&geolocation, &on_success, &on_error)
+        }) {
+            let _ = on_error
+                .unchecked_ref::<js_sys::Function>()
+                .call1(&JsValue::NULL, &err);
+        }
+    });
+
+    js_fut!(position).await.is_ok()
+}
+
 /// Return type of [`use_permission`].
 #[derive(Copy, Clone, Default, Eq, PartialEq)]
 pub enum PermissionState {
@@ -111,13 +292,7 @@ impl From<web_sys::PermissionState> for PermissionState {
     }
 }
 
-#[cfg(not(feature = "ssr"))]
-async fn query_permission(
-    permission: String,
-) -> Result<web_sys::PermissionStatus, wasm_bindgen::JsValue> {
-    use crate::{js, js_fut};
-    use wasm_bindgen::JsCast;
-
+async fn query_permission(permission: String) -> Result<web_sys::PermissionStatus, JsValue> {
     let permission_object = js_sys::Object::new();
     js!(permission_object["name"] = permission);
 
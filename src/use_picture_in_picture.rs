@@ -0,0 +1,231 @@
+use crate::core::ElementMaybeSignal;
+use crate::{js, js_fut, use_event_listener, use_supported};
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{HtmlVideoElement, PictureInPictureEvent, PictureInPictureWindow};
+
+/// Reactive [Picture-in-Picture API](https://developer.mozilla.org/en-US/docs/Web/API/Picture-in-Picture_API).
+/// Plays a `<video>` in a small window that floats on top of other windows.
+///
+/// > This function requires `--cfg=web_sys_unstable_apis` to be activated as
+/// > [described in the wasm-bindgen guide](https://rustwasm.github.io/docs/wasm-bindgen/web-sys/unstable-apis.html).
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Video;
+/// # use leptos_use::use_picture_in_picture;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let video = create_node_ref::<Video>();
+///
+/// let pip = use_picture_in_picture(video);
+///
+/// let toggle = move |_| {
+///     spawn_local(async move {
+///         let _ = pip.toggle().await;
+///     });
+/// };
+///
+/// view! {
+///     <video node_ref=video src="movie.mp4" controls></video>
+///     <button on:click=toggle disabled=move || !pip.is_supported.get()>
+///         {move || if pip.is_active.get() { "Exit Picture-in-Picture" } else { "Picture-in-Picture" }}
+///     </button>
+///     <Show when=move || pip.is_active.get()>
+///         <p>"Window size: " {pip.window_width} "x" {pip.window_height}</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// `enter` has to be called from a user gesture like a click. `is_active` is also updated when
+/// the user closes the window or uses the controls of the browser to enter Picture-in-Picture.
+/// `window_width` and `window_height` follow the size of the floating window while it is open
+/// and are `0` otherwise.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_active` is always `false` and all operations return
+/// `UsePictureInPictureError::NotSupported`.
+pub fn use_picture_in_picture<El, T>(target: El) -> UsePictureInPictureReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let target = target.into();
+
+    let is_supported = use_supported(|| {
+        js!("pictureInPictureEnabled" in &document()) && document().picture_in_picture_enabled()
+    });
+
+    let (is_active, set_active) = create_signal(false);
+    let (pip_window, set_pip_window) = create_signal(None::<PictureInPictureWindow>);
+    let (window_width, set_window_width) = create_signal(0);
+    let (window_height, set_window_height) = create_signal(0);
+    let (error, set_error) = create_signal(None::<UsePictureInPictureError>);
+
+    let video = Signal::derive(move || {
+        target
+            .get()
+            .and_then(|element| element.into().dyn_into::<HtmlVideoElement>().ok())
+    });
+
+    let update_window = move |window: Option<PictureInPictureWindow>| {
+        let (width, height) = window
+            .as_ref()
+            .map(|window| (window.width(), window.height()))
+            .unwrap_or_default();
+
+        set_window_width.set(width);
+        set_window_height.set(height);
+        set_active.set(window.is_some());
+        set_pip_window.set(window);
+    };
+
+    let _ = watch(
+        move || video.get(),
+        move |video, _, _| {
+            if !is_supported.get_untracked() {
+                return;
+            }
+
+            let active = match (video, document().picture_in_picture_element()) {
+                (Some(video), Some(element)) => video.is_same_node(Some(&element)),
+                _ => false,
+            };
+
+            // the window of a video that is already in Picture-in-Picture can't be retrieved
+            if active {
+                set_active.set(true);
+            } else {
+                update_window(None);
+            }
+        },
+        true,
+    );
+
+    let _ = use_event_listener(
+        video,
+        ev::Custom::<PictureInPictureEvent>::new("enterpictureinpicture"),
+        move |event| update_window(Some(event.picture_in_picture_window())),
+    );
+
+    let _ = use_event_listener(
+        video,
+        ev::Custom::<PictureInPictureEvent>::new("leavepictureinpicture"),
+        move |_| update_window(None),
+    );
+
+    let _ = use_event_listener(
+        pip_window,
+        ev::Custom::<web_sys::Event>::new("resize"),
+        move |_| {
+            if let Some(window) = pip_window.get_untracked() {
+                set_window_width.set(window.width());
+                set_window_height.set(window.height());
+            }
+        },
+    );
+
+    UsePictureInPictureReturn {
+        is_supported,
+        is_active: is_active.into(),
+        window_width: window_width.into(),
+        window_height: window_height.into(),
+        error: error.into(),
+        video,
+        set_error,
+    }
+}
+
+/// Return type of [`use_picture_in_picture`].
+#[derive(Clone, Copy)]
+pub struct UsePictureInPictureReturn {
+    /// Whether Picture-in-Picture is supported and enabled in the browser
+    pub is_supported: Signal<bool>,
+
+    /// Whether the video is currently shown in the Picture-in-Picture window
+    pub is_active: Signal<bool>,
+
+    /// The width of the Picture-in-Picture window in pixels
+    pub window_width: Signal<i32>,
+
+    /// The height of the Picture-in-Picture window in pixels
+    pub window_height: Signal<i32>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UsePictureInPictureError>>,
+
+    video: Signal<Option<HtmlVideoElement>>,
+    set_error: WriteSignal<Option<UsePictureInPictureError>>,
+}
+
+impl UsePictureInPictureReturn {
+    /// Shows the video in the Picture-in-Picture window.
+    pub async fn enter(&self) -> Result<(), UsePictureInPictureError> {
+        if !self.is_supported.get_untracked() {
+            return Err(UsePictureInPictureError::NotSupported);
+        }
+
+        let video = self
+            .video
+            .get_untracked()
+            .ok_or_else(|| self.fail(UsePictureInPictureError::NoVideo))?;
+
+        js_fut!(video.request_picture_in_picture())
+            .await
+            .map_err(|err| self.fail(UsePictureInPictureError::Failed(err)))?;
+
+        self.set_error.set(None);
+
+        Ok(())
+    }
+
+    /// Closes the Picture-in-Picture window. Does nothing if it isn't open.
+    pub async fn exit(&self) -> Result<(), UsePictureInPictureError> {
+        if !self.is_supported.get_untracked() {
+            return Err(UsePictureInPictureError::NotSupported);
+        }
+
+        if !self.is_active.get_untracked() {
+            return Ok(());
+        }
+
+        js_fut!(document().exit_picture_in_picture())
+            .await
+            .map_err(|err| self.fail(UsePictureInPictureError::Failed(err)))?;
+
+        self.set_error.set(None);
+
+        Ok(())
+    }
+
+    /// Calls `exit` if the Picture-in-Picture window is open, otherwise `enter`.
+    pub async fn toggle(&self) -> Result<(), UsePictureInPictureError> {
+        if self.is_active.get_untracked() {
+            self.exit().await
+        } else {
+            self.enter().await
+        }
+    }
+
+    fn fail(&self, err: UsePictureInPictureError) -> UsePictureInPictureError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// Errors of [`use_picture_in_picture`].
+#[derive(Error, Debug, Clone)]
+pub enum UsePictureInPictureError {
+    #[error("Picture-in-Picture is not supported or disabled")]
+    NotSupported,
+    #[error("the target is not a video element")]
+    NoVideo,
+    #[error("entering or exiting Picture-in-Picture failed")]
+    Failed(JsValue),
+}
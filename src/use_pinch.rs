@@ -0,0 +1,366 @@
+use crate::core::{ElementMaybeSignal, Position};
+use crate::{use_event_listener_with_options, UseEventListenerOptions};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{pointercancel, pointerdown, pointermove, pointerup};
+use leptos::*;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::PointerEvent;
+
+/// Reactive pinch and rotate gestures of two pointers based on
+/// [`PointerEvent`](https://developer.mozilla.org/en-US/docs/Web/API/PointerEvent).
+///
+/// ## Usage
+///
+/// `scale` and `rotation` are relative to the moment the second pointer touched the target.
+/// `midpoint` is the point in the middle of both pointers in client coordinates.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_pinch, UsePinchReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+///
+/// let UsePinchReturn { scale, rotation, .. } = use_pinch(el);
+///
+/// view! {
+///     <div node_ref=el style="touch-action: none">
+///         <img
+///             src="map.png"
+///             style:transform=move || format!("scale({}) rotate({}deg)", scale.get(), rotation.get())
+///         />
+///     </div>
+/// }
+/// # }
+/// ```
+///
+/// Both pointers are captured by the element that received the `pointerdown` so the gesture
+/// continues when they leave it. Set `touch-action: none` on the element, otherwise the
+/// browser cancels the pointers to zoom or scroll the page itself.
+///
+/// Further pointers are ignored until one of the two is released. If two or more pointers are
+/// still down at that point the gesture ends and a new one starts with the first two of them.
+///
+/// ## Keeping the Transformation
+///
+/// Every gesture starts again at a `scale` of `1.0` and a `rotation` of `0.0`. Use
+/// `on_pinch_end` to accumulate the values of several gestures.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_pinch_with_options, UsePinchOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+/// let (zoom, set_zoom) = create_signal(1.0);
+///
+/// let pinch = use_pinch_with_options(
+///     el,
+///     UsePinchOptions::default().on_pinch_end(move |_, scale, _| {
+///         set_zoom.update(|zoom| *zoom *= scale);
+///     }),
+/// );
+///
+/// let current_zoom = move || zoom.get() * pinch.scale.get();
+/// #
+/// # view! { <div node_ref=el></div> }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_pinching` is always `false`, `scale` is `1.0` and `rotation` is `0.0`.
+pub fn use_pinch<El, T>(target: El) -> UsePinchReturn<impl Fn() + Clone>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+{
+    use_pinch_with_options(target, UsePinchOptions::default())
+}
+
+/// Version of [`use_pinch`] that takes a `UsePinchOptions`. See [`use_pinch`] for how to use.
+pub fn use_pinch_with_options<El, T>(
+    target: El,
+    options: UsePinchOptions,
+) -> UsePinchReturn<impl Fn() + Clone>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+{
+    let UsePinchOptions {
+        on_pinch_start,
+        on_pinch,
+        on_pinch_end,
+    } = options;
+
+    let (is_pinching, set_pinching) = create_signal(false);
+    let (scale, set_scale) = create_signal(1.0);
+    let (rotation, set_rotation) = create_signal(0.0);
+    let (midpoint, set_midpoint) = create_signal(Position::default());
+
+    let state = store_value(PinchState::default());
+
+    let listener_options = UseEventListenerOptions::default().passive(true);
+
+    let target = target.into();
+
+    // starts the gesture with the first two pointers if there are at least two
+    let start_pinch = move |event: PointerEvent| {
+        if let Some((distance, angle, center)) = state.with_value(|state| state.gesture()) {
+            state.update_value(|state| {
+                state.start_distance = distance;
+                state.last_angle = angle;
+            });
+
+            set_scale.set(1.0);
+            set_rotation.set(0.0);
+            set_midpoint.set(center);
+            set_pinching.set(true);
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_pinch_start(event);
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        }
+    };
+
+    let stop_down = use_event_listener_with_options(
+        target.clone(),
+        pointerdown,
+        {
+            let start_pinch = start_pinch.clone();
+
+            move |event| {
+                if let Some(element) = event
+                    .target()
+                    .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+                {
+                    let _ = element.set_pointer_capture(event.pointer_id());
+                }
+
+                let had_two_pointers = state.with_value(|state| state.pointers.len() >= 2);
+
+                state.update_value(|state| state.update_pointer(&event));
+
+                if !had_two_pointers {
+                    start_pinch(event);
+                }
+            }
+        },
+        listener_options,
+    );
+
+    let stop_move = use_event_listener_with_options(
+        target.clone(),
+        pointermove,
+        move |event| {
+            if !state.with_value(|state| state.is_gesture_pointer(event.pointer_id())) {
+                return;
+            }
+
+            state.update_value(|state| state.update_pointer(&event));
+
+            if !is_pinching.get_untracked() {
+                return;
+            }
+
+            let Some((distance, angle, center)) = state.with_value(|state| state.gesture()) else {
+                return;
+            };
+
+            let (start_distance, last_angle) =
+                state.with_value(|state| (state.start_distance, state.last_angle));
+            state.update_value(|state| state.last_angle = angle);
+
+            if start_distance > 0.0 {
+                set_scale.set(distance / start_distance);
+            }
+            set_rotation.update(|rotation| *rotation += normalize_angle(angle - last_angle));
+            set_midpoint.set(center);
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_pinch(event);
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        },
+        listener_options,
+    );
+
+    let on_pointer_up = move |event: PointerEvent| {
+        let was_gesture_pointer =
+            state.with_value(|state| state.is_gesture_pointer(event.pointer_id()));
+
+        state.update_value(|state| {
+            state
+                .pointers
+                .retain(|(pointer_id, _)| *pointer_id != event.pointer_id())
+        });
+
+        if was_gesture_pointer && is_pinching.get_untracked() {
+            set_pinching.set(false);
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_pinch_end(
+                event.clone(),
+                scale.get_untracked(),
+                rotation.get_untracked(),
+            );
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+
+            // if more pointers are down the next two continue as a new gesture
+            start_pinch(event);
+        }
+    };
+
+    let stop_up = use_event_listener_with_options(
+        target.clone(),
+        pointerup,
+        on_pointer_up.clone(),
+        listener_options,
+    );
+    let stop_cancel =
+        use_event_listener_with_options(target, pointercancel, on_pointer_up, listener_options);
+
+    let stop = move || {
+        stop_down();
+        stop_move();
+        stop_up();
+        stop_cancel();
+    };
+
+    UsePinchReturn {
+        is_pinching: is_pinching.into(),
+        scale: scale.into(),
+        rotation: rotation.into(),
+        midpoint: midpoint.into(),
+        stop,
+    }
+}
+
+#[derive(Default)]
+struct PinchState {
+    /// All pointers that are down on the target in the order they went down.
+    /// Only the first two make up the gesture.
+    pointers: Vec<(i32, Position)>,
+    start_distance: f64,
+    last_angle: f64,
+}
+
+impl PinchState {
+    fn update_pointer(&mut self, event: &PointerEvent) {
+        let position = Position {
+            x: event.client_x() as f64,
+            y: event.client_y() as f64,
+        };
+
+        if let Some((_, pointer_position)) = self
+            .pointers
+            .iter_mut()
+            .find(|(pointer_id, _)| *pointer_id == event.pointer_id())
+        {
+            *pointer_position = position;
+        } else {
+            self.pointers.push((event.pointer_id(), position));
+        }
+    }
+
+    fn is_gesture_pointer(&self, pointer_id: i32) -> bool {
+        self.pointers
+            .iter()
+            .take(2)
+            .any(|(id, _)| *id == pointer_id)
+    }
+
+    /// Distance, angle in degrees and midpoint of the first two pointers
+    fn gesture(&self) -> Option<(f64, f64, Position)> {
+        let [(_, a), (_, b), ..] = self.pointers.as_slice() else {
+            return None;
+        };
+
+        let dx = b.x - a.x;
+        let dy = b.y - a.y;
+
+        Some((
+            dx.hypot(dy),
+            dy.atan2(dx).to_degrees(),
+            Position {
+                x: (a.x + b.x) / 2.0,
+                y: (a.y + b.y) / 2.0,
+            },
+        ))
+    }
+}
+
+/// Maps an angle difference into `-180..=180` degrees so the rotation doesn't jump when the
+/// angle between the pointers wraps around.
+fn normalize_angle(angle: f64) -> f64 {
+    if angle > 180.0 {
+        angle - 360.0
+    } else if angle < -180.0 {
+        angle + 360.0
+    } else {
+        angle
+    }
+}
+
+/// Options for [`use_pinch_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UsePinchOptions {
+    /// Called when the second pointer touches the target. Also called when one of the two
+    /// pointers is released while others are still down and the first two of them start a new
+    /// gesture.
+    on_pinch_start: Rc<dyn Fn(PointerEvent)>,
+
+    /// Called on every move of one of the two pointers during the gesture.
+    on_pinch: Rc<dyn Fn(PointerEvent)>,
+
+    /// Called when one of the two pointers is released with the final scale and rotation.
+    on_pinch_end: Rc<dyn Fn(PointerEvent, f64, f64)>,
+}
+
+impl Default for UsePinchOptions {
+    fn default() -> Self {
+        Self {
+            on_pinch_start: Rc::new(|_| {}),
+            on_pinch: Rc::new(|_| {}),
+            on_pinch_end: Rc::new(|_, _, _| {}),
+        }
+    }
+}
+
+/// Return type of [`use_pinch`].
+pub struct UsePinchReturn<F>
+where
+    F: Fn() + Clone,
+{
+    /// Whether two pointers are down on the target
+    pub is_pinching: Signal<bool>,
+
+    /// Distance between the pointers relative to the start of the gesture.
+    /// Greater than `1.0` when zooming in.
+    pub scale: Signal<f64>,
+
+    /// Rotation of the pointers since the start of the gesture in degrees, clockwise
+    pub rotation: Signal<f64>,
+
+    /// The point in the middle of the pointers in client coordinates
+    pub midpoint: Signal<Position>,
+
+    /// Removes the event listeners
+    pub stop: F,
+}
@@ -0,0 +1,296 @@
+use crate::core::{ElementMaybeSignal, PointerType, Position};
+use crate::{js, use_event_listener, use_window, UseWindow};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{pointercancel, pointerdown, pointermove, pointerup};
+use leptos::*;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use wasm_bindgen::JsCast;
+use web_sys::PointerEvent;
+
+/// Reactive state of all active pointers based on
+/// [`PointerEvent`](https://developer.mozilla.org/en-US/docs/Web/API/PointerEvent).
+///
+/// ## Usage
+///
+/// A pointer becomes active when it is pressed down on the target and stays active until it is
+/// released or cancelled, even when it has left the target in the meantime. Every finger on a
+/// touch screen is a pointer of its own.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_pointer, UsePointerReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UsePointerReturn { pointers, .. } = use_pointer();
+///
+/// view! {
+///     <p>{move || pointers.with(|pointers| pointers.len())} " pointers are down"</p>
+/// }
+/// # }
+/// ```
+///
+/// ## Drawing with multiple fingers
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Canvas;
+/// # use leptos_use::{use_pointer_with_options, UsePointerOptions, UsePointerReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let canvas = create_node_ref::<Canvas>();
+///
+/// let UsePointerReturn { pointers, .. } =
+///     use_pointer_with_options(UsePointerOptions::default().target(canvas));
+///
+/// create_effect(move |_| {
+///     pointers.with(|pointers| {
+///         for (pointer_id, pointer) in pointers {
+///             // draw a dot of the size `pointer.pressure` at `pointer.position`
+///             // in the color of `pointer_id`
+///         }
+///     });
+/// });
+///
+/// view! { <canvas node_ref=canvas style="touch-action: none"></canvas> }
+/// # }
+/// ```
+///
+/// Set `touch-action: none` on the target, otherwise the browser cancels touch pointers when it
+/// starts to scroll or zoom the page.
+///
+/// ## Pens
+///
+/// Besides `pressure` every pointer also reports `tilt_x`, `tilt_y`, `twist` and
+/// `tangential_pressure` of a pen. Browsers usually only dispatch one `pointermove` per frame.
+/// The states in between are available in `coalesced` so strokes stay smooth when the pen is
+/// moved fast.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::core::PointerType;
+/// # use leptos_use::{use_pointer_with_options, UsePointerOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let pointer = use_pointer_with_options(
+///     UsePointerOptions::default().pointer_types(vec![PointerType::Pen]),
+/// );
+///
+/// create_effect(move |_| {
+///     if let Some(pen) = pointer.primary.get() {
+///         for sample in &pen.coalesced {
+///             // add a point at `sample.position` with the width `sample.pressure`
+///             // and the angle `sample.tilt_x` to the stroke
+///         }
+///     }
+/// });
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server `pointers` is always empty.
+pub fn use_pointer() -> UsePointerReturn {
+    use_pointer_with_options(UsePointerOptions::default())
+}
+
+/// Version of [`use_pointer`] that takes a `UsePointerOptions`. See [`use_pointer`] for how to use.
+pub fn use_pointer_with_options<El, T>(options: UsePointerOptions<El, T>) -> UsePointerReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+{
+    let UsePointerOptions {
+        target,
+        pointer_types,
+        ..
+    } = options;
+
+    let (pointers, set_pointers) = create_signal(HashMap::<i32, ActivePointer>::new());
+
+    let is_allowed_type = move |event: &PointerEvent| {
+        pointer_types
+            .iter()
+            .any(|pointer_type| pointer_type.to_string() == event.pointer_type())
+    };
+
+    let _ = use_event_listener(target, pointerdown, {
+        let is_allowed_type = is_allowed_type.clone();
+
+        move |event| {
+            if !is_allowed_type(&event) {
+                return;
+            }
+
+            set_pointers.update(|pointers| {
+                pointers.insert(event.pointer_id(), ActivePointer::from(&event));
+            });
+        }
+    });
+
+    // moving and releasing is listened to on the window so it's tracked outside the target too
+    let _ = use_event_listener(use_window(), pointermove, move |event| {
+        if pointers.with_untracked(|pointers| !pointers.contains_key(&event.pointer_id())) {
+            return;
+        }
+
+        set_pointers.update(|pointers| {
+            pointers.insert(event.pointer_id(), ActivePointer::from(&event));
+        });
+    });
+
+    let on_pointer_end = move |event: PointerEvent| {
+        if pointers.with_untracked(|pointers| !pointers.contains_key(&event.pointer_id())) {
+            return;
+        }
+
+        set_pointers.update(|pointers| {
+            pointers.remove(&event.pointer_id());
+        });
+    };
+
+    let _ = use_event_listener(use_window(), pointerup, on_pointer_end);
+    let _ = use_event_listener(use_window(), pointercancel, on_pointer_end);
+
+    UsePointerReturn {
+        pointers: pointers.into(),
+        primary: Signal::derive(move || {
+            pointers.with(|pointers| {
+                pointers
+                    .values()
+                    .find(|pointer| pointer.is_primary)
+                    .cloned()
+            })
+        }),
+    }
+}
+
+/// Options for [`use_pointer_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UsePointerOptions<El, T>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+{
+    /// On which element a pointer has to be pressed to become active. Defaults to `window`.
+    target: El,
+
+    /// Which kinds of pointers are tracked. Defaults to all pointer types.
+    pointer_types: Vec<PointerType>,
+
+    #[builder(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl Default for UsePointerOptions<UseWindow, web_sys::Window> {
+    fn default() -> Self {
+        Self {
+            target: use_window(),
+            pointer_types: vec![PointerType::Mouse, PointerType::Touch, PointerType::Pen],
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Return type of [`use_pointer`].
+#[derive(Clone, Copy)]
+pub struct UsePointerReturn {
+    /// All active pointers by their `pointerId`
+    pub pointers: Signal<HashMap<i32, ActivePointer>>,
+
+    /// The active primary pointer, i.e. the mouse or the first finger that touched the screen
+    pub primary: Signal<Option<ActivePointer>>,
+}
+
+/// State of a pointer that is pressed down. See
+/// [PointerEvent](https://developer.mozilla.org/en-US/docs/Web/API/PointerEvent) for the meaning
+/// of the fields.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActivePointer {
+    /// The `pointerId` that identifies the pointer while it is active
+    pub pointer_id: i32,
+
+    /// Whether the pointer is a mouse, a finger or a pen. `None` if the browser doesn't know.
+    pub pointer_type: Option<PointerType>,
+
+    /// Whether this is the primary pointer of its type
+    pub is_primary: bool,
+
+    /// Position in client coordinates
+    pub position: Position,
+
+    /// Pressure from `0.0` to `1.0`. Pointers without pressure support report `0.5` while pressed.
+    pub pressure: f64,
+
+    /// Pressure of the barrel button of a pen from `-1.0` to `1.0`. `0.0` if not supported.
+    pub tangential_pressure: f64,
+
+    /// Tilt of a pen from the left (negative) to the right (positive) in degrees from `-90.0`
+    /// to `90.0`. `0.0` for pointers that don't support it.
+    pub tilt_x: f64,
+
+    /// Tilt of a pen towards the user (positive) or away from them (negative) in degrees from
+    /// `-90.0` to `90.0`. `0.0` for pointers that don't support it.
+    pub tilt_y: f64,
+
+    /// Clockwise rotation of a pen around its own axis in degrees from `0.0` to `359.0`.
+    /// `0.0` for pointers that don't support it.
+    pub twist: f64,
+
+    /// Width of the contact geometry in CSS pixels
+    pub width: f64,
+
+    /// Height of the contact geometry in CSS pixels
+    pub height: f64,
+
+    /// The states the browser has coalesced into the last event of this pointer, the oldest
+    /// first. Empty if the browser doesn't support
+    /// [`getCoalescedEvents`](https://developer.mozilla.org/en-US/docs/Web/API/PointerEvent/getCoalescedEvents).
+    pub coalesced: Vec<ActivePointer>,
+}
+
+impl From<&PointerEvent> for ActivePointer {
+    fn from(event: &PointerEvent) -> Self {
+        let coalesced = if js!("getCoalescedEvents" in event) {
+            event
+                .get_coalesced_events()
+                .iter()
+                .map(|coalesced| Self::without_coalesced(coalesced.unchecked_ref()))
+                .collect()
+        } else {
+            vec![]
+        };
+
+        Self {
+            coalesced,
+            ..Self::without_coalesced(event)
+        }
+    }
+}
+
+impl ActivePointer {
+    fn without_coalesced(event: &PointerEvent) -> Self {
+        Self {
+            pointer_id: event.pointer_id(),
+            pointer_type: event.pointer_type().parse().ok(),
+            is_primary: event.is_primary(),
+            position: Position {
+                x: event.client_x() as f64,
+                y: event.client_y() as f64,
+            },
+            pressure: event.pressure() as f64,
+            tangential_pressure: event.tangential_pressure() as f64,
+            tilt_x: event.tilt_x() as f64,
+            tilt_y: event.tilt_y() as f64,
+            twist: event.twist() as f64,
+            width: event.width() as f64,
+            height: event.height() as f64,
+            coalesced: vec![],
+        }
+    }
+}
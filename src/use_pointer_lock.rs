@@ -0,0 +1,182 @@
+use crate::core::ElementMaybeSignal;
+use crate::{js, use_document, use_event_listener, use_supported};
+use leptos::ev::mousemove;
+use leptos::*;
+use thiserror::Error;
+
+/// Reactive [Pointer Lock API](https://developer.mozilla.org/en-US/docs/Web/API/Pointer_Lock_API).
+/// Hides the cursor and reports the raw movement of the mouse, for example to look around in a
+/// 3D scene or a game.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Canvas;
+/// # use leptos_use::use_pointer_lock;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let canvas = create_node_ref::<Canvas>();
+///
+/// let pointer_lock = use_pointer_lock(canvas);
+///
+/// create_effect(move |_| {
+///     let (x, y) = (pointer_lock.movement_x.get(), pointer_lock.movement_y.get());
+///     // rotate the camera by `x` and `y`
+/// });
+///
+/// view! {
+///     <canvas node_ref=canvas on:click=move |_| { let _ = pointer_lock.lock(); }></canvas>
+///     <Show when=move || pointer_lock.is_locked.get()>
+///         <p>"Press Escape to get the cursor back"</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// `lock` has to be called from a user gesture like a click. The browser releases the lock when
+/// the user presses <kbd>Escape</kbd> and `is_locked` follows. The lock is also released when the
+/// reactive scope is disposed.
+///
+/// `movement_x` and `movement_y` are the movement of the last `mousemove` in CSS pixels while the
+/// pointer is locked and `0` otherwise.
+///
+/// The browser reports a lock that could not be acquired only asynchronously. In that case
+/// `error` becomes `Some(UsePointerLockError::Failed)`.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_locked` is always `false` and `lock` returns
+/// `UsePointerLockError::NotSupported`.
+pub fn use_pointer_lock<El, T>(target: El) -> UsePointerLockReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let target = target.into();
+
+    let is_supported = use_supported(|| js!("exitPointerLock" in &document()));
+
+    let (is_locked, set_locked) = create_signal(false);
+    let (movement_x, set_movement_x) = create_signal(0.0);
+    let (movement_y, set_movement_y) = create_signal(0.0);
+    let (error, set_error) = create_signal(None::<UsePointerLockError>);
+
+    let element = Signal::derive(move || target.get().map(Into::into));
+
+    let _ = use_event_listener(
+        use_document(),
+        ev::Custom::<web_sys::Event>::new("pointerlockchange"),
+        move |_| {
+            let locked = match (element.get_untracked(), document().pointer_lock_element()) {
+                (Some(element), Some(locked_element)) => {
+                    element.is_same_node(Some(&locked_element))
+                }
+                _ => false,
+            };
+
+            set_locked.set(locked);
+
+            if !locked {
+                set_movement_x.set(0.0);
+                set_movement_y.set(0.0);
+            }
+        },
+    );
+
+    let _ = use_event_listener(
+        use_document(),
+        ev::Custom::<web_sys::Event>::new("pointerlockerror"),
+        move |_| set_error.set(Some(UsePointerLockError::Failed)),
+    );
+
+    let _ = use_event_listener(use_document(), mousemove, move |event| {
+        if is_locked.get_untracked() {
+            set_movement_x.set(event.movement_x() as f64);
+            set_movement_y.set(event.movement_y() as f64);
+        }
+    });
+
+    on_cleanup(move || {
+        if is_locked.try_get_untracked().unwrap_or_default() {
+            if let Some(document) = &*use_document() {
+                document.exit_pointer_lock();
+            }
+        }
+    });
+
+    UsePointerLockReturn {
+        is_supported,
+        is_locked: is_locked.into(),
+        movement_x: movement_x.into(),
+        movement_y: movement_y.into(),
+        error: error.into(),
+        element,
+        set_error,
+    }
+}
+
+/// Return type of [`use_pointer_lock`].
+#[derive(Clone, Copy)]
+pub struct UsePointerLockReturn {
+    /// Whether the Pointer Lock API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// Whether the pointer is locked to the target
+    pub is_locked: Signal<bool>,
+
+    /// Horizontal movement of the last `mousemove` while the pointer is locked
+    pub movement_x: Signal<f64>,
+
+    /// Vertical movement of the last `mousemove` while the pointer is locked
+    pub movement_y: Signal<f64>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UsePointerLockError>>,
+
+    element: Signal<Option<web_sys::Element>>,
+    set_error: WriteSignal<Option<UsePointerLockError>>,
+}
+
+impl UsePointerLockReturn {
+    /// Requests to lock the pointer to the target.
+    pub fn lock(&self) -> Result<(), UsePointerLockError> {
+        if !self.is_supported.get_untracked() {
+            return Err(self.fail(UsePointerLockError::NotSupported));
+        }
+
+        let element = self
+            .element
+            .get_untracked()
+            .ok_or_else(|| self.fail(UsePointerLockError::NoTarget))?;
+
+        self.set_error.set(None);
+        element.request_pointer_lock();
+
+        Ok(())
+    }
+
+    /// Releases the pointer. Does nothing if it isn't locked.
+    pub fn unlock(&self) {
+        if self.is_locked.get_untracked() {
+            document().exit_pointer_lock();
+        }
+    }
+
+    fn fail(&self, err: UsePointerLockError) -> UsePointerLockError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// Errors of [`use_pointer_lock`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum UsePointerLockError {
+    #[error("the Pointer Lock API is not supported")]
+    NotSupported,
+    #[error("the target element is not available")]
+    NoTarget,
+    #[error("the browser refused to lock the pointer")]
+    Failed,
+}
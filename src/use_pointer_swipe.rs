@@ -0,0 +1,259 @@
+use crate::core::{ElementMaybeSignal, PointerType, Position};
+use crate::{use_event_listener_with_options, UseEventListenerOptions, UseSwipeDirection};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{pointercancel, pointerdown, pointermove, pointerup};
+use leptos::*;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::PointerEvent;
+
+pub use crate::use_swipe::UseSwipeReturn as UsePointerSwipeReturn;
+
+/// Reactive swipe detection based on [`PointerEvent`](https://developer.mozilla.org/en-US/docs/Web/API/PointerEvent).
+/// Works with mouse, touch and pen alike.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_pointer_swipe, UsePointerSwipeReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+///
+/// let UsePointerSwipeReturn {
+///     is_swiping,
+///     direction,
+///     ..
+/// } = use_pointer_swipe(el);
+///
+/// view! {
+///     <div node_ref=el style="touch-action: none">
+///         {move || is_swiping.get().then(|| format!("{:?}", direction.get()))}
+///     </div>
+/// }
+/// # }
+/// ```
+///
+/// The pointer is captured by the element that received the `pointerdown` so the swipe
+/// continues when the pointer leaves the element. For touch input you should set
+/// `touch-action: none` on the element, otherwise the browser cancels the pointer to scroll.
+///
+/// ## Pointer Types
+///
+/// Use `pointer_types` to only react to some kinds of input, for example to leave touch input
+/// to the browser.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::core::PointerType;
+/// # use leptos_use::{use_pointer_swipe_with_options, UsePointerSwipeOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+///
+/// let swipe = use_pointer_swipe_with_options(
+///     el,
+///     UsePointerSwipeOptions::default()
+///         .pointer_types(vec![PointerType::Mouse, PointerType::Pen])
+///         .on_swipe_end(|_, direction| {
+///             leptos::logging::log!("Swiped {direction:?}");
+///         }),
+/// );
+/// #
+/// # view! { <div node_ref=el></div> }
+/// # }
+/// ```
+///
+/// For touch only detection see [`fn@crate::use_swipe`].
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_swiping` is always `false` and `direction` is always `UseSwipeDirection::None`.
+pub fn use_pointer_swipe<El, T>(target: El) -> UsePointerSwipeReturn<impl Fn() + Clone>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+{
+    use_pointer_swipe_with_options(target, UsePointerSwipeOptions::default())
+}
+
+/// Version of [`use_pointer_swipe`] that takes a `UsePointerSwipeOptions`. See [`use_pointer_swipe`] for how to use.
+pub fn use_pointer_swipe_with_options<El, T>(
+    target: El,
+    options: UsePointerSwipeOptions,
+) -> UsePointerSwipeReturn<impl Fn() + Clone>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+{
+    let UsePointerSwipeOptions {
+        threshold,
+        pointer_types,
+        on_swipe_start,
+        on_swipe,
+        on_swipe_end,
+    } = options;
+
+    let (coords_start, set_coords_start) = create_signal(Position::default());
+    let (coords_end, set_coords_end) = create_signal(Position::default());
+    let (is_swiping, set_swiping) = create_signal(false);
+    let (is_pointer_down, set_pointer_down) = create_signal(false);
+
+    let length_x = Signal::derive(move || coords_start.get().x - coords_end.get().x);
+    let length_y = Signal::derive(move || coords_start.get().y - coords_end.get().y);
+    let direction = Signal::derive(move || {
+        UseSwipeDirection::from_lengths(length_x.get(), length_y.get(), threshold)
+    });
+
+    let pointer_position = |event: &PointerEvent| Position {
+        x: event.client_x() as f64,
+        y: event.client_y() as f64,
+    };
+
+    let listener_options = UseEventListenerOptions::default().passive(true);
+
+    let target = target.into();
+
+    let stop_down = use_event_listener_with_options(
+        target.clone(),
+        pointerdown,
+        move |event| {
+            let is_allowed_type = pointer_types
+                .iter()
+                .any(|pointer_type| pointer_type.to_string() == event.pointer_type());
+            if !is_allowed_type || !event.is_primary() {
+                return;
+            }
+
+            if let Some(element) = event
+                .target()
+                .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+            {
+                let _ = element.set_pointer_capture(event.pointer_id());
+            }
+
+            set_pointer_down.set(true);
+
+            let position = pointer_position(&event);
+            set_coords_start.set(position);
+            set_coords_end.set(position);
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_swipe_start(event);
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        },
+        listener_options,
+    );
+
+    let stop_move = use_event_listener_with_options(
+        target.clone(),
+        pointermove,
+        move |event| {
+            if !is_pointer_down.get_untracked() || !event.is_primary() {
+                return;
+            }
+
+            set_coords_end.set(pointer_position(&event));
+
+            if !is_swiping.get_untracked() && direction.get_untracked() != UseSwipeDirection::None {
+                set_swiping.set(true);
+            }
+
+            if is_swiping.get_untracked() {
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                on_swipe(event);
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+            }
+        },
+        listener_options,
+    );
+
+    let on_pointer_up = move |event: PointerEvent| {
+        if !is_pointer_down.get_untracked() || !event.is_primary() {
+            return;
+        }
+
+        if is_swiping.get_untracked() {
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_swipe_end(event, direction.get_untracked());
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        }
+
+        set_pointer_down.set(false);
+        set_swiping.set(false);
+    };
+
+    let stop_up = use_event_listener_with_options(
+        target.clone(),
+        pointerup,
+        on_pointer_up.clone(),
+        listener_options,
+    );
+    let stop_cancel =
+        use_event_listener_with_options(target, pointercancel, on_pointer_up, listener_options);
+
+    let stop = move || {
+        stop_down();
+        stop_move();
+        stop_up();
+        stop_cancel();
+    };
+
+    UsePointerSwipeReturn {
+        is_swiping: is_swiping.into(),
+        direction,
+        coords_start: coords_start.into(),
+        coords_end: coords_end.into(),
+        length_x,
+        length_y,
+        stop,
+    }
+}
+
+/// Options for [`use_pointer_swipe_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UsePointerSwipeOptions {
+    /// How many pixels the pointer has to move before a swipe is detected. Defaults to `50.0`.
+    threshold: f64,
+
+    /// Pointer types that can start a swipe. Defaults to all pointer types.
+    pointer_types: Vec<PointerType>,
+
+    /// Called when the pointer is pressed on the target.
+    on_swipe_start: Rc<dyn Fn(PointerEvent)>,
+
+    /// Called on every move during a swipe.
+    on_swipe: Rc<dyn Fn(PointerEvent)>,
+
+    /// Called when the swipe ends with the final direction.
+    on_swipe_end: Rc<dyn Fn(PointerEvent, UseSwipeDirection)>,
+}
+
+impl Default for UsePointerSwipeOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 50.0,
+            pointer_types: vec![PointerType::Mouse, PointerType::Touch, PointerType::Pen],
+            on_swipe_start: Rc::new(|_| {}),
+            on_swipe: Rc::new(|_| {}),
+            on_swipe_end: Rc::new(|_, _| {}),
+        }
+    }
+}
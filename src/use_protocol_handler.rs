@@ -0,0 +1,169 @@
+use crate::{js, use_supported};
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Registers the web app as handler of a custom URL scheme with
+/// [`navigator.registerProtocolHandler`](https://developer.mozilla.org/en-US/docs/Web/API/Navigator/registerProtocolHandler).
+///
+/// ## Usage
+///
+/// `scheme` is either a safelisted scheme like `mailto` or a custom one that starts with `web+`
+/// like `web+myapp`. `url` has to contain `%s` which is replaced by the escaped URL that was
+/// opened and must be of the same origin as the page.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_protocol_handler, ProtocolHandlerState};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let handler = use_protocol_handler("web+myapp", "/open?url=%s");
+///
+/// view! {
+///     <Show when=move || handler.is_supported.get()>
+///         <button on:click=move |_| { let _ = handler.register(); }>
+///             "Open web+myapp links with this app"
+///         </button>
+///         <Show when=move || handler.state.get() == ProtocolHandlerState::Attempted>
+///             <p>"Please confirm in the browser dialog"</p>
+///         </Show>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// The browser asks the user to confirm the registration but doesn't report the answer.
+/// Therefore `state` only tells whether `register` or `unregister` have been called successfully.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `state` is always `Idle` and `register` and `unregister` return
+/// `UseProtocolHandlerError::NotSupported`.
+pub fn use_protocol_handler(
+    scheme: impl Into<String>,
+    url: impl Into<String>,
+) -> UseProtocolHandlerReturn {
+    let is_supported = use_supported(|| js!("registerProtocolHandler" in &window().navigator()));
+
+    let (state, set_state) = create_signal(ProtocolHandlerState::Idle);
+    let (error, set_error) = create_signal(None::<UseProtocolHandlerError>);
+
+    UseProtocolHandlerReturn {
+        is_supported,
+        state: state.into(),
+        error: error.into(),
+        scheme: store_value(scheme.into()),
+        url: store_value(url.into()),
+        set_state,
+        set_error,
+    }
+}
+
+/// Whether the protocol handler has been registered, see [`use_protocol_handler`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProtocolHandlerState {
+    /// Neither `register` nor `unregister` have been called
+    #[default]
+    Idle,
+    /// The browser has been asked to register the handler
+    Attempted,
+    /// The handler has been unregistered
+    Unregistered,
+}
+
+/// Return type of [`use_protocol_handler`].
+#[derive(Clone, Copy)]
+pub struct UseProtocolHandlerReturn {
+    /// Whether the browser supports registering protocol handlers
+    pub is_supported: Signal<bool>,
+
+    /// Whether registering the handler has been attempted
+    pub state: Signal<ProtocolHandlerState>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseProtocolHandlerError>>,
+
+    scheme: StoredValue<String>,
+    url: StoredValue<String>,
+    set_state: WriteSignal<ProtocolHandlerState>,
+    set_error: WriteSignal<Option<UseProtocolHandlerError>>,
+}
+
+impl UseProtocolHandlerReturn {
+    /// Asks the browser to open URLs of the scheme with this app.
+    pub fn register(&self) -> Result<(), UseProtocolHandlerError> {
+        self.navigator()?
+            .register_protocol_handler(&self.scheme.get_value(), &self.url.get_value())
+            .map_err(|err| self.fail(UseProtocolHandlerError::Register(err)))?;
+
+        self.set_state.set(ProtocolHandlerState::Attempted);
+        self.set_error.set(None);
+
+        Ok(())
+    }
+
+    /// Removes the registration. Not supported by all browsers that support `register`.
+    pub fn unregister(&self) -> Result<(), UseProtocolHandlerError> {
+        let navigator = self.navigator()?;
+
+        if !js!("unregisterProtocolHandler" in &navigator) {
+            return Err(self.fail(UseProtocolHandlerError::NotSupported));
+        }
+
+        navigator
+            .unregister_protocol_handler(&self.scheme.get_value(), &self.url.get_value())
+            .map_err(|err| self.fail(UseProtocolHandlerError::Unregister(err)))?;
+
+        self.set_state.set(ProtocolHandlerState::Unregistered);
+        self.set_error.set(None);
+
+        Ok(())
+    }
+
+    fn navigator(&self) -> Result<ProtocolHandlerNavigator, UseProtocolHandlerError> {
+        if self.is_supported.get_untracked() {
+            Ok(window().navigator().unchecked_into())
+        } else {
+            Err(UseProtocolHandlerError::NotSupported)
+        }
+    }
+
+    fn fail(&self, err: UseProtocolHandlerError) -> UseProtocolHandlerError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// Errors of [`use_protocol_handler`].
+#[derive(Error, Debug, Clone)]
+pub enum UseProtocolHandlerError {
+    #[error("registering protocol handlers is not supported")]
+    NotSupported,
+    #[error("registering the protocol handler failed")]
+    Register(JsValue),
+    #[error("unregistering the protocol handler failed")]
+    Unregister(JsValue),
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Clone, Debug)]
+    #[wasm_bindgen(extends = web_sys::Navigator, js_name = Navigator)]
+    type ProtocolHandlerNavigator;
+
+    #[wasm_bindgen(catch, method, js_name = registerProtocolHandler)]
+    fn register_protocol_handler(
+        this: &ProtocolHandlerNavigator,
+        scheme: &str,
+        url: &str,
+    ) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = unregisterProtocolHandler)]
+    fn unregister_protocol_handler(
+        this: &ProtocolHandlerNavigator,
+        scheme: &str,
+        url: &str,
+    ) -> Result<(), JsValue>;
+}
@@ -0,0 +1,317 @@
+use crate::use_web_notification::request_web_notification_permission;
+use crate::utils::ready_service_worker_registration;
+use crate::{js, js_fut, use_supported, NotificationPermission};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{PushManager, PushSubscription, PushSubscriptionOptionsInit};
+
+/// Reactive [Push API](https://developer.mozilla.org/en-US/docs/Web/API/Push_API).
+/// Subscribes to push messages that are delivered to the service worker even when the page
+/// isn't open.
+///
+/// A service worker has to be registered, for example with
+/// [`use_service_worker`](crate::use_service_worker).
+///
+/// ## Usage
+///
+/// `application_server_key` is the public VAPID key of your server, encoded as base64url.
+/// `subscribe` asks for the notification permission if it hasn't been granted yet and then
+/// subscribes. Send the resulting `info` to your server so it can push messages.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_push_subscription, NotificationPermission};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let push = use_push_subscription("BEl62iUYgUivxIkv69yViEuiBIa-Ib9-SkvMeAtA3LFgDzkrxZJjSgSnfckjBJuBkr3qBUYIHBQFLXYp5Nksh8U");
+///
+/// let subscribe = move |_| {
+///     spawn_local(async move {
+///         if push.subscribe().await.is_ok() {
+///             if let Some(info) = push.info.get_untracked() {
+///                 // send `info.endpoint` and `info.keys` to your server
+///             }
+///         }
+///     });
+/// };
+///
+/// let unsubscribe = move |_| spawn_local(async move {
+///     let _ = push.unsubscribe().await;
+/// });
+///
+/// view! {
+///     <Show
+///         when=move || push.subscription.with(Option::is_some)
+///         fallback=move || view! {
+///             <button
+///                 on:click=subscribe
+///                 disabled=move || push.permission.get() == NotificationPermission::Denied
+///             >
+///                 "Subscribe"
+///             </button>
+///         }
+///     >
+///         <button on:click=unsubscribe>"Unsubscribe"</button>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// An existing subscription is loaded as soon as the service worker is ready.
+///
+/// With the feature `serde` enabled, [`PushSubscriptionInfo`] implements `Serialize` and
+/// `Deserialize` in the JSON format of `PushSubscription.toJSON()` that push libraries like
+/// `web-push` expect.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `subscription` is always `None` and `subscribe` and `unsubscribe` return
+/// `UsePushSubscriptionError::NotSupported`.
+pub fn use_push_subscription(
+    application_server_key: impl Into<String>,
+) -> UsePushSubscriptionReturn {
+    use_push_subscription_with_options(
+        application_server_key,
+        UsePushSubscriptionOptions::default(),
+    )
+}
+
+/// Version of [`use_push_subscription`] that takes a `UsePushSubscriptionOptions`. See [`use_push_subscription`] for how to use.
+pub fn use_push_subscription_with_options(
+    application_server_key: impl Into<String>,
+    options: UsePushSubscriptionOptions,
+) -> UsePushSubscriptionReturn {
+    let UsePushSubscriptionOptions { user_visible_only } = options;
+
+    let is_supported = use_supported(|| {
+        js!("PushManager" in &window())
+            && js!("Notification" in &window())
+            && js!("serviceWorker" in &window().navigator())
+    });
+
+    let (subscription, set_subscription) = create_signal(None::<PushSubscription>);
+    let (permission, set_permission) = create_signal(NotificationPermission::default());
+    let (error, set_error) = create_signal(None::<UsePushSubscriptionError>);
+
+    let info = Signal::derive(move || {
+        subscription.with(|subscription| subscription.as_ref().and_then(PushSubscriptionInfo::new))
+    });
+
+    if is_supported.get_untracked() {
+        set_permission.set(web_sys::Notification::permission().into());
+
+        spawn_local(async move {
+            let existing = async {
+                let subscription = js_fut!(push_manager().await?.get_subscription()?).await?;
+
+                Ok::<_, JsValue>(subscription.dyn_into::<PushSubscription>().ok())
+            }
+            .await;
+
+            match existing {
+                Ok(existing) => {
+                    let _ = set_subscription.try_set(existing);
+                }
+                Err(err) => {
+                    let _ = set_error.try_set(Some(UsePushSubscriptionError::Registration(err)));
+                }
+            }
+        });
+    }
+
+    UsePushSubscriptionReturn {
+        is_supported,
+        subscription: subscription.into(),
+        info,
+        permission: permission.into(),
+        error: error.into(),
+        application_server_key: store_value(application_server_key.into()),
+        user_visible_only,
+        set_subscription,
+        set_permission,
+        set_error,
+    }
+}
+
+/// Options for [`use_push_subscription_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UsePushSubscriptionOptions {
+    /// Whether every push message results in a notification that is visible to the user.
+    /// Chrome only supports `true`. Defaults to `true`.
+    user_visible_only: bool,
+}
+
+impl Default for UsePushSubscriptionOptions {
+    fn default() -> Self {
+        Self {
+            user_visible_only: true,
+        }
+    }
+}
+
+/// Return type of [`use_push_subscription`].
+#[derive(Clone, Copy)]
+pub struct UsePushSubscriptionReturn {
+    /// Whether the Push API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The current subscription
+    pub subscription: Signal<Option<PushSubscription>>,
+
+    /// The endpoint and keys of the current subscription that your server needs to push messages
+    pub info: Signal<Option<PushSubscriptionInfo>>,
+
+    /// The permission to show notifications which is required to subscribe
+    pub permission: Signal<NotificationPermission>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UsePushSubscriptionError>>,
+
+    application_server_key: StoredValue<String>,
+    user_visible_only: bool,
+    set_subscription: WriteSignal<Option<PushSubscription>>,
+    set_permission: WriteSignal<NotificationPermission>,
+    set_error: WriteSignal<Option<UsePushSubscriptionError>>,
+}
+
+impl UsePushSubscriptionReturn {
+    /// Requests the notification permission if necessary and subscribes to push messages.
+    /// Returns the existing subscription if there is one.
+    pub async fn subscribe(&self) -> Result<PushSubscription, UsePushSubscriptionError> {
+        self.check_supported()?;
+
+        if let Some(subscription) = self.subscription.get_untracked() {
+            return Ok(subscription);
+        }
+
+        let permission = request_web_notification_permission().await;
+        self.set_permission.set(permission);
+
+        if permission != NotificationPermission::Granted {
+            return Err(self.fail(UsePushSubscriptionError::PermissionDenied));
+        }
+
+        let push_manager = push_manager()
+            .await
+            .map_err(|err| self.fail(UsePushSubscriptionError::Registration(err)))?;
+
+        let subscribe_options = PushSubscriptionOptionsInit::new();
+        subscribe_options.set_user_visible_only(self.user_visible_only);
+        subscribe_options.set_application_server_key(&JsValue::from_str(
+            &self.application_server_key.get_value(),
+        ));
+
+        let subscription = async {
+            js_fut!(push_manager.subscribe_with_options(&subscribe_options)?)
+                .await
+                .map(JsCast::unchecked_into::<PushSubscription>)
+        }
+        .await
+        .map_err(|err| self.fail(UsePushSubscriptionError::Subscribe(err)))?;
+
+        self.set_subscription.set(Some(subscription.clone()));
+        self.set_error.set(None);
+
+        Ok(subscription)
+    }
+
+    /// Cancels the current subscription. Your server can't push messages afterwards.
+    pub async fn unsubscribe(&self) -> Result<(), UsePushSubscriptionError> {
+        self.check_supported()?;
+
+        let Some(subscription) = self.subscription.get_untracked() else {
+            return Ok(());
+        };
+
+        async { js_fut!(subscription.unsubscribe()?).await }
+            .await
+            .map_err(|err| self.fail(UsePushSubscriptionError::Unsubscribe(err)))?;
+
+        self.set_subscription.set(None);
+        self.set_error.set(None);
+
+        Ok(())
+    }
+
+    fn check_supported(&self) -> Result<(), UsePushSubscriptionError> {
+        if self.is_supported.get_untracked() {
+            Ok(())
+        } else {
+            Err(UsePushSubscriptionError::NotSupported)
+        }
+    }
+
+    fn fail(&self, err: UsePushSubscriptionError) -> UsePushSubscriptionError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// Waits for the active service worker and returns the push manager of its registration.
+async fn push_manager() -> Result<PushManager, JsValue> {
+    ready_service_worker_registration().await?.push_manager()
+}
+
+/// The endpoint and keys of a [`PushSubscription`]. This is everything a server needs to send
+/// push messages to the subscription.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct PushSubscriptionInfo {
+    /// The URL the server sends the push messages to
+    pub endpoint: String,
+
+    /// When the subscription expires in milliseconds since the UNIX epoch
+    pub expiration_time: Option<f64>,
+
+    /// The keys to encrypt the push messages with
+    pub keys: PushSubscriptionKeys,
+}
+
+/// The keys of a [`PushSubscriptionInfo`], encoded as base64url.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PushSubscriptionKeys {
+    /// The public key of the client
+    pub p256dh: String,
+
+    /// The authentication secret
+    pub auth: String,
+}
+
+impl PushSubscriptionInfo {
+    fn new(subscription: &PushSubscription) -> Option<Self> {
+        let json = subscription.to_json().ok()?;
+        let keys = js!(json["keys"]).ok()?;
+
+        Some(Self {
+            endpoint: subscription.endpoint(),
+            expiration_time: js!(json["expirationTime"]).ok()?.as_f64(),
+            keys: PushSubscriptionKeys {
+                p256dh: js!(keys["p256dh"]).ok()?.as_string()?,
+                auth: js!(keys["auth"]).ok()?.as_string()?,
+            },
+        })
+    }
+}
+
+/// Errors of [`use_push_subscription`].
+#[derive(Error, Debug, Clone)]
+pub enum UsePushSubscriptionError {
+    #[error("the Push API is not supported")]
+    NotSupported,
+    #[error("the permission to show notifications has not been granted")]
+    PermissionDenied,
+    #[error("failed to get the service worker registration")]
+    Registration(JsValue),
+    #[error("failed to subscribe")]
+    Subscribe(JsValue),
+    #[error("failed to unsubscribe")]
+    Unsubscribe(JsValue),
+}
@@ -0,0 +1,317 @@
+use crate::js_fut;
+use crate::utils::JsCodec;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{ReadableStream, ReadableStreamDefaultReader, ReadableStreamReadResult};
+
+/// Reads a [ReadableStream](https://developer.mozilla.org/en-US/docs/Web/API/ReadableStream)
+/// chunk by chunk into reactive signals.
+///
+/// ## Usage
+///
+/// Reading starts as soon as `stream` is `Some`. Every chunk is decoded with the given
+/// [`JsCodec`] and stored in `chunk`. Use [`JsValueCodec`] to get the chunks as they are, for
+/// example as `js_sys::Uint8Array` for the body of a fetch response.
+///
+/// [`JsValueCodec`]: crate::utils::JsValueCodec
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{js_fut, use_readable_stream, UseReadableStreamReturn};
+/// # use leptos_use::utils::JsValueCodec;
+/// # use wasm_bindgen::JsCast;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (body, set_body) = create_signal(None::<web_sys::ReadableStream>);
+///
+/// spawn_local(async move {
+///     if let Ok(response) = js_fut!(window().fetch_with_str("/log")).await {
+///         set_body.set(response.unchecked_into::<web_sys::Response>().body());
+///     }
+/// });
+///
+/// let UseReadableStreamReturn { chunk, done, .. } =
+///     use_readable_stream::<js_sys::Uint8Array, JsValueCodec>(body);
+///
+/// view! {
+///     <p>"Latest chunk: " {move || chunk.get().map(|chunk| chunk.length())} " bytes"</p>
+///     <Show when=move || done.get()>"Finished"</Show>
+/// }
+/// # }
+/// ```
+///
+/// Set `accumulate` to keep every chunk that has been read in `chunks`.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_readable_stream_with_options, UseReadableStreamOptions, UseReadableStreamReturn};
+/// # use leptos_use::utils::JsValueCodec;
+/// # use wasm_bindgen::JsValue;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// # let stream = None::<web_sys::ReadableStream>;
+/// let UseReadableStreamReturn { chunks, .. } = use_readable_stream_with_options::<JsValue, _>(
+///     stream,
+///     UseReadableStreamOptions::<JsValue, JsValueCodec>::default().accumulate(true),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Backpressure
+///
+/// `pause` stops reading after the current chunk. A paused stream buffers chunks until its queue
+/// is full and then stops pulling from its source, for example the network. `resume` continues
+/// where reading stopped.
+///
+/// `cancel` cancels the stream, which can't be resumed afterwards. This happens automatically
+/// when `stream` changes or the component is cleaned up.
+///
+/// ## Server-Side Rendering
+///
+/// On the server there are no streams so all signals keep their initial values.
+pub fn use_readable_stream<T, C>(
+    stream: impl Into<MaybeSignal<Option<ReadableStream>>>,
+) -> UseReadableStreamReturn<T, C::Error, impl Fn() + Clone, impl Fn() + Clone, impl Fn() + Clone>
+where
+    T: Clone + 'static,
+    C: JsCodec<T> + Default,
+{
+    use_readable_stream_with_options(stream, UseReadableStreamOptions::<T, C>::default())
+}
+
+/// Version of [`use_readable_stream`] that takes a `UseReadableStreamOptions`. See [`use_readable_stream`] for how to use.
+pub fn use_readable_stream_with_options<T, C>(
+    stream: impl Into<MaybeSignal<Option<ReadableStream>>>,
+    options: UseReadableStreamOptions<T, C>,
+) -> UseReadableStreamReturn<T, C::Error, impl Fn() + Clone, impl Fn() + Clone, impl Fn() + Clone>
+where
+    T: Clone + 'static,
+    C: JsCodec<T>,
+{
+    let stream = stream.into();
+    let UseReadableStreamOptions {
+        codec,
+        accumulate,
+        on_chunk,
+    } = options;
+
+    let (chunk, set_chunk) = create_signal(None::<T>);
+    let (chunks, set_chunks) = create_signal(Vec::<T>::new());
+    let (done, set_done) = create_signal(false);
+    let (is_paused, set_paused) = create_signal(false);
+    let (error, set_error) = create_signal(None::<UseReadableStreamError<C::Error>>);
+
+    let reader = store_value(None::<ReadableStreamDefaultReader>);
+    // Incremented for every new reader so that a read of a previous one is ignored.
+    let generation = store_value(0_usize);
+    let is_reading = store_value(false);
+
+    let read = move || {
+        if is_reading.get_value() {
+            return;
+        }
+        is_reading.set_value(true);
+
+        let codec = codec.clone();
+        let on_chunk = Rc::clone(&on_chunk);
+
+        spawn_local(async move {
+            while is_paused.try_get_untracked() == Some(false) {
+                let Some((current, current_generation)) = reader
+                    .try_get_value()
+                    .flatten()
+                    .map(|reader| (reader, generation.get_value()))
+                else {
+                    break;
+                };
+
+                let result = js_fut!(current.read()).await;
+
+                if generation.try_get_value() != Some(current_generation) {
+                    // the stream has been replaced or the component has been cleaned up
+                    continue;
+                }
+
+                let result = match result {
+                    Ok(result) => result.unchecked_into::<ReadableStreamReadResult>(),
+                    Err(err) => {
+                        reader.set_value(None);
+                        set_error.set(Some(UseReadableStreamError::Read(err)));
+                        break;
+                    }
+                };
+
+                if result.get_done().unwrap_or_default() {
+                    current.release_lock();
+                    reader.set_value(None);
+                    set_done.set(true);
+                    break;
+                }
+
+                match codec.decode(result.get_value()) {
+                    Ok(val) => {
+                        #[cfg(debug_assertions)]
+                        let prev = SpecialNonReactiveZone::enter();
+
+                        on_chunk(&val);
+
+                        #[cfg(debug_assertions)]
+                        SpecialNonReactiveZone::exit(prev);
+
+                        if accumulate {
+                            set_chunks.update(|chunks| chunks.push(val.clone()));
+                        }
+                        set_chunk.set(Some(val));
+                    }
+                    Err(err) => set_error.set(Some(UseReadableStreamError::Decode(err))),
+                }
+            }
+
+            let _ = is_reading.try_set_value(false);
+        });
+    };
+
+    let cancel = move || {
+        if let Some(Some(current)) = reader.try_update_value(Option::take) {
+            let _ = current.cancel();
+            generation.update_value(|generation| *generation += 1);
+        }
+    };
+
+    let _ = watch(
+        move || stream.get(),
+        {
+            let read = read.clone();
+
+            move |stream: &Option<ReadableStream>, _, _| {
+                cancel();
+
+                set_chunk.set(None);
+                set_chunks.set(Vec::new());
+                set_done.set(false);
+                set_error.set(None);
+
+                let Some(stream) = stream else {
+                    return;
+                };
+
+                match stream
+                    .get_reader()
+                    .dyn_into::<ReadableStreamDefaultReader>()
+                {
+                    Ok(new_reader) => {
+                        generation.update_value(|generation| *generation += 1);
+                        reader.set_value(Some(new_reader));
+
+                        if !is_paused.get_untracked() {
+                            read();
+                        }
+                    }
+                    Err(err) => set_error.set(Some(UseReadableStreamError::Reader(err.into()))),
+                }
+            }
+        },
+        true,
+    );
+
+    let pause = move || set_paused.set(true);
+
+    let resume = move || {
+        set_paused.set(false);
+        read();
+    };
+
+    on_cleanup(cancel);
+
+    UseReadableStreamReturn {
+        chunk: chunk.into(),
+        chunks: chunks.into(),
+        done: done.into(),
+        is_paused: is_paused.into(),
+        error: error.into(),
+        pause,
+        resume,
+        cancel,
+    }
+}
+
+/// Options for [`use_readable_stream_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseReadableStreamOptions<T, C>
+where
+    T: 'static,
+    C: JsCodec<T>,
+{
+    /// Decodes the chunks of the stream.
+    #[builder(keep_type)]
+    codec: C,
+
+    /// If `true` every chunk that has been read is kept in `chunks`. Defaults to `false`.
+    accumulate: bool,
+
+    /// Called for every successfully decoded chunk.
+    #[builder(keep_type)]
+    on_chunk: Rc<dyn Fn(&T)>,
+}
+
+impl<T, C: JsCodec<T> + Default> Default for UseReadableStreamOptions<T, C> {
+    fn default() -> Self {
+        Self {
+            codec: C::default(),
+            accumulate: false,
+            on_chunk: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// Return type of [`use_readable_stream`].
+#[derive(Clone)]
+pub struct UseReadableStreamReturn<T, Err, PauseFn, ResumeFn, CancelFn>
+where
+    T: 'static,
+    Err: 'static,
+    PauseFn: Fn() + Clone,
+    ResumeFn: Fn() + Clone,
+    CancelFn: Fn() + Clone,
+{
+    /// The latest chunk that has been read
+    pub chunk: Signal<Option<T>>,
+
+    /// All chunks that have been read if `accumulate` is enabled
+    pub chunks: Signal<Vec<T>>,
+
+    /// `true` once the whole stream has been read
+    pub done: Signal<bool>,
+
+    /// Whether reading is paused
+    pub is_paused: Signal<bool>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseReadableStreamError<Err>>>,
+
+    /// Stops reading after the current chunk
+    pub pause: PauseFn,
+
+    /// Continues reading
+    pub resume: ResumeFn,
+
+    /// Cancels the stream
+    pub cancel: CancelFn,
+}
+
+#[derive(Error, Debug, Clone)]
+pub enum UseReadableStreamError<Err> {
+    #[error("failed to get a reader for the stream")]
+    Reader(JsValue),
+    #[error("failed to read from the stream")]
+    Read(JsValue),
+    #[error("failed to decode chunk")]
+    Decode(Err),
+}
@@ -0,0 +1,258 @@
+use crate::{js, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+
+/// Reactive [ReportingObserver](https://developer.mozilla.org/en-US/docs/Web/API/ReportingObserver).
+/// Collects the reports the browser generates for the page, for example when a deprecated API
+/// is used or the browser intervened to block something.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_reporting_observer;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let reporting = use_reporting_observer();
+///
+/// view! {
+///     <ul>
+///         <For each=move || reporting.reports.get() key=|report| report.clone() let:report>
+///             <li>{report.report_type} ": " {report.message}</li>
+///         </For>
+///     </ul>
+/// }
+/// # }
+/// ```
+///
+/// Reports that were generated before the observer was created are included as well. Set
+/// `buffered` to `false` to only get new reports.
+///
+/// ## Filtering and reporting
+///
+/// Use `types` to only observe some kinds of reports like `"deprecation"`, `"intervention"` or
+/// `"crash"` and `on_report` to forward them to your monitoring.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_reporting_observer_with_options, UseReportingObserverOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// use_reporting_observer_with_options(
+///     UseReportingObserverOptions::default()
+///         .types(vec!["deprecation".to_string()])
+///         .on_report(|report| {
+///             // send `report` to your server
+///         }),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// With the feature `serde` enabled, [`BrowserReport`] implements `Serialize` and `Deserialize`.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `reports` is always empty.
+pub fn use_reporting_observer() -> UseReportingObserverReturn {
+    use_reporting_observer_with_options(UseReportingObserverOptions::default())
+}
+
+/// Version of [`use_reporting_observer`] that takes a `UseReportingObserverOptions`. See [`use_reporting_observer`] for how to use.
+pub fn use_reporting_observer_with_options(
+    options: UseReportingObserverOptions,
+) -> UseReportingObserverReturn {
+    let UseReportingObserverOptions {
+        types,
+        buffered,
+        max_reports,
+        on_report,
+    } = options;
+
+    let is_supported = use_supported(|| js!("ReportingObserver" in &window()));
+
+    let (reports, set_reports) = create_signal(Vec::<BrowserReport>::new());
+
+    if is_supported.get_untracked() {
+        let callback = Closure::<dyn Fn(js_sys::Array)>::new(move |entries: js_sys::Array| {
+            let new_reports = entries
+                .iter()
+                .map(|entry| BrowserReport::from_js(&entry))
+                .collect::<Vec<_>>();
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            for report in &new_reports {
+                on_report(report);
+            }
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+
+            if max_reports == 0 {
+                return;
+            }
+
+            let _ = set_reports.try_update(|reports| {
+                reports.extend(new_reports);
+                if reports.len() > max_reports {
+                    reports.drain(..reports.len() - max_reports);
+                }
+            });
+        })
+        .into_js_value();
+
+        let observer_options = js_sys::Object::new();
+        js!(observer_options["buffered"] = buffered);
+        if !types.is_empty() {
+            let types = types.iter().map(JsValue::from).collect::<js_sys::Array>();
+            js!(observer_options["types"] = types);
+        }
+
+        if let Ok(observer) = ReportingObserver::new(callback.unchecked_ref(), &observer_options) {
+            observer.observe();
+            on_cleanup(move || observer.disconnect());
+        }
+    }
+
+    UseReportingObserverReturn {
+        is_supported,
+        reports: reports.into(),
+        set_reports,
+    }
+}
+
+/// Options for [`use_reporting_observer_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseReportingObserverOptions {
+    /// The types of reports to observe, for example `"deprecation"`. Defaults to an empty list
+    /// which observes all types.
+    types: Vec<String>,
+
+    /// Whether reports that have been generated before the observer was created are included.
+    /// Defaults to `true`.
+    buffered: bool,
+
+    /// How many reports are kept in `reports`. The oldest ones are dropped first.
+    /// Defaults to `100`.
+    max_reports: usize,
+
+    /// Called for every report.
+    on_report: Rc<dyn Fn(&BrowserReport)>,
+}
+
+impl Default for UseReportingObserverOptions {
+    fn default() -> Self {
+        Self {
+            types: vec![],
+            buffered: true,
+            max_reports: 100,
+            on_report: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// Return type of [`use_reporting_observer`].
+#[derive(Clone, Copy)]
+pub struct UseReportingObserverReturn {
+    /// Whether the `ReportingObserver` is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The reports that have been observed, the oldest first
+    pub reports: Signal<Vec<BrowserReport>>,
+
+    set_reports: WriteSignal<Vec<BrowserReport>>,
+}
+
+impl UseReportingObserverReturn {
+    /// Removes all reports from `reports`.
+    pub fn clear(&self) {
+        self.set_reports.set(Vec::new());
+    }
+}
+
+/// A report generated by the browser. See
+/// [Report](https://developer.mozilla.org/en-US/docs/Web/API/Report) for the meaning of the fields.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct BrowserReport {
+    /// The type of the report, for example `"deprecation"` or `"intervention"`
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub report_type: String,
+
+    /// The URL of the document that generated the report
+    pub url: String,
+
+    /// The identifier of the deprecated feature or the intervention
+    pub id: Option<String>,
+
+    /// A human readable description
+    pub message: Option<String>,
+
+    /// The URL of the script that caused the report, if known
+    pub source_file: Option<String>,
+
+    /// The line in `source_file`
+    pub line_number: Option<u32>,
+
+    /// The column in `source_file`
+    pub column_number: Option<u32>,
+}
+
+impl BrowserReport {
+    fn from_js(report: &JsValue) -> Self {
+        let string = |obj: &JsValue, key: &str| {
+            js_sys::Reflect::get(obj, &key.into())
+                .ok()
+                .and_then(|value| value.as_string())
+        };
+        let number = |obj: &JsValue, key: &str| {
+            js_sys::Reflect::get(obj, &key.into())
+                .ok()
+                .and_then(|value| value.as_f64())
+                .map(|value| value as u32)
+        };
+
+        let body = js!(report["body"])
+            .ok()
+            .filter(|body| body.is_object())
+            .unwrap_or(JsValue::NULL);
+
+        Self {
+            report_type: string(report, "type").unwrap_or_default(),
+            url: string(report, "url").unwrap_or_default(),
+            id: string(&body, "id"),
+            message: string(&body, "message"),
+            source_file: string(&body, "sourceFile"),
+            line_number: number(&body, "lineNumber"),
+            column_number: number(&body, "columnNumber"),
+        }
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    type ReportingObserver;
+
+    #[wasm_bindgen(catch, constructor)]
+    fn new(
+        callback: &js_sys::Function,
+        options: &js_sys::Object,
+    ) -> Result<ReportingObserver, JsValue>;
+
+    #[wasm_bindgen(method)]
+    fn observe(this: &ReportingObserver);
+
+    #[wasm_bindgen(method)]
+    fn disconnect(this: &ReportingObserver);
+}
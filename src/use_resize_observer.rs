@@ -5,7 +5,7 @@ use leptos::*;
 
 cfg_if! { if #[cfg(not(feature = "ssr"))] {
     use crate::use_supported;
-    use std::cell::RefCell;
+    use std::cell::{Cell, RefCell};
     use std::rc::Rc;
     use wasm_bindgen::prelude::*;
 }}
@@ -44,6 +44,41 @@ cfg_if! { if #[cfg(not(feature = "ssr"))] {
 /// # }
 /// ```
 ///
+/// ## Box
+///
+/// By default the size of the content box is observed. With `box_` you can observe the
+/// border box or the content box in device pixels instead, for example to size a canvas
+/// exactly.
+///
+/// ```
+/// # use leptos::{html::Canvas, *};
+/// # use leptos_use::{use_resize_observer_with_options, UseResizeObserverOptions};
+/// # use web_sys::ResizeObserverBoxOptions;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Canvas>();
+///
+/// use_resize_observer_with_options(
+///     el,
+///     move |entries, _| {
+///         let size = entries[0].device_pixel_content_box_size().get(0);
+///         // set the width and height of the canvas
+///     },
+///     UseResizeObserverOptions::default().box_(ResizeObserverBoxOptions::DevicePixelContentBox),
+/// );
+///
+/// view! { <canvas node_ref=el></canvas> }
+/// # }
+/// ```
+///
+/// ## Shared Observer
+///
+/// All calls with the same `box_` share one `ResizeObserver` which dispatches the entries
+/// to the callbacks of the respective targets. So it's cheap to use this in every item of a
+/// large list. Because of that you shouldn't call `disconnect` on the observer that is passed
+/// to the callback. Use the returned `stop` instead.
+///
 /// ## Server-Side Rendering
 ///
 /// On the server this amounts to a no-op.
@@ -52,7 +87,7 @@ cfg_if! { if #[cfg(not(feature = "ssr"))] {
 ///
 /// - [`use_element_size`]
 pub fn use_resize_observer<El, T, F>(
-    target: El, // TODO : multiple elements?
+    target: El,
     callback: F,
 ) -> UseResizeObserverReturn<impl Fn() + Clone>
 where
@@ -64,10 +99,10 @@ where
 }
 
 /// Version of [`use_resize_observer`] that takes a `web_sys::ResizeObserverOptions`. See [`use_resize_observer`] for how to use.
-#[cfg_attr(feature = "ssr", allow(unused_variables, unused_mut))]
+#[cfg_attr(feature = "ssr", allow(unused_variables))]
 pub fn use_resize_observer_with_options<El, T, F>(
-    target: El, // TODO : multiple elements?
-    mut callback: F,
+    target: El,
+    callback: F,
     options: UseResizeObserverOptions,
 ) -> UseResizeObserverReturn<impl Fn() + Clone>
 where
@@ -87,12 +122,76 @@ where
     {
         use crate::js;
 
+        let is_supported = use_supported(|| js!("ResizeObserver" in &window()));
+
+        let box_ = options
+            .box_
+            .unwrap_or(web_sys::ResizeObserverBoxOptions::ContentBox);
+        let id = NEXT_SUBSCRIPTION_ID.with(|next_id| next_id.replace(next_id.get() + 1));
+        let callback: SharedCallback = Rc::new(RefCell::new(callback));
+
+        let targets = target.into();
+
+        let stop_watch = watch(
+            move || targets.get(),
+            move |targets, _, _| {
+                if is_supported.get_untracked() {
+                    let targets = targets
+                        .iter()
+                        .flatten()
+                        .map(|target| target.clone().into())
+                        .collect();
+
+                    SharedResizeObserver::observe(box_, id, targets, Rc::clone(&callback));
+                }
+            },
+            true,
+        );
+
+        let stop = move || {
+            SharedResizeObserver::unobserve(box_, id);
+            stop_watch();
+        };
+
+        on_cleanup(stop.clone());
+
+        UseResizeObserverReturn { is_supported, stop }
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+type SharedCallback =
+    Rc<RefCell<dyn FnMut(Vec<web_sys::ResizeObserverEntry>, web_sys::ResizeObserver)>>;
+
+#[cfg(not(feature = "ssr"))]
+thread_local! {
+    static SHARED_OBSERVERS: RefCell<Vec<SharedResizeObserver>> = const { RefCell::new(Vec::new()) };
+    static NEXT_SUBSCRIPTION_ID: Cell<usize> = const { Cell::new(0) };
+}
+
+/// One `ResizeObserver` per box option that is shared by all calls of
+/// [`use_resize_observer_with_options`].
+#[cfg(not(feature = "ssr"))]
+struct SharedResizeObserver {
+    box_: web_sys::ResizeObserverBoxOptions,
+    observer: web_sys::ResizeObserver,
+    subscriptions: Vec<Subscription>,
+}
+
+#[cfg(not(feature = "ssr"))]
+struct Subscription {
+    id: usize,
+    targets: Vec<web_sys::Element>,
+    callback: SharedCallback,
+}
+
+#[cfg(not(feature = "ssr"))]
+impl SharedResizeObserver {
+    fn new(box_: web_sys::ResizeObserverBoxOptions) -> Self {
         let closure_js = Closure::<dyn FnMut(js_sys::Array, web_sys::ResizeObserver)>::new(
             move |entries: js_sys::Array, observer| {
-                #[cfg(debug_assertions)]
-                let prev = SpecialNonReactiveZone::enter();
-
-                callback(
+                Self::dispatch(
+                    box_,
                     entries
                         .to_vec()
                         .into_iter()
@@ -100,64 +199,132 @@ where
                         .collect(),
                     observer,
                 );
-
-                #[cfg(debug_assertions)]
-                SpecialNonReactiveZone::exit(prev);
             },
         )
         .into_js_value();
 
-        let observer: Rc<RefCell<Option<web_sys::ResizeObserver>>> = Rc::new(RefCell::new(None));
+        Self {
+            box_,
+            observer: web_sys::ResizeObserver::new(closure_js.unchecked_ref())
+                .expect("failed to create ResizeObserver"),
+            subscriptions: Vec::new(),
+        }
+    }
 
-        let is_supported = use_supported(|| js!("ResizeObserver" in &window()));
+    /// Sets the targets of the subscription `id`, creating the subscription if needed.
+    fn observe(
+        box_: web_sys::ResizeObserverBoxOptions,
+        id: usize,
+        targets: Vec<web_sys::Element>,
+        callback: SharedCallback,
+    ) {
+        SHARED_OBSERVERS.with_borrow_mut(|observers| {
+            let shared = match observers.iter().position(|shared| shared.box_ == box_) {
+                Some(index) => &mut observers[index],
+                None => {
+                    observers.push(Self::new(box_));
+                    observers.last_mut().expect("just pushed")
+                }
+            };
 
-        let cleanup = {
-            let observer = Rc::clone(&observer);
+            let old_targets = match shared.subscriptions.iter_mut().find(|sub| sub.id == id) {
+                Some(subscription) => std::mem::replace(&mut subscription.targets, targets.clone()),
+                None => {
+                    shared.subscriptions.push(Subscription {
+                        id,
+                        targets: targets.clone(),
+                        callback,
+                    });
+                    Vec::new()
+                }
+            };
 
-            move || {
-                let mut observer = observer.borrow_mut();
-                if let Some(o) = observer.as_ref() {
-                    o.disconnect();
-                    *observer = None;
+            for target in &old_targets {
+                if !targets.contains(target) {
+                    shared.unobserve_if_unused(target);
                 }
             }
-        };
 
-        let targets = target.into();
+            let options = UseResizeObserverOptions::default().box_(box_).into();
+            for target in &targets {
+                if !old_targets.contains(target) {
+                    shared.observer.observe_with_options(target, &options);
+                }
+            }
+        });
+    }
 
-        let stop_watch = {
-            let cleanup = cleanup.clone();
-
-            watch(
-                move || targets.get(),
-                move |targets, _, _| {
-                    cleanup();
-
-                    if is_supported.get_untracked() && !targets.is_empty() {
-                        let obs = web_sys::ResizeObserver::new(
-                            closure_js.clone().as_ref().unchecked_ref(),
-                        )
-                        .expect("failed to create ResizeObserver");
-
-                        for target in targets.iter().flatten() {
-                            let target: web_sys::Element = target.clone().into();
-                            obs.observe_with_options(&target, &options.clone().into());
-                        }
-                        observer.replace(Some(obs));
-                    }
-                },
-                true,
-            )
-        };
+    /// Removes the subscription `id`. The observer is disconnected when it was the last one.
+    fn unobserve(box_: web_sys::ResizeObserverBoxOptions, id: usize) {
+        SHARED_OBSERVERS.with_borrow_mut(|observers| {
+            let Some(index) = observers.iter().position(|shared| shared.box_ == box_) else {
+                return;
+            };
+            let shared = &mut observers[index];
 
-        let stop = move || {
-            cleanup();
-            stop_watch();
-        };
+            let Some(position) = shared.subscriptions.iter().position(|sub| sub.id == id) else {
+                return;
+            };
+            let subscription = shared.subscriptions.remove(position);
 
-        on_cleanup(stop.clone());
+            if shared.subscriptions.is_empty() {
+                shared.observer.disconnect();
+                observers.remove(index);
+            } else {
+                for target in &subscription.targets {
+                    shared.unobserve_if_unused(target);
+                }
+            }
+        });
+    }
 
-        UseResizeObserverReturn { is_supported, stop }
+    fn unobserve_if_unused(&self, target: &web_sys::Element) {
+        if !self
+            .subscriptions
+            .iter()
+            .any(|sub| sub.targets.contains(target))
+        {
+            self.observer.unobserve(target);
+        }
+    }
+
+    fn dispatch(
+        box_: web_sys::ResizeObserverBoxOptions,
+        entries: Vec<web_sys::ResizeObserverEntry>,
+        observer: web_sys::ResizeObserver,
+    ) {
+        // collect first so that the callbacks can subscribe and unsubscribe
+        let calls = SHARED_OBSERVERS.with_borrow(|observers| {
+            observers
+                .iter()
+                .find(|shared| shared.box_ == box_)
+                .map(|shared| {
+                    shared
+                        .subscriptions
+                        .iter()
+                        .filter_map(|sub| {
+                            let entries = entries
+                                .iter()
+                                .filter(|entry| sub.targets.contains(&entry.target()))
+                                .cloned()
+                                .collect::<Vec<_>>();
+
+                            (!entries.is_empty()).then(|| (Rc::clone(&sub.callback), entries))
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        });
+
+        for (callback, entries) in calls {
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            (callback.borrow_mut())(entries, observer.clone());
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        }
     }
 }
 
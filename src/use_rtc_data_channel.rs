@@ -0,0 +1,448 @@
+use crate::core::ConnectionReadyState;
+use crate::use_event_listener;
+use crate::utils::{HybridCodec, HybridData};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{
+    Event, MessageEvent, RtcDataChannel, RtcDataChannelEvent, RtcDataChannelInit,
+    RtcDataChannelState, RtcDataChannelType, RtcPeerConnection,
+};
+
+/// Reactive [RTCDataChannel](https://developer.mozilla.org/en-US/docs/Web/API/RTCDataChannel).
+/// Sends messages directly to the other peer of a [`fn@crate::use_rtc_peer_connection`].
+///
+/// ## Usage
+///
+/// Messages are encoded and decoded with the given codec just like with
+/// [`fn@crate::use_websocket`].
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_rtc_data_channel, use_rtc_peer_connection, UseRtcDataChannelReturn};
+/// # use leptos_use::core::ConnectionReadyState;
+/// # use leptos_use::utils::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let peer = use_rtc_peer_connection();
+///
+/// let UseRtcDataChannelReturn {
+///     ready_state,
+///     message,
+///     send,
+///     ..
+/// } = use_rtc_data_channel::<String, FromToStringCodec>(peer.connection, "chat");
+///
+/// view! {
+///     <button
+///         on:click=move |_| send(&"Hello, peer!".to_string())
+///         disabled=move || ready_state.get() != ConnectionReadyState::Open
+///     >
+///         "Send"
+///     </button>
+///     <p>"Received: " {move || message.get()}</p>
+/// }
+/// # }
+/// ```
+///
+/// ## Creating the Channel
+///
+/// By default the channel is negotiated: both peers call `use_rtc_data_channel` with the same
+/// `RtcDataChannelMode::Negotiated` id and the channel opens together with the connection.
+///
+/// Alternatively one peer creates the channel with `RtcDataChannelMode::Create` before it calls
+/// `create_offer` and the other peer waits for it with `RtcDataChannelMode::Accept`. Then the
+/// channel is matched by its label.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{
+/// #     use_rtc_data_channel_with_options, use_rtc_peer_connection, RtcDataChannelMode,
+/// #     UseRtcDataChannelOptions,
+/// # };
+/// # use leptos_use::utils::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// # let peer = use_rtc_peer_connection();
+/// #
+/// let positions = use_rtc_data_channel_with_options::<String, FromToStringCodec>(
+///     peer.connection,
+///     "positions",
+///     UseRtcDataChannelOptions::default()
+///         .mode(RtcDataChannelMode::Accept)
+///         // positions are sent many times per second, so losing one doesn't matter
+///         .ordered(false)
+///         .max_retransmits(Some(0)),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Flow Control
+///
+/// Messages that haven't been sent yet are buffered by the browser. The number of buffered
+/// bytes is available in `buffered_amount`. Once it drops to `buffered_amount_low_threshold`,
+/// `on_buffered_amount_low` is called, which is the right moment to send the next chunk of a
+/// large transfer.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the channel is never opened and `send` does nothing.
+pub fn use_rtc_data_channel<T, C>(
+    connection: Signal<Option<RtcPeerConnection>>,
+    label: impl Into<String>,
+) -> UseRtcDataChannelReturn<T, C::Error, impl Fn() + Clone + 'static, impl Fn(&T) + Clone + 'static>
+where
+    T: 'static,
+    C: HybridCodec<T> + Default,
+{
+    use_rtc_data_channel_with_options::<T, C>(
+        connection,
+        label,
+        UseRtcDataChannelOptions::default(),
+    )
+}
+
+/// Version of [`use_rtc_data_channel`] that takes `UseRtcDataChannelOptions`. See [`use_rtc_data_channel`] for how to use.
+pub fn use_rtc_data_channel_with_options<T, C>(
+    connection: Signal<Option<RtcPeerConnection>>,
+    label: impl Into<String>,
+    options: UseRtcDataChannelOptions<T, C>,
+) -> UseRtcDataChannelReturn<T, C::Error, impl Fn() + Clone + 'static, impl Fn(&T) + Clone + 'static>
+where
+    T: 'static,
+    C: HybridCodec<T> + Default,
+{
+    let label = label.into();
+    let UseRtcDataChannelOptions {
+        codec,
+        mode,
+        ordered,
+        max_retransmits,
+        buffered_amount_low_threshold,
+        on_open,
+        on_message,
+        on_error,
+        on_close,
+        on_buffered_amount_low,
+        _marker,
+    } = options;
+
+    let (channel, set_channel) = create_signal(None::<RtcDataChannel>);
+    let (ready_state, set_ready_state) = create_signal(ConnectionReadyState::Closed);
+    let (message, set_message) = create_signal(None::<T>);
+    let (error, set_error) = create_signal(None::<UseRtcDataChannelError<C::Error>>);
+    let (buffered_amount, set_buffered_amount) = create_signal(0_u32);
+
+    let use_channel = move |channel: RtcDataChannel| {
+        channel.set_binary_type(RtcDataChannelType::Arraybuffer);
+        channel.set_buffered_amount_low_threshold(buffered_amount_low_threshold);
+        set_ready_state.set(channel.ready_state().into());
+        set_channel.set(Some(channel));
+    };
+
+    let _ = watch(
+        move || connection.get(),
+        {
+            let label = label.clone();
+
+            move |connection: &Option<RtcPeerConnection>, _, _| {
+                let Some(connection) = connection else {
+                    return;
+                };
+
+                let init = RtcDataChannelInit::new();
+                init.set_ordered(ordered);
+                if let Some(max_retransmits) = max_retransmits {
+                    init.set_max_retransmits(max_retransmits);
+                }
+
+                match mode {
+                    RtcDataChannelMode::Negotiated(id) => {
+                        init.set_negotiated(true);
+                        init.set_id(id);
+                    }
+                    RtcDataChannelMode::Create => {}
+                    RtcDataChannelMode::Accept => return,
+                }
+
+                use_channel(connection.create_data_channel_with_data_channel_dict(&label, &init));
+            }
+        },
+        true,
+    );
+
+    if mode == RtcDataChannelMode::Accept {
+        let _ = use_event_listener(
+            connection,
+            ev::Custom::<RtcDataChannelEvent>::new("datachannel"),
+            move |event| {
+                let channel = event.channel();
+
+                if channel.label() == label {
+                    use_channel(channel);
+                }
+            },
+        );
+    }
+
+    let _ = use_event_listener(channel, ev::Custom::<Event>::new("open"), move |event| {
+        set_ready_state.set(ConnectionReadyState::Open);
+
+        #[cfg(debug_assertions)]
+        let prev = SpecialNonReactiveZone::enter();
+
+        on_open(event);
+
+        #[cfg(debug_assertions)]
+        SpecialNonReactiveZone::exit(prev);
+    });
+
+    let _ = use_event_listener(channel, ev::Custom::<MessageEvent>::new("message"), {
+        let codec = codec.clone();
+
+        move |event| {
+            let data = event.data();
+
+            let data = if let Some(text) = data.as_string() {
+                HybridData::Text(text)
+            } else if let Some(array_buffer) = data.dyn_ref::<js_sys::ArrayBuffer>() {
+                HybridData::Binary(js_sys::Uint8Array::new(array_buffer).to_vec())
+            } else {
+                return;
+            };
+
+            match codec.decode(data) {
+                Ok(val) => {
+                    #[cfg(debug_assertions)]
+                    let prev = SpecialNonReactiveZone::enter();
+
+                    on_message(&val);
+
+                    #[cfg(debug_assertions)]
+                    SpecialNonReactiveZone::exit(prev);
+
+                    set_message.set(Some(val));
+                }
+                Err(err) => set_error.set(Some(UseRtcDataChannelError::Codec(err))),
+            }
+        }
+    });
+
+    let _ = use_event_listener(channel, ev::Custom::<Event>::new("error"), move |event| {
+        #[cfg(debug_assertions)]
+        let prev = SpecialNonReactiveZone::enter();
+
+        on_error(event.clone());
+
+        #[cfg(debug_assertions)]
+        SpecialNonReactiveZone::exit(prev);
+
+        set_error.set(Some(UseRtcDataChannelError::Event(event)));
+    });
+
+    let _ = use_event_listener(channel, ev::Custom::<Event>::new("closing"), move |_| {
+        set_ready_state.set(ConnectionReadyState::Closing);
+    });
+
+    let _ = use_event_listener(channel, ev::Custom::<Event>::new("close"), move |event| {
+        set_ready_state.set(ConnectionReadyState::Closed);
+        set_buffered_amount.set(0);
+
+        #[cfg(debug_assertions)]
+        let prev = SpecialNonReactiveZone::enter();
+
+        on_close(event);
+
+        #[cfg(debug_assertions)]
+        SpecialNonReactiveZone::exit(prev);
+    });
+
+    let _ = use_event_listener(
+        channel,
+        ev::Custom::<Event>::new("bufferedamountlow"),
+        move |_| {
+            if let Some(channel) = channel.get_untracked() {
+                set_buffered_amount.set(channel.buffered_amount());
+            }
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_buffered_amount_low();
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        },
+    );
+
+    let send = move |val: &T| {
+        let Some(channel) = channel
+            .get_untracked()
+            .filter(|channel| channel.ready_state() == RtcDataChannelState::Open)
+        else {
+            return;
+        };
+
+        let data = match codec.encode(val) {
+            Ok(data) => data,
+            Err(err) => {
+                set_error.set(Some(UseRtcDataChannelError::Codec(err)));
+                return;
+            }
+        };
+
+        let result = match &data {
+            HybridData::Text(text) => channel.send_with_str(text),
+            HybridData::Binary(bytes) => channel.send_with_u8_array(bytes),
+        };
+
+        if let Err(err) = result {
+            set_error.set(Some(UseRtcDataChannelError::Send(err)));
+        }
+
+        set_buffered_amount.set(channel.buffered_amount());
+    };
+
+    let close = move || {
+        if let Some(Some(channel)) = channel.try_get_untracked() {
+            channel.close();
+        }
+    };
+
+    on_cleanup(close);
+
+    UseRtcDataChannelReturn {
+        channel: channel.into(),
+        ready_state: ready_state.into(),
+        message: message.into(),
+        error: error.into(),
+        buffered_amount: buffered_amount.into(),
+        close,
+        send,
+    }
+}
+
+/// How the data channel of [`use_rtc_data_channel`] is created.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RtcDataChannelMode {
+    /// Both peers create the channel with the same id.
+    Negotiated(u16),
+    /// This peer creates the channel. It has to be created before the offer.
+    Create,
+    /// This peer waits until the other peer has created the channel with the same label.
+    Accept,
+}
+
+impl Default for RtcDataChannelMode {
+    fn default() -> Self {
+        Self::Negotiated(0)
+    }
+}
+
+impl From<RtcDataChannelState> for ConnectionReadyState {
+    fn from(state: RtcDataChannelState) -> Self {
+        match state {
+            RtcDataChannelState::Connecting => Self::Connecting,
+            RtcDataChannelState::Open => Self::Open,
+            RtcDataChannelState::Closing => Self::Closing,
+            _ => Self::Closed,
+        }
+    }
+}
+
+/// Options for [`use_rtc_data_channel_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseRtcDataChannelOptions<T, C>
+where
+    T: 'static,
+    C: HybridCodec<T>,
+{
+    /// Encodes sent and decodes received messages.
+    #[builder(keep_type)]
+    codec: C,
+    /// How the channel is created. Defaults to `RtcDataChannelMode::Negotiated(0)`.
+    mode: RtcDataChannelMode,
+    /// Whether messages have to arrive in the order they were sent. Defaults to `true`.
+    ordered: bool,
+    /// How often a lost message is sent again. Defaults to `None` which retries until it arrives.
+    max_retransmits: Option<u16>,
+    /// `on_buffered_amount_low` is called once the buffered amount drops to this number of bytes.
+    /// Defaults to `0`.
+    buffered_amount_low_threshold: u32,
+    /// Called when the channel is open.
+    on_open: Rc<dyn Fn(Event)>,
+    /// Called for every successfully decoded message.
+    #[builder(keep_type)]
+    on_message: Rc<dyn Fn(&T)>,
+    /// Called when an error occurs on the channel.
+    on_error: Rc<dyn Fn(Event)>,
+    /// Called when the channel has been closed.
+    on_close: Rc<dyn Fn(Event)>,
+    /// Called when the buffered amount drops to `buffered_amount_low_threshold`.
+    on_buffered_amount_low: Rc<dyn Fn()>,
+
+    #[builder(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl<T, C: HybridCodec<T> + Default> Default for UseRtcDataChannelOptions<T, C> {
+    fn default() -> Self {
+        Self {
+            codec: C::default(),
+            mode: RtcDataChannelMode::default(),
+            ordered: true,
+            max_retransmits: None,
+            buffered_amount_low_threshold: 0,
+            on_open: Rc::new(|_| {}),
+            on_message: Rc::new(|_| {}),
+            on_error: Rc::new(|_| {}),
+            on_close: Rc::new(|_| {}),
+            on_buffered_amount_low: Rc::new(|| {}),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Return type of [`use_rtc_data_channel`].
+#[derive(Clone)]
+pub struct UseRtcDataChannelReturn<T, Err, CloseFn, SendFn>
+where
+    T: 'static,
+    Err: 'static,
+    CloseFn: Fn() + Clone + 'static,
+    SendFn: Fn(&T) + Clone + 'static,
+{
+    /// The `RTCDataChannel` once it has been created.
+    pub channel: Signal<Option<RtcDataChannel>>,
+    /// The current state of the channel.
+    pub ready_state: Signal<ConnectionReadyState>,
+    /// Latest message received from the other peer decoded by the codec.
+    pub message: Signal<Option<T>>,
+    /// The latest error.
+    pub error: Signal<Option<UseRtcDataChannelError<Err>>>,
+    /// The number of bytes that have been sent but not transmitted yet.
+    pub buffered_amount: Signal<u32>,
+    /// Closes the channel
+    pub close: CloseFn,
+    /// Encodes and sends a message. Messages sent while the channel isn't open are dropped.
+    pub send: SendFn,
+}
+
+#[derive(Error, Debug)]
+pub enum UseRtcDataChannelError<Err> {
+    #[error("RTCDataChannel error event")]
+    Event(Event),
+
+    #[error("Error encoding or decoding message")]
+    Codec(Err),
+
+    #[error("Sending the message failed")]
+    Send(JsValue),
+}
@@ -0,0 +1,498 @@
+use crate::{js, js_fut, use_event_listener, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{
+    MediaStream, MediaStreamTrack, RtcConfiguration, RtcIceCandidateInit, RtcIceConnectionState,
+    RtcIceGatheringState, RtcPeerConnection, RtcPeerConnectionIceEvent, RtcPeerConnectionState,
+    RtcSdpType, RtcSessionDescriptionInit, RtcSignalingState, RtcTrackEvent,
+};
+
+/// Reactive [RTCPeerConnection](https://developer.mozilla.org/en-US/docs/Web/API/RTCPeerConnection).
+/// Connects two browsers directly with WebRTC to exchange audio, video and data.
+///
+/// ## Usage
+///
+/// The two peers have to exchange the session descriptions and ICE candidates over a channel
+/// of your choice, for example a WebSocket to your server. This is called signaling.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{
+/// #     use_rtc_peer_connection_with_options, use_user_media, IceCandidate, IceServer,
+/// #     UseRtcPeerConnectionOptions,
+/// # };
+/// #
+/// # fn send_to_other_peer(message: String) {}
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let camera = use_user_media();
+///
+/// let peer = use_rtc_peer_connection_with_options(
+///     UseRtcPeerConnectionOptions::default()
+///         .ice_servers(vec![IceServer::new("stun:stun.l.google.com:19302")])
+///         .on_ice_candidate(|candidate: IceCandidate| send_to_other_peer(candidate.candidate)),
+/// );
+///
+/// let call = move |_| {
+///     spawn_local(async move {
+///         if let Some(stream) = camera.stream.get_untracked() {
+///             let _ = peer.add_stream(&stream);
+///         }
+///
+///         if let Ok(offer) = peer.create_offer().await {
+///             send_to_other_peer(offer.sdp);
+///         }
+///     });
+/// };
+///
+/// view! {
+///     <button on:click=call>"Call"</button>
+///     <p>"State: " {move || format!("{:?}", peer.connection_state.get())}</p>
+/// }
+/// # }
+/// ```
+///
+/// The peer that starts the call sends the result of `create_offer` to the other peer which
+/// passes it to `create_answer` and sends the answer back. The first peer then calls
+/// `accept_answer` with it. All ICE candidates that are passed to `on_ice_candidate` have to be
+/// given to `add_ice_candidate` of the other peer.
+///
+/// The media streams sent by the other peer are available in `remote_streams`.
+///
+/// The connection is closed when the component is unmounted.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `connection` is always `None` and all operations return
+/// `UseRtcPeerConnectionError::NotSupported`.
+pub fn use_rtc_peer_connection() -> UseRtcPeerConnectionReturn {
+    use_rtc_peer_connection_with_options(UseRtcPeerConnectionOptions::default())
+}
+
+/// Version of [`use_rtc_peer_connection`] that takes a `UseRtcPeerConnectionOptions`. See [`use_rtc_peer_connection`] for how to use.
+pub fn use_rtc_peer_connection_with_options(
+    options: UseRtcPeerConnectionOptions,
+) -> UseRtcPeerConnectionReturn {
+    let UseRtcPeerConnectionOptions {
+        ice_servers,
+        on_ice_candidate,
+        on_track,
+    } = options;
+
+    let is_supported = use_supported(|| js!("RTCPeerConnection" in &window()));
+
+    let (connection_state, set_connection_state) = create_signal(RtcPeerConnectionState::New);
+    let (ice_connection_state, set_ice_connection_state) =
+        create_signal(RtcIceConnectionState::New);
+    let (ice_gathering_state, set_ice_gathering_state) = create_signal(RtcIceGatheringState::New);
+    let (signaling_state, set_signaling_state) = create_signal(RtcSignalingState::Stable);
+    let (remote_streams, set_remote_streams) = create_signal(Vec::<MediaStream>::new());
+    let (error, set_error) = create_signal(None::<UseRtcPeerConnectionError>);
+
+    let connection = if is_supported.get_untracked() {
+        match create_connection(&ice_servers) {
+            Ok(connection) => Some(connection),
+            Err(err) => {
+                set_error.set(Some(UseRtcPeerConnectionError::Create(err)));
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let _ = use_event_listener(
+        connection.clone(),
+        ev::Custom::<web_sys::Event>::new("connectionstatechange"),
+        {
+            let connection = connection.clone();
+
+            move |_| {
+                if let Some(connection) = &connection {
+                    set_connection_state.set(connection.connection_state());
+                }
+            }
+        },
+    );
+
+    let _ = use_event_listener(
+        connection.clone(),
+        ev::Custom::<web_sys::Event>::new("iceconnectionstatechange"),
+        {
+            let connection = connection.clone();
+
+            move |_| {
+                if let Some(connection) = &connection {
+                    set_ice_connection_state.set(connection.ice_connection_state());
+                }
+            }
+        },
+    );
+
+    let _ = use_event_listener(
+        connection.clone(),
+        ev::Custom::<web_sys::Event>::new("icegatheringstatechange"),
+        {
+            let connection = connection.clone();
+
+            move |_| {
+                if let Some(connection) = &connection {
+                    set_ice_gathering_state.set(connection.ice_gathering_state());
+                }
+            }
+        },
+    );
+
+    let _ = use_event_listener(
+        connection.clone(),
+        ev::Custom::<web_sys::Event>::new("signalingstatechange"),
+        {
+            let connection = connection.clone();
+
+            move |_| {
+                if let Some(connection) = &connection {
+                    set_signaling_state.set(connection.signaling_state());
+                }
+            }
+        },
+    );
+
+    let _ = use_event_listener(
+        connection.clone(),
+        ev::Custom::<RtcPeerConnectionIceEvent>::new("icecandidate"),
+        move |event| {
+            // `None` signals that all candidates have been gathered
+            if let Some(candidate) = event.candidate() {
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                on_ice_candidate(IceCandidate {
+                    candidate: candidate.candidate(),
+                    sdp_mid: candidate.sdp_mid(),
+                    sdp_m_line_index: candidate.sdp_m_line_index(),
+                });
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+            }
+        },
+    );
+
+    let _ = use_event_listener(
+        connection.clone(),
+        ev::Custom::<RtcTrackEvent>::new("track"),
+        move |event| {
+            set_remote_streams.update(|remote_streams| {
+                for stream in event.streams().iter() {
+                    let stream = stream.unchecked_into::<MediaStream>();
+
+                    if !remote_streams.iter().any(|other| other.id() == stream.id()) {
+                        remote_streams.push(stream);
+                    }
+                }
+            });
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_track(event);
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        },
+    );
+
+    let connection = store_value(connection);
+
+    let ret = UseRtcPeerConnectionReturn {
+        is_supported,
+        connection: Signal::derive(move || connection.get_value()),
+        connection_state: connection_state.into(),
+        ice_connection_state: ice_connection_state.into(),
+        ice_gathering_state: ice_gathering_state.into(),
+        signaling_state: signaling_state.into(),
+        remote_streams: remote_streams.into(),
+        error: error.into(),
+        set_error,
+    };
+
+    on_cleanup(move || ret.close());
+
+    ret
+}
+
+fn create_connection(ice_servers: &[IceServer]) -> Result<RtcPeerConnection, JsValue> {
+    let configuration = RtcConfiguration::new();
+    configuration.set_ice_servers(
+        &ice_servers
+            .iter()
+            .map(IceServer::to_js)
+            .collect::<js_sys::Array>(),
+    );
+
+    RtcPeerConnection::new_with_configuration(&configuration)
+}
+
+/// Options for [`use_rtc_peer_connection_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseRtcPeerConnectionOptions {
+    /// The STUN and TURN servers that are used to find a way to connect the peers. Defaults to
+    /// none, which only works if both peers are in the same network.
+    ice_servers: Vec<IceServer>,
+
+    /// Called for every ICE candidate of this peer. Send it to the other peer which has to pass
+    /// it to `add_ice_candidate`.
+    on_ice_candidate: Rc<dyn Fn(IceCandidate)>,
+
+    /// Called for every track that is received from the other peer.
+    on_track: Rc<dyn Fn(RtcTrackEvent)>,
+}
+
+impl Default for UseRtcPeerConnectionOptions {
+    fn default() -> Self {
+        Self {
+            ice_servers: vec![],
+            on_ice_candidate: Rc::new(|_| {}),
+            on_track: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// A STUN or TURN server for [`UseRtcPeerConnectionOptions`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IceServer {
+    /// The URLs of the server like `"stun:stun.example.com"` or `"turn:turn.example.com"`
+    pub urls: Vec<String>,
+
+    /// The username for TURN servers
+    pub username: Option<String>,
+
+    /// The password for TURN servers
+    pub credential: Option<String>,
+}
+
+impl IceServer {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            urls: vec![url.into()],
+            ..Default::default()
+        }
+    }
+
+    pub fn credentials(
+        mut self,
+        username: impl Into<String>,
+        credential: impl Into<String>,
+    ) -> Self {
+        self.username = Some(username.into());
+        self.credential = Some(credential.into());
+        self
+    }
+
+    fn to_js(&self) -> web_sys::RtcIceServer {
+        let server = web_sys::RtcIceServer::new();
+        server.set_urls(
+            &self
+                .urls
+                .iter()
+                .map(|url| JsValue::from_str(url))
+                .collect::<js_sys::Array>(),
+        );
+
+        if let Some(username) = &self.username {
+            server.set_username(username);
+        }
+        if let Some(credential) = &self.credential {
+            server.set_credential(credential);
+        }
+
+        server
+    }
+}
+
+/// An offer or answer that has to be sent to the other peer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SessionDescription {
+    pub sdp_type: RtcSdpType,
+    pub sdp: String,
+}
+
+impl SessionDescription {
+    fn to_js(&self) -> RtcSessionDescriptionInit {
+        let description = RtcSessionDescriptionInit::new(self.sdp_type);
+        description.set_sdp(&self.sdp);
+        description
+    }
+}
+
+/// An ICE candidate that has to be sent to the other peer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IceCandidate {
+    pub candidate: String,
+    pub sdp_mid: Option<String>,
+    pub sdp_m_line_index: Option<u16>,
+}
+
+/// Return type of [`use_rtc_peer_connection`].
+#[derive(Clone, Copy)]
+pub struct UseRtcPeerConnectionReturn {
+    /// Whether WebRTC is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The underlying `RTCPeerConnection`
+    pub connection: Signal<Option<RtcPeerConnection>>,
+
+    /// The state of the connection to the other peer
+    pub connection_state: Signal<RtcPeerConnectionState>,
+
+    /// The state of the ICE transport
+    pub ice_connection_state: Signal<RtcIceConnectionState>,
+
+    /// Whether ICE candidates are being gathered
+    pub ice_gathering_state: Signal<RtcIceGatheringState>,
+
+    /// The state of the exchange of offer and answer
+    pub signaling_state: Signal<RtcSignalingState>,
+
+    /// The media streams received from the other peer
+    pub remote_streams: Signal<Vec<MediaStream>>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseRtcPeerConnectionError>>,
+
+    set_error: WriteSignal<Option<UseRtcPeerConnectionError>>,
+}
+
+impl UseRtcPeerConnectionReturn {
+    /// Creates an offer and uses it as the local description. Send it to the other peer.
+    pub async fn create_offer(&self) -> Result<SessionDescription, UseRtcPeerConnectionError> {
+        let connection = self.connection()?;
+
+        let offer = js_fut!(connection.create_offer())
+            .await
+            .map_err(|err| self.fail(UseRtcPeerConnectionError::Offer(err)))?;
+
+        self.set_local_description(&connection, offer).await
+    }
+
+    /// Uses the `offer` of the other peer as the remote description and creates an answer. Send
+    /// it back to the other peer.
+    pub async fn create_answer(
+        &self,
+        offer: &SessionDescription,
+    ) -> Result<SessionDescription, UseRtcPeerConnectionError> {
+        let connection = self.connection()?;
+
+        js_fut!(connection.set_remote_description(&offer.to_js()))
+            .await
+            .map_err(|err| self.fail(UseRtcPeerConnectionError::Description(err)))?;
+
+        let answer = js_fut!(connection.create_answer())
+            .await
+            .map_err(|err| self.fail(UseRtcPeerConnectionError::Answer(err)))?;
+
+        self.set_local_description(&connection, answer).await
+    }
+
+    /// Uses the `answer` of the other peer as the remote description.
+    pub async fn accept_answer(
+        &self,
+        answer: &SessionDescription,
+    ) -> Result<(), UseRtcPeerConnectionError> {
+        let connection = self.connection()?;
+
+        js_fut!(connection.set_remote_description(&answer.to_js()))
+            .await
+            .map_err(|err| self.fail(UseRtcPeerConnectionError::Description(err)))?;
+
+        Ok(())
+    }
+
+    /// Adds an ICE candidate that has been received from the other peer.
+    pub async fn add_ice_candidate(
+        &self,
+        candidate: &IceCandidate,
+    ) -> Result<(), UseRtcPeerConnectionError> {
+        let connection = self.connection()?;
+
+        let init = RtcIceCandidateInit::new(&candidate.candidate);
+        init.set_sdp_mid(candidate.sdp_mid.as_deref());
+        init.set_sdp_m_line_index(candidate.sdp_m_line_index);
+
+        js_fut!(connection.add_ice_candidate_with_opt_rtc_ice_candidate_init(Some(&init)))
+            .await
+            .map_err(|err| self.fail(UseRtcPeerConnectionError::IceCandidate(err)))?;
+
+        Ok(())
+    }
+
+    /// Sends all tracks of `stream` to the other peer. Call this before `create_offer` or
+    /// `create_answer`.
+    pub fn add_stream(&self, stream: &MediaStream) -> Result<(), UseRtcPeerConnectionError> {
+        let connection = self.connection()?;
+
+        for track in stream.get_tracks() {
+            connection.add_track_0(&track.unchecked_into::<MediaStreamTrack>(), stream);
+        }
+
+        Ok(())
+    }
+
+    /// Closes the connection.
+    pub fn close(&self) {
+        if let Some(Some(connection)) = self.connection.try_get_untracked() {
+            connection.close();
+        }
+    }
+
+    async fn set_local_description(
+        &self,
+        connection: &RtcPeerConnection,
+        description: JsValue,
+    ) -> Result<SessionDescription, UseRtcPeerConnectionError> {
+        let description = description.unchecked_into::<RtcSessionDescriptionInit>();
+
+        js_fut!(connection.set_local_description(&description))
+            .await
+            .map_err(|err| self.fail(UseRtcPeerConnectionError::Description(err)))?;
+
+        self.set_error.set(None);
+
+        Ok(SessionDescription {
+            sdp_type: description.get_type(),
+            sdp: description.get_sdp().unwrap_or_default(),
+        })
+    }
+
+    fn connection(&self) -> Result<RtcPeerConnection, UseRtcPeerConnectionError> {
+        self.connection
+            .get_untracked()
+            .ok_or(UseRtcPeerConnectionError::NotSupported)
+    }
+
+    fn fail(&self, err: UseRtcPeerConnectionError) -> UseRtcPeerConnectionError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// Errors of [`use_rtc_peer_connection`].
+#[derive(Error, Debug, Clone)]
+pub enum UseRtcPeerConnectionError {
+    #[error("WebRTC is not supported")]
+    NotSupported,
+    #[error("creating the peer connection failed")]
+    Create(JsValue),
+    #[error("creating the offer failed")]
+    Offer(JsValue),
+    #[error("creating the answer failed")]
+    Answer(JsValue),
+    #[error("setting the session description failed")]
+    Description(JsValue),
+    #[error("adding the ICE candidate failed")]
+    IceCandidate(JsValue),
+}
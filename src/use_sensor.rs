@@ -0,0 +1,304 @@
+use crate::{js, use_event_listener, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::marker::PhantomData;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Reactive [Generic Sensor API](https://developer.mozilla.org/en-US/docs/Web/API/Sensor_APIs).
+/// Starts a sensor and provides its latest reading as a signal.
+///
+/// This is the building block of [`fn@crate::use_accelerometer`], [`fn@crate::use_gyroscope`] and
+/// [`fn@crate::use_ambient_light_sensor`]. Any other sensor can be used by implementing
+/// [`SensorReading`] for a type that holds its values.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_sensor_with_options, SensorReading, UseSensorOptions};
+/// # use wasm_bindgen::JsValue;
+/// #
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// struct Magnetometer {
+///     x: f64,
+///     y: f64,
+///     z: f64,
+/// }
+///
+/// impl SensorReading for Magnetometer {
+///     const SENSOR: &'static str = "Magnetometer";
+///
+///     fn from_sensor(sensor: &JsValue) -> Option<Self> {
+///         let value = |name: &str| {
+///             js_sys::Reflect::get(sensor, &name.into())
+///                 .ok()
+///                 .and_then(|value| value.as_f64())
+///         };
+///
+///         Some(Self {
+///             x: value("x")?,
+///             y: value("y")?,
+///             z: value("z")?,
+///         })
+///     }
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let magnetometer = use_sensor_with_options::<Magnetometer>(UseSensorOptions::default().frequency(10.0));
+///
+/// view! {
+///     <p>{move || format!("{:?}", magnetometer.reading.get())}</p>
+/// }
+/// # }
+/// ```
+///
+/// The sensor is started right away unless `immediate` is `false`. It is stopped when the
+/// component is cleaned up. If the browser doesn't support the sensor or the user denies the
+/// permission `error` is set and `reading` stays `None`.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `reading` is always `None` and `is_supported` is `false`.
+pub fn use_sensor<T>() -> UseSensorReturn<T>
+where
+    T: SensorReading,
+{
+    use_sensor_with_options(UseSensorOptions::default())
+}
+
+/// Version of [`use_sensor`] that takes a `UseSensorOptions`. See [`use_sensor`] for how to use.
+pub fn use_sensor_with_options<T>(options: UseSensorOptions) -> UseSensorReturn<T>
+where
+    T: SensorReading,
+{
+    let UseSensorOptions {
+        frequency,
+        immediate,
+    } = options;
+
+    let is_supported =
+        use_supported(|| js_sys::Reflect::has(&window(), &T::SENSOR.into()).unwrap_or_default());
+
+    let (sensor, set_sensor) = create_signal(None::<Sensor>);
+    let (reading, set_reading) = create_signal(None::<T>);
+    let (timestamp, set_timestamp) = create_signal(None::<f64>);
+    let (is_active, set_active) = create_signal(false);
+    let (error, set_error) = create_signal(None::<UseSensorError>);
+
+    let ret = UseSensorReturn {
+        is_supported,
+        reading: reading.into(),
+        timestamp: timestamp.into(),
+        is_active: is_active.into(),
+        error: error.into(),
+        sensor,
+        set_sensor,
+        set_active,
+        set_error,
+        frequency,
+        _marker: PhantomData,
+    };
+
+    let _ = use_event_listener(
+        sensor,
+        ev::Custom::<web_sys::Event>::new("reading"),
+        move |_| {
+            if let Some(sensor) = sensor.get_untracked() {
+                set_reading.set(T::from_sensor(&sensor));
+                set_timestamp.set(sensor.timestamp());
+            }
+        },
+    );
+
+    let _ = use_event_listener(
+        sensor,
+        ev::Custom::<web_sys::Event>::new("activate"),
+        move |_| set_active.set(true),
+    );
+
+    let _ = use_event_listener(
+        sensor,
+        ev::Custom::<web_sys::Event>::new("error"),
+        move |event| {
+            set_active.set(false);
+
+            let err = js!(event["error"]).unwrap_or(JsValue::UNDEFINED);
+            set_error.set(Some(UseSensorError::from_dom_exception(err)));
+        },
+    );
+
+    if immediate {
+        let _ = ret.start();
+    }
+
+    on_cleanup(move || ret.stop());
+
+    ret
+}
+
+/// Values of a sensor of the [Generic Sensor API](https://developer.mozilla.org/en-US/docs/Web/API/Sensor_APIs).
+pub trait SensorReading: Clone + Copy + PartialEq + 'static {
+    /// The name of the JavaScript class of the sensor like `"Accelerometer"`
+    const SENSOR: &'static str;
+
+    /// Reads the current values from the sensor object. Returns `None` if the sensor doesn't
+    /// have a reading yet.
+    fn from_sensor(sensor: &JsValue) -> Option<Self>;
+}
+
+/// Options for [`use_sensor_with_options`] and the sensor hooks that are built on it.
+#[derive(DefaultBuilder, Clone, Copy)]
+pub struct UseSensorOptions {
+    /// How many readings per second should be taken. The browser may use a lower frequency.
+    /// Defaults to `None` which uses the default frequency of the sensor.
+    #[builder(into)]
+    frequency: Option<f64>,
+
+    /// Start the sensor when the function is called. Defaults to `true`.
+    immediate: bool,
+}
+
+impl Default for UseSensorOptions {
+    fn default() -> Self {
+        Self {
+            frequency: None,
+            immediate: true,
+        }
+    }
+}
+
+/// Return type of [`use_sensor`].
+pub struct UseSensorReturn<T>
+where
+    T: SensorReading,
+{
+    /// Whether the sensor is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The latest reading of the sensor
+    pub reading: Signal<Option<T>>,
+
+    /// The time of the latest reading in ms, relative to the time origin of the page
+    pub timestamp: Signal<Option<f64>>,
+
+    /// Whether the sensor is started and delivers readings
+    pub is_active: Signal<bool>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseSensorError>>,
+
+    sensor: ReadSignal<Option<Sensor>>,
+    set_sensor: WriteSignal<Option<Sensor>>,
+    set_active: WriteSignal<bool>,
+    set_error: WriteSignal<Option<UseSensorError>>,
+    frequency: Option<f64>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Clone for UseSensorReturn<T>
+where
+    T: SensorReading,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for UseSensorReturn<T> where T: SensorReading {}
+
+impl<T> UseSensorReturn<T>
+where
+    T: SensorReading,
+{
+    /// Starts the sensor. Does nothing if it is already started.
+    pub fn start(&self) -> Result<(), UseSensorError> {
+        if !self.is_supported.get_untracked() {
+            return Err(self.fail(UseSensorError::NotSupported));
+        }
+
+        if let Some(sensor) = self.sensor.get_untracked() {
+            sensor.start();
+            return Ok(());
+        }
+
+        let options = js_sys::Object::new();
+        if let Some(frequency) = self.frequency {
+            js!(options["frequency"] = frequency);
+        }
+
+        let class = js_sys::Reflect::get(&window(), &T::SENSOR.into())
+            .and_then(|class| class.dyn_into::<js_sys::Function>())
+            .map_err(|err| self.fail(UseSensorError::from_dom_exception(err)))?;
+
+        let sensor: Sensor = js_sys::Reflect::construct(&class, &js_sys::Array::of1(&options))
+            .map_err(|err| self.fail(UseSensorError::from_dom_exception(err)))?
+            .unchecked_into();
+
+        self.set_error.set(None);
+        self.set_sensor.set(Some(sensor.clone()));
+        sensor.start();
+
+        Ok(())
+    }
+
+    /// Stops the sensor. Readings are kept until the sensor is started again.
+    pub fn stop(&self) {
+        if let Some(sensor) = self.sensor.get_untracked() {
+            sensor.stop();
+        }
+
+        self.set_active.set(false);
+    }
+
+    fn fail(&self, err: UseSensorError) -> UseSensorError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// Errors of [`use_sensor`].
+#[derive(Error, Debug, Clone)]
+pub enum UseSensorError {
+    #[error("the sensor is not supported")]
+    NotSupported,
+    #[error("the permission to use the sensor has been denied")]
+    PermissionDenied,
+    #[error("the sensor couldn't be read")]
+    NotReadable,
+    #[error("the sensor failed")]
+    Failed(JsValue),
+}
+
+impl UseSensorError {
+    fn from_dom_exception(err: JsValue) -> Self {
+        match err
+            .dyn_ref::<web_sys::DomException>()
+            .map(web_sys::DomException::name)
+            .as_deref()
+        {
+            Some("NotSupportedError") => Self::NotSupported,
+            Some("NotAllowedError" | "SecurityError") => Self::PermissionDenied,
+            Some("NotReadableError") => Self::NotReadable,
+            _ => Self::Failed(err),
+        }
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Clone, Debug, PartialEq)]
+    #[wasm_bindgen(extends = web_sys::EventTarget)]
+    type Sensor;
+
+    #[wasm_bindgen(method)]
+    fn start(this: &Sensor);
+
+    #[wasm_bindgen(method)]
+    fn stop(this: &Sensor);
+
+    #[wasm_bindgen(method, getter)]
+    fn timestamp(this: &Sensor) -> Option<f64>;
+}
@@ -0,0 +1,373 @@
+use crate::{js, js_fut, use_event_listener, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{SerialOptions, SerialPort, SerialPortFilter};
+
+/// Reactive [Web Serial API](https://developer.mozilla.org/en-US/docs/Web/API/Web_Serial_API).
+/// Lets the user grant access to serial ports, keeps a signal of the available ports up to
+/// date and reads the data of the opened port into signals.
+///
+/// > This function requires `--cfg=web_sys_unstable_apis` to be activated as
+/// > [described in the wasm-bindgen guide](https://rustwasm.github.io/docs/wasm-bindgen/web-sys/unstable-apis.html).
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_serial;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let serial = use_serial();
+///
+/// let connect = move |_| {
+///     spawn_local(async move {
+///         // shows the browser's port chooser
+///         if let Ok(port) = serial.request_port().await {
+///             let _ = serial.open(&port, 115_200).await;
+///         }
+///     });
+/// };
+///
+/// let send = move |_| {
+///     spawn_local(async move {
+///         let _ = serial.write(b"status\n").await;
+///     });
+/// };
+///
+/// view! {
+///     <Show
+///         when=move || serial.is_open.get()
+///         fallback=move || view! { <button on:click=connect>"Connect"</button> }
+///     >
+///         <button on:click=send>"Send"</button>
+///         <p>"Last line: " {move || serial.line.get()}</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// `request_port` has to be called from a user gesture like a click. Ports the user has
+/// granted access to before are part of `ports` and can be opened without asking again.
+///
+/// ## Reading
+///
+/// While a port is open every chunk of bytes that is received is available in `chunk`. The
+/// received data is also split at `\n` and every complete line (without the line ending) is
+/// available in `line`. Use whatever fits your protocol.
+///
+/// Only one port can be open at a time. Call `close` before opening another one. The port is
+/// closed automatically when the component is cleaned up.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `ports` is always empty and all operations return
+/// `UseSerialError::NotSupported`.
+pub fn use_serial() -> UseSerialReturn {
+    use_serial_with_options(UseSerialOptions::default())
+}
+
+/// Version of [`use_serial`] that takes a `UseSerialOptions`. See [`use_serial`] for how to use.
+pub fn use_serial_with_options(options: UseSerialOptions) -> UseSerialReturn {
+    let UseSerialOptions { filters } = options;
+
+    let is_supported = use_supported(|| js!("serial" in &window().navigator()));
+
+    let (ports, set_ports) = create_signal(Vec::<SerialPort>::new());
+    let (port, set_port) = create_signal(None::<SerialPort>);
+    let (chunk, set_chunk) = create_signal(None::<Vec<u8>>);
+    let (line, set_line) = create_signal(None::<String>);
+    let (error, set_error) = create_signal(None::<UseSerialError>);
+
+    let serial = UseSerialReturn {
+        is_supported,
+        ports: ports.into(),
+        port: port.into(),
+        is_open: Signal::derive(move || port.with(Option::is_some)),
+        chunk: chunk.into(),
+        line: line.into(),
+        error: error.into(),
+        set_ports,
+        set_port,
+        set_chunk,
+        set_line,
+        set_error,
+        filters: store_value(filters),
+        reader: store_value(None),
+        line_buffer: store_value(vec![]),
+    };
+
+    serial.refresh();
+
+    let navigator_serial = if is_supported.get_untracked() {
+        Some(window().navigator().serial())
+    } else {
+        None
+    };
+
+    let _ = use_event_listener(
+        navigator_serial.clone(),
+        ev::Custom::<web_sys::Event>::new("connect"),
+        move |_| serial.refresh(),
+    );
+
+    let _ = use_event_listener(
+        navigator_serial,
+        ev::Custom::<web_sys::Event>::new("disconnect"),
+        move |_| serial.refresh(),
+    );
+
+    on_cleanup(move || {
+        if serial.port.get_untracked().is_some() {
+            spawn_local(async move {
+                let _ = serial.close().await;
+            });
+        }
+    });
+
+    serial
+}
+
+/// Options for [`use_serial_with_options`].
+#[derive(DefaultBuilder, Default)]
+pub struct UseSerialOptions {
+    /// Only ports that match one of these filters are shown in the chooser. If empty (the
+    /// default) all ports are shown.
+    #[builder(into)]
+    filters: Vec<SerialPortFilter>,
+}
+
+/// Return type of [`use_serial`].
+#[derive(Clone, Copy)]
+pub struct UseSerialReturn {
+    /// Whether the Web Serial API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The ports the user has granted access to
+    pub ports: Signal<Vec<SerialPort>>,
+
+    /// The port that is currently open
+    pub port: Signal<Option<SerialPort>>,
+
+    /// Whether a port is open
+    pub is_open: Signal<bool>,
+
+    /// The latest chunk of bytes received from the open port
+    pub chunk: Signal<Option<Vec<u8>>>,
+
+    /// The latest complete line received from the open port
+    pub line: Signal<Option<String>>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseSerialError>>,
+
+    set_ports: WriteSignal<Vec<SerialPort>>,
+    set_port: WriteSignal<Option<SerialPort>>,
+    set_chunk: WriteSignal<Option<Vec<u8>>>,
+    set_line: WriteSignal<Option<String>>,
+    set_error: WriteSignal<Option<UseSerialError>>,
+    filters: StoredValue<Vec<SerialPortFilter>>,
+    reader: StoredValue<Option<web_sys::ReadableStreamDefaultReader>>,
+    line_buffer: StoredValue<Vec<u8>>,
+}
+
+impl UseSerialReturn {
+    /// Shows the browser's port chooser and returns the selected port.
+    pub async fn request_port(&self) -> Result<SerialPort, UseSerialError> {
+        let serial = self.navigator_serial()?;
+
+        let options = web_sys::SerialPortRequestOptions::new();
+        self.filters
+            .with_value(|filters| options.set_filters(filters));
+
+        let port = js_fut!(serial.request_port_with_options(&options))
+            .await
+            .map_err(|err| self.fail(UseSerialError::RequestPort(err)))?;
+
+        self.refresh();
+
+        Ok(port)
+    }
+
+    /// Opens `port` with the given baud rate and starts reading from it.
+    pub async fn open(&self, port: &SerialPort, baud_rate: u32) -> Result<(), UseSerialError> {
+        self.open_with_options(port, &SerialOptions::new(baud_rate))
+            .await
+    }
+
+    /// Version of [`UseSerialReturn::open`] that takes all `SerialOptions` like data bits,
+    /// parity or flow control.
+    pub async fn open_with_options(
+        &self,
+        port: &SerialPort,
+        options: &SerialOptions,
+    ) -> Result<(), UseSerialError> {
+        self.navigator_serial()?;
+
+        if self.port.get_untracked().is_some() {
+            return Err(self.fail(UseSerialError::AlreadyOpen));
+        }
+
+        js_fut!(port.open(options))
+            .await
+            .map_err(|err| self.fail(UseSerialError::Open(err)))?;
+
+        self.line_buffer.set_value(vec![]);
+        self.set_error.set(None);
+        self.set_port.set(Some(port.clone()));
+
+        self.read(port);
+
+        Ok(())
+    }
+
+    /// Stops reading and closes the open port.
+    pub async fn close(&self) -> Result<(), UseSerialError> {
+        let Some(port) = self.port.get_untracked() else {
+            return Ok(());
+        };
+
+        if let Some(reader) = self.reader.get_value() {
+            let _ = js_fut!(reader.cancel()).await;
+        }
+
+        self.set_port.set(None);
+
+        js_fut!(port.close())
+            .await
+            .map_err(|err| self.fail(UseSerialError::Failed(err)))?;
+
+        Ok(())
+    }
+
+    /// Writes `data` to the open port.
+    pub async fn write(&self, data: &[u8]) -> Result<(), UseSerialError> {
+        let port = self.port.get_untracked().ok_or(UseSerialError::NotOpen)?;
+
+        let writer = port
+            .writable()
+            .get_writer()
+            .map_err(|err| self.fail(UseSerialError::Failed(err)))?;
+
+        let result = js_fut!(writer.write_with_chunk(&js_sys::Uint8Array::from(data))).await;
+
+        writer.release_lock();
+
+        result.map_err(|err| self.fail(UseSerialError::Failed(err)))?;
+
+        Ok(())
+    }
+
+    /// Reads the ports the user has granted access to again.
+    pub fn refresh(&self) {
+        let Ok(serial) = self.navigator_serial() else {
+            return;
+        };
+
+        let set_ports = self.set_ports;
+        let set_error = self.set_error;
+
+        spawn_local(async move {
+            match js_fut!(serial.get_ports()).await {
+                Ok(ports) => set_ports.set(ports.iter().collect()),
+                Err(err) => set_error.set(Some(UseSerialError::Failed(err))),
+            }
+        });
+    }
+
+    fn read(&self, port: &SerialPort) {
+        let reader: web_sys::ReadableStreamDefaultReader =
+            port.readable().get_reader().unchecked_into();
+        self.reader.set_value(Some(reader.clone()));
+
+        let serial = *self;
+
+        spawn_local(async move {
+            loop {
+                let result = match js_fut!(reader.read()).await {
+                    Ok(result) => result,
+                    Err(err) => {
+                        // the device has been unplugged or a fatal error occurred
+                        serial.fail(UseSerialError::Read(err));
+                        serial.set_port.set(None);
+                        break;
+                    }
+                };
+
+                if js!(result["done"])
+                    .ok()
+                    .and_then(|done| done.as_bool())
+                    .unwrap_or(true)
+                {
+                    break;
+                }
+
+                let Ok(value) = js!(result["value"]) else {
+                    continue;
+                };
+
+                serial.receive(js_sys::Uint8Array::new(&value).to_vec());
+            }
+
+            reader.release_lock();
+            serial.reader.set_value(None);
+        });
+    }
+
+    fn receive(&self, chunk: Vec<u8>) {
+        let lines = self.line_buffer.try_update_value(|buffer| {
+            buffer.extend_from_slice(&chunk);
+
+            let mut lines = vec![];
+            while let Some(end) = buffer.iter().position(|byte| *byte == b'\n') {
+                let mut line = buffer.drain(..=end).collect::<Vec<_>>();
+                line.pop();
+                if line.last() == Some(&b'\r') {
+                    line.pop();
+                }
+                lines.push(String::from_utf8_lossy(&line).into_owned());
+            }
+            lines
+        });
+
+        self.set_chunk.set(Some(chunk));
+
+        for line in lines.unwrap_or_default() {
+            self.set_line.set(Some(line));
+        }
+    }
+
+    fn navigator_serial(&self) -> Result<web_sys::Serial, UseSerialError> {
+        if !self.is_supported.get_untracked() {
+            return Err(UseSerialError::NotSupported);
+        }
+
+        Ok(window().navigator().serial())
+    }
+
+    fn fail(&self, err: UseSerialError) -> UseSerialError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// Errors of [`use_serial`].
+#[derive(Error, Debug, Clone)]
+pub enum UseSerialError {
+    #[error("the Web Serial API is not supported")]
+    NotSupported,
+    #[error("a port is already open")]
+    AlreadyOpen,
+    #[error("no port is open")]
+    NotOpen,
+    #[error("requesting a port failed")]
+    RequestPort(JsValue),
+    #[error("opening the port failed")]
+    Open(JsValue),
+    #[error("reading from the port failed")]
+    Read(JsValue),
+    #[error("serial operation failed")]
+    Failed(JsValue),
+}
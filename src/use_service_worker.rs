@@ -1,10 +1,12 @@
 use default_struct_builder::DefaultBuilder;
 use leptos::*;
 use std::rc::Rc;
+use thiserror::Error;
 use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
-use web_sys::ServiceWorkerRegistration;
+use web_sys::{MessageEvent, ServiceWorkerRegistration};
 
-use crate::{js_fut, use_window};
+use crate::utils::{JsCodec, JsValueCodec};
+use crate::{js_fut, use_event_listener, use_interval_fn, use_window};
 
 /// Reactive [ServiceWorker API](https://developer.mozilla.org/en-US/docs/Web/API/Service_Worker_API).
 ///
@@ -15,6 +17,8 @@ use crate::{js_fut, use_window};
 /// ```
 /// # use leptos::*;
 /// # use leptos_use::{use_service_worker_with_options, UseServiceWorkerOptions, UseServiceWorkerReturn};
+/// # use leptos_use::utils::JsValueCodec;
+/// # use wasm_bindgen::JsValue;
 /// #
 /// # #[component]
 /// # fn Demo() -> impl IntoView {
@@ -25,7 +29,8 @@ use crate::{js_fut, use_window};
 ///         active,
 ///         skip_waiting,
 ///         check_for_update,
-/// } = use_service_worker_with_options(UseServiceWorkerOptions::default()
+///         ..
+/// } = use_service_worker_with_options::<JsValue, JsValue, JsValueCodec>(UseServiceWorkerOptions::default()
 ///     .script_url("service-worker.js")
 ///     .skip_waiting_message("skipWaiting"),
 /// );
@@ -34,22 +39,141 @@ use crate::{js_fut, use_window};
 /// # }
 /// ```
 ///
+/// ## Updates
+///
+/// The registration is checked for an updated service worker when the page is loaded. Set
+/// `update_interval` to check periodically as well. Once an update has been installed,
+/// `needs_refresh` is `true` until it has been activated. Call `skip_waiting_and_reload` to
+/// activate it and reload the page as soon as it has taken control.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_service_worker_with_options, UseServiceWorkerOptions, UseServiceWorkerReturn};
+/// # use leptos_use::utils::JsValueCodec;
+/// # use wasm_bindgen::JsValue;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseServiceWorkerReturn {
+///     needs_refresh,
+///     skip_waiting_and_reload,
+///     ..
+/// } = use_service_worker_with_options::<JsValue, JsValue, JsValueCodec>(
+///     UseServiceWorkerOptions::default()
+///         .skip_waiting_message("SKIP_WAITING")
+///         .update_interval(Some(60 * 60 * 1000)),
+/// );
+///
+/// view! {
+///     <Show when=move || needs_refresh.get()>
+///         <p>"A new version is available."</p>
+///         <button on:click={
+///             let skip_waiting_and_reload = skip_waiting_and_reload.clone();
+///             move |_| skip_waiting_and_reload()
+///         }>"Reload"</button>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// The service worker has to call `self.skipWaiting()` when it receives the message:
+///
+/// ```js
+/// self.addEventListener("message", (event) => {
+///   if (event.data === "SKIP_WAITING") {
+///     self.skipWaiting();
+///   }
+/// });
+/// ```
+///
+/// ## Messages
+///
+/// `post_message` sends a message to the active service worker. Messages the service worker
+/// sends to this page with `client.postMessage()` are available in `message`. Both are
+/// converted with the given [`JsCodec`]. [`use_service_worker`] uses [`JsValueCodec`] which
+/// passes JavaScript values through unchanged.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_service_worker_with_options, UseServiceWorkerOptions, UseServiceWorkerReturn};
+/// # use leptos_use::utils::SerdeWasmCodec;
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// #[derive(Serialize, Deserialize, Clone)]
+/// enum Request {
+///     ClearCache,
+/// }
+///
+/// #[derive(Serialize, Deserialize, Clone)]
+/// enum Response {
+///     CacheCleared,
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseServiceWorkerReturn {
+///     message,
+///     post_message,
+///     ..
+/// } = use_service_worker_with_options::<Request, Response, SerdeWasmCodec>(
+///     UseServiceWorkerOptions::default(),
+/// );
+///
+/// view! {
+///     <button on:click=move |_| post_message(&Request::ClearCache)>"Clear cache"</button>
+///     <Show when=move || matches!(message.get(), Some(Response::CacheCleared))>
+///         <p>"Cache cleared"</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
 /// ## Server-Side Rendering
 ///
 /// This function does **not** support SSR. Call it inside a `create_effect`.
-pub fn use_service_worker() -> UseServiceWorkerReturn<impl Fn() + Clone, impl Fn() + Clone> {
-    use_service_worker_with_options(UseServiceWorkerOptions::default())
+pub fn use_service_worker() -> UseServiceWorkerReturn<
+    JsValue,
+    JsValue,
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+    impl Fn(&JsValue) + Clone,
+> {
+    use_service_worker_with_options::<JsValue, JsValue, JsValueCodec>(
+        UseServiceWorkerOptions::default(),
+    )
 }
 
 /// Version of [`use_service_worker`] that takes a `UseServiceWorkerOptions`. See [`use_service_worker`] for how to use.
-pub fn use_service_worker_with_options(
-    options: UseServiceWorkerOptions,
-) -> UseServiceWorkerReturn<impl Fn() + Clone, impl Fn() + Clone> {
+#[allow(clippy::type_complexity)]
+pub fn use_service_worker_with_options<T, R, C>(
+    options: UseServiceWorkerOptions<R, C>,
+) -> UseServiceWorkerReturn<
+    R,
+    <C as JsCodec<R>>::Error,
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+    impl Fn(&T) + Clone,
+>
+where
+    T: 'static,
+    R: 'static,
+    C: JsCodec<T, Error = <C as JsCodec<R>>::Error> + JsCodec<R>,
+{
+    // Set by `skip_waiting_and_reload` to reload the page instead of calling `on_controller_change`.
+    let reload_on_controller_change = store_value(false);
+
     // Trigger the user-defined action (page-reload by default)
     // whenever a new ServiceWorker is installed.
     if let Some(navigator) = use_window().navigator() {
         let on_controller_change = options.on_controller_change.clone();
         let js_closure = Closure::wrap(Box::new(move |_event: JsValue| {
+            if reload_on_controller_change.try_get_value() == Some(true) {
+                reload_page();
+                return;
+            }
+
             #[cfg(debug_assertions)]
             let prev = SpecialNonReactiveZone::enter();
 
@@ -118,6 +242,106 @@ pub fn use_service_worker_with_options(
         })
     });
 
+    // Receive the messages the service worker posts to this page.
+    let (message, set_message) = create_signal(None::<R>);
+    let (message_error, set_message_error) =
+        create_signal(None::<ServiceWorkerMessageError<<C as JsCodec<R>>::Error>>);
+
+    let container = use_window()
+        .navigator()
+        .map(|navigator| navigator.service_worker());
+
+    let _ = use_event_listener(container.clone(), ev::message, {
+        let codec = options.codec.clone();
+        let on_message = Rc::clone(&options.on_message);
+
+        move |event| match JsCodec::<R>::decode(&codec, event.data()) {
+            Ok(val) => {
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                on_message(&val);
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+
+                set_message.set(Some(val));
+            }
+            Err(err) => set_message_error.set(Some(ServiceWorkerMessageError::Decode(err))),
+        }
+    });
+
+    let _ = use_event_listener(
+        container,
+        ev::Custom::<MessageEvent>::new("messageerror"),
+        move |event| set_message_error.set(Some(ServiceWorkerMessageError::MessageEvent(event))),
+    );
+
+    let post_message = {
+        let codec = options.codec.clone();
+
+        move |val: &T| {
+            let Some(sw) =
+                registration.with_untracked(|reg| reg.as_ref().ok().and_then(|reg| reg.active()))
+            else {
+                set_message_error.set(Some(ServiceWorkerMessageError::NoActiveWorker));
+                return;
+            };
+
+            match JsCodec::<T>::encode(&codec, val) {
+                Ok(data) => {
+                    if let Err(err) = sw.post_message(&data) {
+                        set_message_error.set(Some(ServiceWorkerMessageError::PostMessage(err)));
+                    }
+                }
+                Err(err) => set_message_error.set(Some(ServiceWorkerMessageError::Encode(err))),
+            }
+        }
+    };
+
+    let check_for_update = move || {
+        registration.with(|reg| {
+            if let Ok(reg) = reg {
+                update_sw.dispatch(reg.clone())
+            }
+        })
+    };
+
+    // Periodically check for an updated SW if requested.
+    if let Some(interval) = options.update_interval {
+        let _ = use_interval_fn(check_for_update, interval);
+    }
+
+    let skip_waiting = move || {
+        registration.with_untracked(|reg| if let Ok(reg) = reg {
+            match reg.waiting() {
+                Some(sw) => {
+                    logging::debug_warn!("Updating to newly installed SW...");
+                    if let Err(err) = sw.post_message(&JsValue::from_str(&options.skip_waiting_message)) {
+                        logging::warn!("Could not send message to active SW: Error: {err:?}");
+                    }
+                },
+                None => {
+                    logging::warn!("You tried to update the SW while no new SW was waiting. This is probably a bug.");
+                },
+            }
+        });
+    };
+
+    let skip_waiting_and_reload = {
+        let skip_waiting = skip_waiting.clone();
+
+        move || {
+            if registration
+                .with_untracked(|reg| reg.as_ref().is_ok_and(|reg| reg.waiting().is_some()))
+            {
+                reload_on_controller_change.set_value(true);
+            }
+
+            skip_waiting();
+        }
+    };
+
     UseServiceWorkerReturn {
         registration,
         installing: Signal::derive(move || {
@@ -141,34 +365,29 @@ pub fn use_service_worker_with_options(
                     .unwrap_or_default()
             })
         }),
-        check_for_update: move || {
+        needs_refresh: Signal::derive(move || {
             registration.with(|reg| {
-                if let Ok(reg) = reg {
-                    update_sw.dispatch(reg.clone())
-                }
+                reg.as_ref()
+                    .map(|reg| reg.waiting().is_some() && reg.active().is_some())
+                    .unwrap_or_default()
             })
-        },
-        skip_waiting: move || {
-            registration.with_untracked(|reg| if let Ok(reg) = reg {
-                match reg.waiting() {
-                    Some(sw) => {
-                        logging::debug_warn!("Updating to newly installed SW...");
-                        if let Err(err) = sw.post_message(&JsValue::from_str(&options.skip_waiting_message)) {
-                            logging::warn!("Could not send message to active SW: Error: {err:?}");
-                        }
-                    },
-                    None => {
-                        logging::warn!("You tried to update the SW while no new SW was waiting. This is probably a bug.");
-                    },
-                }
-            });
-        },
+        }),
+        check_for_update,
+        skip_waiting,
+        skip_waiting_and_reload,
+        message: message.into(),
+        message_error: message_error.into(),
+        post_message,
     }
 }
 
 /// Options for [`use_service_worker_with_options`].
 #[derive(DefaultBuilder)]
-pub struct UseServiceWorkerOptions {
+pub struct UseServiceWorkerOptions<R, C>
+where
+    R: 'static,
+    C: JsCodec<R>,
+{
     /// The name of your service-worker file. Must be deployed alongside your app.
     /// The default name is 'service-worker.js'.
     #[builder(into)]
@@ -183,32 +402,44 @@ pub struct UseServiceWorkerOptions {
     /// What should happen when a new service worker was activated?
     /// The default implementation reloads the current page.
     on_controller_change: Rc<dyn Fn()>,
+
+    /// If set, the registration is checked for an updated service worker every
+    /// `update_interval` milliseconds. Otherwise this only happens when the page is loaded
+    /// and when `check_for_update` is called.
+    /// The default is `None`.
+    update_interval: Option<u64>,
+
+    /// Encodes the messages sent to and decodes the messages received from the service worker.
+    #[builder(keep_type)]
+    codec: C,
+
+    /// Called for every successfully decoded message from the service worker.
+    #[builder(keep_type)]
+    on_message: Rc<dyn Fn(&R)>,
 }
 
-impl Default for UseServiceWorkerOptions {
+impl<R, C: JsCodec<R> + Default> Default for UseServiceWorkerOptions<R, C> {
     fn default() -> Self {
         Self {
             script_url: "service-worker.js".into(),
             skip_waiting_message: "skipWaiting".into(),
-            on_controller_change: Rc::new(move || {
-                use std::ops::Deref;
-                if let Some(window) = use_window().deref() {
-                    if let Err(err) = window.location().reload() {
-                        logging::warn!(
-                            "Detected a ServiceWorkerController change but the page reload failed! Error: {err:?}"
-                        );
-                    }
-                }
-            }),
+            on_controller_change: Rc::new(reload_page),
+            update_interval: None,
+            codec: C::default(),
+            on_message: Rc::new(|_| {}),
         }
     }
 }
 
 /// Return type of [`use_service_worker`].
-pub struct UseServiceWorkerReturn<CheckFn, SkipFn>
+pub struct UseServiceWorkerReturn<R, Err, CheckFn, SkipFn, SkipReloadFn, PostFn>
 where
+    R: 'static,
+    Err: 'static,
     CheckFn: Fn() + Clone,
     SkipFn: Fn() + Clone,
+    SkipReloadFn: Fn() + Clone,
+    PostFn: Clone,
 {
     /// The current registration state.
     pub registration: Signal<Result<ServiceWorkerRegistration, ServiceWorkerRegistrationError>>,
@@ -222,22 +453,64 @@ where
     /// Whether a SW is active.
     pub active: Signal<bool>,
 
+    /// Whether an updated SW was installed and is waiting to replace the active one.
+    /// Use this to show a "new version available" notice.
+    pub needs_refresh: Signal<bool>,
+
     /// Check for a ServiceWorker update.
     pub check_for_update: CheckFn,
 
     /// Call this to activate a new ("waiting") SW if one is available.
     /// Calling this while the [`UseServiceWorkerReturn::waiting`] signal resolves to false has no effect.
     pub skip_waiting: SkipFn,
+
+    /// Like `skip_waiting` but reloads the page as soon as the new SW has taken control,
+    /// instead of calling `on_controller_change`.
+    pub skip_waiting_and_reload: SkipReloadFn,
+
+    /// Latest message received from the service worker.
+    pub message: Signal<Option<R>>,
+
+    /// Latest error that occurred while sending or receiving a message.
+    pub message_error: Signal<Option<ServiceWorkerMessageError<Err>>>,
+
+    /// Sends a message to the active service worker.
+    pub post_message: PostFn,
 }
 
 struct ServiceWorkerScriptUrl(pub String);
 
+fn reload_page() {
+    use std::ops::Deref;
+    if let Some(window) = use_window().deref() {
+        if let Err(err) = window.location().reload() {
+            logging::warn!(
+                "Detected a ServiceWorkerController change but the page reload failed! Error: {err:?}"
+            );
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ServiceWorkerRegistrationError {
     Js(JsValue),
     NeverQueried,
 }
 
+#[derive(Debug, Error, Clone)]
+pub enum ServiceWorkerMessageError<Err> {
+    #[error("no service worker is active")]
+    NoActiveWorker,
+    #[error("failed to post message")]
+    PostMessage(JsValue),
+    #[error("service worker message error")]
+    MessageEvent(MessageEvent),
+    #[error("failed to encode value")]
+    Encode(Err),
+    #[error("failed to decode value")]
+    Decode(Err),
+}
+
 /// A leptos action which asynchronously checks for ServiceWorker updates, given an existing ServiceWorkerRegistration.
 fn create_action_update(
 ) -> Action<ServiceWorkerRegistration, Result<ServiceWorkerRegistration, JsValue>> {
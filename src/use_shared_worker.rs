@@ -0,0 +1,320 @@
+use crate::core::ConnectionReadyState;
+use crate::utils::JsCodec;
+use crate::{js, use_event_listener, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::JsValue;
+use web_sys::{ErrorEvent, MessageEvent, MessagePort, SharedWorker, WorkerOptions, WorkerType};
+
+/// Reactive [SharedWorker](https://developer.mozilla.org/en-US/docs/Web/API/SharedWorker).
+/// Connects to a worker that is shared by all tabs and windows of the same origin.
+///
+/// This allows for example to keep a single WebSocket connection or cache for all open tabs.
+/// The connection is closed automatically when the component is cleaned up.
+///
+/// ## Usage
+///
+/// Messages of type `T` are sent through the port of the connection and messages of type `R` are
+/// received from it. Both are converted with the given [`JsCodec`].
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_shared_worker, UseSharedWorkerReturn};
+/// # use leptos_use::core::ConnectionReadyState;
+/// # use leptos_use::utils::SerdeWasmCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseSharedWorkerReturn {
+///     ready_state,
+///     message,
+///     post,
+///     ..
+/// } = use_shared_worker::<String, String, SerdeWasmCodec>("chat-worker.js");
+///
+/// view! {
+///     <button
+///         on:click=move |_| post(&"Hello from another tab".to_string())
+///         disabled=move || ready_state.get() != ConnectionReadyState::Open
+///     >
+///         "Send"
+///     </button>
+///     <p>"Latest: " {move || message.get()}</p>
+/// }
+/// # }
+/// ```
+///
+/// The worker script gets a port for every connected tab:
+///
+/// ```js
+/// const ports = [];
+///
+/// self.onconnect = (event) => {
+///   const port = event.ports[0];
+///   ports.push(port);
+///   port.onmessage = (event) => ports.forEach((p) => p.postMessage(event.data));
+/// };
+/// ```
+///
+/// Tabs only share a worker if they use the same script URL and the same `name`.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_shared_worker_with_options, UseSharedWorkerOptions};
+/// # use leptos_use::utils::JsValueCodec;
+/// # use wasm_bindgen::JsValue;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let cache = use_shared_worker_with_options::<JsValue, JsValue, JsValueCodec>(
+///     "cache-worker.js",
+///     UseSharedWorkerOptions::default().name("cache-v2"),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// `ready_state` is `Open` as soon as the port has been started. Browsers don't report whether
+/// the worker has handled the connection, so send a message from the worker if you need to know.
+/// It becomes `Closed` when `close` is called or the worker script fails.
+///
+/// ## Server-Side Rendering
+///
+/// On the server no worker is started, `ready_state` is always `Closed` and `post` does nothing.
+pub fn use_shared_worker<T, R, C>(
+    url: &str,
+) -> UseSharedWorkerReturn<
+    R,
+    <C as JsCodec<R>>::Error,
+    impl Fn(&T) + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    T: 'static,
+    R: 'static,
+    C: JsCodec<T, Error = <C as JsCodec<R>>::Error> + JsCodec<R> + Default,
+{
+    use_shared_worker_with_options(url, UseSharedWorkerOptions::<R, C>::default())
+}
+
+/// Version of [`use_shared_worker`] that takes `UseSharedWorkerOptions`. See [`use_shared_worker`] for how to use.
+pub fn use_shared_worker_with_options<T, R, C>(
+    url: &str,
+    options: UseSharedWorkerOptions<R, C>,
+) -> UseSharedWorkerReturn<
+    R,
+    <C as JsCodec<R>>::Error,
+    impl Fn(&T) + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    T: 'static,
+    R: 'static,
+    C: JsCodec<T, Error = <C as JsCodec<R>>::Error> + JsCodec<R>,
+{
+    let UseSharedWorkerOptions {
+        codec,
+        worker_type,
+        name,
+        on_message,
+        on_error,
+    } = options;
+
+    let is_supported = use_supported(|| js!("SharedWorker" in &window()));
+
+    let (worker, set_worker) = create_signal(None::<SharedWorker>);
+    let (port, set_port) = create_signal(None::<MessagePort>);
+    let (ready_state, set_ready_state) = create_signal(ConnectionReadyState::Closed);
+    let (message, set_message) = create_signal(None::<R>);
+    let (error, set_error) = create_signal(None::<UseSharedWorkerError<<C as JsCodec<R>>::Error>>);
+
+    if is_supported.get_untracked() {
+        let worker_options = WorkerOptions::new();
+        worker_options.set_type(worker_type);
+        if let Some(name) = &name {
+            worker_options.set_name(name);
+        }
+
+        match SharedWorker::new_with_worker_options(url, &worker_options) {
+            Ok(worker) => {
+                set_port.set(Some(worker.port()));
+                set_worker.set(Some(worker));
+            }
+            Err(err) => set_error.set(Some(UseSharedWorkerError::Create(err))),
+        }
+    }
+
+    let _ = use_event_listener(port, ev::message, {
+        let codec = codec.clone();
+
+        move |event| match JsCodec::<R>::decode(&codec, event.data()) {
+            Ok(val) => {
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                on_message(&val);
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+
+                set_message.set(Some(val));
+            }
+            Err(err) => set_error.set(Some(UseSharedWorkerError::Decode(err))),
+        }
+    });
+
+    let _ = use_event_listener(
+        port,
+        ev::Custom::<MessageEvent>::new("messageerror"),
+        move |event| set_error.set(Some(UseSharedWorkerError::MessageEvent(event))),
+    );
+
+    let _ = use_event_listener(
+        worker,
+        ev::Custom::<ErrorEvent>::new("error"),
+        move |event| {
+            set_ready_state.set(ConnectionReadyState::Closed);
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_error(event.clone());
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+
+            set_error.set(Some(UseSharedWorkerError::Worker(event)));
+        },
+    );
+
+    // Messages are only delivered to the listeners above once the port has been started.
+    if let Some(port) = port.get_untracked() {
+        port.start();
+        set_ready_state.set(ConnectionReadyState::Open);
+    }
+
+    let post = move |val: &T| {
+        if ready_state.get_untracked() != ConnectionReadyState::Open {
+            return;
+        }
+
+        if let Some(port) = port.get_untracked() {
+            match JsCodec::<T>::encode(&codec, val) {
+                Ok(data) => {
+                    port.post_message(&data)
+                        .map_err(|err| set_error.set(Some(UseSharedWorkerError::PostMessage(err))))
+                        .ok();
+                }
+                Err(err) => set_error.set(Some(UseSharedWorkerError::Encode(err))),
+            }
+        }
+    };
+
+    let close = move || {
+        if let Some(Some(port)) = port.try_get_untracked() {
+            port.close();
+            set_ready_state.set(ConnectionReadyState::Closed);
+        }
+    };
+
+    on_cleanup(close);
+
+    UseSharedWorkerReturn {
+        is_supported,
+        port: port.into(),
+        ready_state: ready_state.into(),
+        message: message.into(),
+        error: error.into(),
+        post,
+        close,
+    }
+}
+
+/// Options for [`use_shared_worker_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseSharedWorkerOptions<R, C>
+where
+    R: 'static,
+    C: JsCodec<R>,
+{
+    /// Encodes sent and decodes received messages.
+    #[builder(keep_type)]
+    codec: C,
+
+    /// Whether the script is a classic script or an ES module. Defaults to `WorkerType::Classic`.
+    worker_type: WorkerType,
+
+    /// The name of the worker. Only connections with the same name share a worker.
+    /// Defaults to `None`.
+    #[builder(into)]
+    name: Option<String>,
+
+    /// Called for every successfully decoded message from the worker.
+    #[builder(keep_type)]
+    on_message: Rc<dyn Fn(&R)>,
+
+    /// Called when the worker script can't be loaded or fails.
+    on_error: Rc<dyn Fn(ErrorEvent)>,
+}
+
+impl<R, C: JsCodec<R> + Default> Default for UseSharedWorkerOptions<R, C> {
+    fn default() -> Self {
+        Self {
+            codec: C::default(),
+            worker_type: WorkerType::Classic,
+            name: None,
+            on_message: Rc::new(|_| {}),
+            on_error: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// Return type of [`use_shared_worker`].
+#[derive(Clone)]
+pub struct UseSharedWorkerReturn<R, Err, PostFn, CloseFn>
+where
+    R: 'static,
+    Err: 'static,
+    PostFn: Clone + 'static,
+    CloseFn: Fn() + Clone + 'static,
+{
+    /// `true` if this browser supports shared workers.
+    pub is_supported: Signal<bool>,
+
+    /// The port of the connection to the worker
+    pub port: Signal<Option<MessagePort>>,
+
+    /// The state of the connection
+    pub ready_state: Signal<ConnectionReadyState>,
+
+    /// Latest message received from the worker
+    pub message: Signal<Option<R>>,
+
+    /// Latest error
+    pub error: Signal<Option<UseSharedWorkerError<Err>>>,
+
+    /// Sends a message to the worker
+    pub post: PostFn,
+
+    /// Closes the connection. The worker keeps running as long as other tabs are connected.
+    pub close: CloseFn,
+}
+
+#[derive(Debug, Error, Clone)]
+pub enum UseSharedWorkerError<Err> {
+    #[error("failed to create shared worker")]
+    Create(JsValue),
+    #[error("shared worker error")]
+    Worker(ErrorEvent),
+    #[error("failed to post message")]
+    PostMessage(JsValue),
+    #[error("port message error")]
+    MessageEvent(MessageEvent),
+    #[error("failed to encode value")]
+    Encode(Err),
+    #[error("failed to decode value")]
+    Decode(Err),
+}
@@ -0,0 +1,472 @@
+use crate::core::{ElementMaybeSignal, Position};
+use crate::use_event_listener;
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{keydown, pointercancel, pointerdown, pointermove, pointerup};
+use leptos::*;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+
+/// How many pixels the pointer has to move before dragging starts so clicks still work
+const DRAG_THRESHOLD: f64 = 4.0;
+
+/// Reorder a list by dragging its items with the pointer or moving them with the keyboard.
+///
+/// ## Usage
+///
+/// The direct children of `container` are the items of the list in the same order as in `list`.
+/// When an item is dropped at another place it is moved in `list` accordingly.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Ul;
+/// # use leptos_use::{use_sortable, UseSortableReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let container = create_node_ref::<Ul>();
+/// let list = create_rw_signal(vec!["Apple", "Banana", "Cherry"]);
+///
+/// let UseSortableReturn {
+///     dragged_index,
+///     drop_index,
+///     ..
+/// } = use_sortable(container, list);
+///
+/// view! {
+///     <ul node_ref=container>
+///         <For each=move || list.get() key=|item| *item let:item>
+///             <li tabindex="0" style="touch-action: none">{item}</li>
+///         </For>
+///     </ul>
+///     <p>{move || format!("Moving {:?} to {:?}", dragged_index.get(), drop_index.get())}</p>
+/// }
+/// # }
+/// ```
+///
+/// Set `touch-action: none` on the items, otherwise dragging with a finger scrolls the page.
+///
+/// ## Drop Indicator and Animations
+///
+/// While dragging, `drop_index` is the index the item will have after it has been dropped.
+/// `drag_offset` is how far the pointer has moved so the dragged item can follow it and
+/// [`UseSortableReturn::shift`] tells by how many places every other item has to make way.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Ul;
+/// # use leptos_use::use_sortable;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let container = create_node_ref::<Ul>();
+/// let list = create_rw_signal(vec!["Apple", "Banana", "Cherry"]);
+///
+/// let sortable = use_sortable(container, list);
+///
+/// let style = move |index: usize| {
+///     if sortable.dragged_index.get() == Some(index) {
+///         let offset = sortable.drag_offset.get();
+///         format!("transform: translate({}px, {}px)", offset.x, offset.y)
+///     } else {
+///         // every item has a height of 40px
+///         format!(
+///             "transform: translateY({}px); transition: transform 0.2s",
+///             sortable.shift(index) * 40
+///         )
+///     }
+/// };
+///
+/// view! {
+///     <ul node_ref=container>
+///         {move || {
+///             list.get()
+///                 .into_iter()
+///                 .enumerate()
+///                 .map(|(index, item)| view! { <li style=move || style(index)>{item}</li> })
+///                 .collect_view()
+///         }}
+///     </ul>
+/// }
+/// # }
+/// ```
+///
+/// ## Keyboard
+///
+/// Make the items focusable with `tabindex="0"`. When an item has the focus, <kbd>Space</kbd> or
+/// <kbd>Enter</kbd> picks it up, the arrow keys change `drop_index` and <kbd>Space</kbd> or
+/// <kbd>Enter</kbd> drops it again. <kbd>Escape</kbd> cancels. The moved item keeps the focus.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_dragging` is always `false` and the list is never changed.
+pub fn use_sortable<El, T, Item>(container: El, list: RwSignal<Vec<Item>>) -> UseSortableReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+    Item: 'static,
+{
+    use_sortable_with_options(container, list, UseSortableOptions::default())
+}
+
+/// Version of [`use_sortable`] that takes a `UseSortableOptions`. See [`use_sortable`] for how to use.
+pub fn use_sortable_with_options<El, T, Item>(
+    container: El,
+    list: RwSignal<Vec<Item>>,
+    options: UseSortableOptions,
+) -> UseSortableReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+    Item: 'static,
+{
+    let UseSortableOptions {
+        direction,
+        handle,
+        on_sort,
+    } = options;
+
+    let container = container.into();
+    let container_element = {
+        let container = container.clone();
+
+        Signal::derive(move || {
+            container
+                .get()
+                .map(|container| container.into().unchecked_into::<web_sys::Element>())
+        })
+    };
+
+    let (dragged_index, set_dragged_index) = create_signal(None::<usize>);
+    let (drop_index, set_drop_index) = create_signal(None::<usize>);
+    let (drag_offset, set_drag_offset) = create_signal(Position::default());
+    let (is_keyboard_drag, set_keyboard_drag) = create_signal(false);
+
+    // the pressed item and where it has been pressed, before dragging has started
+    let pressed = store_value(None::<(i32, usize, Position)>);
+    let dragged_element = store_value(None::<web_sys::Element>);
+
+    let reset = move || {
+        pressed.set_value(None);
+        dragged_element.set_value(None);
+        set_dragged_index.set(None);
+        set_drop_index.set(None);
+        set_drag_offset.set(Position::default());
+        set_keyboard_drag.set(false);
+    };
+
+    let drop_item = move || {
+        if let (Some(from), Some(to)) = (dragged_index.get_untracked(), drop_index.get_untracked())
+        {
+            let len = list.with_untracked(Vec::len);
+
+            if from != to && from < len && to < len {
+                list.update(|items| {
+                    let item = items.remove(from);
+                    items.insert(to, item);
+                });
+
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                on_sort(from, to);
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+            }
+        }
+
+        let element = dragged_element.get_value();
+        let was_keyboard_drag = is_keyboard_drag.get_untracked();
+
+        reset();
+
+        if was_keyboard_drag {
+            if let Some(element) = element.and_then(|el| el.dyn_into::<web_sys::HtmlElement>().ok())
+            {
+                let _ = element.focus();
+            }
+        }
+    };
+
+    let _ = use_event_listener(container.clone(), pointerdown, move |event| {
+        if !event.is_primary() || event.button() != 0 || is_keyboard_drag.get_untracked() {
+            return;
+        }
+
+        let (Some(container), Some(target)) = (
+            container_element.get_untracked(),
+            event_target_element(&event),
+        ) else {
+            return;
+        };
+
+        if let Some(handle) = &handle {
+            if !target.closest(handle).ok().flatten().is_some_and(|handle| {
+                container.contains(Some(&handle)) && !handle.is_same_node(Some(&container))
+            }) {
+                return;
+            }
+        }
+
+        if let Some((index, element)) = item_of(&container, &target) {
+            pressed.set_value(Some((
+                event.pointer_id(),
+                index,
+                Position {
+                    x: event.client_x() as f64,
+                    y: event.client_y() as f64,
+                },
+            )));
+            dragged_element.set_value(Some(element));
+        }
+    });
+
+    let _ = use_event_listener(container.clone(), pointermove, move |event| {
+        let Some((pointer_id, index, start)) = pressed.get_value() else {
+            return;
+        };
+        if pointer_id != event.pointer_id() {
+            return;
+        }
+        let Some(container) = container_element.get_untracked() else {
+            return;
+        };
+
+        let offset = Position {
+            x: event.client_x() as f64 - start.x,
+            y: event.client_y() as f64 - start.y,
+        };
+
+        if dragged_index.get_untracked().is_none() {
+            if offset.x.hypot(offset.y) < DRAG_THRESHOLD {
+                return;
+            }
+
+            // keep receiving the events when the pointer leaves the container
+            let _ = container.set_pointer_capture(pointer_id);
+            set_dragged_index.set(Some(index));
+        }
+
+        let pointer = match direction {
+            UseSortableDirection::Vertical => event.client_y() as f64,
+            UseSortableDirection::Horizontal => event.client_x() as f64,
+        };
+        let new_drop_index = drop_index_at(&container, index, pointer, direction)
+            .min(list.with_untracked(Vec::len).saturating_sub(1));
+
+        set_drag_offset.set(offset);
+        if drop_index.get_untracked() != Some(new_drop_index) {
+            set_drop_index.set(Some(new_drop_index));
+        }
+    });
+
+    let _ = use_event_listener(container.clone(), pointerup, {
+        let drop_item = drop_item.clone();
+
+        move |event| {
+            if pressed
+                .get_value()
+                .is_some_and(|(pointer_id, ..)| pointer_id == event.pointer_id())
+            {
+                drop_item();
+            }
+        }
+    });
+
+    let _ = use_event_listener(container.clone(), pointercancel, move |event| {
+        if pressed
+            .get_value()
+            .is_some_and(|(pointer_id, ..)| pointer_id == event.pointer_id())
+        {
+            reset();
+        }
+    });
+
+    let _ = use_event_listener(container, keydown, move |event| {
+        let is_dragging_with_pointer =
+            dragged_index.get_untracked().is_some() && !is_keyboard_drag.get_untracked();
+        if is_dragging_with_pointer {
+            if event.key() == "Escape" {
+                reset();
+            }
+            return;
+        }
+
+        let (backward, forward) = match direction {
+            UseSortableDirection::Vertical => ("ArrowUp", "ArrowDown"),
+            UseSortableDirection::Horizontal => ("ArrowLeft", "ArrowRight"),
+        };
+
+        match event.key().as_str() {
+            " " | "Enter" => {
+                if is_keyboard_drag.get_untracked() {
+                    event.prevent_default();
+                    drop_item();
+                    return;
+                }
+
+                let (Some(container), Some(target)) = (
+                    container_element.get_untracked(),
+                    event_target_element(&event),
+                ) else {
+                    return;
+                };
+
+                // only the focused item itself is picked up, not a button inside of it
+                if let Some((index, element)) =
+                    item_of(&container, &target).filter(|(_, element)| *element == target)
+                {
+                    event.prevent_default();
+                    dragged_element.set_value(Some(element));
+                    set_keyboard_drag.set(true);
+                    set_dragged_index.set(Some(index));
+                    set_drop_index.set(Some(index));
+                }
+            }
+            key if (key == backward || key == forward) && is_keyboard_drag.get_untracked() => {
+                event.prevent_default();
+
+                let last_index = list.with_untracked(Vec::len).saturating_sub(1);
+                set_drop_index.update(|drop_index| {
+                    *drop_index = drop_index.map(|index| {
+                        if key == backward {
+                            index.saturating_sub(1)
+                        } else {
+                            (index + 1).min(last_index)
+                        }
+                    });
+                });
+            }
+            "Escape" | "Tab" if is_keyboard_drag.get_untracked() => reset(),
+            _ => {}
+        }
+    });
+
+    UseSortableReturn {
+        is_dragging: Signal::derive(move || dragged_index.get().is_some()),
+        dragged_index: dragged_index.into(),
+        drop_index: drop_index.into(),
+        drag_offset: drag_offset.into(),
+    }
+}
+
+fn event_target_element(event: &web_sys::Event) -> Option<web_sys::Element> {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::Node>().ok())
+        .and_then(|node| {
+            if node.node_type() == web_sys::Node::ELEMENT_NODE {
+                Some(node.unchecked_into())
+            } else {
+                node.parent_element()
+            }
+        })
+}
+
+/// The index and element of the direct child of `container` that contains `target`
+fn item_of(
+    container: &web_sys::Element,
+    target: &web_sys::Element,
+) -> Option<(usize, web_sys::Element)> {
+    let children = container.children();
+
+    (0..children.length())
+        .filter_map(|index| children.item(index))
+        .enumerate()
+        .find(|(_, child)| child.contains(Some(target)))
+}
+
+/// The index the dragged item would have if it was dropped at `pointer`
+fn drop_index_at(
+    container: &web_sys::Element,
+    dragged_index: usize,
+    pointer: f64,
+    direction: UseSortableDirection,
+) -> usize {
+    let children = container.children();
+
+    (0..children.length())
+        .filter_map(|index| children.item(index))
+        .enumerate()
+        .filter(|(index, _)| *index != dragged_index)
+        .filter(|(_, child)| {
+            let rect = child.get_bounding_client_rect();
+            let middle = match direction {
+                UseSortableDirection::Vertical => rect.top() + rect.height() / 2.0,
+                UseSortableDirection::Horizontal => rect.left() + rect.width() / 2.0,
+            };
+
+            pointer > middle
+        })
+        .count()
+}
+
+/// Options for [`use_sortable_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseSortableOptions {
+    /// In which direction the items are laid out. Defaults to `UseSortableDirection::Vertical`.
+    direction: UseSortableDirection,
+
+    /// CSS selector of the part of an item it can be dragged by. Defaults to `None` which means
+    /// the whole item.
+    #[builder(into)]
+    handle: Option<String>,
+
+    /// Called after an item has been moved with the old and the new index.
+    on_sort: Rc<dyn Fn(usize, usize)>,
+}
+
+impl Default for UseSortableOptions {
+    fn default() -> Self {
+        Self {
+            direction: UseSortableDirection::default(),
+            handle: None,
+            on_sort: Rc::new(|_, _| {}),
+        }
+    }
+}
+
+/// Direction in which the items of [`use_sortable`] are laid out.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UseSortableDirection {
+    /// From top to bottom
+    #[default]
+    Vertical,
+    /// From left to right
+    Horizontal,
+}
+
+/// Return type of [`use_sortable`].
+#[derive(Clone, Copy)]
+pub struct UseSortableReturn {
+    /// Whether an item is being dragged
+    pub is_dragging: Signal<bool>,
+
+    /// The index of the item that is being dragged
+    pub dragged_index: Signal<Option<usize>>,
+
+    /// The index the dragged item will have after it has been dropped
+    pub drop_index: Signal<Option<usize>>,
+
+    /// How far the pointer has moved since dragging started. Always zero when moving an item
+    /// with the keyboard.
+    pub drag_offset: Signal<Position>,
+}
+
+impl UseSortableReturn {
+    /// By how many places the item at `index` has to move to make way for the dragged item.
+    /// `-1` means one place backward (up or left), `1` one place forward and `0` not at all.
+    pub fn shift(&self, index: usize) -> i32 {
+        let (Some(from), Some(to)) = (self.dragged_index.get(), self.drop_index.get()) else {
+            return 0;
+        };
+
+        if from < index && index <= to {
+            -1
+        } else if to <= index && index < from {
+            1
+        } else {
+            0
+        }
+    }
+}
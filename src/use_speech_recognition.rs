@@ -0,0 +1,316 @@
+use crate::{js, use_event_listener, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{
+    SpeechRecognition, SpeechRecognitionError, SpeechRecognitionErrorCode, SpeechRecognitionEvent,
+};
+
+/// Reactive [SpeechRecognition](https://developer.mozilla.org/en-US/docs/Web/API/SpeechRecognition).
+/// Turns what the user says into text.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_speech_recognition;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let speech = use_speech_recognition();
+///
+/// view! {
+///     <Show
+///         when=move || speech.is_supported.get()
+///         fallback=|| view! { <p>"Your browser doesn't support speech recognition"</p> }
+///     >
+///         <button on:click=move |_| speech.toggle()>
+///             {move || if speech.is_listening.get() { "Stop" } else { "Speak" }}
+///         </button>
+///         <p>{speech.transcript} <i>{speech.interim_transcript}</i></p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// `transcript` contains everything that has been recognized for sure since `start` was called.
+/// While the user is still speaking the current guess is available in `interim_transcript`.
+///
+/// The language can be a signal. Changing it takes effect the next time the recognition is
+/// started.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_speech_recognition_with_options, UseSpeechRecognitionOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (lang, set_lang) = create_signal("de-DE".to_string());
+///
+/// let speech = use_speech_recognition_with_options(
+///     UseSpeechRecognitionOptions::default()
+///         .lang(lang)
+///         .continuous(false),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_supported` is `false` and `start` does nothing.
+pub fn use_speech_recognition() -> UseSpeechRecognitionReturn {
+    use_speech_recognition_with_options(UseSpeechRecognitionOptions::default())
+}
+
+/// Version of [`use_speech_recognition`] that takes a `UseSpeechRecognitionOptions`. See [`use_speech_recognition`] for how to use.
+pub fn use_speech_recognition_with_options(
+    options: UseSpeechRecognitionOptions,
+) -> UseSpeechRecognitionReturn {
+    let UseSpeechRecognitionOptions {
+        lang,
+        continuous,
+        interim_results,
+        max_alternatives,
+    } = options;
+
+    let is_supported = use_supported(|| {
+        js!("SpeechRecognition" in &window()) || js!("webkitSpeechRecognition" in &window())
+    });
+
+    let (is_listening, set_listening) = create_signal(false);
+    let (transcript, set_transcript) = create_signal(String::new());
+    let (interim_transcript, set_interim_transcript) = create_signal(String::new());
+    let (error, set_error) = create_signal(None::<UseSpeechRecognitionError>);
+
+    let recognition = if is_supported.get_untracked() {
+        match create_recognition() {
+            Ok(recognition) => {
+                let _ = recognition.set_continuous(continuous);
+                recognition.set_interim_results(interim_results);
+                recognition.set_max_alternatives(max_alternatives);
+                Some(recognition)
+            }
+            Err(err) => {
+                set_error.set(Some(UseSpeechRecognitionError::Start(err)));
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    if let Some(recognition) = recognition.clone() {
+        let _ = watch(
+            move || lang.get(),
+            move |lang, _, _| recognition.set_lang(lang),
+            true,
+        );
+    }
+
+    let _ = use_event_listener(
+        recognition.clone(),
+        ev::Custom::<web_sys::Event>::new("start"),
+        move |_| set_listening.set(true),
+    );
+
+    let _ = use_event_listener(
+        recognition.clone(),
+        ev::Custom::<web_sys::Event>::new("end"),
+        move |_| {
+            set_listening.set(false);
+            set_interim_transcript.set(String::new());
+        },
+    );
+
+    let _ = use_event_listener(
+        recognition.clone(),
+        ev::Custom::<SpeechRecognitionEvent>::new("result"),
+        move |event| {
+            let Some(results) = event.results() else {
+                return;
+            };
+
+            let mut final_text = String::new();
+            let mut interim_text = String::new();
+
+            for result in (0..results.length()).filter_map(|index| results.get(index)) {
+                let Some(alternative) = result.get(0) else {
+                    continue;
+                };
+
+                if result.is_final() {
+                    final_text.push_str(&alternative.transcript());
+                } else {
+                    interim_text.push_str(&alternative.transcript());
+                }
+            }
+
+            set_transcript.set(final_text);
+            set_interim_transcript.set(interim_text);
+        },
+    );
+
+    let _ = use_event_listener(
+        recognition.clone(),
+        ev::Custom::<SpeechRecognitionError>::new("error"),
+        move |event| {
+            set_error.set(Some(UseSpeechRecognitionError::Recognition {
+                code: event.error(),
+                message: event.message().unwrap_or_default(),
+            }));
+        },
+    );
+
+    let ret = UseSpeechRecognitionReturn {
+        is_supported,
+        is_listening: is_listening.into(),
+        transcript: transcript.into(),
+        interim_transcript: interim_transcript.into(),
+        error: error.into(),
+        recognition: store_value(recognition),
+        set_transcript,
+        set_interim_transcript,
+        set_error,
+    };
+
+    on_cleanup(move || ret.abort());
+
+    ret
+}
+
+fn create_recognition() -> Result<SpeechRecognition, JsValue> {
+    let window = window();
+
+    let class = if js!("SpeechRecognition" in &window) {
+        js!(window["SpeechRecognition"])?
+    } else {
+        js!(window["webkitSpeechRecognition"])?
+    };
+
+    let recognition = js_sys::Reflect::construct(
+        &class.dyn_into::<js_sys::Function>()?,
+        &js_sys::Array::new(),
+    )?;
+
+    Ok(recognition.unchecked_into())
+}
+
+/// Options for [`use_speech_recognition_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseSpeechRecognitionOptions {
+    /// The language to recognize as a BCP 47 tag like `"en-US"`. Defaults to `"en-US"`.
+    #[builder(into)]
+    lang: MaybeSignal<String>,
+
+    /// Keep listening after the user stopped speaking. If `false` the recognition ends after the
+    /// first phrase. Defaults to `true`.
+    continuous: bool,
+
+    /// Provide guesses in `interim_transcript` while the user is still speaking.
+    /// Defaults to `true`.
+    interim_results: bool,
+
+    /// How many alternatives the recognition should consider for each result. Only the most
+    /// likely one ends up in the transcript. Defaults to `1`.
+    max_alternatives: u32,
+}
+
+impl Default for UseSpeechRecognitionOptions {
+    fn default() -> Self {
+        Self {
+            lang: "en-US".to_string().into(),
+            continuous: true,
+            interim_results: true,
+            max_alternatives: 1,
+        }
+    }
+}
+
+/// Return type of [`use_speech_recognition`].
+#[derive(Clone, Copy)]
+pub struct UseSpeechRecognitionReturn {
+    /// Whether speech recognition is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// Whether the recognition is currently listening
+    pub is_listening: Signal<bool>,
+
+    /// The final transcript of everything recognized since the recognition has been started
+    pub transcript: Signal<String>,
+
+    /// The current guess of what the user is saying. Empty if `interim_results` is `false`.
+    pub interim_transcript: Signal<String>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseSpeechRecognitionError>>,
+
+    recognition: StoredValue<Option<SpeechRecognition>>,
+    set_transcript: WriteSignal<String>,
+    set_interim_transcript: WriteSignal<String>,
+    set_error: WriteSignal<Option<UseSpeechRecognitionError>>,
+}
+
+impl UseSpeechRecognitionReturn {
+    /// Starts listening. This asks for the permission to use the microphone the first time.
+    /// Does nothing if it's already listening.
+    pub fn start(&self) {
+        if self.is_listening.get_untracked() {
+            return;
+        }
+
+        self.recognition.with_value(|recognition| {
+            if let Some(recognition) = recognition {
+                self.set_transcript.set(String::new());
+                self.set_interim_transcript.set(String::new());
+                self.set_error.set(None);
+
+                if let Err(err) = recognition.start() {
+                    self.set_error
+                        .set(Some(UseSpeechRecognitionError::Start(err)));
+                }
+            }
+        });
+    }
+
+    /// Stops listening. What has been said so far is still recognized.
+    pub fn stop(&self) {
+        self.recognition.with_value(|recognition| {
+            if let Some(recognition) = recognition {
+                recognition.stop();
+            }
+        });
+    }
+
+    /// Stops listening and discards what hasn't been recognized yet.
+    pub fn abort(&self) {
+        self.recognition.try_with_value(|recognition| {
+            if let Some(recognition) = recognition {
+                recognition.abort();
+            }
+        });
+    }
+
+    /// Starts listening if it isn't listening already, otherwise stops.
+    pub fn toggle(&self) {
+        if self.is_listening.get_untracked() {
+            self.stop();
+        } else {
+            self.start();
+        }
+    }
+}
+
+/// Errors of [`use_speech_recognition`].
+#[derive(Error, Debug, Clone)]
+pub enum UseSpeechRecognitionError {
+    #[error("starting the speech recognition failed")]
+    Start(JsValue),
+    #[error("speech recognition failed with {code:?}: {message}")]
+    Recognition {
+        code: SpeechRecognitionErrorCode,
+        message: String,
+    },
+}
@@ -0,0 +1,346 @@
+use crate::{js, use_event_listener, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    SpeechSynthesis, SpeechSynthesisErrorCode, SpeechSynthesisErrorEvent, SpeechSynthesisUtterance,
+    SpeechSynthesisVoice,
+};
+
+/// Reactive [SpeechSynthesis](https://developer.mozilla.org/en-US/docs/Web/API/SpeechSynthesis).
+/// Reads text out loud.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_speech_synthesis;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let speech = use_speech_synthesis();
+///
+/// view! {
+///     <button on:click=move |_| { let _ = speech.speak("Hello, world!"); }>"Speak"</button>
+///     <button on:click=move |_| speech.pause() disabled=move || !speech.is_speaking.get()>
+///         "Pause"
+///     </button>
+///     <button on:click=move |_| speech.resume()>"Resume"</button>
+///     <button on:click=move |_| speech.cancel()>"Cancel"</button>
+/// }
+/// # }
+/// ```
+///
+/// Every call of `speak` adds the text to the queue of the browser. `is_pending` is `true` while
+/// there are texts in the queue that haven't been started yet. `cancel` empties the queue.
+///
+/// ## Voices
+///
+/// The available voices are loaded asynchronously by most browsers. `voices` is updated once
+/// they are available. The voice, pitch, rate and volume can be signals and are applied to every
+/// text that is spoken afterwards.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_speech_synthesis_with_options, UseSpeechSynthesisOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (voice, set_voice) = create_signal(None::<web_sys::SpeechSynthesisVoice>);
+/// let (rate, set_rate) = create_signal(1.5);
+///
+/// let speech = use_speech_synthesis_with_options(
+///     UseSpeechSynthesisOptions::default()
+///         .voice(voice)
+///         .rate(rate),
+/// );
+///
+/// view! {
+///     <select on:change=move |ev| {
+///         let name = event_target_value(&ev);
+///         set_voice.set(speech.voices.get().into_iter().find(|voice| voice.name() == name));
+///     }>
+///         <For each=move || speech.voices.get() key=|voice| voice.voice_uri() let:voice>
+///             <option value=voice.name()>{voice.name()} " (" {voice.lang()} ")"</option>
+///         </For>
+///     </select>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server `voices` is always empty and `speak` returns
+/// `UseSpeechSynthesisError::NotSupported`.
+pub fn use_speech_synthesis() -> UseSpeechSynthesisReturn {
+    use_speech_synthesis_with_options(UseSpeechSynthesisOptions::default())
+}
+
+/// Version of [`use_speech_synthesis`] that takes a `UseSpeechSynthesisOptions`. See [`use_speech_synthesis`] for how to use.
+pub fn use_speech_synthesis_with_options(
+    options: UseSpeechSynthesisOptions,
+) -> UseSpeechSynthesisReturn {
+    let UseSpeechSynthesisOptions {
+        lang,
+        voice,
+        pitch,
+        rate,
+        volume,
+    } = options;
+
+    let is_supported = use_supported(|| js!("speechSynthesis" in &window()));
+
+    let (voices, set_voices) = create_signal(Vec::<SpeechSynthesisVoice>::new());
+    let (is_speaking, set_speaking) = create_signal(false);
+    let (is_pending, set_pending) = create_signal(false);
+    let (is_paused, set_paused) = create_signal(false);
+    let (error, set_error) = create_signal(None::<UseSpeechSynthesisError>);
+
+    let synthesis = if is_supported.get_untracked() {
+        window().speech_synthesis().ok()
+    } else {
+        None
+    };
+
+    let update_voices = {
+        let synthesis = synthesis.clone();
+
+        move || {
+            if let Some(synthesis) = &synthesis {
+                set_voices.set(
+                    synthesis
+                        .get_voices()
+                        .iter()
+                        .map(JsCast::unchecked_into)
+                        .collect(),
+                );
+            }
+        }
+    };
+
+    update_voices();
+
+    let _ = use_event_listener(
+        synthesis.clone(),
+        ev::Custom::<web_sys::Event>::new("voiceschanged"),
+        move |_| update_voices(),
+    );
+
+    let ret = UseSpeechSynthesisReturn {
+        is_supported,
+        voices: voices.into(),
+        is_speaking: is_speaking.into(),
+        is_pending: is_pending.into(),
+        is_paused: is_paused.into(),
+        error: error.into(),
+        synthesis: store_value(synthesis),
+        on_utterance_event: store_value(None),
+        settings: store_value(UtteranceSettings {
+            lang,
+            voice,
+            pitch,
+            rate,
+            volume,
+        }),
+        set_speaking,
+        set_pending,
+        set_paused,
+        set_error,
+    };
+
+    on_cleanup(move || ret.cancel());
+
+    ret
+}
+
+/// Options for [`use_speech_synthesis_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseSpeechSynthesisOptions {
+    /// The language as a BCP 47 tag like `"en-US"`. Defaults to an empty string which uses the
+    /// language of the document.
+    #[builder(into)]
+    lang: MaybeSignal<String>,
+
+    /// The voice to use. Pick one from `voices`. Defaults to `None` which uses the default voice
+    /// for the language.
+    #[builder(into)]
+    voice: MaybeSignal<Option<SpeechSynthesisVoice>>,
+
+    /// The pitch between `0.0` and `2.0`. Defaults to `1.0`.
+    #[builder(into)]
+    pitch: MaybeSignal<f32>,
+
+    /// The speed between `0.1` and `10.0`. Defaults to `1.0`.
+    #[builder(into)]
+    rate: MaybeSignal<f32>,
+
+    /// The volume between `0.0` and `1.0`. Defaults to `1.0`.
+    #[builder(into)]
+    volume: MaybeSignal<f32>,
+}
+
+impl Default for UseSpeechSynthesisOptions {
+    fn default() -> Self {
+        Self {
+            lang: String::new().into(),
+            voice: None.into(),
+            pitch: 1.0.into(),
+            rate: 1.0.into(),
+            volume: 1.0.into(),
+        }
+    }
+}
+
+type UtteranceEventHandler = Rc<Closure<dyn Fn(web_sys::Event)>>;
+
+struct UtteranceSettings {
+    lang: MaybeSignal<String>,
+    voice: MaybeSignal<Option<SpeechSynthesisVoice>>,
+    pitch: MaybeSignal<f32>,
+    rate: MaybeSignal<f32>,
+    volume: MaybeSignal<f32>,
+}
+
+/// Return type of [`use_speech_synthesis`].
+#[derive(Clone, Copy)]
+pub struct UseSpeechSynthesisReturn {
+    /// Whether speech synthesis is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The voices that are available
+    pub voices: Signal<Vec<SpeechSynthesisVoice>>,
+
+    /// Whether a text is being spoken right now. Also `true` while paused.
+    pub is_speaking: Signal<bool>,
+
+    /// Whether there are texts in the queue that haven't been started yet
+    pub is_pending: Signal<bool>,
+
+    /// Whether speaking is paused
+    pub is_paused: Signal<bool>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseSpeechSynthesisError>>,
+
+    synthesis: StoredValue<Option<SpeechSynthesis>>,
+    on_utterance_event: StoredValue<Option<UtteranceEventHandler>>,
+    settings: StoredValue<UtteranceSettings>,
+    set_speaking: WriteSignal<bool>,
+    set_pending: WriteSignal<bool>,
+    set_paused: WriteSignal<bool>,
+    set_error: WriteSignal<Option<UseSpeechSynthesisError>>,
+}
+
+impl UseSpeechSynthesisReturn {
+    /// Adds `text` to the queue. It is spoken once all texts before it have been spoken.
+    pub fn speak(&self, text: &str) -> Result<(), UseSpeechSynthesisError> {
+        let synthesis = self
+            .synthesis
+            .get_value()
+            .ok_or(UseSpeechSynthesisError::NotSupported)?;
+
+        let utterance = SpeechSynthesisUtterance::new_with_text(text).map_err(|err| {
+            let err = UseSpeechSynthesisError::Failed(err);
+            self.set_error.set(Some(err.clone()));
+            err
+        })?;
+
+        self.settings.with_value(|settings| {
+            utterance.set_lang(&settings.lang.get_untracked());
+            utterance.set_voice(settings.voice.get_untracked().as_ref());
+            utterance.set_pitch(settings.pitch.get_untracked());
+            utterance.set_rate(settings.rate.get_untracked());
+            utterance.set_volume(settings.volume.get_untracked());
+        });
+
+        let on_event = self.on_utterance_event();
+        let on_event = Some(on_event.as_ref().as_ref().unchecked_ref());
+        utterance.set_onstart(on_event);
+        utterance.set_onend(on_event);
+        utterance.set_onpause(on_event);
+        utterance.set_onresume(on_event);
+        utterance.set_onerror(on_event);
+
+        self.set_error.set(None);
+        synthesis.speak(&utterance);
+        self.update();
+
+        Ok(())
+    }
+
+    /// Pauses speaking.
+    pub fn pause(&self) {
+        if let Some(synthesis) = self.synthesis.get_value() {
+            synthesis.pause();
+        }
+    }
+
+    /// Resumes speaking after `pause`.
+    pub fn resume(&self) {
+        if let Some(synthesis) = self.synthesis.get_value() {
+            synthesis.resume();
+        }
+    }
+
+    /// Stops speaking and removes all texts from the queue.
+    pub fn cancel(&self) {
+        if let Some(Some(synthesis)) = self.synthesis.try_get_value() {
+            synthesis.cancel();
+        }
+    }
+
+    fn update(&self) {
+        if let Some(synthesis) = self.synthesis.get_value() {
+            self.set_speaking.set(synthesis.speaking());
+            self.set_pending.set(synthesis.pending());
+            self.set_paused.set(synthesis.paused());
+        }
+    }
+
+    // The same closure is used for the events of all utterances so it lives as long as the hook.
+    fn on_utterance_event(&self) -> UtteranceEventHandler {
+        if let Some(on_event) = self.on_utterance_event.get_value() {
+            return on_event;
+        }
+
+        let this = *self;
+
+        let on_event = Rc::new(Closure::<dyn Fn(web_sys::Event)>::new(
+            move |event: web_sys::Event| {
+                this.update();
+
+                if let Some(event) = event.dyn_ref::<SpeechSynthesisErrorEvent>() {
+                    let code = event.error();
+
+                    // happen when `cancel` is called or another text is spoken and aren't errors
+                    if !matches!(
+                        code,
+                        SpeechSynthesisErrorCode::Canceled | SpeechSynthesisErrorCode::Interrupted
+                    ) {
+                        this.set_error
+                            .set(Some(UseSpeechSynthesisError::Synthesis(code)));
+                    }
+                }
+            },
+        ));
+
+        self.on_utterance_event
+            .set_value(Some(Rc::clone(&on_event)));
+
+        on_event
+    }
+}
+
+/// Errors of [`use_speech_synthesis`].
+#[derive(Error, Debug, Clone)]
+pub enum UseSpeechSynthesisError {
+    #[error("speech synthesis is not supported")]
+    NotSupported,
+    #[error("creating the utterance failed")]
+    Failed(JsValue),
+    #[error("speaking failed with {0:?}")]
+    Synthesis(SpeechSynthesisErrorCode),
+}
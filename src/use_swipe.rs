@@ -0,0 +1,310 @@
+use crate::core::{ElementMaybeSignal, Position};
+use crate::{use_event_listener_with_options, UseEventListenerOptions};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{touchcancel, touchend, touchmove, touchstart};
+use leptos::*;
+use std::rc::Rc;
+use web_sys::TouchEvent;
+
+/// Reactive swipe detection based on [`TouchEvent`](https://developer.mozilla.org/en-US/docs/Web/API/TouchEvent).
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_swipe, UseSwipeDirection, UseSwipeReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+///
+/// let UseSwipeReturn {
+///     is_swiping,
+///     direction,
+///     length_x,
+///     ..
+/// } = use_swipe(el);
+///
+/// view! {
+///     <div node_ref=el>
+///         <Show when=move || is_swiping.get() && direction.get() == UseSwipeDirection::Left>
+///             "Swiping left by " {move || length_x.get()} "px"
+///         </Show>
+///     </div>
+/// }
+/// # }
+/// ```
+///
+/// ## Callbacks and Threshold
+///
+/// A swipe only starts after the touch has moved by `threshold` pixels. Use the callbacks to
+/// react to it, for example to dismiss a card when the swipe ends.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_swipe_with_options, UseSwipeDirection, UseSwipeOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+/// let (is_dismissed, set_dismissed) = create_signal(false);
+///
+/// use_swipe_with_options(
+///     el,
+///     UseSwipeOptions::default()
+///         .threshold(80.0)
+///         .on_swipe_end(move |_, direction| {
+///             if direction == UseSwipeDirection::Right {
+///                 set_dismissed.set(true);
+///             }
+///         }),
+/// );
+///
+/// view! { <div node_ref=el class:dismissed=is_dismissed>"Swipe me to the right"</div> }
+/// # }
+/// ```
+///
+/// By default the listeners are passive so that the page can still be scrolled. Set `passive`
+/// to `false` to prevent scrolling while swiping.
+///
+/// For mouse and pen input see [`fn@crate::use_pointer_swipe`].
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_swiping` is always `false` and `direction` is always `UseSwipeDirection::None`.
+pub fn use_swipe<El, T>(target: El) -> UseSwipeReturn<impl Fn() + Clone>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+{
+    use_swipe_with_options(target, UseSwipeOptions::default())
+}
+
+/// Version of [`use_swipe`] that takes a `UseSwipeOptions`. See [`use_swipe`] for how to use.
+pub fn use_swipe_with_options<El, T>(
+    target: El,
+    options: UseSwipeOptions,
+) -> UseSwipeReturn<impl Fn() + Clone>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+{
+    let UseSwipeOptions {
+        passive,
+        threshold,
+        on_swipe_start,
+        on_swipe,
+        on_swipe_end,
+    } = options;
+
+    let (coords_start, set_coords_start) = create_signal(Position::default());
+    let (coords_end, set_coords_end) = create_signal(Position::default());
+    let (is_swiping, set_swiping) = create_signal(false);
+
+    let length_x = Signal::derive(move || coords_start.get().x - coords_end.get().x);
+    let length_y = Signal::derive(move || coords_start.get().y - coords_end.get().y);
+    let direction = Signal::derive(move || {
+        UseSwipeDirection::from_lengths(length_x.get(), length_y.get(), threshold)
+    });
+
+    let touch_position = |event: &TouchEvent| {
+        event.touches().get(0).map(|touch| Position {
+            x: touch.client_x() as f64,
+            y: touch.client_y() as f64,
+        })
+    };
+
+    let listener_options = UseEventListenerOptions::default()
+        .passive(passive)
+        .capture(!passive);
+
+    let target = target.into();
+
+    let stop_start = use_event_listener_with_options(
+        target.clone(),
+        touchstart,
+        move |event| {
+            if event.touches().length() != 1 {
+                return;
+            }
+
+            if !passive {
+                event.prevent_default();
+            }
+
+            if let Some(position) = touch_position(&event) {
+                set_coords_start.set(position);
+                set_coords_end.set(position);
+            }
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_swipe_start(event);
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        },
+        listener_options,
+    );
+
+    let stop_move = use_event_listener_with_options(
+        target.clone(),
+        touchmove,
+        move |event| {
+            if event.touches().length() != 1 {
+                return;
+            }
+
+            if let Some(position) = touch_position(&event) {
+                set_coords_end.set(position);
+            }
+
+            if !is_swiping.get_untracked() && direction.get_untracked() != UseSwipeDirection::None {
+                set_swiping.set(true);
+            }
+
+            if is_swiping.get_untracked() {
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                on_swipe(event);
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+            }
+        },
+        listener_options,
+    );
+
+    let on_touch_end = move |event: TouchEvent| {
+        if is_swiping.get_untracked() {
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_swipe_end(event, direction.get_untracked());
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        }
+
+        set_swiping.set(false);
+    };
+
+    let stop_end = use_event_listener_with_options(
+        target.clone(),
+        touchend,
+        on_touch_end.clone(),
+        listener_options,
+    );
+    let stop_cancel =
+        use_event_listener_with_options(target, touchcancel, on_touch_end, listener_options);
+
+    let stop = move || {
+        stop_start();
+        stop_move();
+        stop_end();
+        stop_cancel();
+    };
+
+    UseSwipeReturn {
+        is_swiping: is_swiping.into(),
+        direction,
+        coords_start: coords_start.into(),
+        coords_end: coords_end.into(),
+        length_x,
+        length_y,
+        stop,
+    }
+}
+
+/// Options for [`use_swipe_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseSwipeOptions {
+    /// Whether the listeners are passive. If `false`, scrolling is prevented while touching
+    /// the target. Defaults to `true`.
+    passive: bool,
+
+    /// How many pixels the touch has to move before a swipe is detected. Defaults to `50.0`.
+    threshold: f64,
+
+    /// Called when the target is touched.
+    on_swipe_start: Rc<dyn Fn(TouchEvent)>,
+
+    /// Called on every move during a swipe.
+    on_swipe: Rc<dyn Fn(TouchEvent)>,
+
+    /// Called when the swipe ends with the final direction.
+    on_swipe_end: Rc<dyn Fn(TouchEvent, UseSwipeDirection)>,
+}
+
+impl Default for UseSwipeOptions {
+    fn default() -> Self {
+        Self {
+            passive: true,
+            threshold: 50.0,
+            on_swipe_start: Rc::new(|_| {}),
+            on_swipe: Rc::new(|_| {}),
+            on_swipe_end: Rc::new(|_, _| {}),
+        }
+    }
+}
+
+/// Return type of [`use_swipe`] and [`fn@crate::use_pointer_swipe`].
+pub struct UseSwipeReturn<F>
+where
+    F: Fn() + Clone,
+{
+    /// Whether a swipe is in progress
+    pub is_swiping: Signal<bool>,
+
+    /// The direction of the current or last swipe
+    pub direction: Signal<UseSwipeDirection>,
+
+    /// Where the swipe started in client coordinates
+    pub coords_start: Signal<Position>,
+
+    /// Where the swipe is or ended in client coordinates
+    pub coords_end: Signal<Position>,
+
+    /// The distance the swipe has moved to the left. Negative when moved to the right.
+    pub length_x: Signal<f64>,
+
+    /// The distance the swipe has moved up. Negative when moved down.
+    pub length_y: Signal<f64>,
+
+    /// Removes the event listeners
+    pub stop: F,
+}
+
+/// Direction of a swipe.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UseSwipeDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+    /// The swipe hasn't moved further than the threshold
+    #[default]
+    None,
+}
+
+impl UseSwipeDirection {
+    pub(crate) fn from_lengths(length_x: f64, length_y: f64, threshold: f64) -> Self {
+        if length_x.abs().max(length_y.abs()) < threshold {
+            Self::None
+        } else if length_x.abs() > length_y.abs() {
+            if length_x > 0.0 {
+                Self::Left
+            } else {
+                Self::Right
+            }
+        } else if length_y > 0.0 {
+            Self::Up
+        } else {
+            Self::Down
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{use_document, use_event_listener};
+use leptos::*;
+
+/// Reactive text selection of the page based on
+/// [`document.getSelection()`](https://developer.mozilla.org/en-US/docs/Web/API/Document/getSelection).
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_text_selection;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let selection = use_text_selection();
+///
+/// view! {
+///     <p>"Select some text of this paragraph."</p>
+///     <p>"You selected: " {selection.text}</p>
+/// }
+/// # }
+/// ```
+///
+/// ## Highlight and Comment
+///
+/// `rects` contains the rectangles of the selected text in client coordinates. They can be used
+/// to position a popup next to the selection.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_text_selection;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let selection = use_text_selection();
+///
+/// let popup_style = move || {
+///     selection.rects.with(|rects| {
+///         rects.last().map(|rect| {
+///             format!("position: fixed; left: {}px; top: {}px", rect.right(), rect.bottom())
+///         })
+///     })
+/// };
+///
+/// view! {
+///     <Show when=move || !selection.text.with(String::is_empty)>
+///         <button style=popup_style>"Comment"</button>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server `selection` is always `None`, `text` is empty and so on.
+pub fn use_text_selection() -> UseTextSelectionReturn {
+    let (selection, set_selection) = create_signal(None::<web_sys::Selection>);
+    let (text, set_text) = create_signal(String::new());
+    let (ranges, set_ranges) = create_signal(Vec::<web_sys::Range>::new());
+    let (rects, set_rects) = create_signal(Vec::<web_sys::DomRect>::new());
+    let (direction, set_direction) = create_signal(SelectionDirection::None);
+
+    let _ = use_event_listener(
+        use_document(),
+        ev::Custom::<web_sys::Event>::new("selectionchange"),
+        move |_| {
+            let new_selection = document().get_selection().ok().flatten();
+
+            let new_ranges = new_selection
+                .as_ref()
+                .map(|selection| {
+                    (0..selection.range_count())
+                        .filter_map(|index| selection.get_range_at(index).ok())
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+
+            set_text.set(
+                new_selection
+                    .as_ref()
+                    .map(|selection| String::from(selection.to_string()))
+                    .unwrap_or_default(),
+            );
+            set_rects.set(
+                new_ranges
+                    .iter()
+                    .map(|range| range.get_bounding_client_rect())
+                    .collect(),
+            );
+            set_direction.set(
+                new_selection
+                    .as_ref()
+                    .map(SelectionDirection::from)
+                    .unwrap_or_default(),
+            );
+            set_ranges.set(new_ranges);
+            set_selection.set(new_selection);
+        },
+    );
+
+    UseTextSelectionReturn {
+        selection: selection.into(),
+        text: text.into(),
+        ranges: ranges.into(),
+        rects: rects.into(),
+        direction: direction.into(),
+    }
+}
+
+/// Return type of [`use_text_selection`].
+#[derive(Clone, Copy)]
+pub struct UseTextSelectionReturn {
+    /// The selection object of the document. It notifies on every change of the selection.
+    pub selection: Signal<Option<web_sys::Selection>>,
+
+    /// The selected text
+    pub text: Signal<String>,
+
+    /// The ranges of the selection. Most browsers only support one range.
+    pub ranges: Signal<Vec<web_sys::Range>>,
+
+    /// The bounding rectangle of every range in client coordinates
+    pub rects: Signal<Vec<web_sys::DomRect>>,
+
+    /// In which direction the user has made the selection
+    pub direction: Signal<SelectionDirection>,
+}
+
+/// Direction of a text selection, i.e. whether the focus is after or before the anchor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelectionDirection {
+    /// Nothing is selected or the selection is collapsed
+    #[default]
+    None,
+    /// The selection has been made from the start to the end
+    Forward,
+    /// The selection has been made from the end to the start
+    Backward,
+}
+
+impl From<&web_sys::Selection> for SelectionDirection {
+    fn from(selection: &web_sys::Selection) -> Self {
+        let (Some(anchor), Some(focus)) = (selection.anchor_node(), selection.focus_node()) else {
+            return Self::None;
+        };
+
+        if selection.is_collapsed() {
+            return Self::None;
+        }
+
+        let is_backward = if anchor == focus {
+            selection.focus_offset() < selection.anchor_offset()
+        } else {
+            anchor.compare_document_position(&focus) & web_sys::Node::DOCUMENT_POSITION_PRECEDING
+                != 0
+        };
+
+        if is_backward {
+            Self::Backward
+        } else {
+            Self::Forward
+        }
+    }
+}
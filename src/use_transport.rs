@@ -0,0 +1,539 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::{ConnectionReadyState, ReconnectStrategy};
+use crate::utils::{FromToStringCodec, HybridCodec, HybridData};
+use crate::{
+    js, use_event_source_with_options, use_websocket_with_options, UseEventSourceOptions,
+    UseWebSocketOptions,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::cell::Cell;
+use std::fmt;
+use std::rc::Rc;
+use std::time::Duration;
+use wasm_bindgen::JsValue;
+
+/// Connects to a server with the best transport the browser supports and provides one API to
+/// send and receive messages regardless of the transport.
+///
+/// The transports are tried in the order WebTransport, WebSocket and Server-Sent-Events.
+/// A transport is skipped if the browser doesn't support it or if no url is given for it.
+/// If a transport fails before its connection has been opened once, the next one is tried.
+///
+/// Messages are encoded and decoded with the codec `C`. Like with WebSocket, messages are delivered
+/// reliably and in order with every transport. Over WebTransport the hook opens a bidirectional
+/// stream for every session on which every message is prefixed with its length as a 32-bit
+/// big-endian unsigned integer. The server has to answer on the same stream in the same format.
+/// Set `web_transport_datagrams` to send messages as datagrams instead. With Server-Sent-Events messages are received as events and sent as `POST`
+/// requests to `send_url`. Because Server-Sent-Events only transport text, a codec that
+/// produces text like [`JsonCodec`] should be used with this fallback.
+///
+/// > WebTransport requires `--cfg=web_sys_unstable_apis`. Without it, it is always skipped.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_transport_with_options, UseTransportOptions};
+/// # use leptos_use::utils::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let transport = use_transport_with_options::<String, FromToStringCodec>(
+///     UseTransportOptions::default()
+///         .web_transport_url("https://example.com:4433/chat")
+///         .web_socket_url("wss://example.com/chat")
+///         .event_source_url("https://example.com/chat/events")
+///         .send_url("https://example.com/chat/send"),
+/// );
+///
+/// let send_message = move |_| transport.send(&"Hello, world!".to_string());
+///
+/// view! {
+///     <p>"Connected via " {move || transport.transport.get().map(|kind| kind.to_string())}</p>
+///     <p>{move || transport.message.get()}</p>
+///     <button on:click=send_message>"Send"</button>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server no connection is made. `ready_state` is always `ConnectionReadyState::Closed`,
+/// `transport` and `message` are `None` and sending does nothing.
+pub fn use_transport_with_options<T, C>(options: UseTransportOptions) -> UseTransportReturn<T>
+where
+    T: Clone + 'static,
+    C: HybridCodec<T> + Default,
+{
+    let UseTransportOptions {
+        web_transport_url,
+        web_socket_url,
+        event_source_url,
+        send_url,
+        web_transport_datagrams,
+        reconnect_limit,
+        reconnect_strategy,
+        on_transport_change,
+    } = options;
+
+    #[cfg(not(web_sys_unstable_apis))]
+    let _ = web_transport_datagrams;
+
+    let (ready_state, set_ready_state) = create_signal(ConnectionReadyState::Closed);
+    let (transport, set_transport) = create_signal(None::<TransportKind>);
+    let (message, set_message) = create_signal(None::<T>);
+
+    let send_ref: StoredValue<SendFn<T>> = store_value(Rc::new(|_| {}));
+    let close_ref = store_value(None::<Rc<dyn Fn()>>);
+
+    // incremented by `open` to start over with the first transport
+    let (generation, set_generation) = create_signal(0_u64);
+    let (index, set_index) = create_signal(0_usize);
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let kinds = available_transports(
+            web_transport_url.is_some(),
+            web_socket_url.is_some(),
+            event_source_url.is_some(),
+        );
+        let codec = C::default();
+
+        create_effect(move |_| {
+            generation.track();
+
+            let Some(kind) = kinds.get(index.get()).copied() else {
+                return;
+            };
+
+            let fallback = {
+                let next = index.get_untracked() + 1;
+                let has_next = next < kinds.len();
+
+                move || {
+                    if has_next {
+                        set_timeout(move || set_index.set(next), Duration::ZERO);
+                    }
+                }
+            };
+
+            untrack(|| {
+                set_transport.set(Some(kind));
+                on_transport_change(kind);
+
+                match kind {
+                    #[cfg(web_sys_unstable_apis)]
+                    TransportKind::WebTransport => {
+                        let url = web_transport_url.clone().unwrap_or_default();
+                        let web_transport = crate::use_webtransport_with_options(
+                            &url,
+                            crate::UseWebTransportOptions::default()
+                                .reconnect_limit(reconnect_limit)
+                                .reconnect_strategy(reconnect_strategy.clone()),
+                        );
+
+                        forward_ready_state(web_transport.ready_state, set_ready_state, fallback);
+
+                        if web_transport_datagrams {
+                            let datagrams = web_transport.datagrams;
+                            let decoder = codec.clone();
+                            create_effect(move |_| {
+                                if let Some(bytes) = datagrams.get() {
+                                    if let Ok(val) = decoder.decode(HybridData::Binary(bytes)) {
+                                        set_message.set(Some(val));
+                                    }
+                                }
+                            });
+
+                            let codec = codec.clone();
+                            let sender = web_transport.clone();
+                            send_ref.set_value(Rc::new(move |val: &T| {
+                                if let Ok(data) = codec.encode(val) {
+                                    sender.send_datagram(data.as_bytes());
+                                }
+                            }));
+                        } else {
+                            let send =
+                                message_stream(web_transport.clone(), codec.clone(), set_message);
+                            send_ref.set_value(send);
+                        }
+
+                        close_ref.set_value(Some(Rc::new(move || web_transport.close())));
+                    }
+
+                    #[cfg(not(web_sys_unstable_apis))]
+                    TransportKind::WebTransport => unreachable!(),
+
+                    TransportKind::WebSocket => {
+                        let url = web_socket_url.clone().unwrap_or_default();
+                        let web_socket = use_websocket_with_options::<T, C>(
+                            url,
+                            UseWebSocketOptions::default()
+                                .reconnect_limit(reconnect_limit)
+                                .reconnect_strategy(reconnect_strategy.clone())
+                                .on_message(move |val: &T| set_message.set(Some(val.clone())))
+                                .codec(codec.clone()),
+                        );
+
+                        forward_ready_state(web_socket.ready_state, set_ready_state, fallback);
+
+                        let send = web_socket.send.clone();
+                        send_ref.set_value(Rc::new(move |val: &T| send(val)));
+                        let close = web_socket.close.clone();
+                        close_ref.set_value(Some(Rc::new(close)));
+                    }
+
+                    TransportKind::EventSource => {
+                        let url = event_source_url.clone().unwrap_or_default();
+                        let event_source = use_event_source_with_options::<String, FromToStringCodec>(
+                            &url,
+                            UseEventSourceOptions::default()
+                                .reconnect_limit(reconnect_limit)
+                                .reconnect_interval(reconnect_strategy.delay(1).as_millis() as u64),
+                        );
+
+                        forward_ready_state(event_source.ready_state, set_ready_state, fallback);
+
+                        let data = event_source.data;
+                        let decoder = codec.clone();
+                        create_effect(move |_| {
+                            if let Some(text) = data.get() {
+                                if let Ok(val) = decoder.decode(HybridData::Text(text)) {
+                                    set_message.set(Some(val));
+                                }
+                            }
+                        });
+
+                        let codec = codec.clone();
+                        let send_url = send_url.clone();
+                        send_ref.set_value(Rc::new(move |val: &T| {
+                            if let (Some(send_url), Ok(data)) = (&send_url, codec.encode(val)) {
+                                post_message(send_url, data);
+                            }
+                        }));
+                        let close = event_source.close.clone();
+                        close_ref.set_value(Some(Rc::new(close)));
+                    }
+                }
+            });
+        });
+    }
+
+    let open = Rc::new(move || {
+        set_index.set(0);
+        set_generation.update(|generation| *generation += 1);
+    });
+
+    let close = Rc::new(move || {
+        if let Some(close) = close_ref.get_value() {
+            close();
+        }
+    });
+
+    UseTransportReturn {
+        ready_state: ready_state.into(),
+        transport: transport.into(),
+        message: message.into(),
+        send: send_ref,
+        open: store_value(open),
+        close: store_value(close),
+    }
+}
+
+/// Version of [`use_transport_with_options`] that only takes a WebSocket url. Mostly useful in
+/// combination with the default options of the other transports which are all disabled.
+pub fn use_transport<T, C>(web_socket_url: &str) -> UseTransportReturn<T>
+where
+    T: Clone + 'static,
+    C: HybridCodec<T> + Default,
+{
+    use_transport_with_options::<T, C>(
+        UseTransportOptions::default().web_socket_url(web_socket_url.to_string()),
+    )
+}
+
+#[cfg(not(feature = "ssr"))]
+fn available_transports(
+    web_transport: bool,
+    web_socket: bool,
+    event_source: bool,
+) -> Vec<TransportKind> {
+    let window = window();
+    let mut kinds = vec![];
+
+    if cfg!(web_sys_unstable_apis) && web_transport && js!("WebTransport" in &window) {
+        kinds.push(TransportKind::WebTransport);
+    }
+    if web_socket && js!("WebSocket" in &window) {
+        kinds.push(TransportKind::WebSocket);
+    }
+    if event_source && js!("EventSource" in &window) {
+        kinds.push(TransportKind::EventSource);
+    }
+
+    kinds
+}
+
+/// Opens a bidirectional stream whenever the WebTransport session is open and exchanges
+/// length-prefixed messages on it. Returns the function that sends a message. Messages that are
+/// sent while no stream is open are sent once it's open.
+#[cfg(all(web_sys_unstable_apis, not(feature = "ssr")))]
+fn message_stream<T, C>(
+    web_transport: crate::UseWebTransportReturn,
+    codec: C,
+    set_message: WriteSignal<Option<T>>,
+) -> SendFn<T>
+where
+    T: 'static,
+    C: HybridCodec<T> + 'static,
+{
+    use crate::utils::{length_prefixed, LengthPrefixedDecoder};
+    use crate::{CloseableStream, SendableStream};
+
+    let stream = store_value(None::<crate::BidirStream>);
+    let pending = store_value(Vec::<Vec<u8>>::new());
+    let session = store_value(0_usize);
+
+    {
+        let codec = codec.clone();
+
+        create_effect(move |_| {
+            let is_open = web_transport.ready_state.get() == ConnectionReadyState::Open;
+
+            session.update_value(|session| *session += 1);
+            let current_session = session.get_value();
+            stream.set_value(None);
+
+            if !is_open {
+                return;
+            }
+
+            // the readers of previous streams are disposed when this effect runs again
+            let owner = Owner::current();
+            let web_transport = web_transport.clone();
+            let codec = codec.clone();
+
+            spawn_local(async move {
+                let Ok(bidir) = web_transport.open_bidirectional_stream().await else {
+                    return;
+                };
+
+                if session.try_get_value() != Some(current_session) {
+                    bidir.close();
+                    return;
+                }
+
+                for frame in pending.try_update_value(std::mem::take).unwrap_or_default() {
+                    bidir.send_bytes(&frame);
+                }
+
+                let bytes = bidir.bytes;
+                let read = move || {
+                    let decoder = std::cell::RefCell::new(LengthPrefixedDecoder::default());
+
+                    create_effect(move |_| {
+                        if let Some(chunk) = bytes.get() {
+                            let payloads = decoder.borrow_mut().feed(&chunk);
+
+                            for payload in payloads {
+                                if let Ok(val) = codec.decode(HybridData::Binary(payload)) {
+                                    set_message.set(Some(val));
+                                }
+                            }
+                        }
+                    });
+                };
+                match owner {
+                    Some(owner) => with_owner(owner, read),
+                    None => read(),
+                }
+
+                stream.set_value(Some(bidir));
+            });
+        });
+    }
+
+    Rc::new(move |val: &T| {
+        let Ok(data) = codec.encode(val) else {
+            return;
+        };
+        let frame = length_prefixed(data.as_bytes());
+
+        match stream.get_value() {
+            Some(stream) => stream.send_bytes(&frame),
+            None => pending.update_value(|pending| pending.push(frame)),
+        }
+    })
+}
+
+/// Copies the state of the current transport and calls `fallback` if it failed before its
+/// connection has been opened once.
+#[cfg(not(feature = "ssr"))]
+fn forward_ready_state(
+    source: Signal<ConnectionReadyState>,
+    set_ready_state: WriteSignal<ConnectionReadyState>,
+    fallback: impl Fn() + 'static,
+) {
+    let was_open = Cell::new(false);
+
+    create_effect(move |prev: Option<ConnectionReadyState>| {
+        let state = source.get();
+        set_ready_state.set(state);
+
+        match state {
+            ConnectionReadyState::Open => was_open.set(true),
+            ConnectionReadyState::Closed
+                if prev == Some(ConnectionReadyState::Connecting) && !was_open.get() =>
+            {
+                fallback()
+            }
+            _ => {}
+        }
+
+        state
+    });
+}
+
+#[cfg(not(feature = "ssr"))]
+fn post_message(url: &str, data: HybridData) {
+    use wasm_bindgen::JsCast;
+
+    let body: JsValue = match data {
+        HybridData::Text(text) => text.into(),
+        HybridData::Binary(bytes) => js_sys::Uint8Array::from(bytes.as_slice()).into(),
+    };
+
+    let request_init = web_sys::RequestInit::new();
+    request_init.set_method("POST");
+    request_init.set_body(&body);
+
+    let request = window().fetch_with_str_and_init(url, &request_init);
+    spawn_local(async move {
+        let _ = wasm_bindgen_futures::JsFuture::from(request)
+            .await
+            .map(|response| response.unchecked_into::<web_sys::Response>());
+    });
+}
+
+/// The transport that [`use_transport_with_options`] is connected with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TransportKind {
+    WebTransport,
+    WebSocket,
+    EventSource,
+}
+
+impl fmt::Display for TransportKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            TransportKind::WebTransport => write!(f, "WebTransport"),
+            TransportKind::WebSocket => write!(f, "WebSocket"),
+            TransportKind::EventSource => write!(f, "EventSource"),
+        }
+    }
+}
+
+/// Options for [`use_transport_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseTransportOptions {
+    /// Url of the WebTransport server. Defaults to `None` which skips WebTransport.
+    #[builder(into)]
+    web_transport_url: Option<String>,
+
+    /// Url of the WebSocket server. Defaults to `None` which skips WebSocket.
+    #[builder(into)]
+    web_socket_url: Option<String>,
+
+    /// Url of the Server-Sent-Events endpoint. Defaults to `None` which skips Server-Sent-Events.
+    #[builder(into)]
+    event_source_url: Option<String>,
+
+    /// Url that messages are `POST`ed to when connected with Server-Sent-Events.
+    /// If `None`, sending does nothing in that case. Defaults to `None`.
+    #[builder(into)]
+    send_url: Option<String>,
+
+    /// Send and receive messages over WebTransport as datagrams instead of on a bidirectional
+    /// stream. Datagrams have less overhead but can be lost or arrive out of order and a message
+    /// mustn't be larger than about one network packet. Defaults to `false`.
+    web_transport_datagrams: bool,
+
+    /// Retry times of the transport once it has been connected. Defaults to 3.
+    reconnect_limit: u64,
+
+    /// How long to wait between reconnection attempts. For Server-Sent-Events the delay of the
+    /// first attempt is used for all attempts. Defaults to `ReconnectStrategy::Fixed(3000)`.
+    reconnect_strategy: ReconnectStrategy,
+
+    /// Called whenever a transport is chosen, also when falling back to the next one.
+    on_transport_change: Rc<dyn Fn(TransportKind)>,
+}
+
+impl Default for UseTransportOptions {
+    fn default() -> Self {
+        Self {
+            web_transport_url: None,
+            web_socket_url: None,
+            event_source_url: None,
+            send_url: None,
+            web_transport_datagrams: false,
+            reconnect_limit: 3,
+            reconnect_strategy: ReconnectStrategy::default(),
+            on_transport_change: Rc::new(|_| {}),
+        }
+    }
+}
+
+type SendFn<T> = Rc<dyn Fn(&T)>;
+
+/// Return type of [`use_transport_with_options`].
+pub struct UseTransportReturn<T: 'static> {
+    send: StoredValue<SendFn<T>>,
+    open: StoredValue<Rc<dyn Fn()>>,
+    close: StoredValue<Rc<dyn Fn()>>,
+
+    /// The state of the connection of the current transport.
+    pub ready_state: Signal<ConnectionReadyState>,
+
+    /// The transport that is currently used. `None` if none is available.
+    pub transport: Signal<Option<TransportKind>>,
+
+    /// Latest message received decoded by the codec.
+    pub message: Signal<Option<T>>,
+}
+
+impl<T> Clone for UseTransportReturn<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for UseTransportReturn<T> {}
+
+impl<T> fmt::Debug for UseTransportReturn<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UseTransportReturn")
+            .field("ready_state", &self.ready_state)
+            .field("transport", &self.transport)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> UseTransportReturn<T> {
+    /// Encodes and sends a message with the current transport. Does nothing if there is none.
+    pub fn send(&self, val: &T) {
+        (self.send.get_value())(val);
+    }
+
+    /// Starts over with the first available transport.
+    pub fn open(&self) {
+        (self.open.get_value())();
+    }
+
+    /// Closes the connection of the current transport.
+    pub fn close(&self) {
+        (self.close.get_value())();
+    }
+}
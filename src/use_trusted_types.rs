@@ -0,0 +1,217 @@
+use crate::{js, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+thread_local! {
+    static POLICIES: RefCell<HashMap<String, Result<TrustedTypePolicy, JsValue>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Creates a [Trusted Types](https://developer.mozilla.org/en-US/docs/Web/API/Trusted_Types_API)
+/// policy so that components which inject HTML or load scripts keep working on pages that enforce
+/// trusted types with `Content-Security-Policy: require-trusted-types-for 'script'`.
+///
+/// ## Usage
+///
+/// The functions in the options decide how strings are turned into trusted values. This is the
+/// place to sanitize HTML or to check script URLs against an allow list. The values returned
+/// by `create_html` and `create_script_url` can be assigned to `innerHTML`, `src` and the like.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_trusted_types_with_options, UseTrustedTypesOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let trusted_types = use_trusted_types_with_options(
+///     "my-app",
+///     UseTrustedTypesOptions::default().create_html(|html: &str| {
+///         // sanitize `html` here, for example with a sanitizer library
+///         html.replace('<', "&lt;")
+///     }),
+/// );
+///
+/// let el = create_node_ref::<Div>();
+///
+/// create_effect(move |_| {
+///     if let (Some(el), Ok(html)) = (el.get(), trusted_types.create_html("<b>User content</b>")) {
+///         let _ = js_sys::Reflect::set(&el, &"innerHTML".into(), &html);
+///     }
+/// });
+///
+/// view! { <div node_ref=el></div> }
+/// # }
+/// ```
+///
+/// In browsers without Trusted Types the functions are still applied but the strings are
+/// returned as they are. So the same code works everywhere.
+///
+/// A policy can only be created once per name. Calling this function again with the same name,
+/// for example in another instance of the component, returns the existing policy and ignores
+/// the new options.
+///
+/// `is_enforced` tells whether the page enforces trusted types, i.e. whether assigning a plain
+/// string to `innerHTML` would fail.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_supported` and `is_enforced` are always `false` and `create_html` and
+/// `create_script_url` return the strings from the functions of the options.
+pub fn use_trusted_types(policy_name: &str) -> UseTrustedTypesReturn {
+    use_trusted_types_with_options(policy_name, UseTrustedTypesOptions::default())
+}
+
+/// Version of [`use_trusted_types`] that takes a `UseTrustedTypesOptions`. See [`use_trusted_types`] for how to use.
+pub fn use_trusted_types_with_options(
+    policy_name: &str,
+    options: UseTrustedTypesOptions,
+) -> UseTrustedTypesReturn {
+    let is_supported = use_supported(|| js!("trustedTypes" in &window()));
+    let is_enforced = use_supported(|| {
+        document().create_element("div").is_ok_and(|div| {
+            js_sys::Reflect::set(&div, &"innerHTML".into(), &JsValue::from_str("")).is_err()
+        })
+    });
+
+    let policy = if is_supported.get_untracked() {
+        Some(POLICIES.with_borrow_mut(|policies| {
+            policies
+                .entry(policy_name.to_string())
+                .or_insert_with(|| create_policy(policy_name, &options))
+                .clone()
+        }))
+    } else {
+        None
+    };
+
+    UseTrustedTypesReturn {
+        is_supported,
+        is_enforced,
+        policy: store_value(policy),
+        options: store_value(options),
+    }
+}
+
+fn create_policy(
+    policy_name: &str,
+    options: &UseTrustedTypesOptions,
+) -> Result<TrustedTypePolicy, JsValue> {
+    let rules = js_sys::Object::new();
+
+    let create_html = Rc::clone(&options.create_html);
+    js!(rules["createHTML"] =
+        Closure::<dyn Fn(String) -> String>::new(move |html: String| create_html(&html))
+            .into_js_value());
+
+    let create_script_url = Rc::clone(&options.create_script_url);
+    js!(rules["createScriptURL"] =
+        Closure::<dyn Fn(String) -> String>::new(move |url: String| create_script_url(&url))
+            .into_js_value());
+
+    let window = window();
+    js!(window["trustedTypes"])?
+        .unchecked_into::<TrustedTypePolicyFactory>()
+        .create_policy(policy_name, &rules)
+}
+
+/// Options for [`use_trusted_types_with_options`].
+#[derive(DefaultBuilder, Clone)]
+pub struct UseTrustedTypesOptions {
+    /// Turns a string into trusted HTML. Defaults to returning the string unchanged which is
+    /// only safe if it never contains user input.
+    create_html: Rc<dyn Fn(&str) -> String>,
+
+    /// Turns a string into a trusted script URL. Defaults to returning the string unchanged.
+    create_script_url: Rc<dyn Fn(&str) -> String>,
+}
+
+impl Default for UseTrustedTypesOptions {
+    fn default() -> Self {
+        Self {
+            create_html: Rc::new(str::to_string),
+            create_script_url: Rc::new(str::to_string),
+        }
+    }
+}
+
+/// Return type of [`use_trusted_types`].
+#[derive(Clone, Copy)]
+pub struct UseTrustedTypesReturn {
+    /// Whether Trusted Types are supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// Whether the page enforces trusted types
+    pub is_enforced: Signal<bool>,
+
+    policy: StoredValue<Option<Result<TrustedTypePolicy, JsValue>>>,
+    options: StoredValue<UseTrustedTypesOptions>,
+}
+
+impl UseTrustedTypesReturn {
+    /// Returns a `TrustedHTML` for `html` or, if Trusted Types aren't supported, the string
+    /// returned by the `create_html` option.
+    pub fn create_html(&self, html: &str) -> Result<JsValue, UseTrustedTypesError> {
+        match self.policy.get_value() {
+            Some(policy) => policy
+                .map_err(UseTrustedTypesError::Policy)?
+                .create_html(html)
+                .map_err(UseTrustedTypesError::Create),
+            None => Ok(JsValue::from(
+                self.options
+                    .with_value(|options| (options.create_html)(html)),
+            )),
+        }
+    }
+
+    /// Returns a `TrustedScriptURL` for `url` or, if Trusted Types aren't supported, the string
+    /// returned by the `create_script_url` option.
+    pub fn create_script_url(&self, url: &str) -> Result<JsValue, UseTrustedTypesError> {
+        match self.policy.get_value() {
+            Some(policy) => policy
+                .map_err(UseTrustedTypesError::Policy)?
+                .create_script_url(url)
+                .map_err(UseTrustedTypesError::Create),
+            None => Ok(JsValue::from(
+                self.options
+                    .with_value(|options| (options.create_script_url)(url)),
+            )),
+        }
+    }
+}
+
+/// Errors of [`use_trusted_types`].
+#[derive(Error, Debug, Clone)]
+pub enum UseTrustedTypesError {
+    #[error("creating the policy failed, maybe its name isn't allowed by the CSP")]
+    Policy(JsValue),
+    #[error("the policy rejected the value")]
+    Create(JsValue),
+}
+
+#[wasm_bindgen]
+extern "C" {
+    type TrustedTypePolicyFactory;
+
+    #[wasm_bindgen(catch, method, js_name = createPolicy)]
+    fn create_policy(
+        this: &TrustedTypePolicyFactory,
+        policy_name: &str,
+        rules: &js_sys::Object,
+    ) -> Result<TrustedTypePolicy, JsValue>;
+
+    #[derive(Clone, Debug)]
+    type TrustedTypePolicy;
+
+    #[wasm_bindgen(catch, method, js_name = createHTML)]
+    fn create_html(this: &TrustedTypePolicy, input: &str) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = createScriptURL)]
+    fn create_script_url(this: &TrustedTypePolicy, input: &str) -> Result<JsValue, JsValue>;
+}
@@ -0,0 +1,365 @@
+use crate::core::MaybeRwSignal;
+use cfg_if::cfg_if;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Reactive [`mediaDevices.getUserMedia`](https://developer.mozilla.org/en-US/docs/Web/API/MediaDevices/getUserMedia) streaming.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_user_media, UseUserMediaReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let video_ref = create_node_ref::<leptos::html::Video>();
+///
+/// let UseUserMediaReturn { stream, start, error, .. } = use_user_media();
+///
+/// start();
+///
+/// create_effect(move |_|
+///     video_ref.get().map(|v| v.set_src_object(stream.get().as_ref()))
+/// );
+///
+/// view! {
+///     <video node_ref=video_ref controls=false autoplay=true muted=true></video>
+///     <p>{move || error.get().map(|err| err.to_string())}</p>
+/// }
+/// # }
+/// ```
+///
+/// ## Constraints
+///
+/// The constraints can be a signal. Whenever they change while the stream is enabled the stream
+/// is stopped and requested again with the new constraints. This is how you switch between
+/// cameras, for example with the device ids provided by `enumerateDevices`.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_user_media_with_options, UseUserMediaOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (device_id, set_device_id) = create_signal("front-camera-id".to_string());
+///
+/// let constraints = Signal::derive(move || {
+///     let video = web_sys::MediaTrackConstraints::new();
+///     video.set_device_id_str(&device_id.get());
+///
+///     let constraints = web_sys::MediaStreamConstraints::new();
+///     constraints.set_video_media_track_constraints(&video);
+///     constraints.set_audio_bool(true);
+///     constraints
+/// });
+///
+/// let media = use_user_media_with_options(
+///     UseUserMediaOptions::default()
+///         .enabled(true)
+///         .constraints(constraints),
+/// );
+///
+/// let (video_enabled, set_video_enabled) = (media.video_enabled, media.set_video_enabled);
+/// #
+/// # view! {
+/// #    <button on:click=move |_| set_device_id.set("back-camera-id".to_string())>"Switch camera"</button>
+/// #    <button on:click=move |_| set_video_enabled.set(!video_enabled.get_untracked())>"Toggle video"</button>
+/// # }
+/// # }
+/// ```
+///
+/// `audio_enabled` and `video_enabled` control the `enabled` flag of the audio and video tracks
+/// of the stream. Disabled tracks stay part of the stream but only deliver silence or black
+/// frames. This is the usual way to mute the microphone or turn off the camera in a call.
+///
+/// ## Server-Side Rendering
+///
+/// On the server calls to `start` or any other way to enable the stream will be ignored
+/// and the stream will always be `None`.
+pub fn use_user_media() -> UseUserMediaReturn<impl Fn() + Clone, impl Fn() + Clone> {
+    use_user_media_with_options(UseUserMediaOptions::default())
+}
+
+/// Version of [`use_user_media`] that accepts a [`UseUserMediaOptions`].
+pub fn use_user_media_with_options(
+    options: UseUserMediaOptions,
+) -> UseUserMediaReturn<impl Fn() + Clone, impl Fn() + Clone> {
+    let UseUserMediaOptions {
+        enabled,
+        constraints,
+    } = options;
+
+    let (enabled, set_enabled) = enabled.into_signal();
+    let constraints = Signal::derive(move || constraints.get());
+
+    let (stream, set_stream) = create_signal(None::<web_sys::MediaStream>);
+    let (error, set_error) = create_signal(None::<UseUserMediaError>);
+    let (audio_enabled, set_audio_enabled) = create_signal(true);
+    let (video_enabled, set_video_enabled) = create_signal(true);
+
+    let update_tracks = move || {
+        if let Some(stream) = stream.get_untracked() {
+            for track in stream.get_audio_tracks() {
+                track
+                    .unchecked_ref::<web_sys::MediaStreamTrack>()
+                    .set_enabled(audio_enabled.get_untracked());
+            }
+
+            for track in stream.get_video_tracks() {
+                track
+                    .unchecked_ref::<web_sys::MediaStreamTrack>()
+                    .set_enabled(video_enabled.get_untracked());
+            }
+        }
+    };
+
+    // Incremented by every request and every stop so that a request which is still pending
+    // when the stream is stopped or requested again can't set its stream afterwards.
+    let generation = store_value(0_usize);
+
+    let _start = move || async move {
+        cfg_if! { if #[cfg(not(feature = "ssr"))] {
+            if stream.get_untracked().is_some() {
+                return;
+            }
+
+            generation.update_value(|generation| *generation += 1);
+            let current_generation = generation.get_value();
+
+            let result = create_media(&constraints.get_untracked()).await;
+
+            // the scope has been disposed or the request is stale
+            if generation.try_get_value() != Some(current_generation) {
+                if let Ok(media) = result {
+                    stop_tracks(&media);
+                }
+                return;
+            }
+
+            match result {
+                Ok(media) => {
+                    if let Some(previous) = stream.get_untracked() {
+                        stop_tracks(&previous);
+                    }
+
+                    set_error.set(None);
+                    set_stream.set(Some(media));
+                    update_tracks();
+                }
+                Err(err) => set_error.set(Some(err)),
+            }
+        } else {
+            let _ = constraints;
+            let _ = set_error;
+            let _ = update_tracks;
+            let _ = generation;
+        }}
+    };
+
+    let _stop = move || {
+        generation.update_value(|generation| *generation += 1);
+
+        if let Some(stream) = stream.get_untracked() {
+            stop_tracks(&stream);
+        }
+
+        set_stream.set(None);
+    };
+
+    let start = move || {
+        cfg_if! { if #[cfg(not(feature = "ssr"))] {
+            spawn_local(async move {
+                _start().await;
+                if stream.with_untracked(Option::is_some) {
+                    set_enabled.set(true);
+                }
+            });
+        }}
+    };
+
+    let stop = move || {
+        _stop();
+        set_enabled.set(false);
+    };
+
+    let _ = watch(
+        move || enabled.get(),
+        move |enabled, _, _| {
+            if *enabled {
+                spawn_local(async move {
+                    _start().await;
+                });
+            } else {
+                _stop();
+            }
+        },
+        true,
+    );
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = watch(
+            move || constraints.track(),
+            move |_, _, _| {
+                if enabled.get_untracked() {
+                    _stop();
+                    spawn_local(async move {
+                        _start().await;
+                    });
+                }
+            },
+            false,
+        );
+    }
+
+    let _ = watch(
+        move || (audio_enabled.get(), video_enabled.get()),
+        move |_, _, _| update_tracks(),
+        false,
+    );
+
+    on_cleanup(_stop);
+
+    UseUserMediaReturn {
+        stream: stream.into(),
+        start,
+        stop,
+        enabled,
+        set_enabled,
+        audio_enabled: audio_enabled.into(),
+        set_audio_enabled,
+        video_enabled: video_enabled.into(),
+        set_video_enabled,
+        error: error.into(),
+    }
+}
+
+fn stop_tracks(stream: &web_sys::MediaStream) {
+    for track in stream.get_tracks() {
+        track.unchecked_ref::<web_sys::MediaStreamTrack>().stop();
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+async fn create_media(
+    constraints: &web_sys::MediaStreamConstraints,
+) -> Result<web_sys::MediaStream, UseUserMediaError> {
+    use crate::js_fut;
+    use crate::use_window::use_window;
+
+    let media = use_window()
+        .navigator()
+        .and_then(|n| n.media_devices().ok())
+        .ok_or(UseUserMediaError::NotSupported)?;
+
+    let promise = media
+        .get_user_media_with_constraints(constraints)
+        .map_err(UseUserMediaError::from)?;
+    let res = js_fut!(promise).await.map_err(UseUserMediaError::from)?;
+
+    Ok(web_sys::MediaStream::unchecked_from_js(res))
+}
+
+/// Options for [`use_user_media_with_options`].
+#[derive(DefaultBuilder, Clone, Debug)]
+pub struct UseUserMediaOptions {
+    /// If the stream is enabled. Defaults to `false`.
+    #[builder(into)]
+    enabled: MaybeRwSignal<bool>,
+
+    /// The constraints of the requested stream. Whenever they change while the stream is
+    /// enabled, the stream is requested again. Defaults to a video stream without audio.
+    #[builder(into)]
+    constraints: MaybeSignal<web_sys::MediaStreamConstraints>,
+}
+
+impl Default for UseUserMediaOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false.into(),
+            // derived so that no JS object is created on the server
+            constraints: Signal::derive(|| {
+                let constraints = web_sys::MediaStreamConstraints::new();
+                constraints.set_video_bool(true);
+                constraints
+            })
+            .into(),
+        }
+    }
+}
+
+/// Return type of [`use_user_media`]
+#[derive(Clone)]
+pub struct UseUserMediaReturn<StartFn, StopFn>
+where
+    StartFn: Fn() + Clone,
+    StopFn: Fn() + Clone,
+{
+    /// The current [`MediaStream`](https://developer.mozilla.org/en-US/docs/Web/API/MediaStream) if it exists.
+    /// Initially this is `None` until `start` resolved successfully.
+    pub stream: Signal<Option<web_sys::MediaStream>>,
+
+    /// Starts the streaming. Triggers the ask for permission if not already granted.
+    pub start: StartFn,
+
+    /// Stops the streaming and all tracks of the stream
+    pub stop: StopFn,
+
+    /// A value of `true` indicates that the returned [`MediaStream`](https://developer.mozilla.org/en-US/docs/Web/API/MediaStream)
+    /// has resolved successfully and thus the stream is enabled.
+    pub enabled: Signal<bool>,
+
+    /// A value of `true` is the same as calling `start()` whereas `false` is the same as calling `stop()`.
+    pub set_enabled: WriteSignal<bool>,
+
+    /// Whether the audio tracks of the stream are enabled. Defaults to `true`.
+    pub audio_enabled: Signal<bool>,
+
+    /// Enables or disables the audio tracks without stopping the stream
+    pub set_audio_enabled: WriteSignal<bool>,
+
+    /// Whether the video tracks of the stream are enabled. Defaults to `true`.
+    pub video_enabled: Signal<bool>,
+
+    /// Enables or disables the video tracks without stopping the stream
+    pub set_video_enabled: WriteSignal<bool>,
+
+    /// Why the stream couldn't be started, for example because the user didn't grant permission
+    pub error: Signal<Option<UseUserMediaError>>,
+}
+
+/// Errors of [`use_user_media`].
+#[derive(Error, Debug, Clone)]
+pub enum UseUserMediaError {
+    #[error("getUserMedia is not supported")]
+    NotSupported,
+    #[error("the permission to access the media devices has been denied")]
+    PermissionDenied,
+    #[error("no media device matches the constraints")]
+    NotFound,
+    #[error("the media device is already in use")]
+    NotReadable,
+    #[error("the constraints can't be satisfied")]
+    Overconstrained(JsValue),
+    #[error("getUserMedia failed")]
+    Failed(JsValue),
+}
+
+impl From<JsValue> for UseUserMediaError {
+    fn from(err: JsValue) -> Self {
+        let name = js_sys::Reflect::get(&err, &"name".into())
+            .ok()
+            .and_then(|name| name.as_string());
+
+        match name.as_deref() {
+            Some("NotAllowedError" | "SecurityError") => Self::PermissionDenied,
+            Some("NotFoundError") => Self::NotFound,
+            Some("NotReadableError") => Self::NotReadable,
+            Some("OverconstrainedError") => Self::Overconstrained(err),
+            _ => Self::Failed(err),
+        }
+    }
+}
@@ -0,0 +1,340 @@
+use crate::{js, js_fut, use_event_listener, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    CodecState, EncodedVideoChunk, VideoDecoder, VideoDecoderConfig, VideoDecoderInit, VideoFrame,
+};
+
+/// Reactive [VideoDecoder](https://developer.mozilla.org/en-US/docs/Web/API/VideoDecoder)
+/// of the WebCodecs API. Decodes chunks of a codec like VP8, VP9, AV1 or H.264 into `VideoFrame`s.
+///
+/// > This function requires `--cfg=web_sys_unstable_apis` to be activated as
+/// > [described in the wasm-bindgen guide](https://rustwasm.github.io/docs/wasm-bindgen/web-sys/unstable-apis.html).
+///
+/// ## Usage
+///
+/// The decoder is (re-)configured whenever `config` changes to `Some`. The latest decoded frame
+/// is available in `frame`.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Canvas;
+/// # use leptos_use::use_video_decoder;
+/// # use wasm_bindgen::JsCast;
+/// # use web_sys::VideoDecoderConfig;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let canvas = create_node_ref::<Canvas>();
+///
+/// let decoder = use_video_decoder(Some(VideoDecoderConfig::new("vp8")));
+///
+/// // chunks received from the network, e.g. with `use_webtransport`
+/// let on_chunk = move |chunk: web_sys::EncodedVideoChunk| {
+///     let _ = decoder.decode(&chunk);
+/// };
+///
+/// create_effect(move |_| {
+///     if let (Some(frame), Some(canvas)) = (decoder.frame.get(), canvas.get()) {
+///         let context = canvas
+///             .get_context("2d")
+///             .unwrap()
+///             .unwrap()
+///             .unchecked_into::<web_sys::CanvasRenderingContext2d>();
+///
+///         let _ = context.draw_image_with_video_frame(&frame, 0.0, 0.0);
+///     }
+/// });
+///
+/// view! { <canvas node_ref=canvas width="640" height="480"></canvas> }
+/// # }
+/// ```
+///
+/// A `VideoFrame` holds on to memory of the graphics card until it's closed. Therefore the frame
+/// in `frame` is closed as soon as the next one has been decoded. Every frame is also passed to
+/// `on_output`. Call `frame.clone()` (which copies the JavaScript frame and returns a `Result`)
+/// to keep a frame longer and close the copy yourself.
+///
+/// `queue_size` is the number of chunks that are waiting to be decoded. An error closes the
+/// decoder. It's reported in `error` and `on_error` and a new decoder is created the next time
+/// `config` changes.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the decoder isn't created and all operations return
+/// `UseVideoDecoderError::NotSupported`.
+pub fn use_video_decoder(
+    config: impl Into<MaybeSignal<Option<VideoDecoderConfig>>>,
+) -> UseVideoDecoderReturn {
+    use_video_decoder_with_options(config, UseVideoDecoderOptions::default())
+}
+
+/// Version of [`use_video_decoder`] that takes a `UseVideoDecoderOptions`. See [`use_video_decoder`] for how to use.
+pub fn use_video_decoder_with_options(
+    config: impl Into<MaybeSignal<Option<VideoDecoderConfig>>>,
+    options: UseVideoDecoderOptions,
+) -> UseVideoDecoderReturn {
+    let config = config.into();
+    let UseVideoDecoderOptions {
+        on_output,
+        on_error,
+    } = options;
+
+    let is_supported = use_supported(|| js!("VideoDecoder" in &window()));
+
+    let (decoder, set_decoder) = create_signal(None::<VideoDecoder>);
+    let (state, set_state) = create_signal(CodecState::Unconfigured);
+    let (queue_size, set_queue_size) = create_signal(0_u32);
+    let (frame, set_frame) = create_signal(None::<VideoFrame>);
+    let (error, set_error) = create_signal(None::<UseVideoDecoderError>);
+
+    let update = move || {
+        if let Some(decoder) = decoder.get_untracked() {
+            set_state.set(decoder.state());
+            set_queue_size.set(decoder.decode_queue_size());
+        }
+    };
+
+    let close_frame = move || {
+        if let Some(Some(frame)) = frame.try_get_untracked() {
+            frame.close();
+        }
+    };
+
+    let create_decoder = move || -> Result<VideoDecoder, UseVideoDecoderError> {
+        let on_output = Rc::clone(&on_output);
+        let output_callback = Closure::<dyn Fn(VideoFrame)>::new(move |new_frame: VideoFrame| {
+            update();
+            close_frame();
+            set_frame.set(Some(Clone::clone(&new_frame)));
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_output(new_frame);
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        })
+        .into_js_value();
+
+        let on_error = Rc::clone(&on_error);
+        let error_callback = Closure::<dyn Fn(JsValue)>::new(move |err: JsValue| {
+            set_state.set(CodecState::Closed);
+            set_error.set(Some(UseVideoDecoderError::Codec(err.clone())));
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_error(err);
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        })
+        .into_js_value();
+
+        VideoDecoder::new(&VideoDecoderInit::new(
+            error_callback.unchecked_ref(),
+            output_callback.unchecked_ref(),
+        ))
+        .map_err(UseVideoDecoderError::Create)
+    };
+
+    let _ = watch(
+        move || config.get(),
+        move |config, _, _| {
+            let Some(config) = config else {
+                return;
+            };
+
+            if !is_supported.get_untracked() {
+                return;
+            }
+
+            let current = decoder
+                .get_untracked()
+                .filter(|decoder| decoder.state() != CodecState::Closed);
+
+            let result = match current {
+                Some(decoder) => Ok(decoder),
+                None => create_decoder().inspect(|decoder| set_decoder.set(Some(decoder.clone()))),
+            }
+            .and_then(|decoder| {
+                decoder
+                    .configure(config)
+                    .map_err(UseVideoDecoderError::Configure)
+            });
+
+            match result {
+                Ok(()) => set_error.set(None),
+                Err(err) => set_error.set(Some(err)),
+            }
+
+            update();
+        },
+        true,
+    );
+
+    let _ = use_event_listener(decoder, ev::Custom::<web_sys::Event>::new("dequeue"), {
+        move |_| update()
+    });
+
+    on_cleanup(move || {
+        if let Some(Some(decoder)) = decoder.try_get_untracked() {
+            if decoder.state() != CodecState::Closed {
+                let _ = decoder.close();
+            }
+        }
+
+        close_frame();
+    });
+
+    UseVideoDecoderReturn {
+        is_supported,
+        decoder: decoder.into(),
+        state: state.into(),
+        queue_size: queue_size.into(),
+        frame: frame.into(),
+        error: error.into(),
+        set_state,
+        set_queue_size,
+        set_error,
+    }
+}
+
+/// Options for [`use_video_decoder_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseVideoDecoderOptions {
+    /// Called for every decoded frame. The frame is closed once the next one has been decoded.
+    on_output: Rc<dyn Fn(VideoFrame)>,
+
+    /// Called when the decoder fails. The decoder is closed afterwards.
+    on_error: Rc<dyn Fn(JsValue)>,
+}
+
+impl Default for UseVideoDecoderOptions {
+    fn default() -> Self {
+        Self {
+            on_output: Rc::new(|_| {}),
+            on_error: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// Return type of [`use_video_decoder`].
+#[derive(Clone, Copy)]
+pub struct UseVideoDecoderReturn {
+    /// Whether the WebCodecs API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The decoder once it has been created
+    pub decoder: Signal<Option<VideoDecoder>>,
+
+    /// Whether the decoder is unconfigured, configured or closed
+    pub state: Signal<CodecState>,
+
+    /// The number of chunks waiting to be decoded
+    pub queue_size: Signal<u32>,
+
+    /// The latest decoded frame
+    pub frame: Signal<Option<VideoFrame>>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseVideoDecoderError>>,
+
+    set_state: WriteSignal<CodecState>,
+    set_queue_size: WriteSignal<u32>,
+    set_error: WriteSignal<Option<UseVideoDecoderError>>,
+}
+
+impl UseVideoDecoderReturn {
+    /// Queues a chunk for decoding. The first chunk and every chunk after `reset` has to be a
+    /// key frame.
+    pub fn decode(&self, chunk: &EncodedVideoChunk) -> Result<(), UseVideoDecoderError> {
+        let decoder = self.decoder()?;
+
+        decoder
+            .decode(chunk)
+            .map_err(|err| self.fail(UseVideoDecoderError::Decode(err)))?;
+
+        self.set_queue_size.set(decoder.decode_queue_size());
+
+        Ok(())
+    }
+
+    /// Waits until all queued chunks have been decoded.
+    pub async fn flush(&self) -> Result<(), UseVideoDecoderError> {
+        let decoder = self.decoder()?;
+
+        js_fut!(decoder.flush())
+            .await
+            .map_err(|err| self.fail(UseVideoDecoderError::Flush(err)))?;
+
+        Ok(())
+    }
+
+    /// Drops all queued chunks and makes the decoder unconfigured. It's configured again the
+    /// next time the config changes.
+    pub fn reset(&self) -> Result<(), UseVideoDecoderError> {
+        let decoder = self.decoder()?;
+
+        decoder
+            .reset()
+            .map_err(|err| self.fail(UseVideoDecoderError::Codec(err)))?;
+
+        self.set_state.set(decoder.state());
+        self.set_queue_size.set(0);
+
+        Ok(())
+    }
+
+    /// Closes the decoder. This happens automatically when the component is cleaned up.
+    pub fn close(&self) {
+        if let Some(decoder) = self.decoder.get_untracked() {
+            if decoder.state() != CodecState::Closed {
+                let _ = decoder.close();
+            }
+
+            self.set_state.set(CodecState::Closed);
+            self.set_queue_size.set(0);
+        }
+    }
+
+    fn decoder(&self) -> Result<VideoDecoder, UseVideoDecoderError> {
+        if !self.is_supported.get_untracked() {
+            return Err(UseVideoDecoderError::NotSupported);
+        }
+
+        self.decoder
+            .get_untracked()
+            .ok_or(UseVideoDecoderError::NotConfigured)
+    }
+
+    fn fail(&self, err: UseVideoDecoderError) -> UseVideoDecoderError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// Errors of [`use_video_decoder`].
+#[derive(Error, Debug, Clone)]
+pub enum UseVideoDecoderError {
+    #[error("the WebCodecs API is not supported")]
+    NotSupported,
+    #[error("the decoder hasn't been configured yet")]
+    NotConfigured,
+    #[error("creating the decoder failed")]
+    Create(JsValue),
+    #[error("configuring the decoder failed")]
+    Configure(JsValue),
+    #[error("decoding the chunk failed")]
+    Decode(JsValue),
+    #[error("flushing the decoder failed")]
+    Flush(JsValue),
+    #[error("the decoder failed")]
+    Codec(JsValue),
+}
@@ -0,0 +1,346 @@
+use crate::{js, js_fut, use_event_listener, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    CodecState, EncodedVideoChunk, EncodedVideoChunkMetadata, VideoEncoder, VideoEncoderConfig,
+    VideoEncoderEncodeOptions, VideoEncoderInit, VideoFrame,
+};
+
+/// Reactive [VideoEncoder](https://developer.mozilla.org/en-US/docs/Web/API/VideoEncoder)
+/// of the WebCodecs API. Encodes `VideoFrame`s into chunks of a codec like VP8, VP9, AV1 or H.264.
+///
+/// > This function requires `--cfg=web_sys_unstable_apis` to be activated as
+/// > [described in the wasm-bindgen guide](https://rustwasm.github.io/docs/wasm-bindgen/web-sys/unstable-apis.html).
+///
+/// ## Usage
+///
+/// The encoder is (re-)configured whenever `config` changes to `Some`. The encoded chunks are
+/// passed to `on_output` together with their metadata. The latest chunk is also available in
+/// `output`.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_video_encoder_with_options, UseVideoEncoderOptions};
+/// # use web_sys::VideoEncoderConfig;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let config = Signal::derive(|| Some(VideoEncoderConfig::new("vp8", 480, 640)));
+///
+/// let encoder = use_video_encoder_with_options(
+///     config,
+///     UseVideoEncoderOptions::default().on_output(|_chunk, _metadata| {
+///         // send the chunk over the network, e.g. with `use_webtransport`
+///     }),
+/// );
+///
+/// let encode = move |frame: web_sys::VideoFrame| {
+///     let _ = encoder.encode(&frame);
+///     // the encoder keeps its own reference
+///     frame.close();
+/// };
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// `queue_size` is the number of frames that are waiting to be encoded. If it keeps growing the
+/// encoder can't keep up and frames should be dropped before they are passed to `encode`.
+///
+/// An error closes the encoder. It's reported in `error` and `on_error` and a new encoder is
+/// created the next time `config` changes.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the encoder isn't created and all operations return
+/// `UseVideoEncoderError::NotSupported`.
+pub fn use_video_encoder(
+    config: impl Into<MaybeSignal<Option<VideoEncoderConfig>>>,
+) -> UseVideoEncoderReturn {
+    use_video_encoder_with_options(config, UseVideoEncoderOptions::default())
+}
+
+/// Version of [`use_video_encoder`] that takes a `UseVideoEncoderOptions`. See [`use_video_encoder`] for how to use.
+pub fn use_video_encoder_with_options(
+    config: impl Into<MaybeSignal<Option<VideoEncoderConfig>>>,
+    options: UseVideoEncoderOptions,
+) -> UseVideoEncoderReturn {
+    let config = config.into();
+    let UseVideoEncoderOptions {
+        on_output,
+        on_error,
+    } = options;
+
+    let is_supported = use_supported(|| js!("VideoEncoder" in &window()));
+
+    let (encoder, set_encoder) = create_signal(None::<VideoEncoder>);
+    let (state, set_state) = create_signal(CodecState::Unconfigured);
+    let (queue_size, set_queue_size) = create_signal(0_u32);
+    let (output, set_output) = create_signal(None::<EncodedVideoChunk>);
+    let (error, set_error) = create_signal(None::<UseVideoEncoderError>);
+
+    let update = move || {
+        if let Some(encoder) = encoder.get_untracked() {
+            set_state.set(encoder.state());
+            set_queue_size.set(encoder.encode_queue_size());
+        }
+    };
+
+    let create_encoder = move || -> Result<VideoEncoder, UseVideoEncoderError> {
+        let on_output = Rc::clone(&on_output);
+        let output_callback = Closure::<dyn Fn(EncodedVideoChunk, JsValue)>::new(
+            move |chunk: EncodedVideoChunk, metadata: JsValue| {
+                update();
+                set_output.set(Some(chunk.clone()));
+
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                on_output(
+                    chunk,
+                    Some(metadata)
+                        .filter(|metadata| !metadata.is_undefined())
+                        .map(JsCast::unchecked_into),
+                );
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+            },
+        )
+        .into_js_value();
+
+        let on_error = Rc::clone(&on_error);
+        let error_callback = Closure::<dyn Fn(JsValue)>::new(move |err: JsValue| {
+            set_state.set(CodecState::Closed);
+            set_error.set(Some(UseVideoEncoderError::Codec(err.clone())));
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_error(err);
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        })
+        .into_js_value();
+
+        VideoEncoder::new(&VideoEncoderInit::new(
+            error_callback.unchecked_ref(),
+            output_callback.unchecked_ref(),
+        ))
+        .map_err(UseVideoEncoderError::Create)
+    };
+
+    let _ = watch(
+        move || config.get(),
+        move |config, _, _| {
+            let Some(config) = config else {
+                return;
+            };
+
+            if !is_supported.get_untracked() {
+                return;
+            }
+
+            let current = encoder
+                .get_untracked()
+                .filter(|encoder| encoder.state() != CodecState::Closed);
+
+            let result = match current {
+                Some(encoder) => Ok(encoder),
+                None => create_encoder().inspect(|encoder| set_encoder.set(Some(encoder.clone()))),
+            }
+            .and_then(|encoder| {
+                encoder
+                    .configure(config)
+                    .map_err(UseVideoEncoderError::Configure)
+            });
+
+            match result {
+                Ok(()) => set_error.set(None),
+                Err(err) => set_error.set(Some(err)),
+            }
+
+            update();
+        },
+        true,
+    );
+
+    let _ = use_event_listener(encoder, ev::Custom::<web_sys::Event>::new("dequeue"), {
+        move |_| update()
+    });
+
+    on_cleanup(move || {
+        if let Some(Some(encoder)) = encoder.try_get_untracked() {
+            if encoder.state() != CodecState::Closed {
+                let _ = encoder.close();
+            }
+        }
+    });
+
+    UseVideoEncoderReturn {
+        is_supported,
+        encoder: encoder.into(),
+        state: state.into(),
+        queue_size: queue_size.into(),
+        output: output.into(),
+        error: error.into(),
+        set_state,
+        set_queue_size,
+        set_error,
+    }
+}
+
+/// Options for [`use_video_encoder_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseVideoEncoderOptions {
+    /// Called for every encoded chunk. The metadata contains the decoder configuration for key
+    /// frames which the receiver needs to configure its decoder.
+    on_output: Rc<dyn Fn(EncodedVideoChunk, Option<EncodedVideoChunkMetadata>)>,
+
+    /// Called when the encoder fails. The encoder is closed afterwards.
+    on_error: Rc<dyn Fn(JsValue)>,
+}
+
+impl Default for UseVideoEncoderOptions {
+    fn default() -> Self {
+        Self {
+            on_output: Rc::new(|_, _| {}),
+            on_error: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// Return type of [`use_video_encoder`].
+#[derive(Clone, Copy)]
+pub struct UseVideoEncoderReturn {
+    /// Whether the WebCodecs API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The encoder once it has been created
+    pub encoder: Signal<Option<VideoEncoder>>,
+
+    /// Whether the encoder is unconfigured, configured or closed
+    pub state: Signal<CodecState>,
+
+    /// The number of frames waiting to be encoded
+    pub queue_size: Signal<u32>,
+
+    /// The latest encoded chunk
+    pub output: Signal<Option<EncodedVideoChunk>>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseVideoEncoderError>>,
+
+    set_state: WriteSignal<CodecState>,
+    set_queue_size: WriteSignal<u32>,
+    set_error: WriteSignal<Option<UseVideoEncoderError>>,
+}
+
+impl UseVideoEncoderReturn {
+    /// Queues a frame for encoding. The frame can be closed right afterwards.
+    pub fn encode(&self, frame: &VideoFrame) -> Result<(), UseVideoEncoderError> {
+        self.encode_with_options(frame, &VideoEncoderEncodeOptions::new())
+    }
+
+    /// Queues a frame for encoding and forces it to be a key frame. Send one periodically so that
+    /// a receiver can start decoding in the middle of a stream.
+    pub fn encode_key_frame(&self, frame: &VideoFrame) -> Result<(), UseVideoEncoderError> {
+        let options = VideoEncoderEncodeOptions::new();
+        options.set_key_frame(true);
+
+        self.encode_with_options(frame, &options)
+    }
+
+    fn encode_with_options(
+        &self,
+        frame: &VideoFrame,
+        options: &VideoEncoderEncodeOptions,
+    ) -> Result<(), UseVideoEncoderError> {
+        let encoder = self.encoder()?;
+
+        encoder
+            .encode_with_options(frame, options)
+            .map_err(|err| self.fail(UseVideoEncoderError::Encode(err)))?;
+
+        self.set_queue_size.set(encoder.encode_queue_size());
+
+        Ok(())
+    }
+
+    /// Waits until all queued frames have been encoded and passed to `on_output`.
+    pub async fn flush(&self) -> Result<(), UseVideoEncoderError> {
+        let encoder = self.encoder()?;
+
+        js_fut!(encoder.flush())
+            .await
+            .map_err(|err| self.fail(UseVideoEncoderError::Flush(err)))?;
+
+        Ok(())
+    }
+
+    /// Drops all queued frames and makes the encoder unconfigured. It's configured again the
+    /// next time the config changes.
+    pub fn reset(&self) -> Result<(), UseVideoEncoderError> {
+        let encoder = self.encoder()?;
+
+        encoder
+            .reset()
+            .map_err(|err| self.fail(UseVideoEncoderError::Codec(err)))?;
+
+        self.set_state.set(encoder.state());
+        self.set_queue_size.set(0);
+
+        Ok(())
+    }
+
+    /// Closes the encoder. This happens automatically when the component is cleaned up.
+    pub fn close(&self) {
+        if let Some(encoder) = self.encoder.get_untracked() {
+            if encoder.state() != CodecState::Closed {
+                let _ = encoder.close();
+            }
+
+            self.set_state.set(CodecState::Closed);
+            self.set_queue_size.set(0);
+        }
+    }
+
+    fn encoder(&self) -> Result<VideoEncoder, UseVideoEncoderError> {
+        if !self.is_supported.get_untracked() {
+            return Err(UseVideoEncoderError::NotSupported);
+        }
+
+        self.encoder
+            .get_untracked()
+            .ok_or(UseVideoEncoderError::NotConfigured)
+    }
+
+    fn fail(&self, err: UseVideoEncoderError) -> UseVideoEncoderError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// Errors of [`use_video_encoder`].
+#[derive(Error, Debug, Clone)]
+pub enum UseVideoEncoderError {
+    #[error("the WebCodecs API is not supported")]
+    NotSupported,
+    #[error("the encoder hasn't been configured yet")]
+    NotConfigured,
+    #[error("creating the encoder failed")]
+    Create(JsValue),
+    #[error("configuring the encoder failed")]
+    Configure(JsValue),
+    #[error("encoding the frame failed")]
+    Encode(JsValue),
+    #[error("flushing the encoder failed")]
+    Flush(JsValue),
+    #[error("the encoder failed")]
+    Codec(JsValue),
+}
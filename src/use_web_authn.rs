@@ -0,0 +1,705 @@
+use crate::{js, js_fut, use_supported};
+use base64::alphabet;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::Engine;
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Reactive [Web Authentication API](https://developer.mozilla.org/en-US/docs/Web/API/Web_Authentication_API).
+/// Registers and signs in with passkeys and security keys.
+///
+/// ## Usage
+///
+/// Your server creates the options for a ceremony, including a random challenge, and verifies the
+/// result afterwards. All binary values like challenges, ids and the responses of the
+/// authenticator are base64url encoded strings so they can be passed to and from the server as
+/// they are.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{
+/// #     use_web_authn, WebAuthnCreationOptions, WebAuthnRelyingParty, WebAuthnRequestOptions,
+/// #     WebAuthnUser,
+/// # };
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let web_authn = use_web_authn();
+///
+/// let register = move |_| {
+///     spawn_local(async move {
+///         // usually received from your server
+///         let options = WebAuthnCreationOptions {
+///             challenge: "c2VydmVyIGNoYWxsZW5nZQ".to_string(),
+///             rp: WebAuthnRelyingParty {
+///                 id: None,
+///                 name: "Example".to_string(),
+///             },
+///             user: WebAuthnUser {
+///                 id: "dXNlci0x".to_string(),
+///                 name: "jane@example.com".to_string(),
+///                 display_name: "Jane".to_string(),
+///             },
+///             ..Default::default()
+///         };
+///
+///         if let Ok(registration) = web_authn.create(&options).await {
+///             // send `registration` to your server to store the new credential
+///         }
+///     });
+/// };
+///
+/// let sign_in = move |_| {
+///     spawn_local(async move {
+///         let options = WebAuthnRequestOptions {
+///             challenge: "c2VydmVyIGNoYWxsZW5nZQ".to_string(),
+///             ..Default::default()
+///         };
+///
+///         if let Ok(authentication) = web_authn.get(&options).await {
+///             // send `authentication` to your server to verify the signature
+///         }
+///     });
+/// };
+///
+/// view! {
+///     <Show when=move || web_authn.is_supported.get()>
+///         <button on:click=register>"Create passkey"</button>
+///         <button on:click=sign_in disabled=move || web_authn.is_pending.get()>"Sign in"</button>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// `is_uvpa_available` tells if the device has a built-in authenticator that verifies the user,
+/// for example with a fingerprint, which makes it a good candidate to offer passkeys.
+///
+/// With the feature `serde` enabled, all option and result structs implement `Serialize` and
+/// `Deserialize` in the JSON format of the specification (`PublicKeyCredentialCreationOptionsJSON`,
+/// `RegistrationResponseJSON` and so on) that server libraries like `webauthn-rs` or
+/// `SimpleWebAuthn` use.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_supported` and `is_uvpa_available` are always `false` and `create` and `get`
+/// return `UseWebAuthnError::NotSupported`.
+pub fn use_web_authn() -> UseWebAuthnReturn {
+    let is_supported = use_supported(|| {
+        js!("PublicKeyCredential" in &window()) && js!("credentials" in &window().navigator())
+    });
+
+    let (is_uvpa_available, set_uvpa_available) = create_signal(false);
+    let (is_pending, set_pending) = create_signal(false);
+    let (error, set_error) = create_signal(None::<UseWebAuthnError>);
+
+    if is_supported.get_untracked() {
+        spawn_local(async move {
+            let available = async {
+                let window = window();
+                let public_key_credential = js!(window["PublicKeyCredential"])?;
+                let is_available = js_sys::Reflect::get(
+                    &public_key_credential,
+                    &"isUserVerifyingPlatformAuthenticatorAvailable".into(),
+                )?
+                .dyn_into::<js_sys::Function>()?;
+
+                js_fut!(is_available
+                    .call0(&public_key_credential)?
+                    .unchecked_into::<js_sys::Promise>())
+                .await
+            }
+            .await;
+
+            let _ = set_uvpa_available
+                .try_set(available.ok().and_then(|available| available.as_bool()) == Some(true));
+        });
+    }
+
+    UseWebAuthnReturn {
+        is_supported,
+        is_uvpa_available: is_uvpa_available.into(),
+        is_pending: is_pending.into(),
+        error: error.into(),
+        set_pending,
+        set_error,
+    }
+}
+
+/// Return type of [`use_web_authn`].
+#[derive(Clone, Copy)]
+pub struct UseWebAuthnReturn {
+    /// Whether the Web Authentication API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// Whether a user-verifying platform authenticator like Touch ID or Windows Hello is available
+    pub is_uvpa_available: Signal<bool>,
+
+    /// Whether `create` or `get` is waiting for the authenticator
+    pub is_pending: Signal<bool>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseWebAuthnError>>,
+
+    set_pending: WriteSignal<bool>,
+    set_error: WriteSignal<Option<UseWebAuthnError>>,
+}
+
+impl UseWebAuthnReturn {
+    /// Creates a new credential, for example a passkey, with `navigator.credentials.create()`.
+    pub async fn create(
+        &self,
+        options: &WebAuthnCreationOptions,
+    ) -> Result<WebAuthnRegistration, UseWebAuthnError> {
+        self.check_supported()?;
+
+        let js_options = options.to_js().map_err(|err| self.fail(err))?;
+
+        let credential = self
+            .run(|| {
+                window()
+                    .navigator()
+                    .credentials()
+                    .create_with_options(js_options.unchecked_ref())
+            })
+            .await
+            .map_err(|err| self.fail(UseWebAuthnError::Create(err)))?;
+
+        let registration = WebAuthnRegistration::new(&credential)
+            .ok_or_else(|| self.fail(UseWebAuthnError::InvalidCredential))?;
+        self.set_error.set(None);
+
+        Ok(registration)
+    }
+
+    /// Signs in with an existing credential with `navigator.credentials.get()`.
+    pub async fn get(
+        &self,
+        options: &WebAuthnRequestOptions,
+    ) -> Result<WebAuthnAuthentication, UseWebAuthnError> {
+        self.check_supported()?;
+
+        let js_options = options.to_js().map_err(|err| self.fail(err))?;
+
+        let credential = self
+            .run(|| {
+                window()
+                    .navigator()
+                    .credentials()
+                    .get_with_options(js_options.unchecked_ref())
+            })
+            .await
+            .map_err(|err| self.fail(UseWebAuthnError::Get(err)))?;
+
+        let authentication = WebAuthnAuthentication::new(&credential)
+            .ok_or_else(|| self.fail(UseWebAuthnError::InvalidCredential))?;
+        self.set_error.set(None);
+
+        Ok(authentication)
+    }
+
+    async fn run(
+        &self,
+        request: impl FnOnce() -> Result<js_sys::Promise, JsValue>,
+    ) -> Result<JsValue, JsValue> {
+        self.set_pending.set(true);
+        let result = async { js_fut!(request()?).await }.await;
+        self.set_pending.set(false);
+
+        result
+    }
+
+    fn check_supported(&self) -> Result<(), UseWebAuthnError> {
+        if self.is_supported.get_untracked() {
+            Ok(())
+        } else {
+            Err(UseWebAuthnError::NotSupported)
+        }
+    }
+
+    fn fail(&self, err: UseWebAuthnError) -> UseWebAuthnError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// Options of [`UseWebAuthnReturn::create`] to register a new credential.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase", default)
+)]
+pub struct WebAuthnCreationOptions {
+    /// The random challenge of the server, encoded as base64url
+    pub challenge: String,
+
+    /// The website that the credential belongs to
+    pub rp: WebAuthnRelyingParty,
+
+    /// The account that the credential is created for
+    pub user: WebAuthnUser,
+
+    /// The accepted signature algorithms in order of preference.
+    /// Defaults to ES256 and RS256.
+    pub pub_key_cred_params: Vec<WebAuthnCredentialParameters>,
+
+    /// How long the user has to complete the ceremony in milliseconds
+    pub timeout: Option<u32>,
+
+    /// Credentials that already exist for the user so that no second one is created on the same
+    /// authenticator
+    pub exclude_credentials: Vec<WebAuthnCredentialDescriptor>,
+
+    /// Requirements on the authenticator
+    pub authenticator_selection: Option<WebAuthnAuthenticatorSelection>,
+
+    /// `"none"`, `"indirect"`, `"direct"` or `"enterprise"`
+    pub attestation: Option<String>,
+}
+
+impl Default for WebAuthnCreationOptions {
+    fn default() -> Self {
+        Self {
+            challenge: String::new(),
+            rp: WebAuthnRelyingParty::default(),
+            user: WebAuthnUser::default(),
+            pub_key_cred_params: vec![
+                WebAuthnCredentialParameters::new(-7),
+                WebAuthnCredentialParameters::new(-257),
+            ],
+            timeout: None,
+            exclude_credentials: vec![],
+            authenticator_selection: None,
+            attestation: None,
+        }
+    }
+}
+
+impl WebAuthnCreationOptions {
+    fn to_js(&self) -> Result<js_sys::Object, UseWebAuthnError> {
+        let public_key = js_sys::Object::new();
+
+        js!(public_key["challenge"] = decode("challenge", &self.challenge)?);
+
+        let rp = js_sys::Object::new();
+        js!(rp["name"] = &self.rp.name);
+        if let Some(id) = &self.rp.id {
+            js!(rp["id"] = id);
+        }
+        js!(public_key["rp"] = rp);
+
+        let user = js_sys::Object::new();
+        js!(user["id"] = decode("user.id", &self.user.id)?);
+        js!(user["name"] = &self.user.name);
+        js!(user["displayName"] = &self.user.display_name);
+        js!(public_key["user"] = user);
+
+        let params = self
+            .pub_key_cred_params
+            .iter()
+            .map(|param| {
+                let js_param = js_sys::Object::new();
+                js!(js_param["type"] = &param.type_);
+                js!(js_param["alg"] = param.alg);
+                js_param
+            })
+            .collect::<js_sys::Array>();
+        js!(public_key["pubKeyCredParams"] = params);
+
+        if let Some(timeout) = self.timeout {
+            js!(public_key["timeout"] = timeout);
+        }
+
+        js!(public_key["excludeCredentials"] = descriptors_to_js(&self.exclude_credentials)?);
+
+        if let Some(selection) = &self.authenticator_selection {
+            let js_selection = js_sys::Object::new();
+            if let Some(attachment) = &selection.authenticator_attachment {
+                js!(js_selection["authenticatorAttachment"] = attachment);
+            }
+            if let Some(resident_key) = &selection.resident_key {
+                js!(js_selection["residentKey"] = resident_key);
+            }
+            js!(js_selection["requireResidentKey"] = selection.require_resident_key);
+            if let Some(user_verification) = &selection.user_verification {
+                js!(js_selection["userVerification"] = user_verification);
+            }
+            js!(public_key["authenticatorSelection"] = js_selection);
+        }
+
+        if let Some(attestation) = &self.attestation {
+            js!(public_key["attestation"] = attestation);
+        }
+
+        let options = js_sys::Object::new();
+        js!(options["publicKey"] = public_key);
+
+        Ok(options)
+    }
+}
+
+/// Options of [`UseWebAuthnReturn::get`] to sign in with an existing credential.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase", default)
+)]
+pub struct WebAuthnRequestOptions {
+    /// The random challenge of the server, encoded as base64url
+    pub challenge: String,
+
+    /// How long the user has to complete the ceremony in milliseconds
+    pub timeout: Option<u32>,
+
+    /// The domain of the website. Defaults to the current domain.
+    pub rp_id: Option<String>,
+
+    /// The credentials that may be used. If empty, the user can choose any passkey of the website.
+    pub allow_credentials: Vec<WebAuthnCredentialDescriptor>,
+
+    /// `"required"`, `"preferred"` or `"discouraged"`
+    pub user_verification: Option<String>,
+}
+
+impl WebAuthnRequestOptions {
+    fn to_js(&self) -> Result<js_sys::Object, UseWebAuthnError> {
+        let public_key = js_sys::Object::new();
+
+        js!(public_key["challenge"] = decode("challenge", &self.challenge)?);
+
+        if let Some(timeout) = self.timeout {
+            js!(public_key["timeout"] = timeout);
+        }
+        if let Some(rp_id) = &self.rp_id {
+            js!(public_key["rpId"] = rp_id);
+        }
+
+        js!(public_key["allowCredentials"] = descriptors_to_js(&self.allow_credentials)?);
+
+        if let Some(user_verification) = &self.user_verification {
+            js!(public_key["userVerification"] = user_verification);
+        }
+
+        let options = js_sys::Object::new();
+        js!(options["publicKey"] = public_key);
+
+        Ok(options)
+    }
+}
+
+/// The website that a credential belongs to.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WebAuthnRelyingParty {
+    /// The domain of the website. Defaults to the current domain.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub id: Option<String>,
+
+    /// The name of the website that is shown to the user
+    pub name: String,
+}
+
+/// The account that a credential is created for.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct WebAuthnUser {
+    /// The id of the user, encoded as base64url. Must not contain personal information.
+    pub id: String,
+
+    /// The name of the account, for example the email address
+    pub name: String,
+
+    /// The name of the user that is shown
+    pub display_name: String,
+}
+
+/// A signature algorithm that is accepted for a new credential.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WebAuthnCredentialParameters {
+    /// Always `"public-key"`
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub type_: String,
+
+    /// The [COSE algorithm identifier](https://www.iana.org/assignments/cose/cose.xhtml#algorithms),
+    /// for example `-7` for ES256
+    pub alg: i32,
+}
+
+impl WebAuthnCredentialParameters {
+    /// Accepts the algorithm with the COSE identifier `alg`.
+    pub fn new(alg: i32) -> Self {
+        Self {
+            type_: "public-key".to_string(),
+            alg,
+        }
+    }
+}
+
+/// Refers to an existing credential.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WebAuthnCredentialDescriptor {
+    /// The id of the credential, encoded as base64url
+    pub id: String,
+
+    /// Always `"public-key"`
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub type_: String,
+
+    /// How the authenticator can be reached, for example `"usb"`, `"nfc"` or `"internal"`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub transports: Vec<String>,
+}
+
+impl WebAuthnCredentialDescriptor {
+    /// Refers to the credential with the base64url encoded `id`.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            type_: "public-key".to_string(),
+            transports: vec![],
+        }
+    }
+}
+
+/// Requirements on the authenticator of a new credential.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase", default)
+)]
+pub struct WebAuthnAuthenticatorSelection {
+    /// `"platform"` for a built-in authenticator or `"cross-platform"` for a security key
+    pub authenticator_attachment: Option<String>,
+
+    /// `"required"`, `"preferred"` or `"discouraged"`. Passkeys require a resident key.
+    pub resident_key: Option<String>,
+
+    /// Legacy version of `resident_key: Some("required")`
+    pub require_resident_key: bool,
+
+    /// `"required"`, `"preferred"` or `"discouraged"`
+    pub user_verification: Option<String>,
+}
+
+/// The result of [`UseWebAuthnReturn::create`] that your server verifies and stores.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct WebAuthnRegistration {
+    /// The id of the new credential, encoded as base64url
+    pub id: String,
+
+    /// The same as `id`
+    pub raw_id: String,
+
+    /// Always `"public-key"`
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub type_: String,
+
+    /// `"platform"` or `"cross-platform"`
+    pub authenticator_attachment: Option<String>,
+
+    /// The response of the authenticator
+    pub response: WebAuthnAttestationResponse,
+}
+
+impl WebAuthnRegistration {
+    fn new(credential: &JsValue) -> Option<Self> {
+        let response = js!(credential["response"]).ok()?;
+
+        let transports = js_sys::Reflect::get(&response, &"getTransports".into())
+            .ok()
+            .and_then(|get_transports| get_transports.dyn_into::<js_sys::Function>().ok())
+            .and_then(|get_transports| get_transports.call0(&response).ok())
+            .map(|transports| {
+                js_sys::Array::from(&transports)
+                    .iter()
+                    .filter_map(|transport| transport.as_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Some(Self {
+            id: js!(credential["id"]).ok()?.as_string()?,
+            raw_id: encode(credential, "rawId")?,
+            type_: js!(credential["type"]).ok()?.as_string()?,
+            authenticator_attachment: js!(credential["authenticatorAttachment"])
+                .ok()
+                .and_then(|attachment| attachment.as_string()),
+            response: WebAuthnAttestationResponse {
+                client_data_json: encode(&response, "clientDataJSON")?,
+                attestation_object: encode(&response, "attestationObject")?,
+                transports,
+            },
+        })
+    }
+}
+
+/// The response of the authenticator in a [`WebAuthnRegistration`]. All values are encoded as
+/// base64url.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct WebAuthnAttestationResponse {
+    /// The data that the browser passed to the authenticator, including the challenge
+    #[cfg_attr(feature = "serde", serde(rename = "clientDataJSON"))]
+    pub client_data_json: String,
+
+    /// The public key of the new credential and the attestation of the authenticator
+    pub attestation_object: String,
+
+    /// How the authenticator can be reached
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub transports: Vec<String>,
+}
+
+/// The result of [`UseWebAuthnReturn::get`] that your server verifies.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct WebAuthnAuthentication {
+    /// The id of the credential that was used, encoded as base64url
+    pub id: String,
+
+    /// The same as `id`
+    pub raw_id: String,
+
+    /// Always `"public-key"`
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub type_: String,
+
+    /// `"platform"` or `"cross-platform"`
+    pub authenticator_attachment: Option<String>,
+
+    /// The response of the authenticator
+    pub response: WebAuthnAssertionResponse,
+}
+
+impl WebAuthnAuthentication {
+    fn new(credential: &JsValue) -> Option<Self> {
+        let response = js!(credential["response"]).ok()?;
+
+        Some(Self {
+            id: js!(credential["id"]).ok()?.as_string()?,
+            raw_id: encode(credential, "rawId")?,
+            type_: js!(credential["type"]).ok()?.as_string()?,
+            authenticator_attachment: js!(credential["authenticatorAttachment"])
+                .ok()
+                .and_then(|attachment| attachment.as_string()),
+            response: WebAuthnAssertionResponse {
+                client_data_json: encode(&response, "clientDataJSON")?,
+                authenticator_data: encode(&response, "authenticatorData")?,
+                signature: encode(&response, "signature")?,
+                user_handle: encode(&response, "userHandle"),
+            },
+        })
+    }
+}
+
+/// The response of the authenticator in a [`WebAuthnAuthentication`]. All values are encoded as
+/// base64url.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct WebAuthnAssertionResponse {
+    /// The data that the browser passed to the authenticator, including the challenge
+    #[cfg_attr(feature = "serde", serde(rename = "clientDataJSON"))]
+    pub client_data_json: String,
+
+    /// The data of the authenticator that has been signed
+    pub authenticator_data: String,
+
+    /// The signature over `authenticator_data` and `client_data_json`
+    pub signature: String,
+
+    /// The `user.id` that the credential was created with
+    pub user_handle: Option<String>,
+}
+
+/// Errors of [`use_web_authn`].
+#[derive(Error, Debug, Clone)]
+pub enum UseWebAuthnError {
+    #[error("the Web Authentication API is not supported")]
+    NotSupported,
+    #[error("`{0}` is not valid base64url")]
+    InvalidBase64(&'static str),
+    #[error("creating the credential failed")]
+    Create(JsValue),
+    #[error("getting the credential failed")]
+    Get(JsValue),
+    #[error("the authenticator returned an invalid credential")]
+    InvalidCredential,
+}
+
+/// base64url without padding like the JSON formats of WebAuthn, but accepts padded input as well.
+const BASE64URL: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new()
+        .with_encode_padding(false)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+fn decode(field: &'static str, value: &str) -> Result<js_sys::Uint8Array, UseWebAuthnError> {
+    BASE64URL
+        .decode(value)
+        .map(|bytes| js_sys::Uint8Array::from(bytes.as_slice()))
+        .map_err(|_| UseWebAuthnError::InvalidBase64(field))
+}
+
+/// Reads the `ArrayBuffer` at `key` of `obj` as base64url. Returns `None` if it is missing.
+fn encode(obj: &JsValue, key: &str) -> Option<String> {
+    let buffer = js_sys::Reflect::get(obj, &key.into()).ok()?;
+
+    if buffer.is_null() || buffer.is_undefined() {
+        return None;
+    }
+
+    Some(BASE64URL.encode(js_sys::Uint8Array::new(&buffer).to_vec()))
+}
+
+fn descriptors_to_js(
+    descriptors: &[WebAuthnCredentialDescriptor],
+) -> Result<js_sys::Array, UseWebAuthnError> {
+    descriptors
+        .iter()
+        .map(|descriptor| {
+            let js_descriptor = js_sys::Object::new();
+            js!(js_descriptor["id"] = decode("credential id", &descriptor.id)?);
+            js!(js_descriptor["type"] = &descriptor.type_);
+            if !descriptor.transports.is_empty() {
+                let transports = descriptor
+                    .transports
+                    .iter()
+                    .map(JsValue::from)
+                    .collect::<js_sys::Array>();
+                js!(js_descriptor["transports"] = transports);
+            }
+
+            Ok(js_descriptor)
+        })
+        .collect()
+}
@@ -0,0 +1,505 @@
+use crate::utils::data_view_to_vec;
+use crate::{js, js_fut, use_event_listener, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+use std::time::Duration;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{
+    BluetoothDevice, BluetoothLeScanFilterInit, BluetoothRemoteGattCharacteristic,
+    BluetoothRemoteGattServer,
+};
+
+/// Reactive [Web Bluetooth API](https://developer.mozilla.org/en-US/docs/Web/API/Web_Bluetooth_API).
+/// Lets the user pick a Bluetooth Low Energy device, connects to its GATT server and
+/// provides helpers to read, write and be notified about characteristic values.
+///
+/// > This function requires `--cfg=web_sys_unstable_apis` to be activated as
+/// > [described in the wasm-bindgen guide](https://rustwasm.github.io/docs/wasm-bindgen/web-sys/unstable-apis.html).
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_web_bluetooth_with_options, UseWebBluetoothOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let bluetooth = use_web_bluetooth_with_options(
+///     UseWebBluetoothOptions::default()
+///         .optional_services(vec!["battery_service".to_string()])
+///         .reconnect_limit(3),
+/// );
+///
+/// let battery_level = bluetooth.notifications("battery_service", "battery_level");
+///
+/// let request = move |_| {
+///     spawn_local(async move {
+///         // shows the browser's device chooser and connects to the selected device
+///         let _ = bluetooth.request_device().await;
+///     });
+/// };
+///
+/// view! {
+///     <Show
+///         when=move || bluetooth.is_supported.get()
+///         fallback=|| view! { <p>"Your browser does not support Web Bluetooth"</p> }
+///     >
+///         <button on:click=request>"Connect"</button>
+///         <Show when=move || bluetooth.is_connected.get()>
+///             <p>"Battery: " {move || battery_level.get().and_then(|value| value.first().copied())} "%"</p>
+///             <button on:click=move |_| bluetooth.disconnect()>"Disconnect"</button>
+///         </Show>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// `request_device` has to be called from a user gesture like a click. Without `filters` all
+/// devices are shown in the chooser. The services you want to access have to be listed in
+/// `optional_services` or in one of the `filters`.
+///
+/// ### Reading and writing
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_web_bluetooth;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let bluetooth = use_web_bluetooth();
+///
+/// let blink = move |_| {
+///     spawn_local(async move {
+///         let mode = bluetooth.read_value("my-service-uuid", "my-mode-uuid").await;
+///         let _ = bluetooth.write_value("my-service-uuid", "my-led-uuid", &[1]).await;
+///     });
+/// };
+/// #
+/// # view! { <button on:click=blink>"Blink"</button> }
+/// # }
+/// ```
+///
+/// ### Reconnecting
+///
+/// The selected device is remembered. If the connection is lost without calling `disconnect`,
+/// the hook tries to connect to it again up to `reconnect_limit` times waiting
+/// `reconnect_interval` milliseconds between attempts. Auto-reconnect is disabled by default.
+/// You can also call `connect` to reconnect to the remembered device without showing the
+/// chooser again. Signals returned by `notifications` are subscribed again after every
+/// reconnect.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_supported` and `is_connected` are always `false` and all operations return
+/// `UseWebBluetoothError::NotSupported`.
+pub fn use_web_bluetooth() -> UseWebBluetoothReturn {
+    use_web_bluetooth_with_options(UseWebBluetoothOptions::default())
+}
+
+/// Version of [`use_web_bluetooth`] that takes a `UseWebBluetoothOptions`. See [`use_web_bluetooth`] for how to use.
+pub fn use_web_bluetooth_with_options(options: UseWebBluetoothOptions) -> UseWebBluetoothReturn {
+    let UseWebBluetoothOptions {
+        filters,
+        optional_services,
+        reconnect_limit,
+        reconnect_interval,
+        on_reconnect_failed,
+    } = options;
+
+    let is_supported = use_supported(|| js!("bluetooth" in &window().navigator()));
+
+    let (is_available, set_is_available) = create_signal(false);
+    let (device, set_device) = create_signal(None::<BluetoothDevice>);
+    let (server, set_server) = create_signal(None::<BluetoothRemoteGattServer>);
+    let (error, set_error) = create_signal(None::<UseWebBluetoothError>);
+
+    let bluetooth = UseWebBluetoothReturn {
+        is_supported,
+        is_available: is_available.into(),
+        device: device.into(),
+        is_connected: Signal::derive(move || server.with(Option::is_some)),
+        server: server.into(),
+        error: error.into(),
+        set_device,
+        set_server,
+        set_error,
+        request_options: store_value(RequestOptions {
+            filters,
+            optional_services,
+        }),
+        explicitly_disconnected: store_value(false),
+        retried: store_value(0),
+        reconnect_limit,
+        reconnect_interval,
+        on_reconnect_failed: store_value(on_reconnect_failed),
+    };
+
+    let navigator_bluetooth = if is_supported.get_untracked() {
+        window().navigator().bluetooth()
+    } else {
+        None
+    };
+
+    if let Some(navigator_bluetooth) = navigator_bluetooth.clone() {
+        spawn_local(async move {
+            if let Ok(available) = js_fut!(navigator_bluetooth.get_availability()).await {
+                set_is_available.set(available.value_of());
+            }
+        });
+    }
+
+    let _ = use_event_listener(
+        navigator_bluetooth,
+        ev::Custom::<web_sys::Event>::new("availabilitychanged"),
+        move |event| {
+            if let Ok(available) = js!(event["value"]) {
+                set_is_available.set(available.as_bool().unwrap_or_default());
+            }
+        },
+    );
+
+    let _ = use_event_listener(
+        device,
+        ev::Custom::<web_sys::Event>::new("gattserverdisconnected"),
+        move |_| {
+            bluetooth.set_server.set(None);
+            bluetooth.reconnect();
+        },
+    );
+
+    bluetooth
+}
+
+/// Options for [`use_web_bluetooth_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseWebBluetoothOptions {
+    /// Only devices that match one of these filters are shown in the chooser. If empty (the
+    /// default) all devices are shown.
+    #[builder(into)]
+    filters: Vec<BluetoothLeScanFilterInit>,
+
+    /// Names or UUIDs of the services you want to access that aren't part of the `filters`.
+    #[builder(into)]
+    optional_services: Vec<String>,
+
+    /// How many times to try to reconnect after the connection has been lost.
+    /// Defaults to `0` which disables auto-reconnect.
+    reconnect_limit: u64,
+
+    /// Time in ms to wait between reconnect attempts. Defaults to 3000.
+    reconnect_interval: u64,
+
+    /// Called when all reconnect attempts have failed.
+    on_reconnect_failed: Rc<dyn Fn()>,
+}
+
+impl Default for UseWebBluetoothOptions {
+    fn default() -> Self {
+        Self {
+            filters: vec![],
+            optional_services: vec![],
+            reconnect_limit: 0,
+            reconnect_interval: 3000,
+            on_reconnect_failed: Rc::new(|| {}),
+        }
+    }
+}
+
+struct RequestOptions {
+    filters: Vec<BluetoothLeScanFilterInit>,
+    optional_services: Vec<String>,
+}
+
+/// Return type of [`use_web_bluetooth`].
+#[derive(Clone, Copy)]
+pub struct UseWebBluetoothReturn {
+    /// Whether the Web Bluetooth API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// Whether a Bluetooth adapter is available
+    pub is_available: Signal<bool>,
+
+    /// The selected device. It's kept after a disconnect so you can `connect` to it again.
+    pub device: Signal<Option<BluetoothDevice>>,
+
+    /// The GATT server of the device while it's connected
+    pub server: Signal<Option<BluetoothRemoteGattServer>>,
+
+    /// Whether the GATT server of the device is connected
+    pub is_connected: Signal<bool>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseWebBluetoothError>>,
+
+    set_device: WriteSignal<Option<BluetoothDevice>>,
+    set_server: WriteSignal<Option<BluetoothRemoteGattServer>>,
+    set_error: WriteSignal<Option<UseWebBluetoothError>>,
+    request_options: StoredValue<RequestOptions>,
+    explicitly_disconnected: StoredValue<bool>,
+    retried: StoredValue<u64>,
+    reconnect_limit: u64,
+    reconnect_interval: u64,
+    on_reconnect_failed: StoredValue<Rc<dyn Fn()>>,
+}
+
+impl UseWebBluetoothReturn {
+    /// Shows the browser's device chooser and connects to the selected device.
+    pub async fn request_device(&self) -> Result<BluetoothDevice, UseWebBluetoothError> {
+        let navigator_bluetooth = self.navigator_bluetooth()?;
+
+        let options = web_sys::RequestDeviceOptions::new();
+        self.request_options.with_value(|request_options| {
+            if request_options.filters.is_empty() {
+                options.set_accept_all_devices(true);
+            } else {
+                options.set_filters(&request_options.filters);
+            }
+
+            let optional_services = request_options
+                .optional_services
+                .iter()
+                .map(|service| js_sys::JsString::from(service.as_str()))
+                .collect::<Vec<_>>();
+            options.set_optional_services(&optional_services);
+        });
+
+        let device = js_fut!(navigator_bluetooth.request_device(&options))
+            .await
+            .map_err(|err| self.fail(UseWebBluetoothError::RequestDevice(err)))?;
+
+        if let Some(server) = self.server.get_untracked() {
+            self.explicitly_disconnected.set_value(true);
+            server.disconnect();
+        }
+
+        self.set_device.set(Some(device.clone()));
+        self.connect().await?;
+
+        Ok(device)
+    }
+
+    /// Connects to the GATT server of the remembered device.
+    pub async fn connect(&self) -> Result<BluetoothRemoteGattServer, UseWebBluetoothError> {
+        self.navigator_bluetooth()?;
+
+        let gatt = self
+            .device
+            .get_untracked()
+            .ok_or(UseWebBluetoothError::NoDevice)
+            .and_then(|device| device.gatt().ok_or(UseWebBluetoothError::NotSupported))
+            .map_err(|err| self.fail(err))?;
+
+        self.explicitly_disconnected.set_value(false);
+
+        let server = js_fut!(gatt.connect())
+            .await
+            .map_err(|err| self.fail(UseWebBluetoothError::Connect(err)))?;
+
+        self.retried.set_value(0);
+        self.set_error.set(None);
+        self.set_server.set(Some(server.clone()));
+
+        Ok(server)
+    }
+
+    /// Disconnects from the GATT server. The device is remembered and not reconnected
+    /// automatically.
+    pub fn disconnect(&self) {
+        self.explicitly_disconnected.set_value(true);
+
+        if let Some(server) = self.server.get_untracked() {
+            server.disconnect();
+        }
+
+        self.set_server.set(None);
+    }
+
+    /// Returns the characteristic of the primary service of the connected device.
+    /// Both can be given as name like `"heart_rate"` or as full UUID.
+    pub async fn characteristic(
+        &self,
+        service: &str,
+        characteristic: &str,
+    ) -> Result<BluetoothRemoteGattCharacteristic, UseWebBluetoothError> {
+        let server = self
+            .server
+            .get_untracked()
+            .ok_or(UseWebBluetoothError::NotConnected)?;
+
+        let service = js_fut!(server.get_primary_service_with_str(service))
+            .await
+            .map_err(|err| self.fail(UseWebBluetoothError::Gatt(err)))?;
+
+        js_fut!(service.get_characteristic_with_str(characteristic))
+            .await
+            .map_err(|err| self.fail(UseWebBluetoothError::Gatt(err)))
+    }
+
+    /// Reads the current value of a characteristic.
+    pub async fn read_value(
+        &self,
+        service: &str,
+        characteristic: &str,
+    ) -> Result<Vec<u8>, UseWebBluetoothError> {
+        let characteristic = self.characteristic(service, characteristic).await?;
+
+        let value = js_fut!(characteristic.read_value())
+            .await
+            .map_err(|err| self.fail(UseWebBluetoothError::Gatt(err)))?;
+
+        Ok(data_view_to_vec(&value))
+    }
+
+    /// Writes `value` to a characteristic.
+    pub async fn write_value(
+        &self,
+        service: &str,
+        characteristic: &str,
+        value: &[u8],
+    ) -> Result<(), UseWebBluetoothError> {
+        let characteristic = self.characteristic(service, characteristic).await?;
+
+        let promise = characteristic
+            .write_value_with_u8_slice(value)
+            .map_err(|err| self.fail(UseWebBluetoothError::Gatt(err)))?;
+
+        js_fut!(promise)
+            .await
+            .map_err(|err| self.fail(UseWebBluetoothError::Gatt(err)))?;
+
+        Ok(())
+    }
+
+    /// Starts notifications of a characteristic and returns a signal with the latest value
+    /// received by a `characteristicvaluechanged` event. Notifications are started again
+    /// whenever the device is (re)connected.
+    pub fn notifications(
+        &self,
+        service: impl Into<String>,
+        characteristic: impl Into<String>,
+    ) -> Signal<Option<Vec<u8>>> {
+        let service = service.into();
+        let characteristic = characteristic.into();
+
+        let (value, set_value) = create_signal(None::<Vec<u8>>);
+        let (target, set_target) = create_signal(None::<BluetoothRemoteGattCharacteristic>);
+
+        let bluetooth = *self;
+
+        let _ = watch(
+            move || bluetooth.server.get(),
+            move |server, _, _| {
+                set_target.set(None);
+
+                if server.is_none() {
+                    return;
+                }
+
+                let service = service.clone();
+                let characteristic = characteristic.clone();
+
+                spawn_local(async move {
+                    let Ok(characteristic) =
+                        bluetooth.characteristic(&service, &characteristic).await
+                    else {
+                        return;
+                    };
+
+                    match js_fut!(characteristic.start_notifications()).await {
+                        Ok(characteristic) => set_target.set(Some(characteristic)),
+                        Err(err) => {
+                            bluetooth.fail(UseWebBluetoothError::Gatt(err));
+                        }
+                    }
+                });
+            },
+            true,
+        );
+
+        let _ = use_event_listener(
+            target,
+            ev::Custom::<web_sys::Event>::new("characteristicvaluechanged"),
+            move |event| {
+                let value = event
+                    .target()
+                    .and_then(|target| {
+                        target
+                            .unchecked_into::<BluetoothRemoteGattCharacteristic>()
+                            .value()
+                    })
+                    .map(|value| data_view_to_vec(&value));
+
+                set_value.set(value);
+            },
+        );
+
+        value.into()
+    }
+
+    fn reconnect(&self) {
+        if self.explicitly_disconnected.get_value() || self.reconnect_limit == 0 {
+            return;
+        }
+
+        let retried = self.retried.get_value() + 1;
+        self.retried.set_value(retried);
+
+        if retried <= self.reconnect_limit {
+            let bluetooth = *self;
+
+            set_timeout(
+                move || {
+                    spawn_local(async move {
+                        if bluetooth.connect().await.is_err() {
+                            bluetooth.reconnect();
+                        }
+                    });
+                },
+                Duration::from_millis(self.reconnect_interval),
+            );
+        } else {
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            (self.on_reconnect_failed.get_value())();
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        }
+    }
+
+    fn navigator_bluetooth(&self) -> Result<web_sys::Bluetooth, UseWebBluetoothError> {
+        if !self.is_supported.get_untracked() {
+            return Err(UseWebBluetoothError::NotSupported);
+        }
+
+        window()
+            .navigator()
+            .bluetooth()
+            .ok_or(UseWebBluetoothError::NotSupported)
+    }
+
+    fn fail(&self, err: UseWebBluetoothError) -> UseWebBluetoothError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// Errors of [`use_web_bluetooth`].
+#[derive(Error, Debug, Clone)]
+pub enum UseWebBluetoothError {
+    #[error("Web Bluetooth is not supported")]
+    NotSupported,
+    #[error("no device has been requested")]
+    NoDevice,
+    #[error("the device isn't connected")]
+    NotConnected,
+    #[error("requesting a device failed")]
+    RequestDevice(JsValue),
+    #[error("connecting to the GATT server failed")]
+    Connect(JsValue),
+    #[error("GATT operation failed")]
+    Gatt(JsValue),
+}
@@ -0,0 +1,314 @@
+use crate::{js, js_fut, use_event_listener, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{MidiAccess, MidiInput, MidiMessageEvent, MidiOutput};
+
+/// Reactive [Web MIDI API](https://developer.mozilla.org/en-US/docs/Web/API/Web_MIDI_API).
+/// Requests access to the MIDI devices of the user, keeps signals of the available inputs and
+/// outputs up to date and provides the messages of inputs as signals.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_web_midi;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let midi = use_web_midi();
+///
+/// let keyboard = midi.messages("input-0");
+///
+/// let play = move |_| {
+///     // note on, middle C, velocity 127
+///     let _ = midi.send("output-0", &[0x90, 60, 0x7f]);
+///     // note off half a second later
+///     let now = window().performance().map(|performance| performance.now()).unwrap_or_default();
+///     let _ = midi.send_with_timestamp("output-0", &[0x80, 60, 0x40], now + 500.0);
+/// };
+///
+/// view! {
+///     <ul>
+///         <For each=move || midi.inputs.get() key=|input| input.id() let:input>
+///             <li>{input.name()}</li>
+///         </For>
+///     </ul>
+///     <p>"Last message: " {move || format!("{:?}", keyboard.get().map(|message| message.data))}</p>
+///     <button on:click=play>"Play"</button>
+/// }
+/// # }
+/// ```
+///
+/// The inputs and outputs are identified by the `id` of their
+/// [`MIDIPort`](https://developer.mozilla.org/en-US/docs/Web/API/MIDIPort). Both lists are updated
+/// whenever a device is connected or disconnected.
+///
+/// Access is requested right away by default. Set the option `immediate` to `false` and call
+/// `request_access` later, for example from a click handler, if you don't want the permission
+/// prompt to show up on page load.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_web_midi_with_options, UseWebMidiOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let midi = use_web_midi_with_options(UseWebMidiOptions::default().immediate(false).sysex(true));
+///
+/// let enable = move |_| {
+///     spawn_local(async move {
+///         let _ = midi.request_access().await;
+///     });
+/// };
+/// #
+/// # view! { <button on:click=enable>"Enable MIDI"</button> }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server `inputs` and `outputs` are always empty and all operations return
+/// `UseWebMidiError::NotSupported`.
+pub fn use_web_midi() -> UseWebMidiReturn {
+    use_web_midi_with_options(UseWebMidiOptions::default())
+}
+
+/// Version of [`use_web_midi`] that takes a `UseWebMidiOptions`. See [`use_web_midi`] for how to use.
+pub fn use_web_midi_with_options(options: UseWebMidiOptions) -> UseWebMidiReturn {
+    let UseWebMidiOptions {
+        sysex,
+        software,
+        immediate,
+    } = options;
+
+    let is_supported = use_supported(|| js!("requestMIDIAccess" in &window().navigator()));
+
+    let (access, set_access) = create_signal(None::<MidiAccess>);
+    let (inputs, set_inputs) = create_signal(Vec::<MidiInput>::new());
+    let (outputs, set_outputs) = create_signal(Vec::<MidiOutput>::new());
+    let (error, set_error) = create_signal(None::<UseWebMidiError>);
+
+    let midi = UseWebMidiReturn {
+        is_supported,
+        access: access.into(),
+        inputs: inputs.into(),
+        outputs: outputs.into(),
+        error: error.into(),
+        set_access,
+        set_error,
+        sysex,
+        software,
+    };
+
+    let update_ports = move || {
+        if let Some(access) = access.get_untracked() {
+            set_inputs.set(
+                access
+                    .inputs()
+                    .values()
+                    .into_iter()
+                    .filter_map(|input| input.ok())
+                    .map(JsCast::unchecked_into)
+                    .collect(),
+            );
+            set_outputs.set(
+                access
+                    .outputs()
+                    .values()
+                    .into_iter()
+                    .filter_map(|output| output.ok())
+                    .map(JsCast::unchecked_into)
+                    .collect(),
+            );
+        }
+    };
+
+    let _ = watch(move || access.get(), move |_, _, _| update_ports(), false);
+
+    let _ = use_event_listener(
+        access,
+        ev::Custom::<web_sys::Event>::new("statechange"),
+        move |_| update_ports(),
+    );
+
+    if immediate && is_supported.get_untracked() {
+        spawn_local(async move {
+            let _ = midi.request_access().await;
+        });
+    }
+
+    midi
+}
+
+/// Options for [`use_web_midi_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseWebMidiOptions {
+    /// Request the permission to send and receive system exclusive messages. Defaults to `false`.
+    sysex: bool,
+
+    /// Request access to software synthesizers of the system as well. Defaults to `false`.
+    software: bool,
+
+    /// Request MIDI access when the function is called. If `false` you have to call
+    /// `request_access` yourself. Defaults to `true`.
+    immediate: bool,
+}
+
+impl Default for UseWebMidiOptions {
+    fn default() -> Self {
+        Self {
+            sysex: false,
+            software: false,
+            immediate: true,
+        }
+    }
+}
+
+/// A message received from a MIDI input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MidiMessage {
+    /// The raw MIDI data including the status byte
+    pub data: Vec<u8>,
+
+    /// The time in ms when the message was received, relative to the time origin of the page
+    /// like the value returned by `performance.now()`
+    pub timestamp: f64,
+}
+
+/// Return type of [`use_web_midi`].
+#[derive(Clone, Copy)]
+pub struct UseWebMidiReturn {
+    /// Whether the Web MIDI API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The `MIDIAccess` object once access has been granted
+    pub access: Signal<Option<MidiAccess>>,
+
+    /// The available MIDI inputs
+    pub inputs: Signal<Vec<MidiInput>>,
+
+    /// The available MIDI outputs
+    pub outputs: Signal<Vec<MidiOutput>>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseWebMidiError>>,
+
+    set_access: WriteSignal<Option<MidiAccess>>,
+    set_error: WriteSignal<Option<UseWebMidiError>>,
+    sysex: bool,
+    software: bool,
+}
+
+impl UseWebMidiReturn {
+    /// Requests access to the MIDI devices. This shows a permission prompt in some browsers.
+    pub async fn request_access(&self) -> Result<MidiAccess, UseWebMidiError> {
+        if !self.is_supported.get_untracked() {
+            return Err(UseWebMidiError::NotSupported);
+        }
+
+        let options = web_sys::MidiOptions::new();
+        options.set_sysex(self.sysex);
+        options.set_software(self.software);
+
+        let access = window()
+            .navigator()
+            .request_midi_access_with_options(&options)
+            .map_err(|err| self.fail(UseWebMidiError::RequestAccess(err)))?;
+
+        let access: MidiAccess = js_fut!(access)
+            .await
+            .map_err(|err| self.fail(UseWebMidiError::RequestAccess(err)))?
+            .unchecked_into();
+
+        self.set_error.set(None);
+        self.set_access.set(Some(access.clone()));
+
+        Ok(access)
+    }
+
+    /// Returns a signal with the latest message received by the input with the given `id`. It
+    /// starts receiving again when the input is reconnected.
+    pub fn messages(&self, id: impl Into<String>) -> Signal<Option<MidiMessage>> {
+        let id = id.into();
+        let inputs = self.inputs;
+
+        let (message, set_message) = create_signal(None::<MidiMessage>);
+
+        let input = Signal::derive(move || {
+            inputs.with(|inputs| inputs.iter().find(|input| input.id() == id).cloned())
+        });
+
+        let _ = use_event_listener(
+            input,
+            ev::Custom::<MidiMessageEvent>::new("midimessage"),
+            move |event| {
+                if let Ok(data) = event.data() {
+                    set_message.set(Some(MidiMessage {
+                        data,
+                        timestamp: event.time_stamp(),
+                    }));
+                }
+            },
+        );
+
+        message.into()
+    }
+
+    /// Sends `data` to the output with the given `id` right away.
+    pub fn send(&self, id: &str, data: &[u8]) -> Result<(), UseWebMidiError> {
+        let output = self.output(id)?;
+
+        output
+            .send(&js_sys::Uint8Array::from(data))
+            .map_err(|err| self.fail(UseWebMidiError::Send(err)))
+    }
+
+    /// Sends `data` to the output with the given `id` at `timestamp`. The timestamp is in ms
+    /// relative to the time origin of the page like the value returned by `performance.now()`.
+    pub fn send_with_timestamp(
+        &self,
+        id: &str,
+        data: &[u8],
+        timestamp: f64,
+    ) -> Result<(), UseWebMidiError> {
+        let output = self.output(id)?;
+
+        output
+            .send_with_timestamp(&js_sys::Uint8Array::from(data), timestamp)
+            .map_err(|err| self.fail(UseWebMidiError::Send(err)))
+    }
+
+    fn output(&self, id: &str) -> Result<MidiOutput, UseWebMidiError> {
+        let access = self
+            .access
+            .get_untracked()
+            .ok_or(UseWebMidiError::NoAccess)?;
+
+        access
+            .outputs()
+            .get(id)
+            .ok_or_else(|| UseWebMidiError::UnknownPort(id.to_string()))
+    }
+
+    fn fail(&self, err: UseWebMidiError) -> UseWebMidiError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// Errors of [`use_web_midi`].
+#[derive(Error, Debug, Clone)]
+pub enum UseWebMidiError {
+    #[error("the Web MIDI API is not supported")]
+    NotSupported,
+    #[error("MIDI access hasn't been granted yet")]
+    NoAccess,
+    #[error("there is no MIDI port with the id {0}")]
+    UnknownPort(String),
+    #[error("requesting MIDI access failed")]
+    RequestAccess(JsValue),
+    #[error("sending the MIDI message failed")]
+    Send(JsValue),
+}
@@ -0,0 +1,369 @@
+use crate::utils::data_view_to_vec;
+use crate::{js, js_fut, use_event_listener, use_permission, use_supported, PermissionState};
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Reactive [Web NFC API](https://developer.mozilla.org/en-US/docs/Web/API/Web_NFC_API).
+/// Reads and writes NFC tags with an
+/// [`NDEFReader`](https://developer.mozilla.org/en-US/docs/Web/API/NDEFReader).
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_web_nfc;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let nfc = use_web_nfc();
+///
+/// let scan = move |_| {
+///     spawn_local(async move {
+///         let _ = nfc.scan().await;
+///     });
+/// };
+///
+/// let write = move |_| {
+///     spawn_local(async move {
+///         let _ = nfc.write_text("Hello NFC!").await;
+///     });
+/// };
+///
+/// view! {
+///     <Show
+///         when=move || nfc.is_supported.get()
+///         fallback=|| view! { <p>"Your device doesn't support Web NFC"</p> }
+///     >
+///         <button on:click=scan disabled=nfc.is_scanning>"Scan"</button>
+///         <button on:click=write>"Write"</button>
+///         <button on:click=move |_| nfc.stop()>"Stop"</button>
+///         <p>"Permission: " {move || nfc.permission.get().to_string()}</p>
+///         <For
+///             each=move || nfc.last_message.get().map(|message| message.records).unwrap_or_default()
+///             key=|record| record.data.clone()
+///             let:record
+///         >
+///             <p>{record.record_type.clone()} ": " {record.text()}</p>
+///         </For>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// `scan` and the write methods have to be called from a user gesture like a click the first
+/// time because they ask for permission. While scanning every tag that is tapped against the
+/// device is available in `last_message`. Scanning stops when `stop` is called or when the
+/// component is cleaned up.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `last_message` is always `None` and all operations return
+/// `UseWebNfcError::NotSupported`.
+pub fn use_web_nfc() -> UseWebNfcReturn {
+    let is_supported = use_supported(|| js!("NDEFReader" in &window()));
+    let permission = use_permission("nfc");
+
+    let (reader, set_reader) = create_signal(None::<NdefReader>);
+    let (last_message, set_last_message) = create_signal(None::<NdefMessage>);
+    let (error, set_error) = create_signal(None::<UseWebNfcError>);
+
+    let nfc = UseWebNfcReturn {
+        is_supported,
+        is_scanning: Signal::derive(move || reader.with(Option::is_some)),
+        last_message: last_message.into(),
+        permission,
+        error: error.into(),
+        set_reader,
+        set_error,
+        abort_controller: store_value(None),
+    };
+
+    let _ = use_event_listener(
+        reader,
+        ev::Custom::<web_sys::Event>::new("reading"),
+        move |event| set_last_message.set(Some(NdefMessage::from_event(&event))),
+    );
+
+    let _ = use_event_listener(
+        reader,
+        ev::Custom::<web_sys::Event>::new("readingerror"),
+        move |_| set_error.set(Some(UseWebNfcError::Read)),
+    );
+
+    on_cleanup(move || nfc.stop());
+
+    nfc
+}
+
+/// Return type of [`use_web_nfc`].
+#[derive(Clone, Copy)]
+pub struct UseWebNfcReturn {
+    /// Whether the Web NFC API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// Whether the reader is scanning for tags
+    pub is_scanning: Signal<bool>,
+
+    /// The message of the last tag that has been read
+    pub last_message: Signal<Option<NdefMessage>>,
+
+    /// The state of the `"nfc"` permission
+    pub permission: Signal<PermissionState>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseWebNfcError>>,
+
+    set_reader: WriteSignal<Option<NdefReader>>,
+    set_error: WriteSignal<Option<UseWebNfcError>>,
+    abort_controller: StoredValue<Option<web_sys::AbortController>>,
+}
+
+impl UseWebNfcReturn {
+    /// Starts scanning for NFC tags. Does nothing if already scanning.
+    pub async fn scan(&self) -> Result<(), UseWebNfcError> {
+        if self.is_scanning.get_untracked() {
+            return Ok(());
+        }
+
+        let reader = self.new_reader()?;
+
+        let controller =
+            web_sys::AbortController::new().map_err(|err| self.fail(UseWebNfcError::Scan(err)))?;
+
+        let options = js_sys::Object::new();
+        js!(options["signal"] = controller.signal());
+
+        js_fut!(reader.scan(&options))
+            .await
+            .map_err(|err| self.fail(UseWebNfcError::Scan(err)))?;
+
+        self.abort_controller.set_value(Some(controller));
+        self.set_error.set(None);
+        self.set_reader.set(Some(reader));
+
+        Ok(())
+    }
+
+    /// Stops scanning.
+    pub fn stop(&self) {
+        if let Some(controller) = self.abort_controller.get_value() {
+            controller.abort();
+        }
+
+        self.abort_controller.set_value(None);
+        self.set_reader.set(None);
+    }
+
+    /// Writes a single text record to the next tag that is tapped against the device.
+    pub async fn write_text(&self, text: &str) -> Result<(), UseWebNfcError> {
+        self.write_message(&JsValue::from_str(text)).await
+    }
+
+    /// Writes `records` to the next tag that is tapped against the device.
+    pub async fn write(&self, records: &[NdefRecord]) -> Result<(), UseWebNfcError> {
+        let message = js_sys::Object::new();
+        let records = records
+            .iter()
+            .map(NdefRecord::to_init)
+            .collect::<js_sys::Array>();
+        js!(message["records"] = records);
+
+        self.write_message(&message).await
+    }
+
+    async fn write_message(&self, message: &JsValue) -> Result<(), UseWebNfcError> {
+        let reader = self.new_reader()?;
+
+        let promise = reader
+            .write(message)
+            .map_err(|err| self.fail(UseWebNfcError::Write(err)))?;
+
+        js_fut!(promise)
+            .await
+            .map_err(|err| self.fail(UseWebNfcError::Write(err)))?;
+
+        Ok(())
+    }
+
+    fn new_reader(&self) -> Result<NdefReader, UseWebNfcError> {
+        if !self.is_supported.get_untracked() {
+            return Err(UseWebNfcError::NotSupported);
+        }
+
+        NdefReader::new().map_err(|err| self.fail(UseWebNfcError::Scan(err)))
+    }
+
+    fn fail(&self, err: UseWebNfcError) -> UseWebNfcError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// A message read from an NFC tag.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NdefMessage {
+    /// The serial number of the tag or an empty string if it isn't available
+    pub serial_number: String,
+
+    /// The records of the message
+    pub records: Vec<NdefRecord>,
+}
+
+impl NdefMessage {
+    fn from_event(event: &web_sys::Event) -> Self {
+        let serial_number = js!(event["serialNumber"])
+            .ok()
+            .and_then(|serial_number| serial_number.as_string())
+            .unwrap_or_default();
+
+        let records = js!(event["message"])
+            .and_then(|message| js!(message["records"]))
+            .map(|records| {
+                records
+                    .unchecked_into::<js_sys::Array>()
+                    .iter()
+                    .map(|record| NdefRecord::from_js(&record))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            serial_number,
+            records,
+        }
+    }
+}
+
+/// A record of an NFC message. See
+/// [NDEFRecord](https://developer.mozilla.org/en-US/docs/Web/API/NDEFRecord) for the meaning of
+/// the fields.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NdefRecord {
+    /// The type of the record like `"text"`, `"url"` or `"mime"`
+    pub record_type: String,
+
+    /// The MIME type of `"mime"` records
+    pub media_type: Option<String>,
+
+    /// The id of the record
+    pub id: Option<String>,
+
+    /// The encoding of `"text"` records like `"utf-8"`
+    pub encoding: Option<String>,
+
+    /// The language of `"text"` records like `"en"`
+    pub lang: Option<String>,
+
+    /// The raw payload
+    pub data: Vec<u8>,
+}
+
+impl NdefRecord {
+    /// The decoded content of `"text"`, `"url"`, `"absolute-url"` and `text/*` MIME records.
+    /// `None` for all other records.
+    pub fn text(&self) -> Option<String> {
+        match self.record_type.as_str() {
+            "text" => Some(decode_text(&self.data, self.encoding.as_deref())),
+            "url" | "absolute-url" => Some(String::from_utf8_lossy(&self.data).into_owned()),
+            "mime"
+                if self
+                    .media_type
+                    .as_deref()
+                    .is_some_and(|media_type| media_type.starts_with("text/")) =>
+            {
+                Some(String::from_utf8_lossy(&self.data).into_owned())
+            }
+            _ => None,
+        }
+    }
+
+    fn from_js(record: &JsValue) -> Self {
+        let string = |name: &str| {
+            js_sys::Reflect::get(record, &name.into())
+                .ok()
+                .and_then(|value| value.as_string())
+        };
+
+        let data = js_sys::Reflect::get(record, &"data".into())
+            .ok()
+            .and_then(|data| data.dyn_into::<js_sys::DataView>().ok())
+            .map(|data| data_view_to_vec(&data))
+            .unwrap_or_default();
+
+        Self {
+            record_type: string("recordType").unwrap_or_default(),
+            media_type: string("mediaType"),
+            id: string("id"),
+            encoding: string("encoding"),
+            lang: string("lang"),
+            data,
+        }
+    }
+
+    fn to_init(&self) -> js_sys::Object {
+        let init = js_sys::Object::new();
+
+        js!(init["recordType"] = self.record_type.as_str());
+        js!(init["data"] = js_sys::Uint8Array::from(self.data.as_slice()));
+
+        for (name, value) in [
+            ("mediaType", &self.media_type),
+            ("id", &self.id),
+            ("encoding", &self.encoding),
+            ("lang", &self.lang),
+        ] {
+            if let Some(value) = value {
+                let _ = js_sys::Reflect::set(&init, &name.into(), &value.into());
+            }
+        }
+
+        init
+    }
+}
+
+fn decode_text(data: &[u8], encoding: Option<&str>) -> String {
+    let utf16 = |to_u16: fn([u8; 2]) -> u16| {
+        let units = data
+            .chunks_exact(2)
+            .map(|bytes| to_u16([bytes[0], bytes[1]]))
+            .collect::<Vec<_>>();
+        String::from_utf16_lossy(&units)
+    };
+
+    match encoding {
+        Some("utf-16be") => utf16(u16::from_be_bytes),
+        Some("utf-16" | "utf-16le") => utf16(u16::from_le_bytes),
+        _ => String::from_utf8_lossy(data).into_owned(),
+    }
+}
+
+/// Errors of [`use_web_nfc`].
+#[derive(Error, Debug, Clone)]
+pub enum UseWebNfcError {
+    #[error("Web NFC is not supported")]
+    NotSupported,
+    #[error("starting the scan failed")]
+    Scan(JsValue),
+    #[error("writing to the tag failed")]
+    Write(JsValue),
+    #[error("the tag couldn't be read")]
+    Read,
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Clone, Debug, PartialEq)]
+    #[wasm_bindgen(extends = web_sys::EventTarget, js_name = NDEFReader)]
+    type NdefReader;
+
+    #[wasm_bindgen(catch, constructor, js_class = NDEFReader)]
+    fn new() -> Result<NdefReader, JsValue>;
+
+    #[wasm_bindgen(method, js_class = NDEFReader)]
+    fn scan(this: &NdefReader, options: &js_sys::Object) -> js_sys::Promise;
+
+    #[wasm_bindgen(catch, method, js_class = NDEFReader)]
+    fn write(this: &NdefReader, message: &JsValue) -> Result<js_sys::Promise, JsValue>;
+}
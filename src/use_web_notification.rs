@@ -1,4 +1,4 @@
-use crate::{use_supported, use_window};
+use crate::{js, use_supported, use_window};
 use cfg_if::cfg_if;
 use default_struct_builder::DefaultBuilder;
 use leptos::*;
@@ -37,6 +37,41 @@ use std::rc::Rc;
 /// # }
 /// ```
 ///
+/// Call `close` to remove the notification once it's no longer relevant. This happens
+/// automatically when the page becomes visible again or the component is cleaned up.
+///
+/// ## Actions
+///
+/// Notifications can contain buttons. Browsers only support them for notifications that are
+/// shown by a service worker so the clicks have to be handled in its `notificationclick` event.
+/// `on_click`, `on_close` and `on_show` aren't called for these notifications. If showing one
+/// fails, for example because no service worker is active, `on_error` is called with an
+/// `ErrorEvent` whose `error` is the reason.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_web_notification_with_options, UseWebNotificationOptions, ShowOptions, NotificationAction};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let notification = use_web_notification_with_options(
+///     UseWebNotificationOptions::default()
+///         .title("New message")
+///         .tag("chat")
+///         .badge("/badge.png")
+///         .renotify(true)
+///         .actions(vec![
+///             NotificationAction::new("reply", "Reply"),
+///             NotificationAction::new("dismiss", "Dismiss").icon("/dismiss.png"),
+///         ]),
+/// );
+///
+/// (notification.show)(ShowOptions::default().body("Hi there!"));
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
 /// ## Server-Side Rendering
 ///
 /// This function is basically ignored on the server. You can safely call `show` but it will do nothing.
@@ -65,6 +100,7 @@ pub fn use_web_notification_with_options(
     } else {
         use crate::use_event_listener;
         use leptos::ev::visibilitychange;
+        use std::ops::Not;
         use wasm_bindgen::closure::Closure;
         use wasm_bindgen::JsCast;
 
@@ -148,8 +184,45 @@ pub fn use_web_notification_with_options(
                     let mut notification_options = web_sys::NotificationOptions::from(&options);
                     options_override.override_notification_options(&mut notification_options);
 
+                    let title = options_override.title.clone().unwrap_or(options.title);
+
+                    // Notifications with actions can only be shown by a service worker.
+                    let has_actions = options_override
+                        .actions
+                        .as_ref()
+                        .unwrap_or(&options.actions)
+                        .is_empty()
+                        .not();
+
+                    if has_actions {
+                        let tag = options_override.tag.or(options.tag);
+
+                        match show_persistent_notification(&title, &notification_options, tag).await {
+                            Ok(notification_value) => set_notification.set(notification_value),
+                            Err(err) => {
+                                let init = web_sys::ErrorEventInit::new();
+                                init.set_message("Showing the notification failed");
+                                init.set_error(&err);
+
+                                if let Ok(event) =
+                                    web_sys::ErrorEvent::new_with_event_init_dict("error", &init)
+                                {
+                                    #[cfg(debug_assertions)]
+                                    let prev = SpecialNonReactiveZone::enter();
+
+                                    (options.on_error)(event.into());
+
+                                    #[cfg(debug_assertions)]
+                                    SpecialNonReactiveZone::exit(prev);
+                                }
+                            }
+                        }
+
+                        return;
+                    }
+
                     let notification_value = web_sys::Notification::new_with_options(
-                        &options_override.title.unwrap_or(options.title),
+                        &title,
                         &notification_options,
                     )
                     .expect("Notification should be created");
@@ -227,10 +300,7 @@ impl From<NotificationDirection> for web_sys::NotificationDirection {
 /// See [MDN Docs](https://developer.mozilla.org/en-US/docs/Web/API/notification) for more info.
 ///
 /// The following implementations are missing:
-/// - `renotify`
-/// - `vibrate`  
-/// - `silent`
-/// - `image`
+/// - `vibrate`
 #[derive(DefaultBuilder, Clone)]
 #[cfg_attr(feature = "ssr", allow(dead_code))]
 pub struct UseWebNotificationOptions {
@@ -264,14 +334,37 @@ pub struct UseWebNotificationOptions {
     #[builder(into)]
     icon: Option<String>,
 
+    /// The URL of the image used to represent the notification when there isn't enough
+    /// space to display the notification itself, for example in the Android status bar.
+    #[builder(into)]
+    badge: Option<String>,
+
+    /// The URL of an image to be displayed as part of the notification.
+    #[builder(into)]
+    image: Option<String>,
+
     /// A boolean value indicating that a notification should remain active until the
     /// user clicks or dismisses it, rather than closing automatically.
     require_interaction: bool,
 
-    // /// A boolean value specifying whether the user should be notified after a new notification replaces an old one.
-    // /// The default is `false`, which means they won't be notified. If `true`, then `tag` also must be set.
-    // #[builder(into)]
-    // renotify: bool,
+    /// A boolean value specifying whether the user should be notified after a new notification replaces an old one.
+    /// The default is `false`, which means they won't be notified. If `true`, then `tag` also must be set.
+    renotify: bool,
+
+    /// A boolean value specifying whether the notification should be silent, i.e. no sounds
+    /// or vibrations should be issued regardless of the device settings.
+    /// The default is `false`.
+    silent: bool,
+
+    /// The buttons that are displayed in the notification.
+    ///
+    /// Browsers only support actions for notifications that are shown by a service worker.
+    /// If there are actions, the notification is therefore shown with the registration of the
+    /// active service worker, which has to handle the clicks in its `notificationclick` event.
+    /// `on_click`, `on_close` and `on_show` aren't called for such notifications. `on_error` is
+    /// only called with an `ErrorEvent` if the notification couldn't be shown.
+    actions: Vec<NotificationAction>,
+
     /// Called when the user clicks on displayed `Notification`.
     on_click: Rc<dyn Fn(web_sys::Event)>,
 
@@ -295,8 +388,12 @@ impl Default for UseWebNotificationOptions {
             language: None,
             tag: None,
             icon: None,
+            badge: None,
+            image: None,
             require_interaction: false,
-            // renotify: false,
+            renotify: false,
+            silent: false,
+            actions: Vec::new(),
             on_click: Rc::new(|_| {}),
             on_close: Rc::new(|_| {}),
             on_error: Rc::new(|_| {}),
@@ -312,7 +409,9 @@ impl From<&UseWebNotificationOptions> for web_sys::NotificationOptions {
         web_sys_options
             .dir(options.direction.into())
             .require_interaction(options.require_interaction);
-        // .renotify(options.renotify);
+
+        web_sys_options.set_renotify(options.renotify);
+        web_sys_options.set_silent(Some(options.silent));
 
         if let Some(body) = &options.body {
             web_sys_options.body(body);
@@ -330,6 +429,18 @@ impl From<&UseWebNotificationOptions> for web_sys::NotificationOptions {
             web_sys_options.tag(tag);
         }
 
+        if let Some(badge) = &options.badge {
+            web_sys_options.set_badge(badge);
+        }
+
+        if let Some(image) = &options.image {
+            web_sys_options.set_image(image);
+        }
+
+        if !options.actions.is_empty() {
+            web_sys_options.set_actions(&actions_to_js(&options.actions));
+        }
+
         web_sys_options
     }
 }
@@ -339,9 +450,7 @@ impl From<&UseWebNotificationOptions> for web_sys::NotificationOptions {
 /// See [MDN Docs](https://developer.mozilla.org/en-US/docs/Web/API/notification) for more info.
 ///
 /// The following implementations are missing:
-/// - `vibrate`  
-/// - `silent`
-/// - `image`
+/// - `vibrate`
 #[derive(DefaultBuilder, Default)]
 #[cfg_attr(feature = "ssr", allow(dead_code))]
 pub struct ShowOptions {
@@ -376,14 +485,34 @@ pub struct ShowOptions {
     #[builder(into)]
     icon: Option<String>,
 
+    /// The URL of the image used to represent the notification when there isn't enough
+    /// space to display the notification itself, for example in the Android status bar.
+    #[builder(into)]
+    badge: Option<String>,
+
+    /// The URL of an image to be displayed as part of the notification.
+    #[builder(into)]
+    image: Option<String>,
+
     /// A boolean value indicating that a notification should remain active until the
     /// user clicks or dismisses it, rather than closing automatically.
     #[builder(into)]
     require_interaction: Option<bool>,
-    // /// A boolean value specifying whether the user should be notified after a new notification replaces an old one.
-    // /// The default is `false`, which means they won't be notified. If `true`, then `tag` also must be set.
-    // #[builder(into)]
-    // renotify: Option<bool>,
+
+    /// A boolean value specifying whether the user should be notified after a new notification replaces an old one.
+    /// The default is `false`, which means they won't be notified. If `true`, then `tag` also must be set.
+    #[builder(into)]
+    renotify: Option<bool>,
+
+    /// A boolean value specifying whether the notification should be silent, i.e. no sounds
+    /// or vibrations should be issued regardless of the device settings.
+    #[builder(into)]
+    silent: Option<bool>,
+
+    /// The buttons that are displayed in the notification.
+    /// See [`UseWebNotificationOptions::actions`] for the limitations.
+    #[builder(into)]
+    actions: Option<Vec<NotificationAction>>,
 }
 
 #[cfg(not(feature = "ssr"))]
@@ -413,12 +542,98 @@ impl ShowOptions {
             options.tag(tag);
         }
 
-        // if let Some(renotify) = &self.renotify {
-        //     options.renotify(renotify);
-        // }
+        if let Some(badge) = &self.badge {
+            options.set_badge(badge);
+        }
+
+        if let Some(image) = &self.image {
+            options.set_image(image);
+        }
+
+        if let Some(renotify) = self.renotify {
+            options.set_renotify(renotify);
+        }
+
+        if let Some(silent) = self.silent {
+            options.set_silent(Some(silent));
+        }
+
+        if let Some(actions) = &self.actions {
+            options.set_actions(&actions_to_js(actions));
+        }
     }
 }
 
+/// A button that is displayed in a notification.
+/// See [MDN Docs](https://developer.mozilla.org/en-US/docs/Web/API/Notification/actions) for more info.
+#[derive(DefaultBuilder, Default, Clone, Debug, PartialEq, Eq)]
+pub struct NotificationAction {
+    /// The identifier of the action that is passed to the `notificationclick` event of the
+    /// service worker when the button is clicked
+    #[builder(into)]
+    action: String,
+
+    /// The text of the button
+    #[builder(into)]
+    title: String,
+
+    /// The URL of the icon of the button
+    #[builder(into)]
+    icon: Option<String>,
+}
+
+impl NotificationAction {
+    /// Creates an action with the given identifier and button text.
+    pub fn new(action: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            action: action.into(),
+            title: title.into(),
+            icon: None,
+        }
+    }
+}
+
+fn actions_to_js(actions: &[NotificationAction]) -> js_sys::Array {
+    actions
+        .iter()
+        .map(|action| {
+            let obj = js_sys::Object::new();
+            js!(obj["action"] = &action.action);
+            js!(obj["title"] = &action.title);
+            if let Some(icon) = &action.icon {
+                js!(obj["icon"] = icon);
+            }
+            obj
+        })
+        .collect()
+}
+
+/// Shows a notification with the registration of the active service worker and returns it.
+#[cfg(not(feature = "ssr"))]
+async fn show_persistent_notification(
+    title: &str,
+    options: &web_sys::NotificationOptions,
+    tag: Option<String>,
+) -> Result<Option<web_sys::Notification>, wasm_bindgen::JsValue> {
+    use crate::js_fut;
+    use wasm_bindgen::JsCast;
+
+    let registration = crate::utils::ready_service_worker_registration().await?;
+
+    js_fut!(registration.show_notification_with_options(title, options)?).await?;
+
+    let notifications = js_fut!(registration.get_notifications()?)
+        .await?
+        .unchecked_into::<js_sys::Array>();
+
+    // The notifications are sorted by the time they were created.
+    Ok(notifications
+        .iter()
+        .rev()
+        .map(JsCast::unchecked_into::<web_sys::Notification>)
+        .find(|notification| tag.is_none() || notification.tag() == tag))
+}
+
 /// Helper function to determine if browser supports notifications
 fn browser_supports_notifications() -> bool {
     if let Some(window) = use_window().as_ref() {
@@ -448,7 +663,7 @@ impl From<web_sys::NotificationPermission> for NotificationPermission {
             web_sys::NotificationPermission::Default => Self::Default,
             web_sys::NotificationPermission::Granted => Self::Granted,
             web_sys::NotificationPermission::Denied => Self::Denied,
-            web_sys::NotificationPermission::__Nonexhaustive => Self::Default,
+            _ => Self::Default,
         }
     }
 }
@@ -456,8 +671,7 @@ impl From<web_sys::NotificationPermission> for NotificationPermission {
 /// Use `window.Notification.requestPosition()`. Returns a future that should be awaited
 /// at least once before using [`use_web_notification`] to make sure
 /// you have the permission to send notifications.
-#[cfg(not(feature = "ssr"))]
-async fn request_web_notification_permission() -> NotificationPermission {
+pub(crate) async fn request_web_notification_permission() -> NotificationPermission {
     if let Ok(notification_permission) = web_sys::Notification::request_permission() {
         let _ = crate::js_fut!(notification_permission).await;
     }
@@ -0,0 +1,294 @@
+use crate::utils::JsCodec;
+use crate::{js, use_event_listener, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::JsValue;
+use web_sys::{ErrorEvent, MessageEvent, Worker, WorkerOptions, WorkerType};
+
+/// Reactive [Web Worker](https://developer.mozilla.org/en-US/docs/Web/API/Web_Workers_API).
+/// Starts a dedicated worker from a script and exchanges typed messages with it.
+///
+/// The worker is terminated automatically when the component is cleaned up.
+///
+/// ## Usage
+///
+/// Messages of type `T` are sent to the worker and messages of type `R` are received from it.
+/// Both are converted with the given [`JsCodec`].
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_web_worker, UseWebWorkerReturn};
+/// # use leptos_use::utils::SerdeWasmCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseWebWorkerReturn {
+///     message,
+///     post,
+///     error,
+///     ..
+/// } = use_web_worker::<Vec<u64>, u64, SerdeWasmCodec>("sum-worker.js");
+///
+/// view! {
+///     <button on:click=move |_| post(&vec![1, 2, 3])>"Sum"</button>
+///     <p>"Result: " {move || message.get()}</p>
+///     <Show when=move || error.with(Option::is_some)>
+///         <p>"The worker failed"</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// The worker script receives and posts plain JavaScript values:
+///
+/// ```js
+/// self.onmessage = (event) => {
+///   self.postMessage(event.data.reduce((a, b) => a + b, 0));
+/// };
+/// ```
+///
+/// ## Structured Clone
+///
+/// With [`JsValueCodec`](crate::utils::JsValueCodec) JavaScript values are passed through as they
+/// are. The browser copies them with the
+/// [structured clone algorithm](https://developer.mozilla.org/en-US/docs/Web/API/Web_Workers_API/Structured_clone_algorithm)
+/// which supports things like `Map`s, `Blob`s or `ImageData` that serde can't represent.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_web_worker_with_options, UseWebWorkerOptions};
+/// # use leptos_use::utils::JsValueCodec;
+/// # use wasm_bindgen::JsValue;
+/// # use web_sys::WorkerType;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let worker = use_web_worker_with_options::<JsValue, JsValue, JsValueCodec>(
+///     "worker.js",
+///     UseWebWorkerOptions::default()
+///         .worker_type(WorkerType::Module)
+///         .on_message(|data: &JsValue| leptos::logging::log!("{:?}", data)),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server no worker is started and `post` does nothing.
+pub fn use_web_worker<T, R, C>(
+    url: &str,
+) -> UseWebWorkerReturn<
+    R,
+    <C as JsCodec<R>>::Error,
+    impl Fn(&T) + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    T: 'static,
+    R: 'static,
+    C: JsCodec<T, Error = <C as JsCodec<R>>::Error> + JsCodec<R> + Default,
+{
+    use_web_worker_with_options(url, UseWebWorkerOptions::<R, C>::default())
+}
+
+/// Version of [`use_web_worker`] that takes `UseWebWorkerOptions`. See [`use_web_worker`] for how to use.
+pub fn use_web_worker_with_options<T, R, C>(
+    url: &str,
+    options: UseWebWorkerOptions<R, C>,
+) -> UseWebWorkerReturn<
+    R,
+    <C as JsCodec<R>>::Error,
+    impl Fn(&T) + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    T: 'static,
+    R: 'static,
+    C: JsCodec<T, Error = <C as JsCodec<R>>::Error> + JsCodec<R>,
+{
+    let UseWebWorkerOptions {
+        codec,
+        worker_type,
+        name,
+        on_message,
+        on_error,
+    } = options;
+
+    let is_supported = use_supported(|| js!("Worker" in &window()));
+
+    let (worker, set_worker) = create_signal(None::<Worker>);
+    let (message, set_message) = create_signal(None::<R>);
+    let (error, set_error) = create_signal(None::<UseWebWorkerError<<C as JsCodec<R>>::Error>>);
+
+    if is_supported.get_untracked() {
+        let worker_options = WorkerOptions::new();
+        worker_options.set_type(worker_type);
+        if let Some(name) = &name {
+            worker_options.set_name(name);
+        }
+
+        match Worker::new_with_options(url, &worker_options) {
+            Ok(worker) => set_worker.set(Some(worker)),
+            Err(err) => set_error.set(Some(UseWebWorkerError::Create(err))),
+        }
+    }
+
+    let _ = use_event_listener(worker, ev::message, {
+        let codec = codec.clone();
+
+        move |event| match JsCodec::<R>::decode(&codec, event.data()) {
+            Ok(val) => {
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                on_message(&val);
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+
+                set_message.set(Some(val));
+            }
+            Err(err) => set_error.set(Some(UseWebWorkerError::Decode(err))),
+        }
+    });
+
+    let _ = use_event_listener(
+        worker,
+        ev::Custom::<MessageEvent>::new("messageerror"),
+        move |event| set_error.set(Some(UseWebWorkerError::MessageEvent(event))),
+    );
+
+    let _ = use_event_listener(
+        worker,
+        ev::Custom::<ErrorEvent>::new("error"),
+        move |event| {
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_error(event.clone());
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+
+            set_error.set(Some(UseWebWorkerError::Worker(event)));
+        },
+    );
+
+    let post = move |val: &T| {
+        if let Some(worker) = worker.get_untracked() {
+            match JsCodec::<T>::encode(&codec, val) {
+                Ok(data) => {
+                    worker
+                        .post_message(&data)
+                        .map_err(|err| set_error.set(Some(UseWebWorkerError::PostMessage(err))))
+                        .ok();
+                }
+                Err(err) => set_error.set(Some(UseWebWorkerError::Encode(err))),
+            }
+        }
+    };
+
+    let terminate = move || {
+        if let Some(Some(worker)) = worker.try_get_untracked() {
+            worker.terminate();
+            set_worker.set(None);
+        }
+    };
+
+    on_cleanup(terminate);
+
+    UseWebWorkerReturn {
+        is_supported,
+        worker: worker.into(),
+        message: message.into(),
+        error: error.into(),
+        post,
+        terminate,
+    }
+}
+
+/// Options for [`use_web_worker_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseWebWorkerOptions<R, C>
+where
+    R: 'static,
+    C: JsCodec<R>,
+{
+    /// Encodes sent and decodes received messages.
+    #[builder(keep_type)]
+    codec: C,
+
+    /// Whether the script is a classic script or an ES module. Defaults to `WorkerType::Classic`.
+    worker_type: WorkerType,
+
+    /// A name for the worker that is shown in the developer tools. Defaults to `None`.
+    #[builder(into)]
+    name: Option<String>,
+
+    /// Called for every successfully decoded message from the worker.
+    #[builder(keep_type)]
+    on_message: Rc<dyn Fn(&R)>,
+
+    /// Called when an uncaught error occurs in the worker or the script can't be loaded.
+    on_error: Rc<dyn Fn(ErrorEvent)>,
+}
+
+impl<R, C: JsCodec<R> + Default> Default for UseWebWorkerOptions<R, C> {
+    fn default() -> Self {
+        Self {
+            codec: C::default(),
+            worker_type: WorkerType::Classic,
+            name: None,
+            on_message: Rc::new(|_| {}),
+            on_error: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// Return type of [`use_web_worker`].
+#[derive(Clone)]
+pub struct UseWebWorkerReturn<R, Err, PostFn, TerminateFn>
+where
+    R: 'static,
+    Err: 'static,
+    PostFn: Clone + 'static,
+    TerminateFn: Fn() + Clone + 'static,
+{
+    /// `true` if this browser supports web workers.
+    pub is_supported: Signal<bool>,
+
+    /// The worker that is wrapped by this function. `None` after it has been terminated.
+    pub worker: Signal<Option<Worker>>,
+
+    /// Latest message received from the worker
+    pub message: Signal<Option<R>>,
+
+    /// Latest error
+    pub error: Signal<Option<UseWebWorkerError<Err>>>,
+
+    /// Sends a message to the worker
+    pub post: PostFn,
+
+    /// Stops the worker immediately
+    pub terminate: TerminateFn,
+}
+
+#[derive(Debug, Error, Clone)]
+pub enum UseWebWorkerError<Err> {
+    #[error("failed to create worker")]
+    Create(JsValue),
+    #[error("worker error")]
+    Worker(ErrorEvent),
+    #[error("failed to post message")]
+    PostMessage(JsValue),
+    #[error("worker message error")]
+    MessageEvent(MessageEvent),
+    #[error("failed to encode value")]
+    Encode(Err),
+    #[error("failed to decode value")]
+    Decode(Err),
+}
@@ -0,0 +1,501 @@
+use crate::utils::JsCodec;
+use crate::{js, use_event_listener, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::leptos_dom::helpers::TimeoutHandle;
+use leptos::*;
+use std::time::Duration;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Blob, BlobPropertyBag, ErrorEvent, Url, Worker, WorkerOptions, WorkerType};
+
+/// Runs a function in a [Web Worker](https://developer.mozilla.org/en-US/docs/Web/API/Web_Workers_API)
+/// so that heavy computations don't block the main thread.
+///
+/// The worker script is generated and loaded from a `Blob` URL. Every call starts a new worker
+/// that is terminated once the function has returned.
+///
+/// ## Usage
+///
+/// The function can be an exported function of a wasm binary. Export it with `#[wasm_bindgen]`
+/// and pass the URL of the JavaScript module that `wasm-bindgen` generated for the binary (built
+/// with `--target web`) together with the name of the function. See
+/// [Worker crate](#worker-crate) below for how to build it.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_web_worker_fn, UseWebWorkerFnReturn, WebWorkerFn, WebWorkerFnStatus};
+/// # use leptos_use::utils::SerdeWasmCodec;
+/// # use wasm_bindgen::prelude::*;
+/// #
+/// #[wasm_bindgen]
+/// pub fn fibonacci(n: u32) -> u64 {
+///     if n < 2 { n as u64 } else { fibonacci(n - 1) + fibonacci(n - 2) }
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseWebWorkerFnReturn {
+///     status,
+///     result,
+///     run,
+///     terminate,
+///     ..
+/// } = use_web_worker_fn::<u32, u64, SerdeWasmCodec>(WebWorkerFn::wasm_export(
+///     "/worker/my_worker.js",
+///     "fibonacci",
+/// ));
+///
+/// view! {
+///     <button on:click=move |_| run(&40)>"Calculate"</button>
+///     <button on:click=move |_| terminate()>"Cancel"</button>
+///     <Show
+///         when=move || status.get() != WebWorkerFnStatus::Running
+///         fallback=|| view! { <p>"Calculating..."</p> }
+///     >
+///         <p>"Result: " {move || result.get()}</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// Alternatively the source of a JavaScript function can be given. Scripts it depends on can be
+/// loaded with `dependencies`.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_web_worker_fn_with_options, UseWebWorkerFnOptions, WebWorkerFn};
+/// # use leptos_use::utils::SerdeWasmCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let sort = use_web_worker_fn_with_options::<Vec<String>, Vec<String>, SerdeWasmCodec>(
+///     WebWorkerFn::js("(words) => _.sortBy(words)"),
+///     UseWebWorkerFnOptions::default()
+///         .dependencies(vec!["https://cdn.jsdelivr.net/npm/lodash/lodash.min.js".to_string()])
+///         .timeout(Some(5000)),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// The function receives the argument of `run` and may return a promise. Calling `run` while
+/// the function is still running cancels the previous call. `terminate` cancels it without
+/// starting a new one and sets the `status` back to `WebWorkerFnStatus::Pending`.
+///
+/// ## Worker crate
+///
+/// For `WebWorkerFn::WasmExport` the worker imports the module and awaits its default `init()`
+/// before it calls the function. Like every initialization of a `wasm-bindgen` module this runs
+/// the `#[wasm_bindgen(start)]` function of the binary. The start function of a Leptos app
+/// mounts or hydrates the app, which fails in a worker because it has no `document`. So don't
+/// export the function from your app but from a small crate without a start function:
+///
+/// ```toml
+/// # worker/Cargo.toml
+/// [package]
+/// name = "my_worker"
+/// version = "0.1.0"
+/// edition = "2021"
+///
+/// [lib]
+/// crate-type = ["cdylib"]
+///
+/// [dependencies]
+/// wasm-bindgen = "0.2"
+/// ```
+///
+/// ```ignore
+/// // worker/src/lib.rs — no `#[wasm_bindgen(start)]` and no Leptos
+/// use wasm_bindgen::prelude::*;
+///
+/// #[wasm_bindgen]
+/// pub fn fibonacci(n: u32) -> u64 {
+///     if n < 2 { n as u64 } else { fibonacci(n - 1) + fibonacci(n - 2) }
+/// }
+/// ```
+///
+/// Build it with `wasm-pack build worker --target web --out-dir ../public/worker` (or
+/// `wasm-bindgen --target web`) and serve the output so that `/worker/my_worker.js` and its
+/// `.wasm` file are available next to your app.
+///
+/// ## Server-Side Rendering
+///
+/// On the server no worker is started and `run` does nothing.
+pub fn use_web_worker_fn<A, R, C>(
+    worker_fn: impl Into<WebWorkerFn>,
+) -> UseWebWorkerFnReturn<
+    R,
+    <C as JsCodec<R>>::Error,
+    impl Fn(&A) + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    A: 'static,
+    R: 'static,
+    C: JsCodec<A, Error = <C as JsCodec<R>>::Error> + JsCodec<R> + Default,
+{
+    use_web_worker_fn_with_options(worker_fn, UseWebWorkerFnOptions::<C>::default())
+}
+
+/// Version of [`use_web_worker_fn`] that takes `UseWebWorkerFnOptions`. See [`use_web_worker_fn`] for how to use.
+pub fn use_web_worker_fn_with_options<A, R, C>(
+    worker_fn: impl Into<WebWorkerFn>,
+    options: UseWebWorkerFnOptions<C>,
+) -> UseWebWorkerFnReturn<
+    R,
+    <C as JsCodec<R>>::Error,
+    impl Fn(&A) + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    A: 'static,
+    R: 'static,
+    C: JsCodec<A, Error = <C as JsCodec<R>>::Error> + JsCodec<R>,
+{
+    let worker_fn = worker_fn.into();
+    let worker_type = worker_fn.worker_type();
+    let UseWebWorkerFnOptions {
+        codec,
+        timeout,
+        dependencies,
+    } = options;
+
+    let is_supported = use_supported(|| js!("Worker" in &window()));
+
+    let (status, set_status) = create_signal(WebWorkerFnStatus::Pending);
+    let (result, set_result) = create_signal(None::<R>);
+    let (error, set_error) = create_signal(None::<UseWebWorkerFnError<<C as JsCodec<R>>::Error>>);
+    let (worker, set_worker) = create_signal(None::<Worker>);
+
+    let script_url = store_value(None::<String>);
+    let timeout_handle = store_value(None::<TimeoutHandle>);
+
+    if is_supported.get_untracked() {
+        let options = BlobPropertyBag::new();
+        options.set_type("text/javascript");
+
+        let url = Blob::new_with_str_sequence_and_options(
+            &js_sys::Array::of1(&JsValue::from_str(&worker_fn.script(&dependencies))),
+            &options,
+        )
+        .and_then(|blob| Url::create_object_url_with_blob(&blob));
+
+        match url {
+            Ok(url) => script_url.set_value(Some(url)),
+            Err(err) => set_error.set(Some(UseWebWorkerFnError::Create(err))),
+        }
+    }
+
+    let stop = move || {
+        if let Some(Some(worker)) = worker.try_get_untracked() {
+            worker.terminate();
+            set_worker.set(None);
+        }
+
+        if let Some(Some(handle)) = timeout_handle.try_update_value(Option::take) {
+            handle.clear();
+        }
+    };
+
+    let fail = move |err| {
+        stop();
+        set_error.set(Some(err));
+        set_status.set(WebWorkerFnStatus::Error);
+    };
+
+    let _ = use_event_listener(worker, ev::message, {
+        let codec = codec.clone();
+
+        move |event| {
+            stop();
+
+            let data = event.data().unchecked_into::<js_sys::Array>();
+
+            if data.get(0).as_bool() != Some(true) {
+                fail(UseWebWorkerFnError::Function(
+                    data.get(1).as_string().unwrap_or_default(),
+                ));
+                return;
+            }
+
+            match JsCodec::<R>::decode(&codec, data.get(1)) {
+                Ok(val) => {
+                    set_result.set(Some(val));
+                    set_status.set(WebWorkerFnStatus::Success);
+                }
+                Err(err) => fail(UseWebWorkerFnError::Decode(err)),
+            }
+        }
+    });
+
+    let _ = use_event_listener(
+        worker,
+        ev::Custom::<ErrorEvent>::new("error"),
+        move |event| fail(UseWebWorkerFnError::Function(event.message())),
+    );
+
+    let run = move |arg: &A| {
+        stop();
+
+        let Some(url) = script_url.get_value() else {
+            return;
+        };
+
+        let arg = match JsCodec::<A>::encode(&codec, arg) {
+            Ok(arg) => arg,
+            Err(err) => {
+                fail(UseWebWorkerFnError::Encode(err));
+                return;
+            }
+        };
+
+        let worker_options = WorkerOptions::new();
+        worker_options.set_type(worker_type);
+
+        let new_worker = match Worker::new_with_options(&url, &worker_options) {
+            Ok(worker) => worker,
+            Err(err) => {
+                fail(UseWebWorkerFnError::Create(err));
+                return;
+            }
+        };
+
+        if let Err(err) = new_worker.post_message(&arg) {
+            new_worker.terminate();
+            fail(UseWebWorkerFnError::PostMessage(err));
+            return;
+        }
+
+        set_error.set(None);
+        set_status.set(WebWorkerFnStatus::Running);
+        set_worker.set(Some(new_worker));
+
+        if let Some(timeout) = timeout {
+            timeout_handle.set_value(
+                set_timeout_with_handle(
+                    move || fail(UseWebWorkerFnError::Timeout),
+                    Duration::from_millis(timeout),
+                )
+                .ok(),
+            );
+        }
+    };
+
+    let terminate = move || {
+        if status.get_untracked() == WebWorkerFnStatus::Running {
+            stop();
+            set_status.set(WebWorkerFnStatus::Pending);
+        }
+    };
+
+    on_cleanup(move || {
+        stop();
+
+        if let Some(Some(url)) = script_url.try_update_value(Option::take) {
+            let _ = Url::revoke_object_url(&url);
+        }
+    });
+
+    UseWebWorkerFnReturn {
+        is_supported,
+        status: status.into(),
+        result: result.into(),
+        error: error.into(),
+        run,
+        terminate,
+    }
+}
+
+/// The function that is run by [`use_web_worker_fn`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WebWorkerFn {
+    /// The source code of a JavaScript function like `"(n) => n * 2"`.
+    Js(String),
+
+    /// A function that has been exported with `#[wasm_bindgen]`. The worker runs the
+    /// `#[wasm_bindgen(start)]` function of the module first, see
+    /// [Worker crate](fn@crate::use_web_worker_fn#worker-crate).
+    WasmExport {
+        /// URL of the JavaScript module generated by `wasm-bindgen` with `--target web`
+        module_url: String,
+        /// Name of the exported function
+        name: String,
+    },
+}
+
+impl WebWorkerFn {
+    /// A JavaScript function given by its source code.
+    pub fn js(source: impl Into<String>) -> Self {
+        Self::Js(source.into())
+    }
+
+    /// A function exported with `#[wasm_bindgen]` from the module at `module_url`. The module
+    /// must not have a start function that expects to run in a window like the one of a Leptos
+    /// app, see [Worker crate](fn@crate::use_web_worker_fn#worker-crate).
+    pub fn wasm_export(module_url: impl Into<String>, name: impl Into<String>) -> Self {
+        Self::WasmExport {
+            module_url: module_url.into(),
+            name: name.into(),
+        }
+    }
+
+    fn worker_type(&self) -> WorkerType {
+        match self {
+            Self::Js(_) => WorkerType::Classic,
+            Self::WasmExport { .. } => WorkerType::Module,
+        }
+    }
+
+    fn script(&self, dependencies: &[String]) -> String {
+        // the worker is loaded from a blob URL, so relative URLs have to be resolved here
+        let url = |url: &str| {
+            let url = Url::new_with_base(
+                url,
+                &document().base_uri().ok().flatten().unwrap_or_default(),
+            )
+            .map(|url| url.href())
+            .unwrap_or_else(|_| url.to_string());
+
+            quote(&url)
+        };
+
+        let (head, call) = match self {
+            Self::Js(source) => {
+                let imports = if dependencies.is_empty() {
+                    String::new()
+                } else {
+                    let urls = dependencies.iter().map(|dependency| url(dependency));
+                    format!("importScripts({});\n", urls.collect::<Vec<_>>().join(", "))
+                };
+
+                (
+                    format!("{imports}const fn = ({source});\nconst ready = Promise.resolve();"),
+                    "fn(event.data)".to_string(),
+                )
+            }
+            Self::WasmExport { module_url, name } => (
+                format!(
+                    "import init, * as wasm from {};\nconst ready = init();",
+                    url(module_url)
+                ),
+                format!("wasm[{}](event.data)", quote(name)),
+            ),
+        };
+
+        format!(
+            r#"{head}
+self.onmessage = async (event) => {{
+  try {{
+    await ready;
+    self.postMessage([true, await {call}]);
+  }} catch (err) {{
+    self.postMessage([false, String(err)]);
+  }}
+}};
+"#
+        )
+    }
+}
+
+impl From<&str> for WebWorkerFn {
+    fn from(source: &str) -> Self {
+        Self::js(source)
+    }
+}
+
+impl From<String> for WebWorkerFn {
+    fn from(source: String) -> Self {
+        Self::Js(source)
+    }
+}
+
+fn quote(value: &str) -> String {
+    js_sys::JSON::stringify(&JsValue::from_str(value))
+        .map(String::from)
+        .unwrap_or_default()
+}
+
+/// Options for [`use_web_worker_fn_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseWebWorkerFnOptions<C> {
+    /// Encodes the argument and decodes the result of the function.
+    #[builder(keep_type)]
+    codec: C,
+
+    /// Time in milliseconds after which a running call is cancelled with
+    /// `UseWebWorkerFnError::Timeout`. Defaults to `None` which never cancels.
+    timeout: Option<u64>,
+
+    /// URLs of scripts that are loaded with `importScripts` before a JavaScript function is
+    /// defined. Ignored for `WebWorkerFn::WasmExport`. Defaults to none.
+    dependencies: Vec<String>,
+}
+
+impl<C: Default> Default for UseWebWorkerFnOptions<C> {
+    fn default() -> Self {
+        Self {
+            codec: C::default(),
+            timeout: None,
+            dependencies: vec![],
+        }
+    }
+}
+
+/// The status of the function of [`use_web_worker_fn`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WebWorkerFnStatus {
+    /// The function hasn't been run yet or has been cancelled
+    #[default]
+    Pending,
+    /// The function is running in the worker
+    Running,
+    /// The function has returned and `result` has been updated
+    Success,
+    /// The function failed. The reason is available in `error`.
+    Error,
+}
+
+/// Return type of [`use_web_worker_fn`].
+#[derive(Clone)]
+pub struct UseWebWorkerFnReturn<R, Err, RunFn, TerminateFn>
+where
+    R: 'static,
+    Err: 'static,
+    RunFn: Clone + 'static,
+    TerminateFn: Fn() + Clone + 'static,
+{
+    /// `true` if this browser supports web workers.
+    pub is_supported: Signal<bool>,
+
+    /// The status of the latest call
+    pub status: Signal<WebWorkerFnStatus>,
+
+    /// The result of the latest successful call
+    pub result: Signal<Option<R>>,
+
+    /// The error of the latest failed call
+    pub error: Signal<Option<UseWebWorkerFnError<Err>>>,
+
+    /// Runs the function with the given argument in a new worker
+    pub run: RunFn,
+
+    /// Cancels the running call
+    pub terminate: TerminateFn,
+}
+
+#[derive(Debug, Error, Clone)]
+pub enum UseWebWorkerFnError<Err> {
+    #[error("failed to create worker")]
+    Create(JsValue),
+    #[error("failed to post message")]
+    PostMessage(JsValue),
+    #[error("the function failed: {0}")]
+    Function(String),
+    #[error("the function didn't return in time")]
+    Timeout,
+    #[error("failed to encode value")]
+    Encode(Err),
+    #[error("failed to decode value")]
+    Decode(Err),
+}
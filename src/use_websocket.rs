@@ -1,16 +1,24 @@
 #![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
 
 use cfg_if::cfg_if;
-use leptos::{leptos_dom::helpers::TimeoutHandle, *};
+use leptos::{
+    leptos_dom::helpers::{IntervalHandle, TimeoutHandle},
+    *,
+};
 use std::cell::Cell;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
 use std::rc::Rc;
 use std::time::Duration;
+use thiserror::Error;
 
-use crate::core::ConnectionReadyState;
+use crate::core::{ConnectionReadyState, ReconnectStrategy};
+use crate::utils::{HybridCodec, HybridData};
 use default_struct_builder::DefaultBuilder;
 use js_sys::Array;
 use wasm_bindgen::prelude::*;
-use web_sys::{BinaryType, CloseEvent, Event, MessageEvent, WebSocket};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{BinaryType, Blob, CloseEvent, Event, MessageEvent, WebSocket};
 
 /// Creating and managing a [Websocket](https://developer.mozilla.org/en-US/docs/Web/API/WebSocket) connection.
 ///
@@ -20,30 +28,32 @@ use web_sys::{BinaryType, CloseEvent, Event, MessageEvent, WebSocket};
 ///
 /// ## Usage
 ///
+/// Messages are encoded and decoded with the given codec. String codecs like [`FromToStringCodec`]
+/// or [`JsonCodec`] send text messages and binary codecs like [`MsgpackCodec`] send binary messages.
+/// See [`HybridCodec`] for details.
+///
+/// > To use the [`JsonCodec`], you will need to add the `"serde"` feature to your project's `Cargo.toml`.
+/// > To use [`MsgpackCodec`] add the feature `"msgpack"` and for [`CborCodec`] the feature `"cbor"`.
+///
 /// ```
 /// # use leptos::*;
 /// # use leptos_use::{use_websocket, UseWebsocketReturn};
 /// # use leptos_use::core::ConnectionReadyState;
+/// # use leptos_use::utils::FromToStringCodec;
 /// #
 /// # #[component]
 /// # fn Demo() -> impl IntoView {
 /// let UseWebsocketReturn {
 ///     ready_state,
 ///     message,
-///     message_bytes,
 ///     send,
-///     send_bytes,
 ///     open,
 ///     close,
 ///     ..
-/// } = use_websocket("wss://echo.websocket.events/");
+/// } = use_websocket::<String, FromToStringCodec>("wss://echo.websocket.events/");
 ///
 /// let send_message = move |_| {
-///     send("Hello, world!");
-/// };
-///
-/// let send_byte_message = move |_| {
-///     send_bytes(b"Hello, world!\r\n".to_vec());
+///     send(&"Hello, world!".to_string());
 /// };
 ///
 /// let status = move || ready_state.get().to_string();
@@ -63,17 +73,51 @@ use web_sys::{BinaryType, CloseEvent, Event, MessageEvent, WebSocket};
 ///         <p>"status: " {status}</p>
 ///
 ///         <button on:click=send_message disabled=move || !connected()>"Send"</button>
-///         <button on:click=send_byte_message disabled=move || !connected()>"Send bytes"</button>
 ///         <button on:click=open_connection disabled=connected>"Open"</button>
 ///         <button on:click=close_connection disabled=move || !connected()>"Close"</button>
 ///
 ///         <p>"Receive message: " {move || format!("{:?}", message.get())}</p>
-///         <p>"Receive byte message: " {move || format!("{:?}", message_bytes.get())}</p>
 ///     </div>
 /// }
 /// # }
 /// ```
 ///
+/// ### Typed Messages
+///
+/// Any type that can be handled by the codec can be sent and received. If a received message can't
+/// be decoded, the `error` signal is set to [`UseWebSocketError::Codec`].
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_websocket, UseWebsocketReturn};
+/// # use leptos_use::utils::JsonCodec;
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// #[derive(Serialize, Deserialize, Clone, PartialEq)]
+/// pub struct ChatMessage {
+///     pub user: String,
+///     pub text: String,
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseWebsocketReturn {
+///     message,
+///     send,
+///     error,
+///     ..
+/// } = use_websocket::<ChatMessage, JsonCodec>("wss://chat.example.com/");
+///
+/// send(&ChatMessage { user: "leptos".to_string(), text: "Hi!".to_string() });
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ### Create Your Own Custom Codec
+///
+/// All you need to do is to implement the [`HybridCodec`] trait together with `Default` and `Clone`.
+///
 /// ## Relative Paths
 ///
 /// If the provided `url` is relative, it will be resolved relative to the current page.
@@ -88,6 +132,150 @@ use web_sys::{BinaryType, CloseEvent, Event, MessageEvent, WebSocket};
 /// | https://example.com/some/where | //otherdomain.com/api/ws | wss://otherdomain.com/api/ws        |
 ///
 ///
+/// ## Reconnection
+///
+/// If the connection is lost, it's reestablished automatically up to `reconnect_limit` times.
+/// How long to wait between the attempts is defined by a [`ReconnectStrategy`].
+/// `reconnect_attempts` is set to the number of an attempt when it starts and keeps that value
+/// during the delay before the next one. Once the connection is open again the counter goes back
+/// to `0`, unless you set `reset_reconnect_attempts` to `false`. Then the attempts add up over
+/// all connection losses and the counter keeps its value while connected until `open` is called.
+/// Calling `close` stops reconnecting.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_websocket_with_options, UseWebSocketOptions};
+/// # use leptos_use::core::{ConnectionReadyState, ReconnectStrategy};
+/// # use leptos_use::utils::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let ws = use_websocket_with_options::<String, FromToStringCodec>(
+///     "wss://echo.websocket.events/",
+///     UseWebSocketOptions::default()
+///         .reconnect_limit(10)
+///         .reconnect_strategy(ReconnectStrategy::exponential(1000, 30_000))
+///         .on_reconnect_attempt(|attempt| logging::log!("Reconnecting... attempt {attempt}"))
+///         .on_reconnect(|attempts| logging::log!("Reconnected after {attempts} attempts")),
+/// );
+///
+/// // `reconnect_attempts` stays at the last attempt when all of them failed
+/// let reconnect_status = move || {
+///     let attempts = ws.reconnect_attempts.get();
+///     (ws.ready_state.get() != ConnectionReadyState::Open && attempts > 0)
+///         .then(|| format!("Connection lost, attempt {attempts} of 10"))
+/// };
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Sub Protocols
+///
+/// You can offer several sub protocols with the option `protocols`. The one that the server
+/// picked is available in `protocol` once the connection is open.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_websocket_with_options, UseWebSocketOptions};
+/// # use leptos_use::utils::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let ws = use_websocket_with_options::<String, FromToStringCodec>(
+///     "wss://example.com/ws",
+///     UseWebSocketOptions::default()
+///         .protocols(Some(vec!["chat.v2".to_string(), "chat.v1".to_string()])),
+/// );
+///
+/// let is_v2 = move || ws.protocol.get().as_deref() == Some("chat.v2");
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Reactive Url
+///
+/// The url can be a signal. Whenever it changes, the current connection is closed and a new one is
+/// opened with the new url. This is handy if the url contains an auth token that is refreshed.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_websocket;
+/// # use leptos_use::utils::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (token, set_token) = create_signal("initial-token".to_string());
+///
+/// let ws = use_websocket::<String, FromToStringCodec>(Signal::derive(move || {
+///     format!("wss://example.com/ws?token={}", token.get())
+/// }));
+///
+/// // later, after the token has been refreshed, this reconnects automatically
+/// set_token.set("refreshed-token".to_string());
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// If the connection has been closed by calling `close`, changing the url doesn't open it again.
+///
+/// ## Send Buffer
+///
+/// By default messages that are sent while the connection isn't open are dropped. To keep messages
+/// that are sent while connecting or during a reconnect, set `send_buffer_limit`. They are sent
+/// as soon as the connection is open. Calling `close` discards them.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_websocket_with_options, SendBufferOverflow, UseWebSocketOptions};
+/// # use leptos_use::utils::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let ws = use_websocket_with_options::<String, FromToStringCodec>(
+///     "wss://echo.websocket.events/",
+///     UseWebSocketOptions::default()
+///         .send_buffer_limit(50)
+///         .send_buffer_overflow(SendBufferOverflow::DropOldest),
+/// );
+///
+/// // queued until the connection is open
+/// (ws.send)(&"Hello".to_string());
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Heartbeat
+///
+/// Proxies and load balancers often drop idle connections. To keep the connection alive you can
+/// let the hook send pings periodically. If you also specify the expected pong, the connection is
+/// closed and reestablished when the server stops answering.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_websocket_with_options, UseWebSocketOptions, WebSocketHeartbeat};
+/// # use leptos_use::utils::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let ws = use_websocket_with_options::<String, FromToStringCodec>(
+///     "wss://echo.websocket.events/",
+///     UseWebSocketOptions::default().heartbeat(
+///         WebSocketHeartbeat::default()
+///             .interval(10_000)
+///             .message("ping")
+///             .pong("pong")
+///             .pong_timeout(5_000),
+///     ),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
 /// ## Usage with `provide_context`
 ///
 /// The return value of `use_websocket` utilizes several type parameters which can make it
@@ -105,11 +293,11 @@ use web_sys::{BinaryType, CloseEvent, Event, MessageEvent, WebSocket};
 /// #[derive(Clone)]
 /// pub struct WebsocketContext {
 ///     pub message: Signal<Option<String>>,
-///     send: Rc<dyn Fn(&str)>,  // use Rc to make it easily cloneable
+///     send: Rc<dyn Fn(&String)>,  // use Rc to make it easily cloneable
 /// }
 ///
 /// impl WebsocketContext {
-///     pub fn new(message: Signal<Option<String>>, send: Rc<dyn Fn(&str)>) -> Self {
+///     pub fn new(message: Signal<Option<String>>, send: Rc<dyn Fn(&String)>) -> Self {
 ///         Self {
 ///             message,
 ///             send,
@@ -119,7 +307,7 @@ use web_sys::{BinaryType, CloseEvent, Event, MessageEvent, WebSocket};
 ///     // create a method to avoid having to use parantheses around the field
 ///     #[inline(always)]
 ///     pub fn send(&self, message: &str) {
-///         (self.send)(message)
+///         (self.send)(&message.to_string())
 ///     }
 /// }
 /// ```
@@ -129,15 +317,16 @@ use web_sys::{BinaryType, CloseEvent, Event, MessageEvent, WebSocket};
 /// ```
 /// # use leptos::*;
 /// # use leptos_use::{use_websocket, UseWebsocketReturn};
+/// # use leptos_use::utils::FromToStringCodec;
 /// # use std::rc::Rc;
 /// # #[derive(Clone)]
 /// # pub struct WebsocketContext {
 /// #     pub message: Signal<Option<String>>,
-/// #     send: Rc<dyn Fn(&str)>,
+/// #     send: Rc<dyn Fn(&String)>,
 /// # }
 /// #
 /// # impl WebsocketContext {
-/// #     pub fn new(message: Signal<Option<String>>, send: Rc<dyn Fn(&str)>) -> Self {
+/// #     pub fn new(message: Signal<Option<String>>, send: Rc<dyn Fn(&String)>) -> Self {
 /// #         Self {
 /// #             message,
 /// #             send,
@@ -151,7 +340,7 @@ use web_sys::{BinaryType, CloseEvent, Event, MessageEvent, WebSocket};
 ///     message,
 ///     send,
 ///     ..
-/// } = use_websocket("ws:://some.websocket.io");
+/// } = use_websocket::<String, FromToStringCodec>("ws:://some.websocket.io");
 ///
 /// provide_context(WebsocketContext::new(message, Rc::new(send.clone())));
 /// #
@@ -168,13 +357,13 @@ use web_sys::{BinaryType, CloseEvent, Event, MessageEvent, WebSocket};
 /// # #[derive(Clone)]
 /// # pub struct WebsocketContext {
 /// #     pub message: Signal<Option<String>>,
-/// #     send: Rc<dyn Fn(&str)>,
+/// #     send: Rc<dyn Fn(&String)>,
 /// # }
 /// #
 /// # impl WebsocketContext {
 /// #     #[inline(always)]
 /// #     pub fn send(&self, message: &str) {
-/// #         (self.send)(message)
+/// #         (self.send)(&message.to_string())
 /// #     }
 /// # }
 ///
@@ -191,48 +380,87 @@ use web_sys::{BinaryType, CloseEvent, Event, MessageEvent, WebSocket};
 /// ## Server-Side Rendering
 ///
 /// On the server the returned functions amount to no-ops.
-pub fn use_websocket(
-    url: &str,
+pub fn use_websocket<T, C>(
+    url: impl Into<MaybeSignal<String>>,
 ) -> UseWebsocketReturn<
+    T,
+    C::Error,
     impl Fn() + Clone + 'static,
     impl Fn() + Clone + 'static,
-    impl Fn(&str) + Clone + 'static,
-    impl Fn(Vec<u8>) + Clone + 'static,
-> {
-    use_websocket_with_options(url, UseWebSocketOptions::default())
+    impl Fn(&T) + Clone + 'static,
+>
+where
+    T: 'static,
+    C: HybridCodec<T> + Default,
+{
+    use_websocket_with_options(url, UseWebSocketOptions::<T, C>::default())
 }
 
 /// Version of [`use_websocket`] that takes `UseWebSocketOptions`. See [`use_websocket`] for how to use.
-pub fn use_websocket_with_options(
-    url: &str,
-    options: UseWebSocketOptions,
+pub fn use_websocket_with_options<T, C>(
+    url: impl Into<MaybeSignal<String>>,
+    options: UseWebSocketOptions<T, C>,
 ) -> UseWebsocketReturn<
+    T,
+    C::Error,
     impl Fn() + Clone + 'static,
     impl Fn() + Clone + 'static,
-    impl Fn(&str) + Clone + 'static,
-    impl Fn(Vec<u8>) + Clone,
-> {
-    let url = normalize_url(url);
+    impl Fn(&T) + Clone + 'static,
+>
+where
+    T: 'static,
+    C: HybridCodec<T> + Default,
+{
+    let url = url.into();
     let UseWebSocketOptions {
+        codec,
         on_open,
         on_message,
-        on_message_bytes,
+        on_message_raw,
+        on_message_raw_bytes,
         on_error,
         on_close,
         reconnect_limit,
-        reconnect_interval,
+        reconnect_strategy,
+        on_reconnect_attempt,
+        on_reconnect,
+        reset_reconnect_attempts,
         immediate,
         protocols,
+        binary_type,
+        heartbeat,
+        send_buffer_limit,
+        send_buffer_overflow,
+        _marker,
     } = options;
 
     let (ready_state, set_ready_state) = create_signal(ConnectionReadyState::Closed);
-    let (message, set_message) = create_signal(None);
-    let (message_bytes, set_message_bytes) = create_signal(None);
+    let (message, set_message) = create_signal(None::<T>);
+    let (error, set_error) = create_signal(None::<UseWebSocketError<C::Error>>);
+    let (protocol, set_protocol) = create_signal(None::<String>);
     let ws_ref: StoredValue<Option<WebSocket>> = store_value(None);
 
     let reconnect_timer_ref: StoredValue<Option<TimeoutHandle>> = store_value(None);
 
-    let reconnect_times_ref: StoredValue<u64> = store_value(0);
+    let (reconnect_attempts, set_reconnect_attempts) = create_signal(0_u64);
+    let explicitly_closed_ref: StoredValue<bool> = store_value(false);
+
+    let send_buffer_ref: StoredValue<VecDeque<HybridData>> = store_value(VecDeque::new());
+
+    let heartbeat_interval_ref: StoredValue<Option<IntervalHandle>> = store_value(None);
+    let pong_timeout_ref: StoredValue<Option<TimeoutHandle>> = store_value(None);
+
+    let stop_heartbeat = move || {
+        if let Some(interval) = heartbeat_interval_ref.get_value() {
+            interval.clear();
+        }
+        heartbeat_interval_ref.set_value(None);
+
+        if let Some(timeout) = pong_timeout_ref.get_value() {
+            timeout.clear();
+        }
+        pong_timeout_ref.set_value(None);
+    };
 
     let unmounted = Rc::new(Cell::new(false));
 
@@ -242,22 +470,38 @@ pub fn use_websocket_with_options(
     {
         let reconnect_ref: StoredValue<Option<Rc<dyn Fn()>>> = store_value(None);
         reconnect_ref.set_value({
-            let ws = ws_ref.get_value();
             Some(Rc::new(move || {
-                if reconnect_times_ref.get_value() < reconnect_limit
-                    && ws
-                        .clone()
-                        .map_or(false, |ws: WebSocket| ws.ready_state() != WebSocket::OPEN)
+                // the error and the close event both trigger a reconnect
+                if reconnect_timer_ref.get_value().is_some() {
+                    return;
+                }
+
+                if !explicitly_closed_ref.get_value()
+                    && reconnect_attempts.get_untracked() < reconnect_limit
+                    && ws_ref
+                        .get_value()
+                        .is_some_and(|ws: WebSocket| ws.ready_state() != WebSocket::OPEN)
                 {
+                    let attempt = reconnect_attempts.get_untracked() + 1;
+                    let on_reconnect_attempt = Rc::clone(&on_reconnect_attempt);
+
                     reconnect_timer_ref.set_value(
                         set_timeout_with_handle(
                             move || {
                                 if let Some(connect) = connect_ref.get_value() {
+                                    #[cfg(debug_assertions)]
+                                    let prev = SpecialNonReactiveZone::enter();
+
+                                    on_reconnect_attempt(attempt);
+
+                                    #[cfg(debug_assertions)]
+                                    SpecialNonReactiveZone::exit(prev);
+
+                                    set_reconnect_attempts.set(attempt);
                                     connect();
-                                    reconnect_times_ref.update_value(|current| *current += 1);
                                 }
                             },
-                            Duration::from_millis(reconnect_interval),
+                            reconnect_strategy.delay(attempt),
                         )
                         .ok(),
                     );
@@ -266,16 +510,23 @@ pub fn use_websocket_with_options(
         });
 
         connect_ref.set_value({
-            let ws = ws_ref.get_value();
+            let url = url.clone();
             let unmounted = Rc::clone(&unmounted);
+            let codec = codec.clone();
 
             Some(Rc::new(move || {
+                if let Some(timer) = reconnect_timer_ref.get_value() {
+                    timer.clear();
+                }
                 reconnect_timer_ref.set_value(None);
+                stop_heartbeat();
 
-                if let Some(web_socket) = &ws {
+                if let Some(web_socket) = ws_ref.get_value() {
                     let _ = web_socket.close();
                 }
 
+                let url = normalize_url(&url.get_untracked());
+
                 let web_socket = {
                     protocols.as_ref().map_or_else(
                         || WebSocket::new(&url).unwrap_throw(),
@@ -289,16 +540,26 @@ pub fn use_websocket_with_options(
                         },
                     )
                 };
-                web_socket.set_binary_type(BinaryType::Arraybuffer);
+                web_socket.set_binary_type(binary_type);
                 set_ready_state.set(ConnectionReadyState::Connecting);
 
+                // events of sockets that have been replaced by a new connection are ignored
+                let is_current = {
+                    let web_socket = web_socket.clone();
+                    move || ws_ref.get_value().as_ref() == Some(&web_socket)
+                };
+
                 // onopen handler
                 {
                     let unmounted = Rc::clone(&unmounted);
+                    let is_current = is_current.clone();
                     let on_open = Rc::clone(&on_open);
+                    let on_reconnect = Rc::clone(&on_reconnect);
+
+                    let heartbeat = heartbeat.clone();
 
                     let onopen_closure = Closure::wrap(Box::new(move |e: Event| {
-                        if unmounted.get() {
+                        if unmounted.get() || !is_current() {
                             return;
                         }
 
@@ -307,10 +568,39 @@ pub fn use_websocket_with_options(
 
                         on_open(e);
 
+                        let attempts = reconnect_attempts.get_untracked();
+                        if attempts > 0 {
+                            on_reconnect(attempts);
+                        }
+
                         #[cfg(debug_assertions)]
                         SpecialNonReactiveZone::exit(prev);
 
                         set_ready_state.set(ConnectionReadyState::Open);
+
+                        if let Some(web_socket) = ws_ref.get_value() {
+                            set_protocol.set(Some(web_socket.protocol()).filter(|p| !p.is_empty()));
+
+                            let buffered = send_buffer_ref
+                                .try_update_value(std::mem::take)
+                                .unwrap_or_default();
+                            for data in buffered {
+                                send_hybrid_data(&web_socket, &data);
+                            }
+                        }
+
+                        if reset_reconnect_attempts {
+                            set_reconnect_attempts.set(0);
+                        }
+
+                        if let Some(heartbeat) = heartbeat.clone() {
+                            start_heartbeat(
+                                heartbeat,
+                                ws_ref,
+                                heartbeat_interval_ref,
+                                pong_timeout_ref,
+                            );
+                        }
                     })
                         as Box<dyn FnMut(Event)>);
                     web_socket.set_onopen(Some(onopen_closure.as_ref().unchecked_ref()));
@@ -321,53 +611,82 @@ pub fn use_websocket_with_options(
                 // onmessage handler
                 {
                     let unmounted = Rc::clone(&unmounted);
+                    let is_current = is_current.clone();
                     let on_message = Rc::clone(&on_message);
-                    let on_message_bytes = Rc::clone(&on_message_bytes);
+                    let on_message_raw = Rc::clone(&on_message_raw);
+                    let on_message_raw_bytes = Rc::clone(&on_message_raw_bytes);
+                    let codec = codec.clone();
+                    let pong = heartbeat
+                        .as_ref()
+                        .and_then(|heartbeat| heartbeat.pong.clone());
 
-                    let onmessage_closure = Closure::wrap(Box::new(move |e: MessageEvent| {
-                        if unmounted.get() {
-                            return;
+                    let handle_message = move |data: HybridData| {
+                        #[cfg(debug_assertions)]
+                        let prev = SpecialNonReactiveZone::enter();
+
+                        match &data {
+                            HybridData::Text(text) => on_message_raw(text.clone()),
+                            HybridData::Binary(bytes) => on_message_raw_bytes(bytes.clone()),
                         }
 
-                        e.data().dyn_into::<js_sys::ArrayBuffer>().map_or_else(
-                            |_| {
-                                e.data().dyn_into::<js_sys::JsString>().map_or_else(
-                                    |_| {
-                                        unreachable!(
-                                            "message event, received Unknown: {:?}",
-                                            e.data()
-                                        );
-                                    },
-                                    |txt| {
-                                        let txt = String::from(&txt);
-
-                                        #[cfg(debug_assertions)]
-                                        let prev = SpecialNonReactiveZone::enter();
-
-                                        on_message(txt.clone());
-
-                                        #[cfg(debug_assertions)]
-                                        SpecialNonReactiveZone::exit(prev);
-
-                                        set_message.set(Some(txt));
-                                    },
-                                );
-                            },
-                            |array_buffer| {
-                                let array = js_sys::Uint8Array::new(&array_buffer);
-                                let array = array.to_vec();
+                        #[cfg(debug_assertions)]
+                        SpecialNonReactiveZone::exit(prev);
 
+                        match codec.decode(data) {
+                            Ok(val) => {
                                 #[cfg(debug_assertions)]
                                 let prev = SpecialNonReactiveZone::enter();
 
-                                on_message_bytes(array.clone());
+                                on_message(&val);
 
                                 #[cfg(debug_assertions)]
                                 SpecialNonReactiveZone::exit(prev);
 
-                                set_message_bytes.set(Some(array));
-                            },
-                        );
+                                set_message.set(Some(val));
+                            }
+                            Err(err) => {
+                                set_error.set(Some(UseWebSocketError::Codec(err)));
+                            }
+                        }
+                    };
+
+                    let onmessage_closure = Closure::wrap(Box::new(move |e: MessageEvent| {
+                        if unmounted.get() || !is_current() {
+                            return;
+                        }
+
+                        if let Some(pong) = &pong {
+                            if e.data().as_string().as_ref() == Some(pong) {
+                                if let Some(timeout) = pong_timeout_ref.get_value() {
+                                    timeout.clear();
+                                }
+                                pong_timeout_ref.set_value(None);
+                                return;
+                            }
+                        }
+
+                        let data = e.data();
+
+                        if let Some(text) = data.as_string() {
+                            handle_message(HybridData::Text(text));
+                        } else if let Some(array_buffer) = data.dyn_ref::<js_sys::ArrayBuffer>() {
+                            handle_message(HybridData::Binary(
+                                js_sys::Uint8Array::new(array_buffer).to_vec(),
+                            ));
+                        } else if let Some(blob) = data.dyn_ref::<Blob>() {
+                            let handle_message = handle_message.clone();
+                            let read_blob = JsFuture::from(blob.array_buffer());
+
+                            spawn_local(async move {
+                                if let Ok(array_buffer) = read_blob.await {
+                                    handle_message(HybridData::Binary(
+                                        js_sys::Uint8Array::new(&array_buffer).to_vec(),
+                                    ));
+                                }
+                            });
+                        } else {
+                            unreachable!("message event, received Unknown: {:?}", data);
+                        }
                     })
                         as Box<dyn FnMut(MessageEvent)>);
                     web_socket.set_onmessage(Some(onmessage_closure.as_ref().unchecked_ref()));
@@ -377,10 +696,11 @@ pub fn use_websocket_with_options(
                 // onerror handler
                 {
                     let unmounted = Rc::clone(&unmounted);
+                    let is_current = is_current.clone();
                     let on_error = Rc::clone(&on_error);
 
                     let onerror_closure = Closure::wrap(Box::new(move |e: Event| {
-                        if unmounted.get() {
+                        if unmounted.get() || !is_current() {
                             return;
                         }
 
@@ -391,11 +711,12 @@ pub fn use_websocket_with_options(
                         #[cfg(debug_assertions)]
                         let prev = SpecialNonReactiveZone::enter();
 
-                        on_error(e);
+                        on_error(e.clone());
 
                         #[cfg(debug_assertions)]
                         SpecialNonReactiveZone::exit(prev);
 
+                        set_error.set(Some(UseWebSocketError::Event(e)));
                         set_ready_state.set(ConnectionReadyState::Closed);
                     })
                         as Box<dyn FnMut(Event)>);
@@ -406,13 +727,17 @@ pub fn use_websocket_with_options(
                 // onclose handler
                 {
                     let unmounted = Rc::clone(&unmounted);
+                    let is_current = is_current.clone();
                     let on_close = Rc::clone(&on_close);
 
                     let onclose_closure = Closure::wrap(Box::new(move |e: CloseEvent| {
-                        if unmounted.get() {
+                        if unmounted.get() || !is_current() {
                             return;
                         }
 
+                        stop_heartbeat();
+                        set_protocol.set(None);
+
                         if let Some(reconnect) = &reconnect_ref.get_value() {
                             reconnect();
                         }
@@ -437,29 +762,51 @@ pub fn use_websocket_with_options(
         });
     }
 
-    // Send text (String)
-    let send = {
-        Box::new(move |data: &str| {
-            if ready_state.get_untracked() == ConnectionReadyState::Open {
-                if let Some(web_socket) = ws_ref.get_value() {
-                    let _ = web_socket.send_with_str(data);
-                }
-            }
-        })
-    };
+    // Send encoded message
+    let send = move |data: &T| {
+        let web_socket = ws_ref
+            .get_value()
+            .filter(|_| ready_state.get_untracked() == ConnectionReadyState::Open);
 
-    // Send bytes
-    let send_bytes = move |data: Vec<u8>| {
-        if ready_state.get_untracked() == ConnectionReadyState::Open {
-            if let Some(web_socket) = ws_ref.get_value() {
-                let _ = web_socket.send_with_u8_array(&data);
+        let buffering = web_socket.is_none()
+            && send_buffer_limit > 0
+            && !explicitly_closed_ref.get_value()
+            && (ready_state.get_untracked() == ConnectionReadyState::Connecting
+                || reconnect_timer_ref.get_value().is_some());
+
+        if web_socket.is_none() && !buffering {
+            return;
+        }
+
+        let data = match codec.encode(data) {
+            Ok(data) => data,
+            Err(err) => {
+                set_error.set(Some(UseWebSocketError::Codec(err)));
+                return;
             }
+        };
+
+        if let Some(web_socket) = web_socket {
+            send_hybrid_data(&web_socket, &data);
+        } else {
+            send_buffer_ref.update_value(|buffer| {
+                if buffer.len() >= send_buffer_limit {
+                    match send_buffer_overflow {
+                        SendBufferOverflow::DropOldest => {
+                            buffer.pop_front();
+                        }
+                        SendBufferOverflow::DropNewest => return,
+                    }
+                }
+                buffer.push_back(data);
+            });
         }
     };
 
     // Open connection
     let open = move || {
-        reconnect_times_ref.set_value(0);
+        explicitly_closed_ref.set_value(false);
+        set_reconnect_attempts.set(0);
         if let Some(connect) = connect_ref.get_value() {
             connect();
         }
@@ -467,10 +814,14 @@ pub fn use_websocket_with_options(
 
     // Close connection
     let close = {
-        reconnect_timer_ref.set_value(None);
-
         move || {
-            reconnect_times_ref.set_value(reconnect_limit);
+            explicitly_closed_ref.set_value(true);
+            if let Some(timer) = reconnect_timer_ref.get_value() {
+                timer.clear();
+            }
+            reconnect_timer_ref.set_value(None);
+            stop_heartbeat();
+            send_buffer_ref.update_value(VecDeque::clear);
             if let Some(web_socket) = ws_ref.get_value() {
                 let _ = web_socket.close();
             }
@@ -484,6 +835,17 @@ pub fn use_websocket_with_options(
         }
     });
 
+    // Reconnect to the new url unless the connection has been closed explicitly
+    let _ = watch(
+        move || url.get(),
+        move |_, _, _| {
+            if !explicitly_closed_ref.get_value() && ws_ref.get_value().is_some() {
+                open();
+            }
+        },
+        false,
+    );
+
     // clean up (unmount)
     on_cleanup(move || {
         unmounted.set(true);
@@ -493,81 +855,248 @@ pub fn use_websocket_with_options(
     UseWebsocketReturn {
         ready_state: ready_state.into(),
         message: message.into(),
-        message_bytes: message_bytes.into(),
+        error: error.into(),
+        reconnect_attempts: reconnect_attempts.into(),
+        protocol: protocol.into(),
         ws: ws_ref.get_value(),
         open,
         close,
         send,
-        send_bytes,
     }
 }
 
 /// Options for [`use_websocket_with_options`].
 #[derive(DefaultBuilder)]
-pub struct UseWebSocketOptions {
+pub struct UseWebSocketOptions<T, C>
+where
+    T: 'static,
+    C: HybridCodec<T>,
+{
+    /// Encodes sent and decodes received messages.
+    #[builder(keep_type)]
+    codec: C,
     /// `WebSocket` connect callback.
     on_open: Rc<dyn Fn(Event)>,
-    /// `WebSocket` message callback for text.
-    on_message: Rc<dyn Fn(String)>,
-    /// `WebSocket` message callback for binary.
-    on_message_bytes: Rc<dyn Fn(Vec<u8>)>,
+    /// `WebSocket` message callback for successfully decoded messages.
+    #[builder(keep_type)]
+    on_message: Rc<dyn Fn(&T)>,
+    /// `WebSocket` message callback for text before it is decoded.
+    on_message_raw: Rc<dyn Fn(String)>,
+    /// `WebSocket` message callback for binary data before it is decoded.
+    on_message_raw_bytes: Rc<dyn Fn(Vec<u8>)>,
     /// `WebSocket` error callback.
     on_error: Rc<dyn Fn(Event)>,
     /// `WebSocket` close callback.
     on_close: Rc<dyn Fn(CloseEvent)>,
     /// Retry times. Defaults to 3.
     reconnect_limit: u64,
-    /// Retry interval in ms. Defaults to 3000.
-    reconnect_interval: u64,
+    /// How long to wait between reconnection attempts. Defaults to `ReconnectStrategy::Fixed(3000)`.
+    /// See also [`Self::reconnect_interval`].
+    reconnect_strategy: ReconnectStrategy,
+    /// Called right before every reconnection attempt with the number of the attempt starting at 1.
+    on_reconnect_attempt: Rc<dyn Fn(u64)>,
+    /// Called when the connection has been reestablished with the number of attempts it took.
+    on_reconnect: Rc<dyn Fn(u64)>,
+    /// If `true` the attempt counter is reset once a connection is open again so that the
+    /// `reconnect_limit` applies to every connection loss separately. If `false` the attempts
+    /// add up until `open` is called. Defaults to `true`.
+    reset_reconnect_attempts: bool,
     /// If `true` the `WebSocket` connection will immediately be opened when calling this function.
     /// If `false` you have to manually call the `open` function.
     /// Defaults to `true`.
     immediate: bool,
     /// Sub protocols. See [MDN Docs](https://developer.mozilla.org/en-US/docs/Web/API/WebSocket/WebSocket#protocols).
     protocols: Option<Vec<String>>,
+    /// How binary messages are received by the browser before they're decoded.
+    /// `BinaryType::Blob` messages are read asynchronously, which is why they can be processed
+    /// out of order. See [MDN Docs](https://developer.mozilla.org/en-US/docs/Web/API/WebSocket/binaryType).
+    /// Defaults to `BinaryType::Arraybuffer`.
+    binary_type: BinaryType,
+    /// Send pings periodically while the connection is open to keep it alive.
+    /// Defaults to `None` which disables the heartbeat.
+    #[builder(into)]
+    heartbeat: Option<WebSocketHeartbeat>,
+    /// Maximum number of messages that are queued when `send` is called while connecting or
+    /// waiting for a reconnect. Queued messages are sent as soon as the connection is open.
+    /// Defaults to `0` which means that these messages are dropped.
+    send_buffer_limit: usize,
+    /// Which message to drop when the send buffer is full. Defaults to `SendBufferOverflow::DropNewest`.
+    send_buffer_overflow: SendBufferOverflow,
+
+    #[builder(skip)]
+    _marker: PhantomData<T>,
 }
 
-impl Default for UseWebSocketOptions {
+impl<T, C: HybridCodec<T> + Default> Default for UseWebSocketOptions<T, C> {
     fn default() -> Self {
         Self {
+            codec: C::default(),
             on_open: Rc::new(|_| {}),
             on_message: Rc::new(|_| {}),
-            on_message_bytes: Rc::new(|_| {}),
+            on_message_raw: Rc::new(|_| {}),
+            on_message_raw_bytes: Rc::new(|_| {}),
             on_error: Rc::new(|_| {}),
             on_close: Rc::new(|_| {}),
             reconnect_limit: 3,
-            reconnect_interval: 3000,
+            reconnect_strategy: ReconnectStrategy::default(),
+            on_reconnect_attempt: Rc::new(|_| {}),
+            on_reconnect: Rc::new(|_| {}),
+            reset_reconnect_attempts: true,
             immediate: true,
             protocols: Default::default(),
+            binary_type: BinaryType::Arraybuffer,
+            heartbeat: None,
+            send_buffer_limit: 0,
+            send_buffer_overflow: SendBufferOverflow::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, C: HybridCodec<T>> UseWebSocketOptions<T, C> {
+    /// Retry interval in ms. Shorthand for `reconnect_strategy(ReconnectStrategy::Fixed(interval))`.
+    pub fn reconnect_interval(self, interval: u64) -> Self {
+        self.reconnect_strategy(ReconnectStrategy::Fixed(interval))
+    }
+}
+
+/// What happens to a message that is sent while the send buffer of [`use_websocket`] is full.
+/// See [`UseWebSocketOptions::send_buffer_limit`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SendBufferOverflow {
+    /// The new message is dropped.
+    #[default]
+    DropNewest,
+    /// The oldest queued message is dropped to make room for the new one.
+    DropOldest,
+}
+
+/// Keep-alive options for [`UseWebSocketOptions::heartbeat`].
+///
+/// While the connection is open, `message` is sent every `interval` milliseconds.
+/// If `pong` is set, the server has to answer every ping with exactly this text within
+/// `pong_timeout` milliseconds. Otherwise the connection is considered dead, is closed and
+/// a reconnect is attempted.
+#[derive(DefaultBuilder, Clone, Debug)]
+pub struct WebSocketHeartbeat {
+    /// Interval in ms between two pings. Defaults to 30000.
+    interval: u64,
+    /// The text that is sent as ping. It's sent as is without using the codec.
+    /// Defaults to `"ping"`.
+    #[builder(into)]
+    message: String,
+    /// The text that the server answers a ping with. Received pongs are not passed on to
+    /// `message` or `on_message`. Defaults to `None` which means that no answer is expected.
+    #[builder(into)]
+    pong: Option<String>,
+    /// Time in ms to wait for the pong before the connection is closed. Defaults to 10000.
+    pong_timeout: u64,
+}
+
+impl Default for WebSocketHeartbeat {
+    fn default() -> Self {
+        Self {
+            interval: 30000,
+            message: "ping".to_string(),
+            pong: None,
+            pong_timeout: 10000,
         }
     }
 }
 
+fn send_hybrid_data(web_socket: &WebSocket, data: &HybridData) {
+    let _ = match data {
+        HybridData::Text(text) => web_socket.send_with_str(text),
+        HybridData::Binary(bytes) => web_socket.send_with_u8_array(bytes),
+    };
+}
+
+#[cfg(not(feature = "ssr"))]
+fn start_heartbeat(
+    heartbeat: WebSocketHeartbeat,
+    ws_ref: StoredValue<Option<WebSocket>>,
+    heartbeat_interval_ref: StoredValue<Option<IntervalHandle>>,
+    pong_timeout_ref: StoredValue<Option<TimeoutHandle>>,
+) {
+    let WebSocketHeartbeat {
+        interval,
+        message,
+        pong,
+        pong_timeout,
+    } = heartbeat;
+
+    heartbeat_interval_ref.set_value(
+        set_interval_with_handle(
+            move || {
+                let Some(web_socket) = ws_ref.get_value() else {
+                    return;
+                };
+                let _ = web_socket.send_with_str(&message);
+
+                if pong.is_some() && pong_timeout_ref.get_value().is_none() {
+                    pong_timeout_ref.set_value(
+                        set_timeout_with_handle(
+                            move || {
+                                pong_timeout_ref.set_value(None);
+                                // triggers the onclose handler which reconnects
+                                let _ = web_socket.close();
+                            },
+                            Duration::from_millis(pong_timeout),
+                        )
+                        .ok(),
+                    );
+                }
+            },
+            Duration::from_millis(interval),
+        )
+        .ok(),
+    );
+}
+
 /// Return type of [`use_websocket`].
 #[derive(Clone)]
-pub struct UseWebsocketReturn<OpenFn, CloseFn, SendFn, SendBytesFn>
+pub struct UseWebsocketReturn<T, Err, OpenFn, CloseFn, SendFn>
 where
+    T: 'static,
+    Err: 'static,
     OpenFn: Fn() + Clone + 'static,
     CloseFn: Fn() + Clone + 'static,
-    SendFn: Fn(&str) + Clone + 'static,
-    SendBytesFn: Fn(Vec<u8>) + Clone + 'static,
+    SendFn: Fn(&T) + Clone + 'static,
 {
     /// The current state of the `WebSocket` connection.
     pub ready_state: Signal<ConnectionReadyState>,
-    /// Latest text message received from `WebSocket`.
-    pub message: Signal<Option<String>>,
-    /// Latest binary message received from `WebSocket`.
-    pub message_bytes: Signal<Option<Vec<u8>>>,
+    /// Latest message received from `WebSocket` decoded by the codec.
+    pub message: Signal<Option<T>>,
+    /// The latest error. Either an error event of the `WebSocket` or a message that failed to be
+    /// encoded or decoded.
+    pub error: Signal<Option<UseWebSocketError<Err>>>,
+    /// The number of the latest reconnection attempt. It's `0` before the first reconnect and,
+    /// with the default `reset_reconnect_attempts(true)`, again once a connection is open.
+    /// With `reset_reconnect_attempts(false)` it's the total number of attempts since `open`
+    /// and keeps its value while connected. When all attempts failed it stays at the last one.
+    pub reconnect_attempts: Signal<u64>,
+    /// The sub protocol selected by the server once the connection is open. `None` while not
+    /// connected or if the server didn't select one. See the option `protocols`.
+    pub protocol: Signal<Option<String>>,
     /// The `WebSocket` instance.
     pub ws: Option<WebSocket>,
     /// Opens the `WebSocket` connection
     pub open: OpenFn,
     /// Closes the `WebSocket` connection
     pub close: CloseFn,
-    /// Sends `text` (string) based data
+    /// Encodes and sends a message. Depending on the codec it's sent as text or binary data.
+    /// If the connection isn't open, the message is queued or dropped depending on
+    /// [`UseWebSocketOptions::send_buffer_limit`].
     pub send: SendFn,
-    /// Sends binary data
-    pub send_bytes: SendBytesFn,
+}
+
+#[derive(Error, Debug)]
+pub enum UseWebSocketError<Err> {
+    #[error("WebSocket error event")]
+    Event(Event),
+
+    #[error("Error encoding or decoding message")]
+    Codec(Err),
 }
 
 fn normalize_url(url: &str) -> String {
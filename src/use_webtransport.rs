@@ -1,27 +1,24 @@
-use crate::core::ConnectionReadyState;
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::{ConnectionReadyState, ReconnectStrategy};
+use crate::utils::BinCodec;
+use crate::{js, js_fut};
 use async_trait::async_trait;
 use default_struct_builder::DefaultBuilder;
-use js_sys::Reflect;
 use leptos::leptos_dom::helpers::TimeoutHandle;
 use leptos::*;
 use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
 use std::rc::Rc;
-use std::time::Duration;
 use wasm_bindgen::prelude::*;
-use wasm_bindgen_futures::JsFuture;
-
-#[cfg(any(feature = "bincode", feature = "msgpack"))]
-use serde::{Deserialize, Serialize};
 
-#[cfg(feature = "msgpack")]
-use rmp_serde::{from_slice, to_vec};
 use thiserror::Error;
 use web_sys::WebTransportBidirectionalStream;
 
-#[cfg(feature = "bincode")]
-use bincode::serde::{decode_from_slice as from_slice, encode_to_vec as to_vec};
-
+/// Reactive [WebTransport](https://developer.mozilla.org/en-US/docs/Web/API/WebTransport) connection.
 ///
+/// > This function requires `--cfg=web_sys_unstable_apis` to be activated as
+/// [described in the wasm-bindgen guide](https://rustwasm.github.io/docs/wasm-bindgen/web-sys/unstable-apis.html).
 ///
 /// ## Demo
 ///
@@ -31,15 +28,166 @@ use bincode::serde::{decode_from_slice as from_slice, encode_to_vec as to_vec};
 ///
 /// ```
 /// # use leptos::*;
+/// # use leptos_use::{use_webtransport, UseWebTransportReturn};
+/// # use leptos_use::core::ConnectionReadyState;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let transport = use_webtransport("https://echo.webtransport.day");
+///
+/// let ready_state = transport.ready_state;
+/// let datagrams = transport.datagrams;
+///
+/// let send = move |_| {
+///     transport.send_datagram(b"Hello, world!");
+/// };
+///
+/// view! {
+///     <button on:click=send disabled=move || ready_state.get() != ConnectionReadyState::Open>
+///         "Send datagram"
+///     </button>
+///     <p>"Received: " {move || format!("{:?}", datagrams.get())}</p>
+/// }
+/// # }
+/// ```
+///
+/// ## Datagrams
+///
+/// Datagrams are small, unreliable and unordered messages. They are ideal for latency critical
+/// data like game state or sensor readings where an outdated message is useless anyway.
+/// Send them with [`UseWebTransportReturn::send_datagram`] (or [`UseWebTransportReturn::send_datagram_async`]
+/// if you care about errors) and read the latest one received from the `datagrams` signal.
+/// The datagrams are only read from the connection once this signal is accessed for the first time.
+///
+/// ## Bidirectional Streams
+///
+/// Streams are reliable and ordered. You can open a bidirectional stream yourself with
+/// [`UseWebTransportReturn::open_bidirectional_stream`]. Streams opened by the server are passed to
+/// [`UseWebTransportOptions::on_bidir_stream`] and the latest one is also available as the
+/// `incoming_bidir_stream` signal.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_webtransport, SendableStream};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let transport = use_webtransport("https://echo.webtransport.day");
+///
+/// let open_stream = move |_| {
+///     let transport = transport.clone();
+///
+///     spawn_local(async move {
+///         if let Ok(stream) = transport.open_bidirectional_stream().await {
+///             let _ = stream.send_bytes_async(b"Hello, stream!").await;
+///
+///             create_effect(move |_| logging::log!("Received: {:?}", stream.bytes.get()));
+///         }
+///     });
+/// };
+///
+/// view! { <button on:click=open_stream>"Open stream"</button> }
+/// # }
+/// ```
+///
+/// ## Unidirectional Streams
+///
+/// A unidirectional stream only transmits data in one direction. Open one for sending with
+/// [`UseWebTransportReturn::open_unidirectional_stream`]. Streams opened by the server for receiving
+/// are passed to [`UseWebTransportOptions::on_receive_stream`] and the latest one is available as the
+/// `incoming_receive_stream` signal.
+///
+/// All streams are closed automatically when the component that called `use_webtransport` is
+/// disposed. You can also close them earlier with [`CloseableStream::close`].
+///
+/// ## Closing
+///
+/// The connection is closed automatically when the component is disposed. To close it earlier
+/// call [`UseWebTransportReturn::close`] or [`UseWebTransportReturn::close_with_info`] to tell the
+/// server why. When the session ends the code and reason are available in the
+/// `close_info` signal.
+///
+/// ```
+/// # use leptos::*;
 /// # use leptos_use::use_webtransport;
 /// #
 /// # #[component]
 /// # fn Demo() -> impl IntoView {
-/// use_webtransport();
+/// let transport = use_webtransport("https://echo.webtransport.day");
+/// let close_info = transport.close_info;
+///
+/// let leave = move |_| transport.close_with_info(0, "user left");
+///
+/// view! {
+///     <button on:click=leave>"Leave"</button>
+///     <p>{move || close_info.get().map(|info| format!("Closed: {}", info.reason))}</p>
+/// }
+/// # }
+/// ```
+///
+/// ## Self-Signed Certificates
+///
+/// During development servers often use self-signed certificates. To be able to connect to them
+/// you have to provide the hashes of the certificates the server may use.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_webtransport_with_options, ServerCertificateHash, UseWebTransportOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// # let certificate_hash: Vec<u8> = vec![];
+/// let transport = use_webtransport_with_options(
+///     "https://localhost:4433",
+///     UseWebTransportOptions::default()
+///         .server_certificate_hashes(vec![ServerCertificateHash::sha256(certificate_hash)]),
+/// );
 /// #
 /// # view! { }
 /// # }
 /// ```
+///
+/// ## Typed Messages
+///
+/// Instead of raw bytes you can send and receive any type that can be encoded by a [`BinCodec`].
+/// This works for datagrams as well as for streams.
+///
+/// > To use the [`JsonBytesCodec`], you will need to add the `"serde"` feature to your project's `Cargo.toml`.
+/// > To use [`MsgpackCodec`] add the feature `"msgpack"` and for [`BincodeCodec`] the feature `"bincode"`.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_webtransport;
+/// # use leptos_use::utils::MsgpackCodec;
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// #[derive(Serialize, Deserialize, Clone, PartialEq)]
+/// pub struct Position {
+///     pub x: f32,
+///     pub y: f32,
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let transport = use_webtransport("https://echo.webtransport.day");
+///
+/// let position = transport.message::<Position, MsgpackCodec>();
+///
+/// transport.send::<_, MsgpackCodec>(&Position { x: 1.0, y: 2.0 });
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Create Your Own Custom Codec
+///
+/// All you need to do is to implement the [`BinCodec`] trait together with `Default` and `Clone`.
+///
+/// ## Server-Side Rendering
+///
+/// On the server no connection is made. `ready_state` is always `ConnectionReadyState::Closed`,
+/// the signals are `None`, sending does nothing and opening streams returns
+/// `WebTransportError::NotConnected`. On the client the connection is opened after hydration.
 pub fn use_webtransport(url: &str) -> UseWebTransportReturn {
     use_webtransport_with_options(url, UseWebTransportOptions::default())
 }
@@ -51,25 +199,46 @@ pub fn use_webtransport_with_options(
 ) -> UseWebTransportReturn {
     let UseWebTransportOptions {
         on_open,
-        // on_error,
+        on_error,
         on_close,
         on_receive_stream,
         on_bidir_stream,
         reconnect_limit,
-        reconnect_interval,
+        reconnect_strategy,
+        on_reconnect_attempt,
         immediate,
+        congestion_control,
+        allow_pooling,
+        require_unreliable,
+        server_certificate_hashes,
+        send_buffer_limit,
     } = options;
     let url = url.to_string();
 
     let (ready_state, set_ready_state) = create_signal(ConnectionReadyState::Closed);
     let ready_state: Signal<_> = ready_state.into();
 
+    let (incoming_bidir_stream, set_incoming_bidir_stream) = create_signal(None::<BidirStream>);
+    let (incoming_receive_stream, set_incoming_receive_stream) =
+        create_signal(None::<ReceiveStream>);
+
+    let context = StreamContext {
+        ready_state,
+        owner: Owner::current(),
+        on_error,
+    };
+
     let transport = Rc::new(RefCell::new(None::<web_sys::WebTransport>));
-    let datagrams_reader_initialized = Rc::new(Cell::new(false));
+    let datagrams_reader = Rc::new(RefCell::new(None::<web_sys::ReadableStreamDefaultReader>));
     let datagrams_writer = Rc::new(RefCell::new(None::<web_sys::WritableStreamDefaultWriter>));
 
+    let send_buffer = Rc::new(RefCell::new(VecDeque::<Vec<u8>>::new()));
+    let (overflowed, set_overflowed) = create_signal(false);
+    let (close_info, set_close_info) = create_signal(None::<CloseInfo>);
+
     let reconnect_timer = Rc::new(Cell::new(None::<TimeoutHandle>));
     let reconnect_count = Rc::new(Cell::new(0_u64));
+    let generation = Rc::new(Cell::new(0_u64));
 
     let unmounted = Rc::new(Cell::new(false));
 
@@ -81,17 +250,29 @@ pub fn use_webtransport_with_options(
 
         move || {
             if reconnect_count.get() < reconnect_limit
-                && ready_state.get_untracked() == ConnectionReadyState::Open
+                && ready_state.get_untracked() != ConnectionReadyState::Open
             {
+                let reconnect_count = Rc::clone(&reconnect_count);
+                let on_reconnect_attempt = Rc::clone(&on_reconnect_attempt);
+                let attempt = reconnect_count.get() + 1;
+
                 reconnect_timer.set(
                     set_timeout_with_handle(
                         move || {
                             if let Some(connect) = connect_ref.get_value() {
+                                #[cfg(debug_assertions)]
+                                let prev = SpecialNonReactiveZone::enter();
+
+                                on_reconnect_attempt(attempt);
+
+                                #[cfg(debug_assertions)]
+                                SpecialNonReactiveZone::exit(prev);
+
                                 connect();
-                                reconnect_count.set(reconnect_count.get() + 1);
+                                reconnect_count.set(attempt);
                             }
                         },
-                        Duration::from_millis(reconnect_interval),
+                        reconnect_strategy.delay(attempt),
                     )
                     .ok(),
                 )
@@ -99,84 +280,207 @@ pub fn use_webtransport_with_options(
         }
     });
 
-    connect_ref.set_value(Some(Rc::new({
-        let transport = Rc::clone(&transport);
-        let reconnect_timer = Rc::clone(&reconnect_timer);
-        let on_open = Rc::clone(&on_open);
-        let on_bidir_stream = Rc::clone(&on_bidir_stream);
-        let on_receive_stream = Rc::clone(&on_receive_stream);
+    let on_closed = {
+        let reconnect = Rc::clone(&reconnect);
+        let unmounted = Rc::clone(&unmounted);
 
-        move || {
-            reconnect_timer.set(None);
+        move |closed_cleanly: bool| {
+            if unmounted.get() {
+                return;
+            }
 
-            if let Some(transport) = transport.borrow().as_ref() {
-                transport.close();
+            on_close();
+
+            // the server ended the session on purpose
+            if !closed_cleanly {
+                reconnect();
             }
+        }
+    };
 
-            let options = web_sys::WebTransportOptions::new();
-            transport.replace(Some(
-                web_sys::WebTransport::new_with_options(&url, &options).unwrap_throw(),
-            ));
+    #[cfg(not(feature = "ssr"))]
+    {
+        let transport_options = web_sys::WebTransportOptions::new();
+        transport_options.set_congestion_control(congestion_control);
+        transport_options.set_allow_pooling(allow_pooling);
+        transport_options.set_require_unreliable(require_unreliable);
+        if !server_certificate_hashes.is_empty() {
+            let hashes = server_certificate_hashes
+                .iter()
+                .map(web_sys::WebTransportHash::from)
+                .collect::<Vec<_>>();
+            transport_options.set_server_certificate_hashes(&hashes);
+        }
 
-            set_ready_state.set(ConnectionReadyState::Connecting);
+        connect_ref.set_value(Some(Rc::new({
+            let transport = Rc::clone(&transport);
+            let reconnect_timer = Rc::clone(&reconnect_timer);
+            let reconnect_count = Rc::clone(&reconnect_count);
+            let generation = Rc::clone(&generation);
+            let context = context.clone();
+            let datagrams_writer = Rc::clone(&datagrams_writer);
+            let send_buffer = Rc::clone(&send_buffer);
+
+            move || {
+                reconnect_timer.set(None);
+
+                if let Some(transport) = transport.borrow().as_ref() {
+                    transport.close();
+                }
 
-            spawn_local({
-                let transport = Rc::clone(&transport);
-                let on_open = Rc::clone(&on_open);
-                let on_bidir_stream = Rc::clone(&on_bidir_stream);
-                let on_receive_stream = Rc::clone(&on_receive_stream);
+                datagrams_writer.replace(None);
+
+                generation.set(generation.get() + 1);
+                let current_generation = generation.get();
+
+                let new_transport =
+                    match web_sys::WebTransport::new_with_options(&url, &transport_options) {
+                        Ok(new_transport) => new_transport,
+                        Err(err) => {
+                            transport.replace(None);
+                            set_ready_state.set(ConnectionReadyState::Closed);
+                            context.report_error(WebTransportError::SessionFailed(err));
+                            return;
+                        }
+                    };
+                transport.replace(Some(new_transport.clone()));
+
+                set_ready_state.set(ConnectionReadyState::Connecting);
+                set_close_info.set(None);
+
+                spawn_local({
+                    let transport = new_transport.clone();
+                    let generation = Rc::clone(&generation);
+                    let context = context.clone();
+                    let on_open = Rc::clone(&on_open);
+                    let on_bidir_stream = Rc::clone(&on_bidir_stream);
+                    let on_receive_stream = Rc::clone(&on_receive_stream);
+                    let datagrams_writer = Rc::clone(&datagrams_writer);
+                    let send_buffer = Rc::clone(&send_buffer);
+                    let reconnect_count = Rc::clone(&reconnect_count);
+
+                    async move {
+                        // errors are handled below when the `closed` promise is rejected
+                        if js_fut!(transport.ready()).await.is_err()
+                            || generation.get() != current_generation
+                        {
+                            return;
+                        }
+
+                        set_ready_state.set(ConnectionReadyState::Open);
+
+                        // the next connection loss starts with the first attempt again
+                        reconnect_count.set(0);
+
+                        let buffered = send_buffer.take();
+                        if !buffered.is_empty() {
+                            let writer =
+                                get_or_create_datagrams_writer(datagrams_writer, &transport);
+                            for data in buffered {
+                                let _ = writer
+                                    .write_with_chunk(&js_sys::Uint8Array::from(data.as_slice()));
+                            }
+                        }
+                        set_overflowed.set(false);
 
-                async move {
-                    let transport = transport.borrow();
-                    let transport = transport.as_ref().expect("Transport should be set");
+                        on_open();
 
-                    match js_fut!(transport.ready()).await {
-                        Ok(_) => {
-                            set_ready_state.set(ConnectionReadyState::Open);
-                            on_open();
+                        listen_to_stream(
+                            transport.incoming_bidirectional_streams(),
+                            {
+                                let context = context.clone();
 
-                            listen_to_stream(
-                                transport.incoming_bidirectional_streams(),
                                 move |value| {
                                     let stream: web_sys::WebTransportBidirectionalStream =
                                         value.unchecked_into();
 
-                                    if let Ok(stream) = create_bidir_stream(stream, ready_state) {
-                                        on_bidir_stream(stream);
+                                    match create_bidir_stream(stream, &context) {
+                                        Ok(stream) => {
+                                            on_bidir_stream(stream.clone());
+                                            set_incoming_bidir_stream.set(Some(stream));
+                                        }
+                                        Err(err) => context.report_error(err),
                                     }
-                                },
-                                || {},
-                            );
-                            listen_to_stream(
-                                transport.incoming_unidirectional_streams(),
-                                move |value| {
-                                    let stream: web_sys::ReadableStream = value.unchecked_into();
-
-                                    let (state, set_state, bytes) = create_state_and_bytes_signal(
-                                        stream.unchecked_into(),
-                                        ready_state,
-                                    );
-
-                                    on_receive_stream(ReceiveStream {
-                                        bytes,
-                                        state,
-                                        set_state,
-                                    });
-                                },
-                                || {},
-                            );
+                                }
+                            },
+                            |_| {},
+                            || {},
+                        );
+                        listen_to_stream(
+                            transport.incoming_unidirectional_streams(),
+                            move |value| {
+                                let stream: web_sys::ReadableStream = value.unchecked_into();
+                                let stream = create_receive_stream(stream, &context);
+
+                                on_receive_stream(stream.clone());
+                                set_incoming_receive_stream.set(Some(stream));
+                            },
+                            |_| {},
+                            || {},
+                        );
+                    }
+                });
+
+                spawn_local({
+                    let generation = Rc::clone(&generation);
+                    let context = context.clone();
+                    let on_closed = on_closed.clone();
+
+                    async move {
+                        let result = js_fut!(new_transport.closed()).await;
+
+                        if generation.get() != current_generation {
+                            return;
                         }
-                        Err(e) => {
-                            // TODO : handle error?
-                            set_ready_state.set(ConnectionReadyState::Closed);
+
+                        let closed_by_server = result.is_ok();
+
+                        let error = match result {
+                            Ok(info) => {
+                                let info: web_sys::WebTransportCloseInfo = info.unchecked_into();
+                                let info = CloseInfo {
+                                    code: info.get_close_code().unwrap_or_default(),
+                                    reason: info.get_reason().unwrap_or_default(),
+                                };
+                                set_close_info.set(Some(info.clone()));
+
+                                if info.code != 0 {
+                                    Some(WebTransportError::ClosedWithError {
+                                        code: info.code,
+                                        reason: info.reason,
+                                    })
+                                } else {
+                                    None
+                                }
+                            }
+                            Err(err) => match ready_state.get_untracked() {
+                                ConnectionReadyState::Connecting => {
+                                    Some(WebTransportError::SessionFailed(err))
+                                }
+                                ConnectionReadyState::Open => {
+                                    Some(WebTransportError::ConnectionLost(err))
+                                }
+                                _ => None,
+                            },
+                        };
+
+                        set_ready_state.set(ConnectionReadyState::Closed);
+
+                        // closed with the code `0`
+                        let closed_cleanly = closed_by_server && error.is_none();
+
+                        if let Some(error) = error {
+                            context.report_error(error);
                         }
+
+                        on_closed(closed_cleanly);
                     }
-                }
-            });
-        }
-    })));
+                });
+            }
+        })));
+    }
 
-    let open = {
+    let open: Rc<dyn Fn()> = Rc::new({
         let reconnect_count = Rc::clone(&reconnect_count);
 
         move || {
@@ -185,62 +489,50 @@ pub fn use_webtransport_with_options(
                 connect();
             }
         }
-    };
-
-    let on_closed = {
-        let reconnect = Rc::clone(&reconnect);
-        let unmounted = Rc::clone(&unmounted);
-
-        move || {
-            if unmounted.get() {
-                return;
-            }
-
-            // TODO
-            // reconnect();
-        }
-    };
+    });
 
-    let close = {
+    let close: Rc<dyn Fn(Option<CloseInfo>)> = Rc::new({
         let transport = Rc::clone(&transport);
+        let send_buffer = Rc::clone(&send_buffer);
+        let reconnect_timer = Rc::clone(&reconnect_timer);
         let reconnect_count = Rc::clone(&reconnect_count);
 
-        move || {
+        move |info: Option<CloseInfo>| {
             reconnect_count.set(reconnect_limit);
+            send_buffer.borrow_mut().clear();
+            if let Some(timer) = reconnect_timer.take() {
+                timer.clear();
+            }
 
-            if let Some(transport) = transport.take() {
-                transport.close();
-                set_ready_state.set(ConnectionReadyState::Closing);
-
-                spawn_local(async move {
-                    let result = js_fut!(transport.closed()).await;
-                    set_ready_state.set(ConnectionReadyState::Closed);
-
-                    on_closed();
-
-                    match result {
-                        Ok(_) => {}
-                        Err(e) => {
-                            // TODO : handle error?
-                        }
+            if let Some(transport) = transport.borrow().as_ref() {
+                if matches!(
+                    ready_state.get_untracked(),
+                    ConnectionReadyState::Connecting | ConnectionReadyState::Open
+                ) {
+                    set_ready_state.set(ConnectionReadyState::Closing);
+
+                    match info {
+                        Some(info) => transport.close_with_close_info(&(&info).into()),
+                        None => transport.close(),
                     }
-                });
+                }
             }
         }
-    };
+    });
 
     let (datagrams_signal, set_datagrams) = create_signal(None::<Vec<u8>>);
 
     let datagrams = Signal::derive({
         let transport = Rc::clone(&transport);
-        let datagrams_reader_initialized = Rc::clone(&datagrams_reader_initialized);
+        let context = context.clone();
+        let datagrams_reader = Rc::clone(&datagrams_reader);
 
         move || {
             let transport = Rc::clone(&transport);
 
             lazy_initialize_u8_reader(
                 ready_state,
-                Rc::clone(&datagrams_reader_initialized),
+                Rc::clone(&datagrams_reader),
                 move || {
                     transport
                         .borrow()
@@ -250,6 +542,7 @@ pub fn use_webtransport_with_options(
                         .readable()
                 },
                 set_datagrams,
+                context.stream_error_handler(),
                 || {},
             );
 
@@ -260,9 +553,11 @@ pub fn use_webtransport_with_options(
     {
         let unmounted = Rc::clone(&unmounted);
 
+        let close = Rc::clone(&close);
+
         on_cleanup(move || {
             unmounted.set(true);
-            close();
+            close(None);
         });
     }
 
@@ -274,7 +569,17 @@ pub fn use_webtransport_with_options(
         transport,
         ready_state,
         datagrams,
+        incoming_bidir_stream: incoming_bidir_stream.into(),
+        incoming_receive_stream: incoming_receive_stream.into(),
         datagrams_writer,
+        send_buffer,
+        send_buffer_limit,
+        overflowed: overflowed.into(),
+        set_overflowed,
+        close_info: close_info.into(),
+        open: store_value(open),
+        close: store_value(close),
+        context,
     }
 }
 
@@ -299,78 +604,94 @@ fn get_or_create_datagrams_writer(
 
 fn lazy_initialize_u8_reader(
     ready_state: Signal<ConnectionReadyState>,
-    initialized: Rc<Cell<bool>>,
+    reader: Rc<RefCell<Option<web_sys::ReadableStreamDefaultReader>>>,
     get_readable_stream: impl Fn() -> web_sys::ReadableStream,
     set_signal: WriteSignal<Option<Vec<u8>>>,
+    on_error: impl Fn(JsValue) + 'static,
     on_done: impl Fn() + 'static,
 ) {
     lazy_initialize_reader(
         ready_state,
-        initialized,
+        reader,
         get_readable_stream,
         move |value| {
             let value: js_sys::Uint8Array = value.into();
             set_signal.set(Some(value.to_vec()));
         },
+        on_error,
         on_done,
     );
 }
 
+/// Starts reading from the stream returned by `get_readable_stream` once the connection is open.
+/// The active reader is stored in `reader` so it can be cancelled and so it's only created once.
 fn lazy_initialize_reader(
     ready_state: Signal<ConnectionReadyState>,
-    initialized: Rc<Cell<bool>>,
+    reader: Rc<RefCell<Option<web_sys::ReadableStreamDefaultReader>>>,
     get_readable_stream: impl Fn() -> web_sys::ReadableStream,
     on_value: impl Fn(JsValue) + 'static,
+    on_error: impl Fn(JsValue) + 'static,
     on_done: impl Fn() + 'static,
 ) {
-    if ready_state.get() == ConnectionReadyState::Open {
-        if !initialized.get() {
-            initialized.set(true);
+    if ready_state.get() == ConnectionReadyState::Open && reader.borrow().is_none() {
+        let active_reader = listen_to_stream(get_readable_stream(), on_value, on_error, {
+            let reader = Rc::clone(&reader);
 
-            listen_to_stream(get_readable_stream(), on_value, move || {
-                initialized.set(false);
+            move || {
+                reader.replace(None);
                 on_done();
-            });
-        }
+            }
+        });
+
+        reader.replace(Some(active_reader));
     }
 }
 
+/// Reads all chunks of `readable_stream` and passes them to `on_value`. `on_done` is called
+/// when the stream ends, on a read error after `on_error` has been called.
 fn listen_to_stream(
     readable_stream: web_sys::ReadableStream,
     on_value: impl Fn(JsValue) + 'static,
+    on_error: impl Fn(JsValue) + 'static,
     on_done: impl Fn() + 'static,
-) {
+) -> web_sys::ReadableStreamDefaultReader {
     let reader: web_sys::ReadableStreamDefaultReader =
         readable_stream.get_reader().unchecked_into();
 
-    spawn_local(async move {
-        loop {
-            let result = js_fut!(reader.read()).await;
-            match result {
-                Ok(result) => {
-                    let done = js!(result["done"])
-                        .expect("done should always be there")
-                        .as_bool()
-                        .unwrap_or(true);
-
-                    if done {
-                        // TODO : close connection?
-                        break;
-                    }
+    spawn_local({
+        let reader = reader.clone();
+
+        async move {
+            loop {
+                let result = js_fut!(reader.read()).await;
+                match result {
+                    Ok(result) => {
+                        let done = js!(result["done"])
+                            .expect("done should always be there")
+                            .as_bool()
+                            .unwrap_or(true);
+
+                        if done {
+                            break;
+                        }
 
-                    let value = js!(result["value"]).expect("if not done there should be a value");
+                        let value =
+                            js!(result["value"]).expect("if not done there should be a value");
 
-                    on_value(value);
-                }
-                Err(..) => {
-                    // TODO : error handling?
-                    break;
+                        on_value(value);
+                    }
+                    Err(err) => {
+                        on_error(err);
+                        break;
+                    }
                 }
             }
-        }
 
-        on_done();
+            on_done();
+        }
     });
+
+    reader
 }
 
 /// Options for [`use_webtransport_with_options`].
@@ -379,8 +700,9 @@ pub struct UseWebTransportOptions {
     /// Callback when `WebTransport` is ready.
     on_open: Rc<dyn Fn()>,
 
-    /// Error callback.
-    // TODO : ? on_error: Rc<dyn Fn(WebTransportError)>,
+    /// Error callback. Called when the session can't be established, when the connection is
+    /// closed with an error or when reading from a stream fails.
+    on_error: Rc<dyn Fn(WebTransportError)>,
 
     /// Callback when `WebTransport` is closed.
     on_close: Rc<dyn Fn()>,
@@ -391,33 +713,127 @@ pub struct UseWebTransportOptions {
     /// Callback when the server opens a bidirectional stream.
     on_bidir_stream: Rc<dyn Fn(BidirStream)>,
 
-    /// Retry times. Defaults to 3.
+    /// Retry times for every loss of the connection. The count starts over once the connection
+    /// is open again. There is no reconnect when the server closes the session cleanly with the
+    /// code `0`. Defaults to 3.
     reconnect_limit: u64,
 
-    /// Retry interval in ms. Defaults to 3000.
-    reconnect_interval: u64,
+    /// How long to wait between reconnection attempts. Defaults to `ReconnectStrategy::Fixed(3000)`.
+    /// See also [`Self::reconnect_interval`].
+    reconnect_strategy: ReconnectStrategy,
+
+    /// Called right before every reconnection attempt with the number of the attempt starting at 1.
+    on_reconnect_attempt: Rc<dyn Fn(u64)>,
 
     /// If `true` the `WebSocket` connection will immediately be opened when calling this function.
     /// If `false` you have to manually call the `open` function.
     /// Defaults to `true`.
     immediate: bool,
+
+    /// Hint to the browser whether the application prefers throughput or low latency.
+    /// Defaults to `WebTransportCongestionControl::Default`.
+    congestion_control: web_sys::WebTransportCongestionControl,
+
+    /// If `true` the underlying network connection may be shared with other sessions.
+    /// Defaults to `false`.
+    allow_pooling: bool,
+
+    /// If `true` the connection can only be established over a protocol that supports
+    /// unreliable datagrams (HTTP/3). Defaults to `false`.
+    require_unreliable: bool,
+
+    /// Hashes of the certificates the server is allowed to use. If not empty the browser
+    /// verifies the server against these hashes instead of the Web PKI. This is
+    /// required for self-signed certificates as they are common during development.
+    /// Defaults to `vec![]`.
+    #[builder(into)]
+    server_certificate_hashes: Vec<ServerCertificateHash>,
+
+    /// How many datagrams are kept while the connection is being established. They are sent
+    /// as soon as the connection is open. If more are sent, they are dropped and
+    /// [`UseWebTransportReturn::overflowed`] is set to `true`. Set to `0` to disable buffering.
+    /// Defaults to 100.
+    send_buffer_limit: usize,
 }
 
 impl Default for UseWebTransportOptions {
     fn default() -> Self {
         Self {
             on_open: Rc::new(|| {}),
-            // on_error: Rc::new(|_| {}),
+            on_error: Rc::new(|_| {}),
             on_close: Rc::new(|| {}),
             on_receive_stream: Rc::new(|_| {}),
             on_bidir_stream: Rc::new(|_| {}),
             reconnect_limit: 3,
-            reconnect_interval: 3000,
+            reconnect_strategy: ReconnectStrategy::default(),
+            on_reconnect_attempt: Rc::new(|_| {}),
             immediate: true,
+            congestion_control: web_sys::WebTransportCongestionControl::Default,
+            allow_pooling: false,
+            require_unreliable: false,
+            server_certificate_hashes: vec![],
+            send_buffer_limit: 100,
         }
     }
 }
 
+impl UseWebTransportOptions {
+    /// Retry interval in ms. Shorthand for `reconnect_strategy(ReconnectStrategy::Fixed(interval))`.
+    pub fn reconnect_interval(self, interval: u64) -> Self {
+        self.reconnect_strategy(ReconnectStrategy::Fixed(interval))
+    }
+}
+
+/// Code and reason a `WebTransport` session was closed with.
+/// See [`UseWebTransportReturn::close_info`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CloseInfo {
+    /// Application specific close code. `0` if the session was closed without an error.
+    pub code: u32,
+
+    /// Human readable reason why the session was closed.
+    pub reason: String,
+}
+
+impl From<&CloseInfo> for web_sys::WebTransportCloseInfo {
+    fn from(info: &CloseInfo) -> Self {
+        let web_info = web_sys::WebTransportCloseInfo::new();
+        web_info.set_close_code(info.code);
+        web_info.set_reason(&info.reason);
+        web_info
+    }
+}
+
+/// Hash of a certificate the server is allowed to use.
+/// See [`UseWebTransportOptions::server_certificate_hashes`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServerCertificateHash {
+    /// Name of the hash algorithm. Currently browsers only support `"sha-256"`.
+    pub algorithm: String,
+
+    /// The raw bytes of the hash value.
+    pub value: Vec<u8>,
+}
+
+impl ServerCertificateHash {
+    /// Creates a SHA-256 certificate hash from the given raw bytes.
+    pub fn sha256(value: impl Into<Vec<u8>>) -> Self {
+        Self {
+            algorithm: "sha-256".to_string(),
+            value: value.into(),
+        }
+    }
+}
+
+impl From<&ServerCertificateHash> for web_sys::WebTransportHash {
+    fn from(hash: &ServerCertificateHash) -> Self {
+        let web_hash = web_sys::WebTransportHash::new();
+        web_hash.set_algorithm(&hash.algorithm);
+        web_hash.set_value(&js_sys::Uint8Array::from(hash.value.as_slice()));
+        web_hash
+    }
+}
+
 /// Wether the stream is open or closed
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum StreamState {
@@ -459,43 +875,70 @@ pub trait SendableStream: CloseableStream {
         }
 
         let arr = js_sys::Uint8Array::from(data);
-        let _ = js_fut!(self.writer().write_with_chunk(&arr))
+        js_fut!(self.writer().write_with_chunk(&arr))
             .await
-            .map_err(|e| SendError::FailedToWrite(e));
+            .map_err(SendError::FailedToWrite)?;
 
         Ok(())
     }
 
-    #[cfg(any(feature = "msgpack", feature = "bincode"))]
-    /// Send data in the form of a serializable object ignoring potential errors
-    /// Requires the feature `msgpack` or `bincode`
-    fn send<T: Serialize>(&self, data: &T) {
-        self.send_bytes(
-            to_vec(data)
-                .expect("Serialization should not fail")
-                .as_slice(),
-        );
+    /// Send a value encoded by the codec `C` ignoring potential errors
+    fn send<T, C>(&self, data: &T)
+    where
+        C: BinCodec<T> + Default,
+    {
+        if let Ok(bytes) = C::default().encode(data) {
+            self.send_bytes(&bytes);
+        }
     }
 
-    #[cfg(any(feature = "msgpack", feature = "bincode"))]
-    /// Send data in the form of a serializable object asynchronously with a result providing potential errors
-    /// Requires the feature `msgpack` or `bincode`
-    async fn send_async<T: Serialize>(&self, data: &T) -> Result<(), SendError> {
-        let serialized = to_vec(data)?;
-        self.send_bytes_async(&serialized).await
+    /// Send a value encoded by the codec `C` asynchronously with a result providing potential errors
+    async fn send_async<T, C>(&self, data: &T) -> Result<(), SendMessageError<C::Error>>
+    where
+        C: BinCodec<T> + Default,
+    {
+        let bytes = C::default()
+            .encode(data)
+            .map_err(SendMessageError::Encode)?;
+        self.send_bytes_async(&bytes).await?;
+
+        Ok(())
     }
 }
 
-#[async_trait(?Send)]
 /// Trait to receive data in a stream
 pub trait ReceivableStream: CloseableStream {
-    #[cfg(any(feature = "msgpack", feature = "bincode"))]
-    /// Receive data in the form of a serializable object ignoring potential errors
-    fn receive<T: for<'a> Deserialize<'a>>(&self) -> Signal<Option<T>>;
+    /// Latest bytes received on this stream. The stream is only read once this signal is accessed.
+    fn bytes(&self) -> Signal<Option<Vec<u8>>>;
+
+    /// Latest value received on this stream decoded by the codec `C`. Values that fail to decode are ignored.
+    fn receive<T, C>(&self) -> Signal<Option<T>>
+    where
+        T: 'static,
+        C: BinCodec<T> + Default,
+    {
+        let bytes = self.bytes();
+        let codec = C::default();
+
+        Signal::derive(move || {
+            bytes
+                .get()
+                .and_then(|bytes| codec.decode(bytes.as_slice()).ok())
+        })
+    }
+
+    /// Latest value received on this stream decoded by the codec `C` including potential decoding errors.
+    fn try_receive<T, C>(&self) -> Signal<Option<Result<T, C::Error>>>
+    where
+        T: 'static,
+        C: BinCodec<T> + Default,
+        C::Error: 'static,
+    {
+        let bytes = self.bytes();
+        let codec = C::default();
 
-    #[cfg(any(feature = "msgpack", feature = "bincode"))]
-    /// Receive data in the form of a serializable object asynchronously with a result providing potential errors
-    fn try_receive<T: for<'a> Deserialize<'a>>(&self) -> Signal<Option<Result<T, ReceiveError>>>;
+        Signal::derive(move || bytes.get().map(|bytes| codec.decode(bytes.as_slice())))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -508,55 +951,65 @@ pub struct SendStream {
 
 #[derive(Clone, Debug)]
 /// Stream for receiving data
-#[allow(dead_code)]
 pub struct ReceiveStream {
+    reader: StreamReader,
+    /// Latest bytes received on this stream. The stream is only read once this signal is accessed.
+    /// See [`ReceivableStream::receive`] to decode the bytes into a value.
     pub bytes: Signal<Option<Vec<u8>>>,
     state: Signal<StreamState>,
     set_state: WriteSignal<StreamState>,
 }
 
-// TODO : implement ReceiveStream
-
 #[derive(Clone, Debug)]
 /// Bidirectional stream for sending and receiving data
 pub struct BidirStream {
     writer: web_sys::WritableStreamDefaultWriter,
+    reader: StreamReader,
+    /// Latest bytes received on this stream. The stream is only read once this signal is accessed.
+    /// See [`ReceivableStream::receive`] to decode the bytes into a value.
     pub bytes: Signal<Option<Vec<u8>>>,
     state: Signal<StreamState>,
     set_state: WriteSignal<StreamState>,
 }
 
+/// The readable side of a stream together with its reader once reading has started.
+#[derive(Clone, Debug)]
+struct StreamReader {
+    stream: web_sys::ReadableStream,
+    reader: Rc<RefCell<Option<web_sys::ReadableStreamDefaultReader>>>,
+}
+
+impl StreamReader {
+    fn new(stream: web_sys::ReadableStream) -> Self {
+        Self {
+            stream,
+            reader: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    /// Cancels the reader if reading has started already or the stream itself otherwise.
+    async fn cancel(&self) -> Result<(), WebTransportError> {
+        let reader = self.reader.take();
+
+        let promise = match reader {
+            Some(reader) => reader.cancel(),
+            None => self.stream.cancel(),
+        };
+
+        js_fut!(promise)
+            .await
+            .map_err(WebTransportError::OnCloseReader)?;
+
+        Ok(())
+    }
+}
+
 macro_rules! impl_receivable_stream {
     ($ty:ty) => {
-        impl BidirStream {
-            #[cfg(any(feature = "msgpack", feature = "bincode"))]
-            pub fn receive<T: for<'a> Deserialize<'a>>(&self) -> Signal<Option<T>> {
-                let bytes = self.bytes;
-
-                Signal::derive(move || {
-                    if self.state.get() != StreamState::Open {
-                        None
-                    } else {
-                        bytes
-                            .get()
-                            .and_then(|bytes| from_slice(bytes.as_slice()).ok())
-                    }
-                })
-            }
-
-            #[cfg(any(feature = "msgpack", feature = "bincode"))]
-            pub fn try_receive<T: for<'a> Deserialize<'a>>(
-                &self,
-            ) -> Signal<Option<Result<T, ReceiveError>>> {
-                let bytes = self.bytes;
-
-                Signal::derive(move || {
-                    if self.state.get() != StreamState::Open {
-                        None
-                    } else {
-                        bytes.get().map(|bytes| Ok(from_slice(bytes.as_slice())?))
-                    }
-                })
+        impl ReceivableStream for $ty {
+            #[inline(always)]
+            fn bytes(&self) -> Signal<Option<Vec<u8>>> {
+                self.bytes
             }
         }
     };
@@ -591,50 +1044,133 @@ macro_rules! impl_closable_stream {
 
             #[inline(always)]
             fn close(&self) {
-                spawn_local(async {
-                    self.close_async().await.ok();
+                let stream = self.clone();
+
+                spawn_local(async move {
+                    stream.close_async().await.ok();
                 })
             }
 
             async fn close_async(&self) -> Result<(), WebTransportError> {
-                let _ = js_fut!(self.writer.close()).await.map_err(|e| {
-                    let error = WebTransportError::OnCloseWriter(e);
-                    self.set_state.set(StreamState::Closed);
-                    error
-                })?;
-
+                let result = self.close_inner().await;
                 self.set_state.set(StreamState::Closed);
-
-                Ok(())
+                result
             }
         }
     };
 }
 
 impl_closable_stream!(SendStream);
+impl_closable_stream!(ReceiveStream);
 impl_closable_stream!(BidirStream);
 
+impl SendStream {
+    async fn close_inner(&self) -> Result<(), WebTransportError> {
+        js_fut!(self.writer.close())
+            .await
+            .map_err(WebTransportError::OnCloseWriter)?;
+
+        Ok(())
+    }
+}
+
+impl ReceiveStream {
+    async fn close_inner(&self) -> Result<(), WebTransportError> {
+        self.reader.cancel().await
+    }
+}
+
+impl BidirStream {
+    async fn close_inner(&self) -> Result<(), WebTransportError> {
+        let writer_result = js_fut!(self.writer.close())
+            .await
+            .map_err(WebTransportError::OnCloseWriter);
+        let reader_result = self.reader.cancel().await;
+
+        writer_result?;
+        reader_result
+    }
+}
+
 /// Return type of [`use_webtransport`].
 #[derive(Clone, Debug)]
 pub struct UseWebTransportReturn {
     transport: Rc<RefCell<Option<web_sys::WebTransport>>>,
     datagrams_writer: Rc<RefCell<Option<web_sys::WritableStreamDefaultWriter>>>,
+    send_buffer: Rc<RefCell<VecDeque<Vec<u8>>>>,
+    send_buffer_limit: usize,
+    set_overflowed: WriteSignal<bool>,
+    open: StoredValue<Rc<dyn Fn()>>,
+    close: StoredValue<Rc<dyn Fn(Option<CloseInfo>)>>,
 
     /// The current state of the `WebTransport` connection.
     pub ready_state: Signal<ConnectionReadyState>,
 
     /// Latest datagrams message received
     pub datagrams: Signal<Option<Vec<u8>>>,
+
+    /// `true` if datagrams had to be dropped because the send buffer was full while connecting.
+    /// Reset to `false` once the connection is open.
+    /// See [`UseWebTransportOptions::send_buffer_limit`].
+    pub overflowed: Signal<bool>,
+
+    /// Code and reason the session was closed with. `None` while a session is active or if it
+    /// was closed because of an error.
+    pub close_info: Signal<Option<CloseInfo>>,
+
+    /// Latest bidirectional stream opened by the server.
+    /// See also [`UseWebTransportOptions::on_bidir_stream`] to get notified of every new stream.
+    pub incoming_bidir_stream: Signal<Option<BidirStream>>,
+
+    /// Latest unidirectional (receive only) stream opened by the server.
+    /// See also [`UseWebTransportOptions::on_receive_stream`] to get notified of every new stream.
+    pub incoming_receive_stream: Signal<Option<ReceiveStream>>,
+
+    context: StreamContext,
 }
 
 impl UseWebTransportReturn {
+    /// (Re-)Opens the `WebTransport` connection.
+    /// If the current one is active, it's closed before opening a new one.
+    pub fn open(&self) {
+        (self.open.get_value())();
+    }
+
+    /// Closes the `WebTransport` connection.
+    pub fn close(&self) {
+        (self.close.get_value())(None);
+    }
+
+    /// Closes the `WebTransport` connection with an application specific error `code` and a
+    /// `reason`. The server receives both and they are also available in [`Self::close_info`]
+    /// once the connection is closed.
+    pub fn close_with_info(&self, code: u32, reason: &str) {
+        (self.close.get_value())(Some(CloseInfo {
+            code,
+            reason: reason.to_string(),
+        }));
+    }
+
     /// Access to the underlying `WebTransport`
     pub async fn transport(&self) -> Option<web_sys::WebTransport> {
         self.transport.borrow().clone()
     }
 
-    /// Sends binary data through the datagrams stream
-    pub fn send_datagrams(&self, data: &[u8]) {
+    /// Sends a single datagram ignoring potential errors.
+    ///
+    /// Datagrams are unreliable and unordered. Use a stream if you need delivery guarantees.
+    /// While the connection is being established, datagrams are buffered.
+    /// See [`UseWebTransportOptions::send_buffer_limit`].
+    pub fn send_datagram(&self, data: &[u8]) {
+        match self.ready_state.get_untracked() {
+            ConnectionReadyState::Open => {}
+            ConnectionReadyState::Connecting => {
+                let _ = self.buffer_datagram(data);
+                return;
+            }
+            _ => return,
+        }
+
         if let Some(transport) = self.transport.borrow().as_ref() {
             let writer =
                 get_or_create_datagrams_writer(Rc::clone(&self.datagrams_writer), transport);
@@ -644,50 +1180,177 @@ impl UseWebTransportReturn {
         }
     }
 
-    // TODO : send_datagrams_async
+    /// Sends a single datagram asynchronously with a result providing potential errors.
+    /// While the connection is being established, the datagram is buffered and `Ok` is returned
+    /// right away. See [`UseWebTransportOptions::send_buffer_limit`].
+    pub async fn send_datagram_async(&self, data: &[u8]) -> Result<(), SendError> {
+        match self.ready_state.get_untracked() {
+            ConnectionReadyState::Open => {}
+            ConnectionReadyState::Connecting => return self.buffer_datagram(data),
+            _ => return Err(SendError::StreamNotOpen),
+        }
 
-    /// Open a unidirectional send stream
-    pub async fn open_send_stream(&self) -> Result<SendStream, WebTransportError> {
-        if let Some(transport) = self.transport.borrow().as_ref() {
+        let writer = {
+            let transport = self.transport.borrow();
+            let transport = transport.as_ref().ok_or(SendError::StreamNotOpen)?;
+            get_or_create_datagrams_writer(Rc::clone(&self.datagrams_writer), transport)
+        };
+
+        let arr = js_sys::Uint8Array::from(data);
+        js_fut!(writer.write_with_chunk(&arr))
+            .await
+            .map_err(SendError::FailedToWrite)?;
+
+        Ok(())
+    }
+
+    fn buffer_datagram(&self, data: &[u8]) -> Result<(), SendError> {
+        let mut send_buffer = self.send_buffer.borrow_mut();
+
+        if send_buffer.len() < self.send_buffer_limit {
+            send_buffer.push_back(data.to_vec());
+            Ok(())
+        } else {
+            self.set_overflowed.set(true);
+            Err(SendError::BufferFull)
+        }
+    }
+
+    /// Sends a value encoded by the codec `C` as a single datagram ignoring potential errors.
+    pub fn send<T, C>(&self, data: &T)
+    where
+        C: BinCodec<T> + Default,
+    {
+        if let Ok(bytes) = C::default().encode(data) {
+            self.send_datagram(&bytes);
+        }
+    }
+
+    /// Sends a value encoded by the codec `C` as a single datagram asynchronously with a result
+    /// providing potential errors.
+    pub async fn send_async<T, C>(&self, data: &T) -> Result<(), SendMessageError<C::Error>>
+    where
+        C: BinCodec<T> + Default,
+    {
+        let bytes = C::default()
+            .encode(data)
+            .map_err(SendMessageError::Encode)?;
+        self.send_datagram_async(&bytes).await?;
+
+        Ok(())
+    }
+
+    /// Latest datagram received decoded by the codec `C`. Datagrams that fail to decode are ignored.
+    pub fn message<T, C>(&self) -> Signal<Option<T>>
+    where
+        T: 'static,
+        C: BinCodec<T> + Default,
+    {
+        let datagrams = self.datagrams;
+        let codec = C::default();
+
+        Signal::derive(move || {
+            datagrams
+                .get()
+                .and_then(|bytes| codec.decode(bytes.as_slice()).ok())
+        })
+    }
+
+    /// The maximum size in bytes of a datagram that can be passed to [`Self::send_datagram`].
+    /// Returns `None` if there is no connection yet.
+    pub fn max_datagram_size(&self) -> Option<u32> {
+        self.transport
+            .borrow()
+            .as_ref()
+            .map(|transport| transport.datagrams().max_datagram_size())
+    }
+
+    /// Open a unidirectional (send only) stream. Use the methods of [`SendableStream`] to write to it.
+    pub async fn open_unidirectional_stream(&self) -> Result<SendStream, WebTransportError> {
+        let transport = self.transport.borrow().clone();
+
+        if let Some(transport) = transport {
             let result = js_fut!(transport.create_unidirectional_stream())
                 .await
-                .map_err(|e| WebTransportError::FailedToOpenStream(e))?;
+                .map_err(WebTransportError::FailedToOpenStream)?;
             let stream: web_sys::WritableStream = result.unchecked_into();
-            let writer = stream
-                .get_writer()
-                .map_err(|e| WebTransportError::FailedToOpenWriter(e))?;
 
-            let (state, set_state) = create_signal(StreamState::Open);
-
-            Ok(SendStream {
-                writer,
-                state: state.into(),
-                set_state,
-            })
+            create_send_stream(stream, &self.context)
         } else {
             Err(WebTransportError::NotConnected)
         }
     }
 
-    /// Open a bidirectional stream
-    pub async fn open_bidir_stream(&self) -> Result<BidirStream, WebTransportError> {
-        if let Some(transport) = self.transport.borrow().as_ref() {
+    /// Open a bidirectional stream. The returned stream can be used to send data with the
+    /// methods of [`SendableStream`] and received data is available in [`BidirStream::bytes`].
+    pub async fn open_bidirectional_stream(&self) -> Result<BidirStream, WebTransportError> {
+        let transport = self.transport.borrow().clone();
+
+        if let Some(transport) = transport {
             let result = js_fut!(transport.create_bidirectional_stream())
                 .await
-                .map_err(|e| WebTransportError::FailedToOpenStream(e))?;
+                .map_err(WebTransportError::FailedToOpenStream)?;
             let stream: web_sys::WebTransportBidirectionalStream = result.unchecked_into();
-            let ready_state = self.ready_state;
 
-            create_bidir_stream(stream, ready_state)
+            create_bidir_stream(stream, &self.context)
         } else {
             Err(WebTransportError::NotConnected)
         }
     }
 }
 
-fn create_state_and_bytes_signal(
-    stream: web_sys::ReadableStream,
+/// State of the hook that is shared with all the streams it creates.
+#[derive(Clone)]
+struct StreamContext {
     ready_state: Signal<ConnectionReadyState>,
+    owner: Option<Owner>,
+    on_error: Rc<dyn Fn(WebTransportError)>,
+}
+
+impl StreamContext {
+    /// Runs `f` with the owner of the hook if there is one.
+    /// This way signals and cleanups created from async callbacks are disposed together with the hook.
+    fn with_hook_owner<T>(&self, f: impl FnOnce() -> T) -> T {
+        match self.owner {
+            Some(owner) => with_owner(owner, f),
+            None => f(),
+        }
+    }
+
+    fn report_error(&self, error: WebTransportError) {
+        #[cfg(debug_assertions)]
+        let prev = SpecialNonReactiveZone::enter();
+
+        (self.on_error)(error);
+
+        #[cfg(debug_assertions)]
+        SpecialNonReactiveZone::exit(prev);
+    }
+
+    /// Reports read errors as long as the connection is open. Once it's closing all readers
+    /// fail which is expected and not worth reporting.
+    fn stream_error_handler(&self) -> impl Fn(JsValue) + 'static {
+        let context = self.clone();
+
+        move |err| {
+            if context.ready_state.get_untracked() == ConnectionReadyState::Open {
+                context.report_error(WebTransportError::FailedToRead(err));
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for StreamContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamContext")
+            .field("ready_state", &self.ready_state)
+            .finish_non_exhaustive()
+    }
+}
+
+fn create_state_and_bytes_signal(
+    reader: &StreamReader,
+    context: &StreamContext,
 ) -> (
     Signal<StreamState>,
     WriteSignal<StreamState>,
@@ -696,19 +1359,24 @@ fn create_state_and_bytes_signal(
     let (state, set_state) = create_signal(StreamState::Open);
 
     let bytes = Signal::derive({
-        let reader_initialized = Rc::new(Cell::new(false));
         let (message_signal, set_message) = create_signal(None::<Vec<u8>>);
 
-        let stream = stream.clone();
+        let StreamReader { stream, reader } = reader.clone();
+        let ready_state = context.ready_state;
+        let on_error = Rc::new(context.stream_error_handler());
 
         move || {
             let stream = stream.clone();
 
             lazy_initialize_u8_reader(
                 ready_state,
-                Rc::clone(&reader_initialized),
-                move || stream.clone().unchecked_into(),
+                Rc::clone(&reader),
+                move || stream.clone(),
                 set_message,
+                {
+                    let on_error = Rc::clone(&on_error);
+                    move |err| on_error(err)
+                },
                 move || {
                     set_state.set(StreamState::Closed);
                 },
@@ -721,26 +1389,86 @@ fn create_state_and_bytes_signal(
     (state.into(), set_state, bytes)
 }
 
+fn create_send_stream(
+    stream: web_sys::WritableStream,
+    context: &StreamContext,
+) -> Result<SendStream, WebTransportError> {
+    let writer = stream
+        .get_writer()
+        .map_err(WebTransportError::FailedToOpenWriter)?;
+
+    Ok(context.with_hook_owner(|| {
+        let (state, set_state) = create_signal(StreamState::Open);
+
+        let send_stream = SendStream {
+            writer,
+            state: state.into(),
+            set_state,
+        };
+
+        on_cleanup({
+            let send_stream = send_stream.clone();
+            move || send_stream.close()
+        });
+
+        send_stream
+    }))
+}
+
+fn create_receive_stream(
+    stream: web_sys::ReadableStream,
+    context: &StreamContext,
+) -> ReceiveStream {
+    context.with_hook_owner(|| {
+        let reader = StreamReader::new(stream);
+
+        let (state, set_state, bytes) = create_state_and_bytes_signal(&reader, context);
+
+        let receive_stream = ReceiveStream {
+            reader,
+            bytes,
+            state,
+            set_state,
+        };
+
+        on_cleanup({
+            let receive_stream = receive_stream.clone();
+            move || receive_stream.close()
+        });
+
+        receive_stream
+    })
+}
+
 fn create_bidir_stream(
     stream: WebTransportBidirectionalStream,
-    ready_state: Signal<ConnectionReadyState>,
+    context: &StreamContext,
 ) -> Result<BidirStream, WebTransportError> {
     let writer = stream
         .writable()
         .get_writer()
-        .map_err(|e| WebTransportError::FailedToOpenWriter(e))?;
+        .map_err(WebTransportError::FailedToOpenWriter)?;
 
-    let (state, set_state, bytes) =
-        create_state_and_bytes_signal(stream.readable().unchecked_into(), ready_state);
+    Ok(context.with_hook_owner(|| {
+        let reader = StreamReader::new(stream.readable().unchecked_into());
 
-    let bidir_stream = BidirStream {
-        writer,
-        bytes,
-        state,
-        set_state,
-    };
+        let (state, set_state, bytes) = create_state_and_bytes_signal(&reader, context);
+
+        let bidir_stream = BidirStream {
+            writer,
+            reader,
+            bytes,
+            state,
+            set_state,
+        };
+
+        on_cleanup({
+            let bidir_stream = bidir_stream.clone();
+            move || bidir_stream.close()
+        });
 
-    Ok(bidir_stream)
+        bidir_stream
+    }))
 }
 
 /// Error enum for [`UseWebTransportOptions::on_error`]
@@ -748,6 +1476,12 @@ fn create_bidir_stream(
 pub enum WebTransportError {
     #[error("The `WebTransport` is not connected yet. Call `open` first.")]
     NotConnected,
+    #[error("Failed to establish the session: {0:?}")]
+    SessionFailed(JsValue),
+    #[error("The connection was lost: {0:?}")]
+    ConnectionLost(JsValue),
+    #[error("The server closed the connection with code {code}: {reason}")]
+    ClosedWithError { code: u32, reason: String },
     #[error("Failed to open stream: {0:?}")]
     FailedToOpenStream(JsValue),
     #[error("Failed to open writer: {0:?}")]
@@ -762,31 +1496,25 @@ pub enum WebTransportError {
     OnCloseReader(JsValue),
 }
 
-/// Error enum for [`SendStream::send`]
+/// Error enum for [`SendableStream::send_bytes_async`]
 #[derive(Error, Debug)]
 pub enum SendError {
     #[error("Stream is not open")]
     StreamNotOpen,
 
+    #[error("Send buffer is full")]
+    BufferFull,
+
     #[error("Failed to write to stream")]
     FailedToWrite(JsValue),
-
-    #[cfg(feature = "bincode")]
-    #[error("Serialization failed: {0}")]
-    SerializationFailed(#[from] bincode::Error),
-
-    #[cfg(feature = "msgpack")]
-    #[error("Serialization failed: {0}")]
-    SerializationFailed(#[from] rmp_serde::encode::Error),
 }
 
+/// Error enum for [`SendableStream::send_async`] and [`UseWebTransportReturn::send_async`]
 #[derive(Error, Debug)]
-pub enum ReceiveError {
-    #[cfg(feature = "bincode")]
-    #[error("Serialization failed: {0}")]
-    DeserializationFailed(#[from] bincode::Error),
-
-    #[cfg(feature = "msgpack")]
-    #[error("Serialization failed: {0}")]
-    DeserializationFailed(#[from] rmp_serde::decode::Error),
+pub enum SendMessageError<Err> {
+    #[error("Failed to send: {0}")]
+    Send(#[from] SendError),
+
+    #[error("Failed to encode value")]
+    Encode(Err),
 }
@@ -0,0 +1,387 @@
+use crate::utils::data_view_to_vec;
+use crate::{js, js_fut, use_event_listener, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::JsValue;
+use web_sys::{
+    UsbConnectionEvent, UsbControlTransferParameters, UsbDevice, UsbDeviceFilter,
+    UsbInTransferResult, UsbTransferStatus,
+};
+
+/// Reactive [WebUSB API](https://developer.mozilla.org/en-US/docs/Web/API/WebUSB_API).
+/// Lets the user grant access to USB devices, keeps a signal of the connected devices
+/// up to date and provides async helpers to claim interfaces and transfer data.
+///
+/// > This function requires `--cfg=web_sys_unstable_apis` to be activated as
+/// > [described in the wasm-bindgen guide](https://rustwasm.github.io/docs/wasm-bindgen/web-sys/unstable-apis.html).
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_webusb_with_options, UseWebUsbOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let filter = web_sys::UsbDeviceFilter::new();
+/// filter.set_vendor_id(0x2341);
+///
+/// let usb = use_webusb_with_options(
+///     UseWebUsbOptions::default()
+///         .filters(vec![filter])
+///         .on_disconnect(|device: web_sys::UsbDevice| {
+///             logging::log!("{:?} was unplugged", device.product_name())
+///         }),
+/// );
+///
+/// let connect = move |_| {
+///     spawn_local(async move {
+///         // shows the browser's device chooser
+///         let Ok(device) = usb.request_device().await else {
+///             return;
+///         };
+///
+///         if usb.claim(&device, 1, 0).await.is_ok() {
+///             let _ = usb.transfer_out(&device, 4, b"hello").await;
+///             let answer = usb.transfer_in(&device, 5, 64).await;
+///         }
+///     });
+/// };
+///
+/// view! {
+///     <Show
+///         when=move || usb.is_supported.get()
+///         fallback=|| view! { <p>"Your browser does not support WebUSB"</p> }
+///     >
+///         <button on:click=connect>"Connect"</button>
+///         <p>{move || usb.devices.get().len()} " devices connected"</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// `request_device` has to be called from a user gesture like a click. Devices the user has
+/// granted access to before are part of `devices` right away whenever they are plugged in.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `devices` is always empty and all operations return
+/// `UseWebUsbError::NotSupported`.
+pub fn use_webusb() -> UseWebUsbReturn {
+    use_webusb_with_options(UseWebUsbOptions::default())
+}
+
+/// Version of [`use_webusb`] that takes a `UseWebUsbOptions`. See [`use_webusb`] for how to use.
+pub fn use_webusb_with_options(options: UseWebUsbOptions) -> UseWebUsbReturn {
+    let UseWebUsbOptions {
+        filters,
+        on_connect,
+        on_disconnect,
+    } = options;
+
+    let is_supported = use_supported(|| js!("usb" in &window().navigator()));
+
+    let (devices, set_devices) = create_signal(Vec::<UsbDevice>::new());
+    let (error, set_error) = create_signal(None::<UseWebUsbError>);
+
+    let usb = UseWebUsbReturn {
+        is_supported,
+        devices: devices.into(),
+        error: error.into(),
+        set_devices,
+        set_error,
+        filters: store_value(filters),
+    };
+
+    usb.refresh();
+
+    let navigator_usb = if is_supported.get_untracked() {
+        Some(window().navigator().usb())
+    } else {
+        None
+    };
+
+    let _ = use_event_listener(
+        navigator_usb.clone(),
+        ev::Custom::<UsbConnectionEvent>::new("connect"),
+        move |event| {
+            usb.refresh();
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_connect(event.device());
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        },
+    );
+
+    let _ = use_event_listener(
+        navigator_usb,
+        ev::Custom::<UsbConnectionEvent>::new("disconnect"),
+        move |event| {
+            usb.refresh();
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_disconnect(event.device());
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        },
+    );
+
+    usb
+}
+
+/// Options for [`use_webusb_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseWebUsbOptions {
+    /// Only devices that match one of these filters are shown in the chooser. If empty (the
+    /// default) all devices are shown.
+    #[builder(into)]
+    filters: Vec<UsbDeviceFilter>,
+
+    /// Called when a device the user has granted access to is plugged in.
+    on_connect: Rc<dyn Fn(UsbDevice)>,
+
+    /// Called when a device the user has granted access to is unplugged.
+    on_disconnect: Rc<dyn Fn(UsbDevice)>,
+}
+
+impl Default for UseWebUsbOptions {
+    fn default() -> Self {
+        Self {
+            filters: vec![],
+            on_connect: Rc::new(|_| {}),
+            on_disconnect: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// Return type of [`use_webusb`].
+#[derive(Clone, Copy)]
+pub struct UseWebUsbReturn {
+    /// Whether the WebUSB API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// The connected devices the user has granted access to
+    pub devices: Signal<Vec<UsbDevice>>,
+
+    /// The last error that occurred
+    pub error: Signal<Option<UseWebUsbError>>,
+
+    set_devices: WriteSignal<Vec<UsbDevice>>,
+    set_error: WriteSignal<Option<UseWebUsbError>>,
+    filters: StoredValue<Vec<UsbDeviceFilter>>,
+}
+
+impl UseWebUsbReturn {
+    /// Shows the browser's device chooser and returns the selected device.
+    pub async fn request_device(&self) -> Result<UsbDevice, UseWebUsbError> {
+        let usb = self.navigator_usb()?;
+
+        let options = self
+            .filters
+            .with_value(|filters| web_sys::UsbDeviceRequestOptions::new(filters));
+
+        let device = js_fut!(usb.request_device(&options))
+            .await
+            .map_err(|err| self.fail(UseWebUsbError::RequestDevice(err)))?;
+
+        self.refresh();
+
+        Ok(device)
+    }
+
+    /// Opens `device`, selects the `configuration` if it isn't active yet and claims the
+    /// `interface` so that data can be transferred.
+    pub async fn claim(
+        &self,
+        device: &UsbDevice,
+        configuration: u8,
+        interface: u8,
+    ) -> Result<(), UseWebUsbError> {
+        self.navigator_usb()?;
+
+        if !device.opened() {
+            self.wait(js_fut!(device.open())).await?;
+        }
+
+        let active_configuration = device
+            .configuration()
+            .map(|configuration| configuration.configuration_value());
+
+        if active_configuration != Some(configuration) {
+            self.wait(js_fut!(device.select_configuration(configuration)))
+                .await?;
+        }
+
+        self.wait(js_fut!(device.claim_interface(interface)))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Releases the claimed `interface` of `device`.
+    pub async fn release(&self, device: &UsbDevice, interface: u8) -> Result<(), UseWebUsbError> {
+        self.navigator_usb()?;
+        self.wait(js_fut!(device.release_interface(interface)))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Closes `device`. This releases all claimed interfaces.
+    pub async fn close(&self, device: &UsbDevice) -> Result<(), UseWebUsbError> {
+        self.navigator_usb()?;
+        self.wait(js_fut!(device.close())).await?;
+
+        Ok(())
+    }
+
+    /// Receives up to `length` bytes from the bulk or interrupt `endpoint`.
+    pub async fn transfer_in(
+        &self,
+        device: &UsbDevice,
+        endpoint: u8,
+        length: u32,
+    ) -> Result<Vec<u8>, UseWebUsbError> {
+        self.navigator_usb()?;
+
+        let result = self
+            .wait(js_fut!(device.transfer_in(endpoint, length)))
+            .await?;
+
+        self.in_transfer_data(result)
+    }
+
+    /// Sends `data` to the bulk or interrupt `endpoint`. Returns the number of bytes written.
+    pub async fn transfer_out(
+        &self,
+        device: &UsbDevice,
+        endpoint: u8,
+        data: &[u8],
+    ) -> Result<u32, UseWebUsbError> {
+        self.navigator_usb()?;
+
+        let promise = device
+            .transfer_out_with_u8_slice(endpoint, data)
+            .map_err(|err| self.fail(UseWebUsbError::Failed(err)))?;
+
+        let result = self.wait(js_fut!(promise)).await?;
+
+        self.check_status(result.status())?;
+
+        Ok(result.bytes_written())
+    }
+
+    /// Receives up to `length` bytes with a control transfer described by `setup`.
+    pub async fn control_transfer_in(
+        &self,
+        device: &UsbDevice,
+        setup: &UsbControlTransferParameters,
+        length: u16,
+    ) -> Result<Vec<u8>, UseWebUsbError> {
+        self.navigator_usb()?;
+
+        let result = self
+            .wait(js_fut!(device.control_transfer_in(setup, length)))
+            .await?;
+
+        self.in_transfer_data(result)
+    }
+
+    /// Sends `data` with a control transfer described by `setup`. Returns the number of bytes
+    /// written.
+    pub async fn control_transfer_out(
+        &self,
+        device: &UsbDevice,
+        setup: &UsbControlTransferParameters,
+        data: &[u8],
+    ) -> Result<u32, UseWebUsbError> {
+        self.navigator_usb()?;
+
+        let promise = device
+            .control_transfer_out_with_u8_slice(setup, data)
+            .map_err(|err| self.fail(UseWebUsbError::Failed(err)))?;
+
+        let result = self.wait(js_fut!(promise)).await?;
+
+        self.check_status(result.status())?;
+
+        Ok(result.bytes_written())
+    }
+
+    /// Reads the connected devices again.
+    pub fn refresh(&self) {
+        let Ok(usb) = self.navigator_usb() else {
+            return;
+        };
+
+        let set_devices = self.set_devices;
+        let set_error = self.set_error;
+
+        spawn_local(async move {
+            match js_fut!(usb.get_devices()).await {
+                Ok(devices) => set_devices.set(devices.iter().collect()),
+                Err(err) => set_error.set(Some(UseWebUsbError::Failed(err))),
+            }
+        });
+    }
+
+    fn in_transfer_data(&self, result: UsbInTransferResult) -> Result<Vec<u8>, UseWebUsbError> {
+        self.check_status(result.status())?;
+
+        Ok(result
+            .data()
+            .map(|data| data_view_to_vec(&data))
+            .unwrap_or_default())
+    }
+
+    fn check_status(&self, status: UsbTransferStatus) -> Result<(), UseWebUsbError> {
+        match status {
+            UsbTransferStatus::Ok => Ok(()),
+            status => Err(self.fail(UseWebUsbError::Transfer(status))),
+        }
+    }
+
+    async fn wait<T>(
+        &self,
+        future: wasm_bindgen_futures::JsFuture<T>,
+    ) -> Result<T, UseWebUsbError> {
+        future
+            .await
+            .map_err(|err| self.fail(UseWebUsbError::Failed(err)))
+    }
+
+    fn navigator_usb(&self) -> Result<web_sys::Usb, UseWebUsbError> {
+        if !self.is_supported.get_untracked() {
+            return Err(UseWebUsbError::NotSupported);
+        }
+
+        Ok(window().navigator().usb())
+    }
+
+    fn fail(&self, err: UseWebUsbError) -> UseWebUsbError {
+        self.set_error.set(Some(err.clone()));
+        err
+    }
+}
+
+/// Errors of [`use_webusb`].
+#[derive(Error, Debug, Clone)]
+pub enum UseWebUsbError {
+    #[error("WebUSB is not supported")]
+    NotSupported,
+    #[error("requesting a device failed")]
+    RequestDevice(JsValue),
+    #[error("USB operation failed")]
+    Failed(JsValue),
+    #[error("transfer finished with status {0:?}")]
+    Transfer(UsbTransferStatus),
+}
@@ -0,0 +1,60 @@
+use super::BinCodec;
+
+/// A codec for binary messages that relies on [`bincode`] to encode them.
+///
+/// Bincode produces very small payloads but isn't self-describing so both sides have to agree on
+/// the exact same Rust types. This is ideal if client and server are both written in Rust and
+/// share the message definitions. Any type that implements [`serde::Serialize`] and
+/// [`serde::Deserialize`] can be used.
+///
+/// ## Example
+/// ```
+/// # use leptos_use::utils::{BinCodec, BincodeCodec};
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+/// pub struct Position {
+///     pub x: f32,
+///     pub y: f32,
+/// }
+///
+/// let codec = BincodeCodec;
+/// let bytes = codec.encode(&Position { x: 1.0, y: 2.0 }).unwrap();
+/// let position: Position = codec.decode(&bytes).unwrap();
+/// ```
+#[derive(Copy, Clone, Default, PartialEq)]
+pub struct BincodeCodec;
+
+impl<T: serde::Serialize + serde::de::DeserializeOwned> BinCodec<T> for BincodeCodec {
+    type Error = bincode::Error;
+
+    fn encode(&self, val: &T) -> Result<Vec<u8>, Self::Error> {
+        bincode::serialize(val)
+    }
+
+    fn decode(&self, val: &[u8]) -> Result<T, Self::Error> {
+        bincode::deserialize(val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bincode_codec() {
+        #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Test {
+            s: String,
+            i: i32,
+        }
+        let t = Test {
+            s: String::from("party time 🎉"),
+            i: 42,
+        };
+        let codec = BincodeCodec;
+        let enc = codec.encode(&t).unwrap();
+        let dec: Test = codec.decode(&enc).unwrap();
+        assert_eq!(dec, t);
+    }
+}
@@ -0,0 +1,70 @@
+use super::BinCodec;
+use thiserror::Error;
+
+/// A codec for binary messages that relies on [`ciborium`] to encode them as
+/// [CBOR](https://cbor.io/).
+///
+/// CBOR is a compact, self-describing binary format standardized in RFC 8949.
+/// Any type that implements [`serde::Serialize`] and [`serde::Deserialize`] can be used.
+///
+/// ## Example
+/// ```
+/// # use leptos_use::utils::{BinCodec, CborCodec};
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+/// pub struct Position {
+///     pub x: f32,
+///     pub y: f32,
+/// }
+///
+/// let codec = CborCodec;
+/// let bytes = codec.encode(&Position { x: 1.0, y: 2.0 }).unwrap();
+/// let position: Position = codec.decode(&bytes).unwrap();
+/// ```
+#[derive(Copy, Clone, Default, PartialEq)]
+pub struct CborCodec;
+
+#[derive(Error, Debug)]
+pub enum CborCodecError {
+    #[error("failed to encode cbor")]
+    Encode(#[from] ciborium::ser::Error<std::io::Error>),
+    #[error("failed to decode cbor")]
+    Decode(#[from] ciborium::de::Error<std::io::Error>),
+}
+
+impl<T: serde::Serialize + serde::de::DeserializeOwned> BinCodec<T> for CborCodec {
+    type Error = CborCodecError;
+
+    fn encode(&self, val: &T) -> Result<Vec<u8>, Self::Error> {
+        let mut bytes = vec![];
+        ciborium::into_writer(val, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn decode(&self, val: &[u8]) -> Result<T, Self::Error> {
+        Ok(ciborium::from_reader(val)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cbor_codec() {
+        #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Test {
+            s: String,
+            i: i32,
+        }
+        let t = Test {
+            s: String::from("party time 🎉"),
+            i: 42,
+        };
+        let codec = CborCodec;
+        let enc = codec.encode(&t).unwrap();
+        let dec: Test = codec.decode(&enc).unwrap();
+        assert_eq!(dec, t);
+    }
+}
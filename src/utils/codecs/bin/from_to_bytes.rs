@@ -77,3 +77,15 @@ impl BinCodec<String> for FromToBytesCodec {
         Ok(String::from_utf8(val.to_vec())?)
     }
 }
+
+impl BinCodec<Vec<u8>> for FromToBytesCodec {
+    type Error = FromToBytesCodecError;
+
+    fn encode(&self, val: &Vec<u8>) -> Result<Vec<u8>, Self::Error> {
+        Ok(val.clone())
+    }
+
+    fn decode(&self, val: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        Ok(val.to_vec())
+    }
+}
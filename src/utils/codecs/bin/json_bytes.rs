@@ -0,0 +1,59 @@
+use super::BinCodec;
+
+/// A codec for binary messages that relies on [`serde_json`] to encode them as UTF-8 JSON.
+///
+/// This is the binary counterpart of [`JsonCodec`](crate::utils::JsonCodec) for APIs that only
+/// transport bytes like `WebTransport`. Any type that implements [`serde::Serialize`] and
+/// [`serde::Deserialize`] can be used.
+///
+/// ## Example
+/// ```
+/// # use leptos_use::utils::{BinCodec, JsonBytesCodec};
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+/// pub struct Position {
+///     pub x: f32,
+///     pub y: f32,
+/// }
+///
+/// let codec = JsonBytesCodec;
+/// let bytes = codec.encode(&Position { x: 1.0, y: 2.0 }).unwrap();
+/// let position: Position = codec.decode(&bytes).unwrap();
+/// ```
+#[derive(Copy, Clone, Default, PartialEq)]
+pub struct JsonBytesCodec;
+
+impl<T: serde::Serialize + serde::de::DeserializeOwned> BinCodec<T> for JsonBytesCodec {
+    type Error = serde_json::Error;
+
+    fn encode(&self, val: &T) -> Result<Vec<u8>, Self::Error> {
+        serde_json::to_vec(val)
+    }
+
+    fn decode(&self, val: &[u8]) -> Result<T, Self::Error> {
+        serde_json::from_slice(val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_bytes_codec() {
+        #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Test {
+            s: String,
+            i: i32,
+        }
+        let t = Test {
+            s: String::from("party time 🎉"),
+            i: 42,
+        };
+        let codec = JsonBytesCodec;
+        let enc = codec.encode(&t).unwrap();
+        let dec: Test = codec.decode(&enc).unwrap();
+        assert_eq!(dec, t);
+    }
+}
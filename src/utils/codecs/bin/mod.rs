@@ -1,15 +1,30 @@
+#[cfg(feature = "bincode")]
+mod bincode;
+#[cfg(feature = "cbor")]
+mod cbor;
 mod from_to_bytes;
+#[cfg(feature = "serde_json")]
+mod json_bytes;
+#[cfg(feature = "msgpack")]
+mod msgpack;
 
-#[allow(unused_imports)]
+#[cfg(feature = "bincode")]
+pub use self::bincode::*;
+#[cfg(feature = "cbor")]
+pub use cbor::*;
 pub use from_to_bytes::*;
+#[cfg(feature = "serde_json")]
+pub use json_bytes::*;
+#[cfg(feature = "msgpack")]
+pub use msgpack::*;
 
-/// A codec for encoding and decoding values to and from strings.
-/// These strings are intended to be  sent over the network.
+/// A codec for encoding and decoding values to and from bytes.
+/// These bytes are intended to be sent over the network.
 pub trait BinCodec<T>: Clone + 'static {
     /// The error type returned when encoding or decoding fails.
     type Error;
-    /// Encodes a value to a string.
+    /// Encodes a value to bytes.
     fn encode(&self, val: &T) -> Result<Vec<u8>, Self::Error>;
-    /// Decodes a string to a value. Should be able to decode any string encoded by [`encode`].
+    /// Decodes bytes to a value. Should be able to decode any bytes encoded by [`encode`].
     fn decode(&self, val: &[u8]) -> Result<T, Self::Error>;
 }
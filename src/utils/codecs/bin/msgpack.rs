@@ -0,0 +1,69 @@
+use super::BinCodec;
+use thiserror::Error;
+
+/// A codec for binary messages that relies on [`rmp_serde`] to encode them as
+/// [MessagePack](https://msgpack.org/).
+///
+/// MessagePack is a compact binary format which makes it a good fit for network protocols
+/// like `WebTransport`. Any type that implements [`serde::Serialize`] and [`serde::Deserialize`]
+/// can be used.
+///
+/// ## Example
+/// ```
+/// # use leptos_use::utils::{BinCodec, MsgpackCodec};
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+/// pub struct Position {
+///     pub x: f32,
+///     pub y: f32,
+/// }
+///
+/// let codec = MsgpackCodec;
+/// let bytes = codec.encode(&Position { x: 1.0, y: 2.0 }).unwrap();
+/// let position: Position = codec.decode(&bytes).unwrap();
+/// ```
+#[derive(Copy, Clone, Default, PartialEq)]
+pub struct MsgpackCodec;
+
+#[derive(Error, Debug)]
+pub enum MsgpackCodecError {
+    #[error("failed to encode msgpack")]
+    Encode(#[from] rmp_serde::encode::Error),
+    #[error("failed to decode msgpack")]
+    Decode(#[from] rmp_serde::decode::Error),
+}
+
+impl<T: serde::Serialize + serde::de::DeserializeOwned> BinCodec<T> for MsgpackCodec {
+    type Error = MsgpackCodecError;
+
+    fn encode(&self, val: &T) -> Result<Vec<u8>, Self::Error> {
+        Ok(rmp_serde::to_vec(val)?)
+    }
+
+    fn decode(&self, val: &[u8]) -> Result<T, Self::Error> {
+        Ok(rmp_serde::from_slice(val)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_msgpack_codec() {
+        #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Test {
+            s: String,
+            i: i32,
+        }
+        let t = Test {
+            s: String::from("party time 🎉"),
+            i: 42,
+        };
+        let codec = MsgpackCodec;
+        let enc = codec.encode(&t).unwrap();
+        let dec: Test = codec.decode(&enc).unwrap();
+        assert_eq!(dec, t);
+    }
+}
@@ -0,0 +1,164 @@
+use super::{BinCodec, StringCodec};
+
+/// A message that is either text or binary like the messages of a `WebSocket`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HybridData {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+impl HybridData {
+    /// Returns the text. Binary data is interpreted as (lossy) UTF-8.
+    pub fn into_text(self) -> String {
+        match self {
+            Self::Text(text) => text,
+            Self::Binary(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        }
+    }
+
+    /// Returns the raw bytes. For text these are the UTF-8 bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::Text(text) => text.as_bytes(),
+            Self::Binary(bytes) => bytes,
+        }
+    }
+}
+
+/// A codec for encoding and decoding values to and from either text or binary messages.
+///
+/// String codecs like [`JsonCodec`](super::JsonCodec) produce text messages and binary codecs
+/// like [`MsgpackCodec`](super::MsgpackCodec) produce binary messages. All codecs of this crate
+/// implement this trait. To use your own [`StringCodec`] or [`BinCodec`] just implement it by
+/// delegating to the respective trait.
+///
+/// ```
+/// # use leptos_use::utils::{HybridCodec, HybridData, StringCodec};
+/// #
+/// #[derive(Clone, Default)]
+/// struct UpperCaseCodec;
+///
+/// impl StringCodec<String> for UpperCaseCodec {
+///     type Error = ();
+///
+///     fn encode(&self, val: &String) -> Result<String, Self::Error> {
+///         Ok(val.to_uppercase())
+///     }
+///
+///     fn decode(&self, str: String) -> Result<String, Self::Error> {
+///         Ok(str)
+///     }
+/// }
+///
+/// impl HybridCodec<String> for UpperCaseCodec {
+///     type Error = ();
+///
+///     fn encode(&self, val: &String) -> Result<HybridData, Self::Error> {
+///         StringCodec::encode(self, val).map(HybridData::Text)
+///     }
+///
+///     fn decode(&self, data: HybridData) -> Result<String, Self::Error> {
+///         StringCodec::decode(self, data.into_text())
+///     }
+/// }
+/// ```
+pub trait HybridCodec<T>: Clone + 'static {
+    /// The error type returned when encoding or decoding fails.
+    type Error;
+    /// Encodes a value to either a text or a binary message.
+    fn encode(&self, val: &T) -> Result<HybridData, Self::Error>;
+    /// Decodes a message to a value. Should be able to decode any message encoded by [`encode`].
+    fn decode(&self, data: HybridData) -> Result<T, Self::Error>;
+}
+
+macro_rules! impl_hybrid_codec_for_string_codec {
+    ($codec:ty) => {
+        impl<T> HybridCodec<T> for $codec
+        where
+            $codec: StringCodec<T>,
+        {
+            type Error = <$codec as StringCodec<T>>::Error;
+
+            fn encode(&self, val: &T) -> Result<HybridData, Self::Error> {
+                StringCodec::encode(self, val).map(HybridData::Text)
+            }
+
+            fn decode(&self, data: HybridData) -> Result<T, Self::Error> {
+                StringCodec::decode(self, data.into_text())
+            }
+        }
+    };
+}
+
+macro_rules! impl_hybrid_codec_for_bin_codec {
+    ($codec:ty) => {
+        impl<T> HybridCodec<T> for $codec
+        where
+            $codec: BinCodec<T>,
+        {
+            type Error = <$codec as BinCodec<T>>::Error;
+
+            fn encode(&self, val: &T) -> Result<HybridData, Self::Error> {
+                BinCodec::encode(self, val).map(HybridData::Binary)
+            }
+
+            fn decode(&self, data: HybridData) -> Result<T, Self::Error> {
+                BinCodec::decode(self, data.as_bytes())
+            }
+        }
+    };
+}
+
+impl_hybrid_codec_for_string_codec!(super::FromToStringCodec);
+#[cfg(feature = "serde_json")]
+impl_hybrid_codec_for_string_codec!(super::JsonCodec);
+#[cfg(feature = "prost")]
+impl_hybrid_codec_for_string_codec!(super::ProstCodec);
+
+#[cfg(feature = "base64")]
+impl<T, C> HybridCodec<T> for super::Base64<C>
+where
+    C: BinCodec<T>,
+{
+    type Error = <Self as StringCodec<T>>::Error;
+
+    fn encode(&self, val: &T) -> Result<HybridData, Self::Error> {
+        StringCodec::encode(self, val).map(HybridData::Text)
+    }
+
+    fn decode(&self, data: HybridData) -> Result<T, Self::Error> {
+        StringCodec::decode(self, data.into_text())
+    }
+}
+
+impl_hybrid_codec_for_bin_codec!(super::FromToBytesCodec);
+#[cfg(feature = "msgpack")]
+impl_hybrid_codec_for_bin_codec!(super::MsgpackCodec);
+#[cfg(feature = "bincode")]
+impl_hybrid_codec_for_bin_codec!(super::BincodeCodec);
+#[cfg(feature = "cbor")]
+impl_hybrid_codec_for_bin_codec!(super::CborCodec);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{FromToBytesCodec, FromToStringCodec};
+
+    #[test]
+    fn test_hybrid_string_codec() {
+        let codec = FromToStringCodec;
+        let enc = HybridCodec::<i32>::encode(&codec, &42).unwrap();
+        assert_eq!(enc, HybridData::Text("42".to_string()));
+        let dec: i32 = HybridCodec::decode(&codec, enc).unwrap();
+        assert_eq!(dec, 42);
+    }
+
+    #[test]
+    fn test_hybrid_bin_codec() {
+        let codec = FromToBytesCodec;
+        let enc = HybridCodec::<u16>::encode(&codec, &258).unwrap();
+        assert_eq!(enc, HybridData::Binary(vec![1, 2]));
+        let dec: u16 = HybridCodec::decode(&codec, enc).unwrap();
+        assert_eq!(dec, 258);
+    }
+}
@@ -0,0 +1,34 @@
+use super::JsCodec;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// A codec that passes JavaScript values through unchanged.
+///
+/// Works for `JsValue` itself and every type that implements [`JsCast`] like `js_sys::Object`
+/// or `web_sys::ImageData`. Decoding fails with the received value if it isn't an instance of
+/// the expected type.
+///
+/// ## Example
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_web_worker;
+/// # use leptos_use::utils::JsValueCodec;
+/// #
+/// # pub fn Demo() -> impl IntoView {
+/// let worker = use_web_worker::<js_sys::Array, js_sys::Object, JsValueCodec>("worker.js");
+/// #    view! { }
+/// # }
+/// ```
+#[derive(Copy, Clone, Default, PartialEq)]
+pub struct JsValueCodec;
+
+impl<T: JsCast> JsCodec<T> for JsValueCodec {
+    type Error = JsValue;
+
+    fn encode(&self, val: &T) -> Result<JsValue, Self::Error> {
+        Ok(val.as_ref().clone())
+    }
+
+    fn decode(&self, val: JsValue) -> Result<T, Self::Error> {
+        val.dyn_into()
+    }
+}
@@ -0,0 +1,24 @@
+mod js_value;
+#[cfg(feature = "serde-wasm-bindgen")]
+mod serde_wasm;
+
+pub use js_value::*;
+#[cfg(feature = "serde-wasm-bindgen")]
+pub use serde_wasm::*;
+
+use wasm_bindgen::JsValue;
+
+/// A codec for encoding and decoding values to and from JavaScript values.
+/// These values are intended to be sent with `postMessage` to workers or other windows which
+/// copies them with the [structured clone algorithm](https://developer.mozilla.org/en-US/docs/Web/API/Web_Workers_API/Structured_clone_algorithm).
+///
+/// Use [`JsValueCodec`] to send `JsValue`s and other JavaScript types as they are and
+/// [`SerdeWasmCodec`] (feature `"serde"`) for Rust types that implement serde's traits.
+pub trait JsCodec<T>: Clone + 'static {
+    /// The error type returned when encoding or decoding fails.
+    type Error;
+    /// Encodes a value to a JavaScript value.
+    fn encode(&self, val: &T) -> Result<JsValue, Self::Error>;
+    /// Decodes a JavaScript value to a value. Should be able to decode any value encoded by [`encode`].
+    fn decode(&self, val: JsValue) -> Result<T, Self::Error>;
+}
@@ -0,0 +1,40 @@
+use super::JsCodec;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsValue;
+
+/// A codec that converts values to JavaScript objects with [`serde_wasm_bindgen`].
+///
+/// Unlike [`JsonCodec`](crate::utils::JsonCodec) the values aren't turned into strings, so the
+/// receiver can work with them directly, even if it is written in JavaScript.
+///
+/// ## Example
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_web_worker;
+/// # use leptos_use::utils::SerdeWasmCodec;
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// #[derive(Serialize, Deserialize, Clone)]
+/// pub struct Job {
+///     pub input: Vec<f64>,
+/// }
+///
+/// # pub fn Demo() -> impl IntoView {
+/// let worker = use_web_worker::<Job, f64, SerdeWasmCodec>("worker.js");
+/// #    view! { }
+/// # }
+/// ```
+#[derive(Copy, Clone, Default, PartialEq)]
+pub struct SerdeWasmCodec;
+
+impl<T: Serialize + for<'de> Deserialize<'de>> JsCodec<T> for SerdeWasmCodec {
+    type Error = serde_wasm_bindgen::Error;
+
+    fn encode(&self, val: &T) -> Result<JsValue, Self::Error> {
+        serde_wasm_bindgen::to_value(val)
+    }
+
+    fn decode(&self, val: JsValue) -> Result<T, Self::Error> {
+        serde_wasm_bindgen::from_value(val)
+    }
+}
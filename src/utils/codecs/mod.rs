@@ -1,4 +1,9 @@
 mod bin;
+mod hybrid;
+mod js;
 mod string;
 
+pub use bin::*;
+pub use hybrid::*;
+pub use js::*;
 pub use string::*;
@@ -0,0 +1,75 @@
+use super::StringCodec;
+use crate::utils::BinCodec;
+use base64::Engine;
+use thiserror::Error;
+
+/// Wraps a [`BinCodec`] to represent its bytes as a [`base64`](https://docs.rs/base64) string.
+///
+/// This makes binary codecs usable everywhere a [`StringCodec`] is required, for example
+/// to store MessagePack or bincode encoded values in browser storage.
+///
+/// ## Example
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::storage::use_local_storage;
+/// # use leptos_use::utils::{Base64, MsgpackCodec};
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// # pub fn Demo() -> impl IntoView {
+/// #[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
+/// pub struct MyState {
+///     pub hello: String,
+///     pub count: u32,
+/// }
+///
+/// let (get, set, remove) = use_local_storage::<MyState, Base64<MsgpackCodec>>("my-state");
+/// #    view! { }
+/// # }
+/// ```
+///
+/// You need to enable the feature `"base64"` together with the feature of the binary codec.
+#[derive(Copy, Clone, Default, PartialEq)]
+pub struct Base64<C>(pub C);
+
+#[derive(Error, Debug, PartialEq)]
+pub enum Base64CodecError<Err> {
+    #[error("failed to decode base64")]
+    DecodeBase64(base64::DecodeError),
+    #[error("failed to encode / decode bytes")]
+    Codec(Err),
+}
+
+impl<T, C: BinCodec<T>> StringCodec<T> for Base64<C> {
+    type Error = Base64CodecError<C::Error>;
+
+    fn encode(&self, val: &T) -> Result<String, Self::Error> {
+        let buf = self.0.encode(val).map_err(Base64CodecError::Codec)?;
+        Ok(base64::engine::general_purpose::STANDARD.encode(buf))
+    }
+
+    fn decode(&self, str: String) -> Result<T, Self::Error> {
+        let buf = base64::engine::general_purpose::STANDARD
+            .decode(str)
+            .map_err(Base64CodecError::DecodeBase64)?;
+        self.0.decode(&buf).map_err(Base64CodecError::Codec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::FromToBytesCodec;
+
+    #[test]
+    fn test_base64_codec() {
+        let codec = Base64(FromToBytesCodec);
+        let enc = codec.encode(&0x0102_0304_u32).unwrap();
+        assert_eq!(enc, "AQIDBA==");
+        let dec: u32 = codec.decode(enc).unwrap();
+        assert_eq!(dec, 0x0102_0304);
+        assert!(matches!(
+            StringCodec::<u32>::decode(&codec, "not base64!".to_string()),
+            Err(Base64CodecError::DecodeBase64(_))
+        ));
+    }
+}
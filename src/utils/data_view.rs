@@ -0,0 +1,9 @@
+/// Copies the bytes of a `DataView` like the ones returned by the device APIs into a `Vec`.
+pub(crate) fn data_view_to_vec(value: &js_sys::DataView) -> Vec<u8> {
+    js_sys::Uint8Array::new_with_byte_offset_and_length(
+        &value.buffer(),
+        value.byte_offset() as u32,
+        value.byte_length() as u32,
+    )
+    .to_vec()
+}
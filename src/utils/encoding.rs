@@ -0,0 +1,15 @@
+/// Encodes the bytes as lowercase hex.
+pub(crate) fn encode_hex(data: &[u8]) -> String {
+    data.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_hex() {
+        assert_eq!(encode_hex(b""), "");
+        assert_eq!(encode_hex(&[0x00, 0x0f, 0xab, 0xff]), "000fabff");
+    }
+}
@@ -0,0 +1,22 @@
+use crate::{js_fut, PermissionState};
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Calls the static `requestPermission` method of an event class like `DeviceOrientationEvent`.
+/// Only iOS Safari has this method. In all other browsers the permission is granted right away.
+pub(crate) async fn request_event_permission(class: &str) -> Result<PermissionState, JsValue> {
+    let class = js_sys::Reflect::get(&leptos::window(), &class.into())?;
+    let request_permission = js_sys::Reflect::get(&class, &"requestPermission".into())?;
+
+    let Some(request_permission) = request_permission.dyn_ref::<js_sys::Function>() else {
+        return Ok(PermissionState::Granted);
+    };
+
+    let promise: js_sys::Promise = request_permission.call0(&class)?.unchecked_into();
+    let state = js_fut!(promise).await?;
+
+    Ok(match state.as_string().as_deref() {
+        Some("granted") => PermissionState::Granted,
+        Some("denied") => PermissionState::Denied,
+        _ => PermissionState::Unknown,
+    })
+}
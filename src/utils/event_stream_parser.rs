@@ -0,0 +1,186 @@
+/// A single event parsed from a `text/event-stream`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ServerSentEvent {
+    /// The event type. `"message"` if the server didn't specify one.
+    pub event: String,
+    pub data: String,
+    /// The last event id that has been sent by the server on this stream so far.
+    pub last_event_id: String,
+}
+
+/// Incremental parser for the
+/// [event stream format](https://html.spec.whatwg.org/multipage/server-sent-events.html#event-stream-interpretation).
+/// Chunks can be split at arbitrary byte positions.
+#[derive(Default)]
+pub(crate) struct EventStreamParser {
+    buffer: Vec<u8>,
+    started: bool,
+    event: String,
+    data: String,
+    last_event_id: String,
+    retry: Option<u64>,
+}
+
+impl EventStreamParser {
+    /// Creates a parser that continues a stream which was last at `last_event_id`.
+    pub fn new(last_event_id: impl Into<String>) -> Self {
+        Self {
+            last_event_id: last_event_id.into(),
+            ..Default::default()
+        }
+    }
+
+    /// The reconnection time in ms requested by the server with a `retry` field.
+    pub fn retry(&self) -> Option<u64> {
+        self.retry
+    }
+
+    /// Feeds the next chunk of the stream and returns the events that are complete.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<ServerSentEvent> {
+        self.buffer.extend_from_slice(chunk);
+
+        if !self.started {
+            if self.buffer.len() < 3 && b"\xEF\xBB\xBF".starts_with(&self.buffer) {
+                return vec![];
+            }
+            if self.buffer.starts_with(b"\xEF\xBB\xBF") {
+                self.buffer.drain(..3);
+            }
+            self.started = true;
+        }
+
+        let mut events = vec![];
+        let mut start = 0;
+
+        while let Some(offset) = self.buffer[start..]
+            .iter()
+            .position(|b| *b == b'\n' || *b == b'\r')
+        {
+            let end = start + offset;
+            let line_break_len = if self.buffer[end] == b'\r' {
+                match self.buffer.get(end + 1) {
+                    Some(b'\n') => 2,
+                    Some(_) => 1,
+                    // could be the first half of a CRLF. Wait for the next chunk.
+                    None => break,
+                }
+            } else {
+                1
+            };
+
+            let line = String::from_utf8_lossy(&self.buffer[start..end]).into_owned();
+            if let Some(event) = self.process_line(&line) {
+                events.push(event);
+            }
+
+            start = end + line_break_len;
+        }
+
+        self.buffer.drain(..start);
+
+        events
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<ServerSentEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+
+        match field {
+            "event" => self.event = value.to_string(),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => self.last_event_id = value.to_string(),
+            "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                self.retry = value.parse().ok();
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<ServerSentEvent> {
+        let event = std::mem::take(&mut self.event);
+        let mut data = std::mem::take(&mut self.data);
+
+        if data.is_empty() {
+            return None;
+        }
+
+        data.pop();
+
+        Some(ServerSentEvent {
+            event: if event.is_empty() {
+                "message".to_string()
+            } else {
+                event
+            },
+            data,
+            last_event_id: self.last_event_id.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(event: &str, data: &str, last_event_id: &str) -> ServerSentEvent {
+        ServerSentEvent {
+            event: event.to_string(),
+            data: data.to_string(),
+            last_event_id: last_event_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_events() {
+        let mut parser = EventStreamParser::default();
+
+        let events = parser.feed(
+            b": comment\ndata: first\n\nevent: update\nid: 42\ndata: line 1\ndata:line 2\n\n",
+        );
+
+        assert_eq!(
+            events,
+            vec![
+                event("message", "first", ""),
+                event("update", "line 1\nline 2", "42"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_split_chunks() {
+        let mut parser = EventStreamParser::new("7");
+
+        assert_eq!(parser.feed(b"\xEF\xBB"), vec![]);
+        assert_eq!(parser.feed(b"\xBFdata: hel"), vec![]);
+        assert_eq!(parser.feed(b"lo\r"), vec![]);
+        assert_eq!(
+            parser.feed(b"\n\r\nretry: 1500\r\r"),
+            vec![event("message", "hello", "7")]
+        );
+        assert_eq!(parser.retry(), Some(1500));
+    }
+
+    #[test]
+    fn test_ignore_empty_data() {
+        let mut parser = EventStreamParser::default();
+
+        assert_eq!(parser.feed(b"event: ping\n\nid\n\n"), vec![]);
+        assert_eq!(parser.feed(b"data\n\n"), vec![event("message", "", "")]);
+    }
+}
@@ -0,0 +1,77 @@
+/// Prefixes `payload` with its length as a 32-bit big-endian unsigned integer.
+pub(crate) fn length_prefixed(payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(4 + payload.len());
+    frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// Incremental decoder for messages framed by [`length_prefixed`].
+/// Chunks can be split at arbitrary byte positions.
+#[derive(Default)]
+pub(crate) struct LengthPrefixedDecoder {
+    buffer: Vec<u8>,
+}
+
+impl LengthPrefixedDecoder {
+    /// Feeds the next chunk of the stream and returns the payloads that are complete.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<Vec<u8>> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut payloads = vec![];
+        let mut start = 0;
+
+        while let Some(header) = self.buffer.get(start..start + 4) {
+            let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+
+            let Some(payload) = self.buffer.get(start + 4..start + 4 + len) else {
+                break;
+            };
+
+            payloads.push(payload.to_vec());
+            start += 4 + len;
+        }
+
+        self.buffer.drain(..start);
+
+        payloads
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_length_prefixed() {
+        assert_eq!(length_prefixed(b"abc"), vec![0, 0, 0, 3, b'a', b'b', b'c']);
+        assert_eq!(length_prefixed(b""), vec![0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_decode_multiple_frames() {
+        let mut decoder = LengthPrefixedDecoder::default();
+
+        let mut chunk = length_prefixed(b"first");
+        chunk.extend(length_prefixed(b""));
+        chunk.extend(length_prefixed(b"third"));
+
+        assert_eq!(
+            decoder.feed(&chunk),
+            vec![b"first".to_vec(), vec![], b"third".to_vec()]
+        );
+    }
+
+    #[test]
+    fn test_decode_split_chunks() {
+        let mut decoder = LengthPrefixedDecoder::default();
+
+        let mut stream = length_prefixed(b"hello");
+        stream.extend(length_prefixed(b"world"));
+
+        assert!(decoder.feed(&stream[..2]).is_empty());
+        assert!(decoder.feed(&stream[2..7]).is_empty());
+        assert_eq!(decoder.feed(&stream[7..11]), vec![b"hello".to_vec()]);
+        assert_eq!(decoder.feed(&stream[11..]), vec![b"world".to_vec()]);
+    }
+}
@@ -1,16 +1,32 @@
 mod codecs;
+mod data_view;
+mod encoding;
+mod event_permission;
+mod event_stream_parser;
 mod filters;
 mod is;
 mod js;
 mod js_value_from_to_string;
+#[cfg(any(all(web_sys_unstable_apis, not(feature = "ssr")), test))]
+mod length_prefixed;
 mod pausable;
+mod service_worker;
 mod signal_filtered;
 mod use_derive_signal;
+mod web_crypto;
 
 pub use codecs::*;
+pub(crate) use data_view::*;
+pub(crate) use encoding::*;
+pub(crate) use event_permission::*;
+pub(crate) use event_stream_parser::*;
 pub use filters::*;
 pub use is::*;
 pub(crate) use js_value_from_to_string::*;
+#[cfg(all(web_sys_unstable_apis, not(feature = "ssr")))]
+pub(crate) use length_prefixed::*;
 pub use pausable::*;
+pub(crate) use service_worker::*;
 pub(crate) use signal_filtered::*;
 pub(crate) use use_derive_signal::*;
+pub use web_crypto::*;
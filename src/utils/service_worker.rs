@@ -0,0 +1,12 @@
+use crate::js_fut;
+use leptos::window;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::ServiceWorkerRegistration;
+
+/// Waits until a service worker is active and returns its registration.
+pub(crate) async fn ready_service_worker_registration() -> Result<ServiceWorkerRegistration, JsValue>
+{
+    let registration = js_fut!(window().navigator().service_worker().ready()?).await?;
+
+    Ok(registration.unchecked_into())
+}
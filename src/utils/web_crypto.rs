@@ -0,0 +1,130 @@
+use crate::js_fut;
+use leptos::window;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::CryptoKey;
+
+const HMAC: &str = "HMAC";
+
+/// Hash algorithms of the [Web Crypto API](https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/digest#supported_algorithms).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum HashAlgorithm {
+    /// Not secure for cryptographic purposes anymore but still used for checksums
+    Sha1,
+    #[default]
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    /// The name of the algorithm in the Web Crypto API, for example `"SHA-256"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sha1 => "SHA-1",
+            Self::Sha256 => "SHA-256",
+            Self::Sha384 => "SHA-384",
+            Self::Sha512 => "SHA-512",
+        }
+    }
+}
+
+/// Hashes `data` with the browser's native
+/// [`crypto.subtle.digest()`](https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/digest).
+/// See [`fn@crate::use_hash`] for a reactive version.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::utils::{digest, HashAlgorithm};
+/// #
+/// # fn demo() {
+/// spawn_local(async {
+///     let hash = digest(HashAlgorithm::Sha256, b"Hello, World!").await;
+/// });
+/// # }
+/// ```
+pub async fn digest(algorithm: HashAlgorithm, data: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let hash = js_fut!(window()
+        .crypto()?
+        .subtle()
+        .digest_with_str_and_u8_array(algorithm.as_str(), data)?)
+    .await?;
+
+    Ok(js_sys::Uint8Array::new(&hash).to_vec())
+}
+
+/// Generates a new random HMAC key that can be used with [`hmac_sign`] and [`hmac_verify`].
+/// Set `extractable` to be able to export it with [`export_raw_key`].
+pub async fn generate_hmac_key(
+    hash: HashAlgorithm,
+    extractable: bool,
+) -> Result<CryptoKey, JsValue> {
+    let key = js_fut!(window().crypto()?.subtle().generate_key_with_object(
+        &web_sys::HmacKeyGenParams::new_with_str(HMAC, hash.as_str()),
+        extractable,
+        &hmac_key_usages(),
+    )?)
+    .await?;
+
+    Ok(key.unchecked_into())
+}
+
+/// Imports a raw HMAC key, for example a secret that is shared with your server.
+pub async fn import_hmac_key(raw_key: &[u8], hash: HashAlgorithm) -> Result<CryptoKey, JsValue> {
+    let key = js_fut!(window().crypto()?.subtle().import_key_with_object(
+        "raw",
+        &js_sys::Uint8Array::from(raw_key),
+        &web_sys::HmacImportParams::new_with_str(HMAC, hash.as_str()),
+        false,
+        &hmac_key_usages(),
+    )?)
+    .await?;
+
+    Ok(key.unchecked_into())
+}
+
+/// Exports the raw bytes of an extractable key.
+pub async fn export_raw_key(key: &CryptoKey) -> Result<Vec<u8>, JsValue> {
+    let raw_key = js_fut!(window().crypto()?.subtle().export_key("raw", key)?).await?;
+
+    Ok(js_sys::Uint8Array::new(&raw_key).to_vec())
+}
+
+/// Signs `data` with an HMAC key and returns the signature.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::utils::{hmac_sign, hmac_verify, import_hmac_key, HashAlgorithm};
+/// #
+/// # fn demo() {
+/// spawn_local(async {
+///     let key = import_hmac_key(b"shared secret", HashAlgorithm::Sha256).await.unwrap();
+///
+///     let signature = hmac_sign(&key, b"message").await.unwrap();
+///     assert_eq!(hmac_verify(&key, &signature, b"message").await, Ok(true));
+/// });
+/// # }
+/// ```
+pub async fn hmac_sign(key: &CryptoKey, data: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let signature = js_fut!(window()
+        .crypto()?
+        .subtle()
+        .sign_with_str_and_u8_array(HMAC, key, data)?)
+    .await?;
+
+    Ok(js_sys::Uint8Array::new(&signature).to_vec())
+}
+
+/// Checks in constant time if `signature` is the HMAC signature of `data`.
+pub async fn hmac_verify(key: &CryptoKey, signature: &[u8], data: &[u8]) -> Result<bool, JsValue> {
+    let is_valid = js_fut!(window()
+        .crypto()?
+        .subtle()
+        .verify_with_str_and_u8_array_and_u8_array(HMAC, key, signature, data)?)
+    .await?;
+
+    Ok(is_valid.as_bool().unwrap_or_default())
+}
+
+fn hmac_key_usages() -> JsValue {
+    js_sys::Array::of2(&"sign".into(), &"verify".into()).into()
+}